@@ -154,6 +154,9 @@ where ModuleProvider: module::ModuleProvider + Send + 'static
 		if let Some(command) = config.post_modified_line_exec_command.as_deref() {
 			todo_file_options.line_changed_command(command);
 		}
+		for (action, command) in &config.post_modified_line_exec_action_commands {
+			todo_file_options.line_changed_action_command(*action, command);
+		}
 		todo_file_options
 	}
 
@@ -205,6 +208,7 @@ mod tests {
 			DefaultTestModule,
 			TestModuleProvider,
 		},
+		todo_file::Action,
 	};
 
 	fn args(args: &[&str]) -> Args {
@@ -302,6 +306,22 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn todo_file_options_with_action_commands() {
+		let mut config = create_config();
+		config.undo_limit = 10;
+		config.git.comment_char = String::from("#");
+		config.post_modified_line_exec_action_commands = vec![(Action::Drop, String::from("drop-command"))];
+
+		let mut expected = TodoFileOptions::new(10, "#");
+		expected.line_changed_action_command(Action::Drop, "drop-command");
+
+		assert_eq!(
+			Application::<TestModuleProvider<DefaultTestModule>>::todo_file_options(&config),
+			expected
+		);
+	}
+
 	#[test]
 	fn load_todo_file_load_error() {
 		with_git_directory("fixtures/simple", |_| {
@@ -2,7 +2,9 @@ use std::ffi::OsString;
 
 use pico_args::Arguments;
 
-use crate::{exit::Exit, module::ExitStatus};
+use crate::{exit::Exit, help::build_help, module::ExitStatus};
+
+const REBASE_FLAG: &str = "--rebase";
 
 #[derive(Debug, Eq, PartialEq)]
 pub(crate) enum Mode {
@@ -10,12 +12,16 @@ pub(crate) enum Mode {
 	Help,
 	Version,
 	License,
+	Rebase,
 }
 
 #[derive(Debug)]
 pub(crate) struct Args {
 	mode: Mode,
+	rebase_merges: bool,
 	todo_file_path: Option<String>,
+	update_refs: bool,
+	upstream: Option<String>,
 }
 
 impl Args {
@@ -26,6 +32,18 @@ impl Args {
 	pub(crate) const fn todo_file_path(&self) -> &Option<String> {
 		&self.todo_file_path
 	}
+
+	pub(crate) const fn upstream(&self) -> &Option<String> {
+		&self.upstream
+	}
+
+	pub(crate) const fn rebase_merges(&self) -> bool {
+		self.rebase_merges
+	}
+
+	pub(crate) const fn update_refs(&self) -> bool {
+		self.update_refs
+	}
 }
 
 impl TryFrom<Vec<OsString>> for Args {
@@ -47,11 +65,51 @@ impl TryFrom<Vec<OsString>> for Args {
 			Mode::Editor
 		};
 
-		let todo_file_path = pargs
+		let rebase_merges = pargs.contains("--rebase-merges");
+		let update_refs = pargs.contains("--update-refs");
+
+		let upstream: Option<String> = match pargs.opt_value_from_str(REBASE_FLAG) {
+			Ok(upstream) => upstream,
+			Err(pico_args::Error::OptionWithoutAValue(_)) => {
+				return Err(Exit::new(
+					ExitStatus::StateError,
+					build_help(Some(String::from("An upstream must be provided to rebase."))).as_str(),
+				));
+			},
+			Err(err) => return Err(Exit::new(ExitStatus::StateError, err.to_string().as_str())),
+		};
+
+		let free_argument: Option<String> = pargs
 			.opt_free_from_str()
 			.map_err(|err| Exit::new(ExitStatus::StateError, err.to_string().as_str()))?;
 
-		Ok(Self { mode, todo_file_path })
+		if mode == Mode::Editor && upstream.is_some() {
+			return Ok(Self {
+				mode: Mode::Rebase,
+				rebase_merges,
+				todo_file_path: None,
+				update_refs,
+				upstream,
+			});
+		}
+
+		if rebase_merges || update_refs {
+			return Err(Exit::new(
+				ExitStatus::StateError,
+				build_help(Some(String::from(
+					"The --rebase-merges and --update-refs flags can only be used with rebase.",
+				)))
+				.as_str(),
+			));
+		}
+
+		Ok(Self {
+			mode,
+			rebase_merges,
+			todo_file_path: free_argument,
+			update_refs,
+			upstream: None,
+		})
 	}
 }
 
@@ -100,6 +158,50 @@ mod tests {
 		assert!(args.todo_file_path().is_none());
 	}
 
+	#[test]
+	fn mode_rebase() {
+		let args = Args::try_from(create_args(&["--rebase", "main"])).unwrap();
+		assert_eq!(args.mode(), &Mode::Rebase);
+		assert_eq!(args.upstream(), &Some(String::from("main")));
+		assert!(args.todo_file_path().is_none());
+		assert!(!args.rebase_merges());
+		assert!(!args.update_refs());
+	}
+
+	#[test]
+	fn mode_rebase_with_flags() {
+		let args = Args::try_from(create_args(&["--rebase", "main", "--rebase-merges", "--update-refs"])).unwrap();
+		assert_eq!(args.mode(), &Mode::Rebase);
+		assert_eq!(args.upstream(), &Some(String::from("main")));
+		assert!(args.rebase_merges());
+		assert!(args.update_refs());
+	}
+
+	#[test]
+	fn mode_rebase_missing_upstream() {
+		let exit = Args::try_from(create_args(&["--rebase"])).unwrap_err();
+		assert_eq!(exit.get_status(), &ExitStatus::StateError);
+		assert!(
+			exit.get_message()
+				.as_ref()
+				.unwrap()
+				.contains("An upstream must be provided to rebase.")
+		);
+	}
+
+	#[test]
+	fn todo_file_named_rebase() {
+		let args = Args::try_from(create_args(&["rebase"])).unwrap();
+		assert_eq!(args.mode(), &Mode::Editor);
+		assert_eq!(args.todo_file_path(), &Some(String::from("rebase")));
+	}
+
+	#[test]
+	fn rebase_flags_without_rebase() {
+		let exit = Args::try_from(create_args(&["--update-refs", "todofile"])).unwrap_err();
+		assert_eq!(exit.get_status(), &ExitStatus::StateError);
+	}
+
 	#[cfg(unix)]
 	#[test]
 	#[allow(unsafe_code)]
@@ -6,3 +6,6 @@ pub(crate) mod search_bar;
 mod shared;
 #[allow(dead_code)]
 pub(crate) mod spin_indicator;
+pub(crate) mod text_editor;
+
+pub(crate) use self::shared::{EditableLine, HistoryKind, InputHistory};
@@ -4,7 +4,7 @@ mod tests;
 use lazy_static::lazy_static;
 
 use crate::{
-	components::shared::EditableLine,
+	components::shared::{EditableLine, HistoryKind, InputHistory},
 	display::DisplayColor,
 	input::{Event, InputOptions, KeyCode, KeyEvent, KeyModifiers},
 	view::{LineSegment, LineSegmentOptions, ViewData, ViewDataUpdater, ViewLine},
@@ -14,6 +14,8 @@ lazy_static! {
 	pub(crate) static ref INPUT_OPTIONS: InputOptions = InputOptions::RESIZE;
 }
 
+const COMPLETION_POPUP_SIZE: usize = 5;
+
 const FINISH_EVENT: Event = Event::Key(KeyEvent {
 	code: KeyCode::Enter,
 	modifiers: KeyModifiers::NONE,
@@ -22,6 +24,7 @@ const FINISH_EVENT: Event = Event::Key(KeyEvent {
 pub(crate) struct Edit {
 	editable_line: EditableLine,
 	finished: bool,
+	history: Option<InputHistory>,
 	view_data: ViewData,
 }
 
@@ -33,6 +36,7 @@ impl Edit {
 		Self {
 			editable_line: EditableLine::new(),
 			finished: false,
+			history: None,
 			view_data,
 		}
 	}
@@ -46,6 +50,25 @@ impl Edit {
 			updater.clear();
 			before_build(updater);
 			updater.push_line(ViewLine::from(self.editable_line.line_segments()));
+			if let Some((matches, index)) = self.editable_line.completion_matches() {
+				// keep the applied candidate within the popup, scrolling the candidates as completion cycles
+				let start = index.saturating_sub(COMPLETION_POPUP_SIZE - 1);
+				for (offset, candidate) in matches.iter().skip(start).take(COMPLETION_POPUP_SIZE).enumerate() {
+					updater.push_line(ViewLine::from(vec![
+						LineSegment::new("  "),
+						if start + offset == index {
+							LineSegment::new_with_color(candidate, DisplayColor::IndicatorColor)
+						}
+						else {
+							LineSegment::new_with_color_and_style(
+								candidate,
+								DisplayColor::Normal,
+								LineSegmentOptions::DIMMED,
+							)
+						},
+					]));
+				}
+			}
 			updater.push_trailing_line(ViewLine::new_pinned(vec![LineSegment::new_with_color(
 				"Enter to finish",
 				DisplayColor::IndicatorColor,
@@ -63,6 +86,7 @@ impl Edit {
 
 	pub(crate) fn handle_event(&mut self, event: Event) {
 		if event == FINISH_EVENT {
+			self.editable_line.save_history();
 			self.finished = true;
 		}
 		else {
@@ -78,6 +102,24 @@ impl Edit {
 		));
 	}
 
+	/// Set the history of the inputs, shared by all the edits.
+	pub(crate) fn set_history(&mut self, history: InputHistory) {
+		self.history = Some(history);
+	}
+
+	/// Set the kind of the input being edited, so the previous inputs of that kind can be recalled with the up and
+	/// down keys. The kind is cleared on reset.
+	pub(crate) fn set_history_kind(&mut self, kind: HistoryKind) {
+		self.editable_line
+			.set_history(self.history.as_ref().map(|history| (history.clone(), kind)));
+	}
+
+	/// Set the candidates that the input can be completed to with the tab key, shown below the input while completing.
+	/// The candidates are cleared on reset.
+	pub(crate) fn set_completions(&mut self, completions: Vec<String>) {
+		self.editable_line.set_completions(completions);
+	}
+
 	pub(crate) fn set_content(&mut self, content: &str) {
 		self.editable_line.set_content(content);
 	}
@@ -85,6 +127,8 @@ impl Edit {
 	pub(crate) fn reset(&mut self) {
 		self.editable_line.clear();
 		self.editable_line.set_read_only(false);
+		self.editable_line.set_history(None);
+		self.editable_line.set_completions(vec![]);
 		self.finished = false;
 	}
 
@@ -43,6 +43,67 @@ fn edit_event() {
 	);
 }
 
+#[test]
+fn completion_popup() {
+	let mut module = Edit::new();
+	module.set_completions(vec![String::from("foo"), String::from("bar"), String::from("foobar")]);
+	module.set_content("fo");
+	module.handle_event(Event::from(KeyCode::Tab));
+	module.handle_event(Event::from(KeyCode::Tab));
+	let view_data = module.get_view_data();
+
+	assert_rendered_output!(
+		Style view_data,
+		"{TITLE}",
+		"{BODY}",
+		"{Normal}foobar{Normal,Underline}",
+		"{Normal}  {Normal,Dimmed}foo",
+		"{Normal}  {IndicatorColor}foobar",
+		"{TRAILING}",
+		"{IndicatorColor}Enter to finish"
+	);
+}
+
+#[test]
+fn completion_popup_scrolls_to_candidate() {
+	let mut module = Edit::new();
+	module.set_completions((1..=7).map(|index| format!("label{index}")).collect());
+	for _ in 0..7 {
+		module.handle_event(Event::from(KeyCode::Tab));
+	}
+	let view_data = module.get_view_data();
+
+	assert_rendered_output!(
+		Body view_data,
+		"label7",
+		"  label3",
+		"  label4",
+		"  label5",
+		"  label6",
+		"  label7"
+	);
+}
+
+#[test]
+fn completion_popup_closed_on_edit() {
+	let mut module = Edit::new();
+	module.set_completions(vec![String::from("foo"), String::from("foobar")]);
+	module.handle_event(Event::from(KeyCode::Tab));
+	module.handle_event(Event::from(KeyCode::Backspace));
+	let view_data = module.get_view_data();
+
+	assert_rendered_output!(Body view_data, "fo");
+}
+
+#[test]
+fn reset_clears_completions() {
+	let mut module = Edit::new();
+	module.set_completions(vec![String::from("foo")]);
+	module.reset();
+	module.handle_event(Event::from(KeyCode::Tab));
+	assert_eq!(module.get_content(), "");
+}
+
 #[test]
 fn finish_event() {
 	let mut module = Edit::new();
@@ -66,3 +127,31 @@ fn reset() {
 	assert_eq!(module.get_content(), "");
 	assert!(!module.is_finished());
 }
+
+#[test]
+fn history_saved_on_finish_and_recalled() {
+	let history = InputHistory::new(10);
+	let mut module = Edit::new();
+	module.set_history(history.clone());
+	module.set_history_kind(HistoryKind::Exec);
+	module.set_content("make");
+	module.handle_event(Event::from(KeyCode::Enter));
+	assert_eq!(history.entries(HistoryKind::Exec), vec!["make"]);
+
+	module.reset();
+	module.set_history_kind(HistoryKind::Exec);
+	module.handle_event(Event::from(KeyCode::Up));
+	assert_eq!(module.get_content(), "make");
+}
+
+#[test]
+fn history_not_saved_without_kind() {
+	let history = InputHistory::new(10);
+	let mut module = Edit::new();
+	module.set_history(history.clone());
+	module.set_history_kind(HistoryKind::Exec);
+	module.reset();
+	module.set_content("make");
+	module.handle_event(Event::from(KeyCode::Enter));
+	assert!(history.entries(HistoryKind::Exec).is_empty());
+}
@@ -7,7 +7,7 @@ pub(crate) use self::action::Action as SearchBarAction;
 use crate::{
 	components::{
 		search_bar::state::State,
-		shared::{EditAction, EditableLine},
+		shared::{EditAction, EditableLine, HistoryKind, InputHistory},
 	},
 	input::{Event, InputOptions, KeyCode, KeyEvent, KeyModifiers, StandardEvent},
 	view::{LineSegment, ViewLine},
@@ -34,6 +34,11 @@ impl SearchBar {
 		}
 	}
 
+	/// Set the history of the search terms, which are recalled with the up and down keys while editing.
+	pub(crate) fn set_history(&mut self, history: InputHistory) {
+		self.editable_line.set_history(Some((history, HistoryKind::Search)));
+	}
+
 	pub(crate) fn start_search(&mut self, initial_value: Option<&str>) {
 		if let Some(value) = initial_value {
 			self.editable_line.set_content(value);
@@ -87,6 +92,7 @@ impl SearchBar {
 			},
 			Event::Standard(StandardEvent::SearchFinish) => {
 				self.editable_line.set_read_only(true);
+				self.editable_line.save_history();
 				self.state = State::Searching;
 				SearchBarAction::Start(String::from(self.editable_line.get_content()))
 			},
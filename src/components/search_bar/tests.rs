@@ -265,3 +265,19 @@ fn build_view_line() {
 		"{Normal}/{Normal,Underline}"
 	);
 }
+
+#[test]
+fn history_saved_on_search_finish_and_recalled() {
+	let history = InputHistory::new(10);
+	let mut search_bar = SearchBar::new();
+	search_bar.set_history(history.clone());
+	search_bar.start_search(Some("foo"));
+	_ = search_bar.handle_event(Event::from(StandardEvent::SearchFinish));
+	assert_eq!(history.entries(HistoryKind::Search), vec!["foo"]);
+
+	search_bar.start_search(Some(""));
+	assert_eq!(
+		search_bar.handle_event(Event::from(KeyCode::Up)),
+		SearchBarAction::Update(String::from("foo"))
+	);
+}
@@ -1,3 +1,7 @@
 mod editable_line;
+mod input_history;
 
-pub(crate) use self::editable_line::{EditAction, EditableLine};
+pub(crate) use self::{
+	editable_line::{EditAction, EditableLine},
+	input_history::{HistoryKind, InputHistory},
+};
@@ -1,6 +1,9 @@
+use std::mem;
+
 use unicode_segmentation::UnicodeSegmentation;
 
 use crate::{
+	components::shared::{HistoryKind, InputHistory},
 	display::DisplayColor,
 	input::{Event, KeyCode, KeyEvent, KeyModifiers},
 	view::{LineSegment, LineSegmentOptions},
@@ -13,9 +16,22 @@ pub(crate) enum EditAction {
 	None,
 }
 
+/// An in progress completion, that cycles through the candidates matching the completed text.
+struct Completion {
+	head: String,
+	index: usize,
+	matches: Vec<String>,
+	tail: String,
+}
+
 pub(crate) struct EditableLine {
+	completion: Option<Completion>,
+	completions: Vec<String>,
 	content: String,
 	cursor_position: usize,
+	history: Option<(InputHistory, HistoryKind)>,
+	history_draft: String,
+	history_index: Option<usize>,
 	label: Option<LineSegment>,
 	read_only: bool,
 }
@@ -23,13 +39,45 @@ pub(crate) struct EditableLine {
 impl EditableLine {
 	pub(crate) const fn new() -> Self {
 		Self {
+			completion: None,
+			completions: vec![],
 			content: String::new(),
 			cursor_position: 0,
+			history: None,
+			history_draft: String::new(),
+			history_index: None,
 			label: None,
 			read_only: false,
 		}
 	}
 
+	/// Set the history of the inputs that can be recalled with the up and down keys.
+	pub(crate) fn set_history(&mut self, history: Option<(InputHistory, HistoryKind)>) {
+		self.history = history;
+		self.history_index = None;
+	}
+
+	/// Add the content to the history, as the newest entry.
+	pub(crate) fn save_history(&mut self) {
+		if let Some((history, kind)) = self.history.as_ref() {
+			history.push(*kind, self.content.as_str());
+		}
+		self.history_index = None;
+	}
+
+	/// Set the candidates that the text before the cursor can be completed to with the tab key.
+	pub(crate) fn set_completions(&mut self, completions: Vec<String>) {
+		self.completions = completions;
+		self.completion = None;
+	}
+
+	/// The candidates matching the completed text, and the index of the candidate in the content, while completing.
+	pub(crate) fn completion_matches(&self) -> Option<(&[String], usize)> {
+		self.completion
+			.as_ref()
+			.map(|completion| (completion.matches.as_slice(), completion.index))
+	}
+
 	pub(crate) fn set_label(&mut self, label: LineSegment) {
 		self.label = Some(label);
 	}
@@ -37,6 +85,8 @@ impl EditableLine {
 	pub(crate) fn set_content(&mut self, content: &str) {
 		self.content = String::from(content);
 		self.cursor_position = UnicodeSegmentation::graphemes(content, true).count();
+		self.history_index = None;
+		self.completion = None;
 	}
 
 	pub(crate) fn set_read_only(&mut self, read_only: bool) {
@@ -46,6 +96,8 @@ impl EditableLine {
 	pub(crate) fn clear(&mut self) {
 		self.content.clear();
 		self.cursor_position = 0;
+		self.history_index = None;
+		self.completion = None;
 	}
 
 	pub(crate) fn get_content(&self) -> &str {
@@ -56,6 +108,12 @@ impl EditableLine {
 		self.cursor_position
 	}
 
+	/// Move the cursor to the position, or to the end of the content when the position is past the end.
+	pub(crate) fn set_cursor_position(&mut self, position: usize) {
+		self.cursor_position = position.min(UnicodeSegmentation::graphemes(self.content.as_str(), true).count());
+		self.completion = None;
+	}
+
 	pub(crate) fn line_segments(&self) -> Vec<LineSegment> {
 		if self.read_only {
 			return vec![LineSegment::new(self.get_content())];
@@ -96,11 +154,123 @@ impl EditableLine {
 		segments
 	}
 
+	/// Replace the content with the previous (older) entry of the history, keeping the entered content so it can be
+	/// restored after the newest entry.
+	fn recall_previous(&mut self) -> EditAction {
+		let Some((history, kind)) = self.history.as_ref()
+		else {
+			return EditAction::None;
+		};
+		let entries = history.entries(*kind);
+		let index = match self.history_index {
+			None if entries.is_empty() => return EditAction::None,
+			None => {
+				self.history_draft = self.content.clone();
+				entries.len() - 1
+			},
+			Some(0) => return EditAction::None,
+			Some(index) => index - 1,
+		};
+		self.set_content(entries[index].as_str());
+		self.history_index = Some(index);
+		EditAction::ContentUpdate
+	}
+
+	/// Replace the content with the next (newer) entry of the history, restoring the entered content after the newest
+	/// entry.
+	fn recall_next(&mut self) -> EditAction {
+		let (Some((history, kind)), Some(index)) = (self.history.as_ref(), self.history_index)
+		else {
+			return EditAction::None;
+		};
+		let entries = history.entries(*kind);
+		if let Some(entry) = entries.get(index + 1) {
+			self.set_content(entry.as_str());
+			self.history_index = Some(index + 1);
+		}
+		else {
+			let draft = mem::take(&mut self.history_draft);
+			self.set_content(draft.as_str());
+		}
+		EditAction::ContentUpdate
+	}
+
+	/// Does the candidate complete the text, matching from the start of the candidate, or after any `/`, so that
+	/// references can also be completed from their short name.
+	fn is_completion_match(candidate: &str, text: &str) -> bool {
+		candidate.starts_with(text)
+			|| candidate
+				.match_indices('/')
+				.any(|(index, _)| candidate.get(index + 1..).is_some_and(|rest| rest.starts_with(text)))
+	}
+
+	/// Complete the text before the cursor to the first matching candidate, or to the next matching candidate when
+	/// already completing. The completed text is the longest text, starting at the start of the content or after a
+	/// space, that a candidate matches, so both whole commands and the last word of a line can be completed.
+	fn complete(&mut self) -> EditAction {
+		if self.completion.is_none() {
+			let before = UnicodeSegmentation::graphemes(self.content.as_str(), true)
+				.take(self.cursor_position)
+				.collect::<String>();
+			let tail = UnicodeSegmentation::graphemes(self.content.as_str(), true)
+				.skip(self.cursor_position)
+				.collect::<String>();
+			let starts = std::iter::once(0).chain(before.match_indices(' ').map(|(index, _)| index + 1));
+			for start in starts {
+				let (head, text) = before.split_at(start);
+				let matches: Vec<String> = self
+					.completions
+					.iter()
+					.filter(|candidate| Self::is_completion_match(candidate, text))
+					.cloned()
+					.collect();
+				if !matches.is_empty() {
+					self.completion = Some(Completion {
+						head: String::from(head),
+						index: 0,
+						matches,
+						tail,
+					});
+					break;
+				}
+			}
+		}
+		else if let Some(completion) = self.completion.as_mut() {
+			completion.index = (completion.index + 1) % completion.matches.len();
+		}
+
+		let Some(completion) = self.completion.as_ref()
+		else {
+			return EditAction::None;
+		};
+		let completed = format!("{}{}", completion.head, completion.matches[completion.index]);
+		self.cursor_position = UnicodeSegmentation::graphemes(completed.as_str(), true).count();
+		self.content = format!("{completed}{}", completion.tail);
+		self.history_index = None;
+		EditAction::ContentUpdate
+	}
+
 	pub(crate) fn handle_event(&mut self, event: Event) -> EditAction {
 		if self.read_only {
 			return EditAction::None;
 		}
+		if event
+			== Event::Key(KeyEvent {
+				code: KeyCode::Tab,
+				modifiers: KeyModifiers::NONE,
+			}) {
+			return self.complete();
+		}
+		self.completion = None;
 		match event {
+			Event::Key(KeyEvent {
+				code: KeyCode::Up,
+				modifiers: KeyModifiers::NONE,
+			}) => self.recall_previous(),
+			Event::Key(KeyEvent {
+				code: KeyCode::Down,
+				modifiers: KeyModifiers::NONE,
+			}) => self.recall_next(),
 			Event::Key(KeyEvent {
 				code: KeyCode::Backspace,
 				modifiers: KeyModifiers::NONE,
@@ -394,6 +564,16 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn set_cursor_position() {
+		let mut editable_line = EditableLine::new();
+		editable_line.set_content("foobar");
+		editable_line.set_cursor_position(2);
+		assert_eq!(editable_line.cursor_position(), 2);
+		editable_line.set_cursor_position(10);
+		assert_eq!(editable_line.cursor_position(), 6);
+	}
+
 	#[test]
 	fn multiple_width_unicode_single_width() {
 		let mut editable_line = EditableLine::new();
@@ -785,4 +965,166 @@ mod tests {
 		editable_line.set_content("abcd");
 		assert_eq!(editable_line.handle_event(Event::from(KeyCode::Esc)), EditAction::None);
 	}
+
+	fn create_editable_line_with_history(entries: &[&str]) -> EditableLine {
+		let history = InputHistory::new(10);
+		for entry in entries {
+			history.push(HistoryKind::Exec, entry);
+		}
+		let mut editable_line = EditableLine::new();
+		editable_line.set_history(Some((history, HistoryKind::Exec)));
+		editable_line
+	}
+
+	#[test]
+	fn history_recall_previous_and_next() {
+		let mut editable_line = create_editable_line_with_history(&["first", "second"]);
+		editable_line.set_content("draft");
+		assert_eq!(
+			editable_line.handle_event(Event::from(KeyCode::Up)),
+			EditAction::ContentUpdate
+		);
+		assert_eq!(editable_line.get_content(), "second");
+		_ = editable_line.handle_event(Event::from(KeyCode::Up));
+		assert_eq!(editable_line.get_content(), "first");
+		assert_eq!(editable_line.handle_event(Event::from(KeyCode::Up)), EditAction::None);
+		assert_eq!(editable_line.get_content(), "first");
+		_ = editable_line.handle_event(Event::from(KeyCode::Down));
+		assert_eq!(editable_line.get_content(), "second");
+		_ = editable_line.handle_event(Event::from(KeyCode::Down));
+		assert_eq!(editable_line.get_content(), "draft");
+		assert_eq!(editable_line.handle_event(Event::from(KeyCode::Down)), EditAction::None);
+	}
+
+	#[test]
+	fn history_recall_moves_cursor_to_end() {
+		let mut editable_line = create_editable_line_with_history(&["foo"]);
+		_ = editable_line.handle_event(Event::from(KeyCode::Up));
+		assert_eq!(editable_line.cursor_position(), 3);
+	}
+
+	#[test]
+	fn history_recall_empty_history() {
+		let mut editable_line = create_editable_line_with_history(&[]);
+		assert_eq!(editable_line.handle_event(Event::from(KeyCode::Up)), EditAction::None);
+		assert_eq!(editable_line.handle_event(Event::from(KeyCode::Down)), EditAction::None);
+	}
+
+	#[test]
+	fn history_recall_without_history() {
+		let mut editable_line = EditableLine::new();
+		assert_eq!(editable_line.handle_event(Event::from(KeyCode::Up)), EditAction::None);
+	}
+
+	#[test]
+	fn history_recall_read_only() {
+		let mut editable_line = create_editable_line_with_history(&["foo"]);
+		editable_line.set_read_only(true);
+		assert_eq!(editable_line.handle_event(Event::from(KeyCode::Up)), EditAction::None);
+	}
+
+	#[test]
+	fn save_history() {
+		let mut editable_line = create_editable_line_with_history(&["foo"]);
+		editable_line.set_content("bar");
+		editable_line.save_history();
+		_ = editable_line.handle_event(Event::from(KeyCode::Up));
+		assert_eq!(editable_line.get_content(), "bar");
+	}
+
+	fn create_editable_line_with_completions(completions: &[&str]) -> EditableLine {
+		let mut editable_line = EditableLine::new();
+		editable_line.set_completions(completions.iter().map(|completion| String::from(*completion)).collect());
+		editable_line
+	}
+
+	#[test]
+	fn complete_first_match() {
+		let mut editable_line = create_editable_line_with_completions(&["bar", "foo", "foobar"]);
+		editable_line.set_content("fo");
+		assert_eq!(
+			editable_line.handle_event(Event::from(KeyCode::Tab)),
+			EditAction::ContentUpdate
+		);
+		assert_eq!(editable_line.get_content(), "foo");
+		assert_eq!(editable_line.cursor_position(), 3);
+		assert_eq!(
+			editable_line.completion_matches(),
+			Some((vec![String::from("foo"), String::from("foobar")].as_slice(), 0))
+		);
+	}
+
+	#[test]
+	fn complete_cycles_matches() {
+		let mut editable_line = create_editable_line_with_completions(&["foo", "foobar"]);
+		editable_line.set_content("f");
+		_ = editable_line.handle_event(Event::from(KeyCode::Tab));
+		_ = editable_line.handle_event(Event::from(KeyCode::Tab));
+		assert_eq!(editable_line.get_content(), "foobar");
+		_ = editable_line.handle_event(Event::from(KeyCode::Tab));
+		assert_eq!(editable_line.get_content(), "foo");
+	}
+
+	#[test]
+	fn complete_whole_content_before_last_word() {
+		let mut editable_line = create_editable_line_with_completions(&["cargo test", "test"]);
+		editable_line.set_content("cargo t");
+		_ = editable_line.handle_event(Event::from(KeyCode::Tab));
+		assert_eq!(editable_line.get_content(), "cargo test");
+	}
+
+	#[test]
+	fn complete_last_word() {
+		let mut editable_line = create_editable_line_with_completions(&["feature"]);
+		editable_line.set_content("-C abc f");
+		_ = editable_line.handle_event(Event::from(KeyCode::Tab));
+		assert_eq!(editable_line.get_content(), "-C abc feature");
+	}
+
+	#[test]
+	fn complete_after_slash() {
+		let mut editable_line = create_editable_line_with_completions(&["refs/heads/feature"]);
+		editable_line.set_content("fea");
+		_ = editable_line.handle_event(Event::from(KeyCode::Tab));
+		assert_eq!(editable_line.get_content(), "refs/heads/feature");
+	}
+
+	#[test]
+	fn complete_keeps_content_after_cursor() {
+		let mut editable_line = create_editable_line_with_completions(&["foo"]);
+		editable_line.set_content("f bar");
+		for _ in 0..4 {
+			_ = editable_line.handle_event(Event::from(KeyCode::Left));
+		}
+		_ = editable_line.handle_event(Event::from(KeyCode::Tab));
+		assert_eq!(editable_line.get_content(), "foo bar");
+		assert_eq!(editable_line.cursor_position(), 3);
+	}
+
+	#[test]
+	fn complete_no_match() {
+		let mut editable_line = create_editable_line_with_completions(&["foo"]);
+		editable_line.set_content("x");
+		assert_eq!(editable_line.handle_event(Event::from(KeyCode::Tab)), EditAction::None);
+		assert_eq!(editable_line.get_content(), "x");
+		assert_eq!(editable_line.completion_matches(), None);
+	}
+
+	#[test]
+	fn complete_restarts_after_edit() {
+		let mut editable_line = create_editable_line_with_completions(&["foo", "foobar"]);
+		editable_line.set_content("f");
+		_ = editable_line.handle_event(Event::from(KeyCode::Tab));
+		_ = editable_line.handle_event(Event::from(KeyCode::Char('b')));
+		assert_eq!(editable_line.completion_matches(), None);
+		_ = editable_line.handle_event(Event::from(KeyCode::Tab));
+		assert_eq!(editable_line.get_content(), "foobar");
+	}
+
+	#[test]
+	fn complete_read_only() {
+		let mut editable_line = create_editable_line_with_completions(&["foo"]);
+		editable_line.set_read_only(true);
+		assert_eq!(editable_line.handle_event(Event::from(KeyCode::Tab)), EditAction::None);
+	}
 }
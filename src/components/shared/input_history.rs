@@ -0,0 +1,264 @@
+use std::{
+	fmt::{Display, Formatter},
+	fs::{read_to_string, write},
+	io,
+	path::{Path, PathBuf},
+	sync::Arc,
+};
+
+use parking_lot::Mutex;
+
+const DEFAULT_LIMIT: u32 = 100;
+
+/// The kind of input that is recalled, each kind keeping its own history.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum HistoryKind {
+	/// The commands of `exec` lines.
+	Exec,
+	/// The labels of `label`, `reset` and `merge` lines.
+	Label,
+	/// The terms of searches.
+	Search,
+}
+
+impl HistoryKind {
+	fn parse(kind: &str) -> Option<Self> {
+		match kind {
+			"exec" => Some(Self::Exec),
+			"label" => Some(Self::Label),
+			"search" => Some(Self::Search),
+			_ => None,
+		}
+	}
+}
+
+impl Display for HistoryKind {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match *self {
+			Self::Exec => write!(f, "exec"),
+			Self::Label => write!(f, "label"),
+			Self::Search => write!(f, "search"),
+		}
+	}
+}
+
+#[derive(Debug)]
+struct State {
+	entries: Vec<(HistoryKind, String)>,
+	limit: usize,
+	modified: bool,
+	path: Option<PathBuf>,
+}
+
+// the limit is read from the Git config as a u32, which only exceeds a usize on 16-bit targets
+fn clamp_limit(limit: u32) -> usize {
+	usize::try_from(limit).unwrap_or(usize::MAX)
+}
+
+/// The previously entered inputs, shared between the components that edit a line, and optionally persisted to a file.
+/// Each kind of input holds at most the limit of entries, ordered from oldest to newest, without duplicates.
+#[derive(Debug, Clone)]
+pub(crate) struct InputHistory {
+	state: Arc<Mutex<State>>,
+}
+
+impl InputHistory {
+	/// Create a history that is only kept in memory.
+	pub(crate) fn new(limit: u32) -> Self {
+		Self {
+			state: Arc::new(Mutex::new(State {
+				entries: vec![],
+				limit: clamp_limit(limit),
+				modified: false,
+				path: None,
+			})),
+		}
+	}
+
+	/// Create a history that is loaded from, and saved to, the file at the path. A missing or unreadable file results
+	/// in an empty history.
+	pub(crate) fn load(path: &Path, limit: u32) -> Self {
+		let max_entries = clamp_limit(limit);
+		let mut entries: Vec<(HistoryKind, String)> = read_to_string(path)
+			.unwrap_or_default()
+			.lines()
+			.filter_map(|line| {
+				let (kind, entry) = line.split_once(' ')?;
+				Some((HistoryKind::parse(kind)?, String::from(entry)))
+			})
+			.filter(|(_, entry)| !entry.is_empty())
+			.collect();
+		for kind in [HistoryKind::Exec, HistoryKind::Label, HistoryKind::Search] {
+			let total = entries.iter().filter(|&&(k, _)| k == kind).count();
+			let mut excess = total.saturating_sub(max_entries);
+			entries.retain(|&(k, _)| {
+				if k == kind && excess > 0 {
+					excess -= 1;
+					return false;
+				}
+				true
+			});
+		}
+		Self {
+			state: Arc::new(Mutex::new(State {
+				entries,
+				limit: max_entries,
+				modified: false,
+				path: Some(PathBuf::from(path)),
+			})),
+		}
+	}
+
+	/// Add an entry as the newest of its kind, removing an earlier identical entry and the oldest entry when over the
+	/// limit.
+	pub(crate) fn push(&self, kind: HistoryKind, entry: &str) {
+		let mut state = self.state.lock();
+		if entry.is_empty() || state.limit == 0 {
+			return;
+		}
+		state.entries.retain(|(k, e)| *k != kind || e != entry);
+		state.entries.push((kind, String::from(entry)));
+		let total = state.entries.iter().filter(|&&(k, _)| k == kind).count();
+		if total > state.limit {
+			if let Some(index) = state.entries.iter().position(|&(k, _)| k == kind) {
+				let _previous = state.entries.remove(index);
+			}
+		}
+		state.modified = true;
+	}
+
+	/// Write the entries to the file of the history, when entries were added since the last write. The entries are
+	/// kept in memory when the write fails, and the write is not retried until another entry is added.
+	///
+	/// # Errors
+	/// Will result in an error if the file cannot be written.
+	pub(crate) fn save(&self) -> Result<(), io::Error> {
+		let mut state = self.state.lock();
+		if !state.modified {
+			return Ok(());
+		}
+		state.modified = false;
+		let Some(path) = state.path.as_ref()
+		else {
+			return Ok(());
+		};
+		let mut contents = String::new();
+		for (kind, entry) in &state.entries {
+			contents.push_str(format!("{kind} {entry}\n").as_str());
+		}
+		write(path, contents)
+	}
+
+	/// Get the entries of a kind, ordered from oldest to newest.
+	pub(crate) fn entries(&self, kind: HistoryKind) -> Vec<String> {
+		self.state
+			.lock()
+			.entries
+			.iter()
+			.filter(|&&(k, _)| k == kind)
+			.map(|(_, entry)| entry.clone())
+			.collect()
+	}
+}
+
+impl Default for InputHistory {
+	fn default() -> Self {
+		Self::new(DEFAULT_LIMIT)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use claims::{assert_err, assert_ok};
+
+	use super::*;
+	use crate::test_helpers::with_temporary_path;
+
+	#[test]
+	fn push_and_entries() {
+		let history = InputHistory::new(10);
+		history.push(HistoryKind::Exec, "make");
+		history.push(HistoryKind::Search, "foo");
+		history.push(HistoryKind::Exec, "cargo test");
+		assert_eq!(history.entries(HistoryKind::Exec), vec!["make", "cargo test"]);
+		assert_eq!(history.entries(HistoryKind::Search), vec!["foo"]);
+		assert!(history.entries(HistoryKind::Label).is_empty());
+	}
+
+	#[test]
+	fn push_duplicate_moves_to_newest() {
+		let history = InputHistory::new(10);
+		history.push(HistoryKind::Exec, "make");
+		history.push(HistoryKind::Exec, "cargo test");
+		history.push(HistoryKind::Exec, "make");
+		assert_eq!(history.entries(HistoryKind::Exec), vec!["cargo test", "make"]);
+	}
+
+	#[test]
+	fn push_empty() {
+		let history = InputHistory::new(10);
+		history.push(HistoryKind::Exec, "");
+		assert!(history.entries(HistoryKind::Exec).is_empty());
+	}
+
+	#[test]
+	fn push_over_limit() {
+		let history = InputHistory::new(2);
+		history.push(HistoryKind::Search, "keep");
+		history.push(HistoryKind::Exec, "a");
+		history.push(HistoryKind::Exec, "b");
+		history.push(HistoryKind::Exec, "c");
+		assert_eq!(history.entries(HistoryKind::Exec), vec!["b", "c"]);
+		assert_eq!(history.entries(HistoryKind::Search), vec!["keep"]);
+	}
+
+	#[test]
+	fn push_zero_limit() {
+		let history = InputHistory::new(0);
+		history.push(HistoryKind::Exec, "make");
+		assert!(history.entries(HistoryKind::Exec).is_empty());
+	}
+
+	#[test]
+	fn load_and_save() {
+		with_temporary_path(|path| {
+			let file = path.join("history");
+			let history = InputHistory::load(file.as_path(), 10);
+			assert!(history.entries(HistoryKind::Exec).is_empty());
+			history.push(HistoryKind::Exec, "cargo test --workspace");
+			history.push(HistoryKind::Label, "onto");
+			history.save().unwrap();
+
+			let history = InputHistory::load(file.as_path(), 10);
+			assert_eq!(history.entries(HistoryKind::Exec), vec!["cargo test --workspace"]);
+			assert_eq!(history.entries(HistoryKind::Label), vec!["onto"]);
+		});
+	}
+
+	#[test]
+	fn save_write_error() {
+		with_temporary_path(|path| {
+			let history = InputHistory::load(path.join("missing").join("history").as_path(), 10);
+			assert_ok!(history.save());
+			history.push(HistoryKind::Exec, "make");
+			assert_err!(history.save());
+			assert_ok!(history.save());
+			assert_eq!(history.entries(HistoryKind::Exec), vec!["make"]);
+		});
+	}
+
+	#[test]
+	fn load_skips_invalid_lines_and_limits() {
+		with_temporary_path(|path| {
+			let file = path.join("history");
+			write(
+				file.as_path(),
+				"exec a\nunknown b\nexec\nsearch c\nexec d\nexec e\n",
+			)
+			.unwrap();
+			let history = InputHistory::load(file.as_path(), 2);
+			assert_eq!(history.entries(HistoryKind::Exec), vec!["d", "e"]);
+			assert_eq!(history.entries(HistoryKind::Search), vec!["c"]);
+		});
+	}
+}
@@ -0,0 +1,190 @@
+#[cfg(test)]
+mod tests;
+
+use lazy_static::lazy_static;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{
+	components::shared::EditableLine,
+	display::DisplayColor,
+	input::{Event, InputOptions, KeyCode, KeyEvent, KeyModifiers},
+	view::{LineSegment, ViewData, ViewDataUpdater, ViewLine},
+};
+
+lazy_static! {
+	pub(crate) static ref INPUT_OPTIONS: InputOptions = InputOptions::RESIZE;
+}
+
+const FINISH_EVENT: Event = Event::Key(KeyEvent {
+	code: KeyCode::Char('d'),
+	modifiers: KeyModifiers::CONTROL,
+});
+
+const CANCEL_EVENT: Event = Event::Key(KeyEvent {
+	code: KeyCode::Esc,
+	modifiers: KeyModifiers::NONE,
+});
+
+/// A multiple line text editor, where `Enter` starts a new line, and editing is finished with `Ctrl+D` or cancelled
+/// with `Esc`.
+pub(crate) struct TextEditor {
+	cancelled: bool,
+	cursor_row: usize,
+	finished: bool,
+	lines: Vec<EditableLine>,
+	view_data: ViewData,
+}
+
+impl TextEditor {
+	pub(crate) fn new() -> Self {
+		let view_data = ViewData::new(|updater| {
+			updater.set_show_title(true);
+		});
+		Self {
+			cancelled: false,
+			cursor_row: 0,
+			finished: false,
+			lines: vec![EditableLine::new()],
+			view_data,
+		}
+	}
+
+	fn create_line(content: &str, cursor_position: usize) -> EditableLine {
+		let mut line = EditableLine::new();
+		line.set_content(content);
+		line.set_cursor_position(cursor_position);
+		line
+	}
+
+	fn line_length(line: &EditableLine) -> usize {
+		UnicodeSegmentation::graphemes(line.get_content(), true).count()
+	}
+
+	pub(crate) fn build_view_data<F, G>(&mut self, before_build: F, after_build: G) -> &ViewData
+	where
+		F: FnOnce(&mut ViewDataUpdater<'_>),
+		G: FnOnce(&mut ViewDataUpdater<'_>),
+	{
+		self.view_data.update_view_data(|updater| {
+			updater.clear();
+			before_build(updater);
+			for (row, line) in self.lines.iter().enumerate() {
+				if row == self.cursor_row {
+					updater.push_line(ViewLine::from(line.line_segments()));
+				}
+				else {
+					updater.push_line(ViewLine::from(vec![LineSegment::new(line.get_content())]));
+				}
+			}
+			updater.push_trailing_line(ViewLine::new_pinned(vec![LineSegment::new_with_color(
+				"Ctrl+D to finish, Esc to cancel",
+				DisplayColor::IndicatorColor,
+			)]));
+			updater.ensure_column_visible(self.lines[self.cursor_row].cursor_position());
+			updater.ensure_line_visible(self.cursor_row);
+			after_build(updater);
+		});
+		&self.view_data
+	}
+
+	pub(crate) fn handle_event(&mut self, event: Event) {
+		if event == FINISH_EVENT {
+			self.finished = true;
+			return;
+		}
+		if event == CANCEL_EVENT {
+			self.cancelled = true;
+			self.finished = true;
+			return;
+		}
+
+		let Event::Key(KeyEvent {
+			code,
+			modifiers: KeyModifiers::NONE,
+		}) = event
+		else {
+			return;
+		};
+		let row = self.cursor_row;
+		let column = self.lines[row].cursor_position();
+		let length = Self::line_length(&self.lines[row]);
+		match code {
+			KeyCode::Enter => {
+				let content = self.lines[row].get_content();
+				let before = UnicodeSegmentation::graphemes(content, true)
+					.take(column)
+					.collect::<String>();
+				let after = UnicodeSegmentation::graphemes(content, true)
+					.skip(column)
+					.collect::<String>();
+				self.lines[row] = Self::create_line(before.as_str(), column);
+				self.lines.insert(row + 1, Self::create_line(after.as_str(), 0));
+				self.cursor_row += 1;
+			},
+			KeyCode::Backspace if column == 0 && row > 0 => {
+				let removed = self.lines.remove(row);
+				let previous = &self.lines[row - 1];
+				let previous_length = Self::line_length(previous);
+				let joined = format!("{}{}", previous.get_content(), removed.get_content());
+				self.lines[row - 1] = Self::create_line(joined.as_str(), previous_length);
+				self.cursor_row -= 1;
+			},
+			KeyCode::Delete if column == length && row + 1 < self.lines.len() => {
+				let removed = self.lines.remove(row + 1);
+				let joined = format!("{}{}", self.lines[row].get_content(), removed.get_content());
+				self.lines[row] = Self::create_line(joined.as_str(), column);
+			},
+			KeyCode::Up if row > 0 => {
+				self.cursor_row -= 1;
+				self.lines[row - 1].set_cursor_position(column);
+			},
+			KeyCode::Down if row + 1 < self.lines.len() => {
+				self.cursor_row += 1;
+				self.lines[row + 1].set_cursor_position(column);
+			},
+			KeyCode::Left if column == 0 && row > 0 => {
+				self.cursor_row -= 1;
+				let previous_length = Self::line_length(&self.lines[row - 1]);
+				self.lines[row - 1].set_cursor_position(previous_length);
+			},
+			KeyCode::Right if column == length && row + 1 < self.lines.len() => {
+				self.cursor_row += 1;
+				self.lines[row + 1].set_cursor_position(0);
+			},
+			// the lines do not have a history to recall or candidates to complete
+			KeyCode::Up | KeyCode::Down | KeyCode::Tab => {},
+			_ => _ = self.lines[row].handle_event(event),
+		}
+	}
+
+	/// Set the content, with a line for each line of the content, and the cursor at the end of the first line.
+	pub(crate) fn set_content(&mut self, content: &str) {
+		self.lines = content.split('\n').map(|line| Self::create_line(line, 0)).collect();
+		self.cursor_row = 0;
+		let first_length = Self::line_length(&self.lines[0]);
+		self.lines[0].set_cursor_position(first_length);
+	}
+
+	pub(crate) fn reset(&mut self) {
+		self.set_content("");
+		self.cancelled = false;
+		self.finished = false;
+	}
+
+	pub(crate) const fn is_finished(&self) -> bool {
+		self.finished
+	}
+
+	pub(crate) const fn is_cancelled(&self) -> bool {
+		self.cancelled
+	}
+
+	/// Get the content of each line.
+	pub(crate) fn lines(&self) -> impl Iterator<Item = &str> {
+		self.lines.iter().map(EditableLine::get_content)
+	}
+
+	pub(crate) fn get_content(&self) -> String {
+		self.lines().collect::<Vec<&str>>().join("\n")
+	}
+}
@@ -0,0 +1,176 @@
+use rstest::rstest;
+
+use super::*;
+use crate::{assert_rendered_output, test_helpers::assertions::assert_rendered_output::AssertRenderOptions};
+
+fn handle_events(module: &mut TextEditor, events: &[Event]) {
+	for event in events {
+		module.handle_event(*event);
+	}
+}
+
+#[test]
+fn with_before_and_after_build() {
+	let mut module = TextEditor::new();
+	module.set_content("subject\n\nbody");
+	let view_data = module.build_view_data(
+		|updater| {
+			updater.push_leading_line(ViewLine::from("Before"));
+		},
+		|updater| {
+			updater.push_trailing_line(ViewLine::from("After"));
+		},
+	);
+	assert_rendered_output!(
+		Options AssertRenderOptions::INCLUDE_TRAILING_WHITESPACE | AssertRenderOptions::INCLUDE_STYLE,
+		view_data,
+		"{TITLE}",
+		"{LEADING}",
+		"{Normal}Before",
+		"{BODY}",
+		"{Normal}subject{Normal,Underline} ",
+		"{Normal}",
+		"{Normal}body",
+		"{TRAILING}",
+		"{IndicatorColor}Ctrl+D to finish, Esc to cancel",
+		"{Normal}After"
+	);
+}
+
+#[test]
+fn edit_line() {
+	let mut module = TextEditor::new();
+	module.set_content("foo\nbar");
+	handle_events(&mut module, &[
+		Event::from(KeyCode::Backspace),
+		Event::from('x'),
+		Event::from(KeyCode::Left),
+		Event::from(KeyCode::Left),
+	]);
+	assert_eq!(module.get_content(), "fox\nbar");
+	let view_data = module.build_view_data(|_| {}, |_| {});
+	assert_rendered_output!(
+		Style view_data,
+		"{TITLE}",
+		"{BODY}",
+		"{Normal}f{Normal,Underline}o{Normal}x",
+		"{Normal}bar",
+		"{TRAILING}",
+		"{IndicatorColor}Ctrl+D to finish, Esc to cancel"
+	);
+}
+
+#[test]
+fn enter_splits_line() {
+	let mut module = TextEditor::new();
+	module.set_content("foobar");
+	handle_events(&mut module, &[
+		Event::from(KeyCode::Left),
+		Event::from(KeyCode::Left),
+		Event::from(KeyCode::Enter),
+		Event::from(KeyCode::Enter),
+	]);
+	assert_eq!(module.get_content(), "foob\n\nar");
+	assert_eq!(module.cursor_row, 2);
+	assert_eq!(module.lines[2].cursor_position(), 0);
+}
+
+#[test]
+fn backspace_joins_with_previous_line() {
+	let mut module = TextEditor::new();
+	module.set_content("foo\nbar");
+	handle_events(&mut module, &[
+		Event::from(KeyCode::Down),
+		Event::from(KeyCode::Home),
+		Event::from(KeyCode::Backspace),
+	]);
+	assert_eq!(module.get_content(), "foobar");
+	assert_eq!(module.cursor_row, 0);
+	assert_eq!(module.lines[0].cursor_position(), 3);
+}
+
+#[test]
+fn backspace_at_start_of_content() {
+	let mut module = TextEditor::new();
+	module.set_content("foo");
+	handle_events(&mut module, &[Event::from(KeyCode::Home), Event::from(KeyCode::Backspace)]);
+	assert_eq!(module.get_content(), "foo");
+}
+
+#[test]
+fn delete_joins_with_next_line() {
+	let mut module = TextEditor::new();
+	module.set_content("foo\nbar");
+	module.handle_event(Event::from(KeyCode::Delete));
+	assert_eq!(module.get_content(), "foobar");
+	assert_eq!(module.lines[0].cursor_position(), 3);
+}
+
+#[test]
+fn delete_at_end_of_content() {
+	let mut module = TextEditor::new();
+	module.set_content("foo");
+	module.handle_event(Event::from(KeyCode::Delete));
+	assert_eq!(module.get_content(), "foo");
+}
+
+#[rstest]
+#[case::up_keeps_column(&[KeyCode::Down, KeyCode::Down, KeyCode::Left, KeyCode::Up], 1, 1)]
+#[case::up_clamps_column(&[KeyCode::Down, KeyCode::Down, KeyCode::Up], 1, 2)]
+#[case::up_on_first_line(&[KeyCode::Up], 0, 6)]
+#[case::down_clamps_column(&[KeyCode::Down], 1, 2)]
+#[case::down_on_last_line(&[KeyCode::Down, KeyCode::Down, KeyCode::Down], 2, 2)]
+#[case::left_to_previous_line(&[KeyCode::Down, KeyCode::Home, KeyCode::Left], 0, 6)]
+#[case::right_to_next_line(&[KeyCode::Right], 1, 0)]
+#[case::right_on_last_line(&[KeyCode::Down, KeyCode::Down, KeyCode::End, KeyCode::Right], 2, 3)]
+fn move_cursor(#[case] keys: &[KeyCode], #[case] row: usize, #[case] column: usize) {
+	let mut module = TextEditor::new();
+	module.set_content("foobar\nfo\nbaz");
+	for key in keys {
+		module.handle_event(Event::from(*key));
+	}
+	assert_eq!(module.cursor_row, row);
+	assert_eq!(module.lines[row].cursor_position(), column);
+}
+
+#[test]
+fn finish() {
+	let mut module = TextEditor::new();
+	module.handle_event(Event::from(KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL)));
+	assert!(module.is_finished());
+	assert!(!module.is_cancelled());
+}
+
+#[test]
+fn cancel() {
+	let mut module = TextEditor::new();
+	module.handle_event(Event::from(KeyCode::Esc));
+	assert!(module.is_finished());
+	assert!(module.is_cancelled());
+}
+
+#[test]
+fn ignores_other_modifiers() {
+	let mut module = TextEditor::new();
+	module.set_content("foo");
+	module.handle_event(Event::from(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::ALT)));
+	assert_eq!(module.get_content(), "foo");
+}
+
+#[test]
+fn reset() {
+	let mut module = TextEditor::new();
+	module.set_content("foo\nbar");
+	module.handle_event(Event::from(KeyCode::Esc));
+	module.reset();
+	assert_eq!(module.get_content(), "");
+	assert!(!module.is_finished());
+	assert!(!module.is_cancelled());
+}
+
+#[test]
+fn lines() {
+	let mut module = TextEditor::new();
+	module.set_content("foo\n\nbar");
+	assert_eq!(module.lines().collect::<Vec<&str>>(), vec!["foo", "", "bar"]);
+}
@@ -16,7 +16,15 @@ mod key_bindings;
 mod theme;
 mod utils;
 
-use self::utils::{get_bool, get_diff_ignore_whitespace, get_diff_show_whitespace, get_string, get_unsigned_integer};
+use self::utils::{
+	get_action_commands,
+	get_bool,
+	get_diff_ignore_whitespace,
+	get_diff_show_whitespace,
+	get_named_strings,
+	get_string,
+	get_unsigned_integer,
+};
 pub(crate) use self::{
 	color::Color,
 	diff_ignore_whitespace_setting::DiffIgnoreWhitespaceSetting,
@@ -24,6 +32,7 @@ pub(crate) use self::{
 	git_config::GitConfig,
 	key_bindings::KeyBindings,
 	theme::Theme,
+	utils::MAX_KEY_SEQUENCE_LENGTH,
 };
 use crate::{
 	config::{
@@ -31,6 +40,7 @@ use crate::{
 		utils::get_optional_string,
 	},
 	git::Repository,
+	todo_file::Action,
 };
 
 const DEFAULT_SPACE_SYMBOL: &str = "\u{b7}"; // ·
@@ -54,8 +64,16 @@ pub(crate) struct Config {
 	pub(crate) diff_tab_symbol: String,
 	/// The display width of the tab character.
 	pub(crate) diff_tab_width: u32,
+	/// If set, the argument passed to the external editor before the file, with `%` replaced by the selected line.
+	pub(crate) editor_line_argument: Option<String>,
+	/// The named commands that can be entered when inserting exec lines after the commits.
+	pub(crate) exec_templates: Vec<(String, String)>,
+	/// The maximum number of entries kept in each input history.
+	pub(crate) input_history_limit: u32,
 	/// If set, automatically add an exec line with the command after every modified line
 	pub(crate) post_modified_line_exec_command: Option<String>,
+	/// The commands to add after modified lines with an action, instead of the command for all modified lines.
+	pub(crate) post_modified_line_exec_action_commands: Vec<(Action, String)>,
 	/// The maximum number of undo steps.
 	pub(crate) undo_limit: u32,
 	/// Configuration options loaded directly from Git.
@@ -83,11 +101,18 @@ impl Config {
 			)?,
 			diff_tab_symbol: get_string(git_config, "interactive-rebase-tool.diffTabSymbol", DEFAULT_TAB_SYMBOL)?,
 			diff_tab_width: get_unsigned_integer(git_config, "interactive-rebase-tool.diffTabWidth", 4)?,
+			editor_line_argument: get_optional_string(git_config, "interactive-rebase-tool.editorLineArgument")?,
+			exec_templates: get_named_strings(git_config, "interactive-rebase-tool.execTemplate")?,
+			input_history_limit: get_unsigned_integer(git_config, "interactive-rebase-tool.inputHistoryLimit", 100)?,
 			undo_limit: get_unsigned_integer(git_config, "interactive-rebase-tool.undoLimit", 5000)?,
 			post_modified_line_exec_command: get_optional_string(
 				git_config,
 				"interactive-rebase-tool.postModifiedLineExecCommand",
 			)?,
+			post_modified_line_exec_action_commands: get_action_commands(
+				git_config,
+				"interactive-rebase-tool.postModifiedLineExecCommand",
+			)?,
 			git: GitConfig::new_with_config(git_config)?,
 			key_bindings: KeyBindings::new_with_config(git_config)?,
 			theme: Theme::new_with_config(git_config)?,
@@ -123,7 +148,7 @@ impl TryFrom<&crate::git::Config> for Config {
 mod tests {
 	use std::fmt::Debug;
 
-	use claims::{assert_err_eq, assert_ok};
+	use claims::{assert_err_eq, assert_ok, assert_some_eq};
 	use rstest::rstest;
 
 	use super::*;
@@ -300,6 +325,20 @@ mod tests {
 		|config: Config| config.diff_space_symbol)
 	]
 	#[case::diff_space_symbol("diffSpaceSymbol", "-", String::from("-"), |config: Config| config.diff_space_symbol)]
+	#[case::editor_line_argument(
+		"editorLineArgument",
+		"+%",
+		Some(String::from("+%")),
+		|config: Config| config.editor_line_argument
+	)]
+	#[case::editor_line_argument_default(
+		"editorLineArgument",
+		"",
+		None,
+		|config: Config| config.editor_line_argument
+	)]
+	#[case::input_history_limit_default("inputHistoryLimit", "", 100, |config: Config| config.input_history_limit)]
+	#[case::input_history_limit("inputHistoryLimit", "42", 42, |config: Config| config.input_history_limit)]
 	#[case::undo_limit_default("undoLimit", "", 5000, |config: Config| config.undo_limit)]
 	#[case::undo_limit("undoLimit", "42", 42, |config: Config| config.undo_limit)]
 	#[case::post_modified_line_exec_command(
@@ -336,6 +375,45 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn exec_templates() {
+		with_git_config(
+			&[
+				"[interactive-rebase-tool \"execTemplate\"]",
+				"test = cargo test",
+				"lint = cargo clippy",
+			],
+			|git_config| {
+				let config = Config::new_with_config(Some(&git_config)).unwrap();
+				assert_eq!(config.exec_templates, vec![
+					(String::from("test"), String::from("cargo test")),
+					(String::from("lint"), String::from("cargo clippy")),
+				]);
+			},
+		);
+	}
+
+	#[test]
+	fn post_modified_line_exec_action_commands() {
+		with_git_config(
+			&[
+				"[interactive-rebase-tool]",
+				"postModifiedLineExecCommand = command",
+				"[interactive-rebase-tool \"postModifiedLineExecCommand\"]",
+				"drop = drop-command",
+				"update-ref = ref-command",
+			],
+			|git_config| {
+				let config = Config::new_with_config(Some(&git_config)).unwrap();
+				assert_some_eq!(config.post_modified_line_exec_command, "command");
+				assert_eq!(config.post_modified_line_exec_action_commands, vec![
+					(Action::Drop, String::from("drop-command")),
+					(Action::UpdateRef, String::from("ref-command")),
+				]);
+			},
+		);
+	}
+
 	#[rstest]
 	#[case::auto_select_next("autoSelectNext", "invalid", ConfigErrorCause::InvalidBoolean)]
 	#[case::diff_ignore_whitespace("diffIgnoreWhitespace", "invalid", ConfigErrorCause::InvalidDiffIgnoreWhitespace)]
@@ -343,6 +421,7 @@ mod tests {
 	#[case::diff_show_whitespace("diffShowWhitespace", "invalid", ConfigErrorCause::InvalidShowWhitespace)]
 	#[case::diff_tab_width_non_integer("diffTabWidth", "invalid", ConfigErrorCause::InvalidUnsignedInteger)]
 	#[case::diff_tab_width_non_poitive_integer("diffTabWidth", "-100", ConfigErrorCause::InvalidUnsignedInteger)]
+	#[case::input_history_limit_non_integer("inputHistoryLimit", "invalid", ConfigErrorCause::InvalidUnsignedInteger)]
 	#[case::undo_limit_non_integer("undoLimit", "invalid", ConfigErrorCause::InvalidUnsignedInteger)]
 	#[case::undo_limit_non_positive_integer("undoLimit", "-100", ConfigErrorCause::InvalidUnsignedInteger)]
 	fn value_parsing_invalid(#[case] config_name: &str, #[case] config_value: &str, #[case] cause: ConfigErrorCause) {
@@ -369,6 +448,7 @@ mod tests {
 	#[case::diff_show_whitespace("diffShowWhitespace")]
 	#[case::diff_tab_symbol("diffTabSymbol")]
 	#[case::diff_space_symbol("diffSpaceSymbol")]
+	#[case::editor_line_argument("editorLineArgument")]
 	#[case::post_modified_line_exec_command("postModifiedLineExecCommand")]
 	fn value_parsing_invalid_utf(#[case] config_name: &str) {
 		with_git_config(
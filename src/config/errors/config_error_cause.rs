@@ -28,6 +28,9 @@ pub(crate) enum ConfigErrorCause {
 	/// The input provided is outside of valid range for an unsigned 32-bit integer.
 	#[error("The input provided is outside of valid range for an unsigned 32-bit integer")]
 	InvalidUnsignedInteger,
+	/// The input provided is not the name of a todo file action.
+	#[error("Must match the name of a todo file action, for example 'pick', 'drop' or 'update-ref'")]
+	InvalidAction,
 	/// The input provided is not a valid input keybinding.
 	#[error("The input provided is not a valid input keybinding.")]
 	InvalidKeyBinding,
@@ -1,5 +1,8 @@
 use crate::{
-	config::{utils::get_input, ConfigError},
+	config::{
+		utils::{get_input, get_unsigned_integer},
+		ConfigError,
+	},
 	git::Config,
 };
 
@@ -28,6 +31,8 @@ pub(crate) struct KeyBindings {
 	pub(crate) action_fixup: Vec<String>,
 	/// Key bindings for the pick action.
 	pub(crate) action_pick: Vec<String>,
+	/// Key bindings for the revert action.
+	pub(crate) action_revert: Vec<String>,
 	/// Key bindings for the reword action.
 	pub(crate) action_reword: Vec<String>,
 	/// Key bindings for the squash action.
@@ -46,6 +51,14 @@ pub(crate) struct KeyBindings {
 	pub(crate) help: Vec<String>,
 	/// Key bindings for inserting a line.
 	pub(crate) insert_line: Vec<String>,
+	/// Key bindings for inserting the update-ref lines for branches.
+	pub(crate) insert_update_refs: Vec<String>,
+	/// Key bindings for inserting exec lines after the commits.
+	pub(crate) insert_exec_lines: Vec<String>,
+	/// Key bindings for composing a new message for the selected commit.
+	pub(crate) reword_message: Vec<String>,
+	/// Key bindings for changing the author, committer date or trailers of the selected commits.
+	pub(crate) rewrite_metadata: Vec<String>,
 
 	/// Key bindings for moving down.
 	pub(crate) move_down: Vec<String>,
@@ -87,6 +100,8 @@ pub(crate) struct KeyBindings {
 
 	/// Key bindings for opening the external editor.
 	pub(crate) open_in_external_editor: Vec<String>,
+	/// Key bindings for opening the selected lines in the external editor.
+	pub(crate) open_selection_in_external_editor: Vec<String>,
 	/// Key bindings for rebasing.
 	pub(crate) rebase: Vec<String>,
 	/// Key bindings for redoing a change.
@@ -105,12 +120,44 @@ pub(crate) struct KeyBindings {
 	pub(crate) show_diff: Vec<String>,
 	/// Key bindings for toggling visual mode.
 	pub(crate) toggle_visual_mode: Vec<String>,
+	/// Key bindings for toggling the stack view.
+	pub(crate) toggle_stack_view: Vec<String>,
+	/// Key bindings for toggling the folding of fixup and squash lines.
+	pub(crate) toggle_fold_view: Vec<String>,
+	/// Key bindings for toggling the filter of the list by the search term.
+	pub(crate) toggle_filter: Vec<String>,
+	/// Key bindings for collapsing or expanding a section of the stack view.
+	pub(crate) toggle_section: Vec<String>,
 	/// Key bindings for undoing a change.
 	pub(crate) undo: Vec<String>,
+	/// Key bindings for browsing the undo history.
+	pub(crate) show_undo_history: Vec<String>,
+	/// Key bindings for applying the next action to all search matches.
+	pub(crate) apply_to_matches: Vec<String>,
+	/// Key bindings for marking or unmarking the selected lines.
+	pub(crate) toggle_mark: Vec<String>,
+	/// Key bindings for moving the marked lines after the selected line.
+	pub(crate) gather_marked: Vec<String>,
+	/// Key bindings for clearing the marked lines.
+	pub(crate) clear_marks: Vec<String>,
+	/// Key bindings for naming a mark for the selected line.
+	pub(crate) set_named_mark: Vec<String>,
+	/// Key bindings for jumping to the line of a named mark.
+	pub(crate) jump_to_named_mark: Vec<String>,
+	/// Key bindings for sorting the selected lines by the author date of the commits.
+	pub(crate) sort_by_author_date: Vec<String>,
+	/// Key bindings for sorting the selected lines by the committer date of the commits.
+	pub(crate) sort_by_commit_date: Vec<String>,
+	/// Key bindings for reversing the order of the selected lines.
+	pub(crate) reverse_selection: Vec<String>,
+	/// Key bindings for restoring the original order of the selected lines.
+	pub(crate) restore_order: Vec<String>,
 	/// Key bindings for the fixup specific action to toggle the c option.
 	pub(crate) fixup_keep_message_with_editor: Vec<String>,
 	/// Key bindings for the fixup specific action to toggle the c option.
 	pub(crate) fixup_keep_message: Vec<String>,
+	/// The time, in milliseconds, to wait for the next key of a key sequence.
+	pub(crate) key_sequence_timeout: u32,
 }
 
 impl KeyBindings {
@@ -130,6 +177,7 @@ impl KeyBindings {
 			action_edit: get_input(git_config, "interactive-rebase-tool.inputActionEdit", "e")?,
 			action_fixup: get_input(git_config, "interactive-rebase-tool.inputActionFixup", "f")?,
 			action_pick: get_input(git_config, "interactive-rebase-tool.inputActionPick", "p")?,
+			action_revert: get_input(git_config, "interactive-rebase-tool.inputActionRevert", "R")?,
 			action_reword: get_input(git_config, "interactive-rebase-tool.inputActionReword", "r")?,
 			action_squash: get_input(git_config, "interactive-rebase-tool.inputActionSquash", "s")?,
 			confirm_no,
@@ -139,9 +187,13 @@ impl KeyBindings {
 			force_rebase: get_input(git_config, "interactive-rebase-tool.inputForceRebase", "W")?,
 			help: get_input(git_config, "interactive-rebase-tool.inputHelp", "?")?,
 			insert_line: get_input(git_config, "interactive-rebase-tool.insertLine", "I")?,
+			insert_update_refs: get_input(git_config, "interactive-rebase-tool.insertUpdateRefs", "B")?,
+			insert_exec_lines: get_input(git_config, "interactive-rebase-tool.insertExecLines", "x")?,
+			reword_message: get_input(git_config, "interactive-rebase-tool.inputRewordMessage", "C")?,
+			rewrite_metadata: get_input(git_config, "interactive-rebase-tool.inputRewriteMetadata", "T")?,
 			move_down: get_input(git_config, "interactive-rebase-tool.inputMoveDown", "Down")?,
 			move_end: get_input(git_config, "interactive-rebase-tool.inputMoveEnd", "End")?,
-			move_home: get_input(git_config, "interactive-rebase-tool.inputMoveHome", "Home")?,
+			move_home: get_input(git_config, "interactive-rebase-tool.inputMoveHome", "Home g,g")?,
 			move_left: get_input(git_config, "interactive-rebase-tool.inputMoveLeft", "Left")?,
 			move_right: get_input(git_config, "interactive-rebase-tool.inputMoveRight", "Right")?,
 			move_down_step: get_input(git_config, "interactive-rebase-tool.inputMoveStepDown", "PageDown")?,
@@ -158,6 +210,11 @@ impl KeyBindings {
 			scroll_step_down: get_input(git_config, "interactive-rebase-tool.inputScrollStepDown", "PageDown")?,
 			scroll_step_up: get_input(git_config, "interactive-rebase-tool.inputScrollStepUp", "PageUp")?,
 			open_in_external_editor: get_input(git_config, "interactive-rebase-tool.inputOpenInExternalEditor", "!")?,
+			open_selection_in_external_editor: get_input(
+				git_config,
+				"interactive-rebase-tool.inputOpenSelectionInExternalEditor",
+				"@",
+			)?,
 			rebase: get_input(git_config, "interactive-rebase-tool.inputRebase", "w")?,
 			redo: get_input(git_config, "interactive-rebase-tool.inputRedo", "control+y")?,
 			remove_line: get_input(git_config, "interactive-rebase-tool.removeLine", "delete")?,
@@ -167,13 +224,33 @@ impl KeyBindings {
 			show_commit: get_input(git_config, "interactive-rebase-tool.inputShowCommit", "c")?,
 			show_diff: get_input(git_config, "interactive-rebase-tool.inputShowDiff", "d")?,
 			toggle_visual_mode: get_input(git_config, "interactive-rebase-tool.inputToggleVisualMode", "v")?,
+			toggle_stack_view: get_input(git_config, "interactive-rebase-tool.inputToggleStackView", "S")?,
+			toggle_fold_view: get_input(git_config, "interactive-rebase-tool.inputToggleFoldView", "Z")?,
+			toggle_filter: get_input(git_config, "interactive-rebase-tool.inputToggleFilter", "F")?,
+			toggle_section: get_input(git_config, "interactive-rebase-tool.inputToggleSection", "z")?,
 			undo: get_input(git_config, "interactive-rebase-tool.inputUndo", "control+z")?,
+			show_undo_history: get_input(git_config, "interactive-rebase-tool.inputShowUndoHistory", "H")?,
+			apply_to_matches: get_input(git_config, "interactive-rebase-tool.inputApplyToMatches", "A")?,
+			toggle_mark: get_input(git_config, "interactive-rebase-tool.inputToggleMark", "Space")?,
+			gather_marked: get_input(git_config, "interactive-rebase-tool.inputGatherMarked", "G")?,
+			clear_marks: get_input(git_config, "interactive-rebase-tool.inputClearMarks", "M")?,
+			set_named_mark: get_input(git_config, "interactive-rebase-tool.inputSetNamedMark", "m")?,
+			jump_to_named_mark: get_input(git_config, "interactive-rebase-tool.inputJumpToNamedMark", "'")?,
+			sort_by_author_date: get_input(git_config, "interactive-rebase-tool.inputSortByAuthorDate", "o")?,
+			sort_by_commit_date: get_input(git_config, "interactive-rebase-tool.inputSortByCommitDate", "O")?,
+			reverse_selection: get_input(git_config, "interactive-rebase-tool.inputReverseSelection", "V")?,
+			restore_order: get_input(git_config, "interactive-rebase-tool.inputRestoreOrder", "X")?,
 			fixup_keep_message_with_editor: get_input(
 				git_config,
 				"interactive-rebase-tool.fixupKeepMessageWithEditor",
 				"U",
 			)?,
 			fixup_keep_message: get_input(git_config, "interactive-rebase-tool.fixupKeepMessage", "u")?,
+			key_sequence_timeout: get_unsigned_integer(
+				git_config,
+				"interactive-rebase-tool.inputKeySequenceTimeout",
+				1000,
+			)?,
 		})
 	}
 }
@@ -229,6 +306,25 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn move_home_default_sequence() {
+		assert_eq!(KeyBindings::new_with_config(None).unwrap().move_home, vec![
+			String::from("Home"),
+			String::from("g g")
+		]);
+	}
+
+	#[test]
+	fn key_sequence_timeout() {
+		assert_eq!(KeyBindings::new_with_config(None).unwrap().key_sequence_timeout, 1000);
+		with_git_config(
+			&["[interactive-rebase-tool]", "inputKeySequenceTimeout = 250"],
+			|git_config| {
+				assert_eq!(KeyBindings::try_from(&git_config).unwrap().key_sequence_timeout, 250);
+			},
+		);
+	}
+
 	#[test]
 	fn try_from_git_config_error() {
 		with_git_config(&["[interactive-rebase-tool]", "inputAbort = invalid"], |git_config| {
@@ -244,6 +340,7 @@ mod tests {
 		config_test!(action_edit, "inputActionEdit", "e");
 		config_test!(action_fixup, "inputActionFixup", "f");
 		config_test!(action_pick, "inputActionPick", "p");
+		config_test!(action_revert, "inputActionRevert", "R");
 		config_test!(action_reword, "inputActionReword", "r");
 		config_test!(action_squash, "inputActionSquash", "s");
 		config_test!(confirm_no, "inputConfirmNo", "n");
@@ -253,6 +350,10 @@ mod tests {
 		config_test!(force_rebase, "inputForceRebase", "W");
 		config_test!(help, "inputHelp", "?");
 		config_test!(insert_line, "insertLine", "I");
+		config_test!(insert_update_refs, "insertUpdateRefs", "B");
+		config_test!(insert_exec_lines, "insertExecLines", "x");
+		config_test!(reword_message, "inputRewordMessage", "C");
+		config_test!(rewrite_metadata, "inputRewriteMetadata", "T");
 		config_test!(move_down, "inputMoveDown", "Down");
 		config_test!(move_end, "inputMoveEnd", "End");
 		config_test!(move_home, "inputMoveHome", "Home");
@@ -272,6 +373,7 @@ mod tests {
 		config_test!(scroll_step_down, "inputScrollStepDown", "PageDown");
 		config_test!(scroll_step_up, "inputScrollStepUp", "PageUp");
 		config_test!(open_in_external_editor, "inputOpenInExternalEditor", "!");
+		config_test!(open_selection_in_external_editor, "inputOpenSelectionInExternalEditor", "@");
 		config_test!(rebase, "inputRebase", "w");
 		config_test!(redo, "inputRedo", "Controly");
 		config_test!(remove_line, "removeLine", "Delete");
@@ -281,7 +383,22 @@ mod tests {
 		config_test!(show_commit, "inputShowCommit", "c");
 		config_test!(show_diff, "inputShowDiff", "d");
 		config_test!(toggle_visual_mode, "inputToggleVisualMode", "v");
+		config_test!(toggle_stack_view, "inputToggleStackView", "S");
+		config_test!(toggle_fold_view, "inputToggleFoldView", "Z");
+		config_test!(toggle_filter, "inputToggleFilter", "F");
+		config_test!(toggle_section, "inputToggleSection", "z");
 		config_test!(undo, "inputUndo", "Controlz");
+		config_test!(show_undo_history, "inputShowUndoHistory", "H");
+		config_test!(apply_to_matches, "inputApplyToMatches", "A");
+		config_test!(toggle_mark, "inputToggleMark", "Space");
+		config_test!(gather_marked, "inputGatherMarked", "G");
+		config_test!(clear_marks, "inputClearMarks", "M");
+		config_test!(set_named_mark, "inputSetNamedMark", "m");
+		config_test!(jump_to_named_mark, "inputJumpToNamedMark", "'");
+		config_test!(sort_by_author_date, "inputSortByAuthorDate", "o");
+		config_test!(sort_by_commit_date, "inputSortByCommitDate", "O");
+		config_test!(reverse_selection, "inputReverseSelection", "V");
+		config_test!(restore_order, "inputRestoreOrder", "X");
 		config_test!(fixup_keep_message_with_editor, "fixupKeepMessageWithEditor", "U");
 		config_test!(fixup_keep_message, "fixupKeepMessage", "u");
 	}
@@ -41,6 +41,8 @@ pub(crate) struct Theme {
 	pub(crate) color_action_fixup: Color,
 	/// The color for the pick action.
 	pub(crate) color_action_pick: Color,
+	/// The color for the revert action.
+	pub(crate) color_action_revert: Color,
 	/// The color for the reword action.
 	pub(crate) color_action_reword: Color,
 	/// The color for the squash action.
@@ -88,6 +90,7 @@ impl Theme {
 			color_action_exec: get_color(git_config, "interactive-rebase-tool.execColor", Color::LightWhite)?,
 			color_action_fixup: get_color(git_config, "interactive-rebase-tool.fixupColor", Color::LightMagenta)?,
 			color_action_pick: get_color(git_config, "interactive-rebase-tool.pickColor", Color::LightGreen)?,
+			color_action_revert: get_color(git_config, "interactive-rebase-tool.revertColor", Color::DarkRed)?,
 			color_action_reword: get_color(git_config, "interactive-rebase-tool.rewordColor", Color::LightYellow)?,
 			color_action_squash: get_color(git_config, "interactive-rebase-tool.squashColor", Color::LightCyan)?,
 			color_action_label: get_color(git_config, "interactive-rebase-tool.labelColor", Color::DarkYellow)?,
@@ -205,6 +208,7 @@ mod tests {
 		config_test!(color_action_exec, "execColor", Color::LightWhite);
 		config_test!(color_action_fixup, "fixupColor", Color::LightMagenta);
 		config_test!(color_action_pick, "pickColor", Color::LightGreen);
+		config_test!(color_action_revert, "revertColor", Color::DarkRed);
 		config_test!(color_action_reword, "rewordColor", Color::LightYellow);
 		config_test!(color_action_squash, "squashColor", Color::LightCyan);
 		config_test!(color_action_label, "labelColor", Color::DarkYellow);
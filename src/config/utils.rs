@@ -1,17 +1,21 @@
+mod get_action_commands;
 mod get_bool;
 mod get_diff_ignore_whitespace;
 mod get_diff_rename;
 mod get_diff_show_whitespace;
 mod get_input;
+mod get_named_strings;
 mod get_string;
 mod get_unsigned_integer;
 
 pub(crate) use self::{
+	get_action_commands::get_action_commands,
 	get_bool::get_bool,
 	get_diff_ignore_whitespace::get_diff_ignore_whitespace,
 	get_diff_rename::git_diff_renames,
 	get_diff_show_whitespace::get_diff_show_whitespace,
-	get_input::get_input,
+	get_input::{get_input, MAX_KEY_SEQUENCE_LENGTH},
+	get_named_strings::get_named_strings,
 	get_string::{get_optional_string, get_string},
 	get_unsigned_integer::get_unsigned_integer,
 };
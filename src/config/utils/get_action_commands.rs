@@ -0,0 +1,63 @@
+use crate::{
+	config::{utils::get_named_strings, ConfigError, ConfigErrorCause},
+	git::Config,
+	todo_file::Action,
+};
+
+/// Get the entries of a subsection that are named by a todo file action, as the action and the command for it.
+pub(crate) fn get_action_commands(
+	config: Option<&Config>,
+	subsection: &str,
+) -> Result<Vec<(Action, String)>, ConfigError> {
+	get_named_strings(config, subsection)?
+		.into_iter()
+		.map(|(name, command)| {
+			Action::try_from(name.as_str())
+				.map(|action| (action, command))
+				.map_err(|_| {
+					ConfigError::new(
+						format!("{subsection}.{name}").as_str(),
+						name.as_str(),
+						ConfigErrorCause::InvalidAction,
+					)
+				})
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use claims::{assert_err_eq, assert_ok_eq};
+
+	use super::*;
+	use crate::test_helpers::with_git_config;
+
+	#[test]
+	fn read_values() {
+		with_git_config(
+			&["[test \"sub\"]", "pick = foo", "update-ref = bar", "d = baz"],
+			|git_config| {
+				assert_ok_eq!(get_action_commands(Some(&git_config), "test.sub"), vec![
+					(Action::Pick, String::from("foo")),
+					(Action::UpdateRef, String::from("bar")),
+					(Action::Drop, String::from("baz")),
+				]);
+			},
+		);
+	}
+
+	#[test]
+	fn read_without_config() {
+		assert_ok_eq!(get_action_commands(None, "test.sub"), vec![]);
+	}
+
+	#[test]
+	fn read_invalid_action() {
+		with_git_config(&["[test \"sub\"]", "invalid = foo"], |git_config| {
+			assert_err_eq!(
+				get_action_commands(Some(&git_config), "test.sub"),
+				ConfigError::new("test.sub.invalid", "invalid", ConfigErrorCause::InvalidAction)
+			);
+		});
+	}
+}
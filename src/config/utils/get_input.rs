@@ -3,73 +3,103 @@ use crate::{
 	git::Config,
 };
 
+/// The maximum number of keys in a key sequence binding.
+pub(crate) const MAX_KEY_SEQUENCE_LENGTH: usize = 4;
+
 #[allow(clippy::string_slice)]
-pub(crate) fn get_input(config: Option<&Config>, name: &str, default: &str) -> Result<Vec<String>, ConfigError> {
-	let mut values = vec![];
-	let input = get_string(config, name, default)?;
-	for mut value in input.split_whitespace().map(String::from) {
-		let mut modifiers = vec![];
+fn get_key(mut value: String) -> Option<String> {
+	let mut modifiers = vec![];
 
-		let shift_index = value.to_lowercase().find("shift+");
-		if let Some(index) = shift_index {
-			value.replace_range(index..index + 6, "");
-		}
-		if let Some(index) = value.to_lowercase().find("control+") {
-			modifiers.push("Control");
-			value.replace_range(index..index + 8, "");
+	let shift_index = value.to_lowercase().find("shift+");
+	if let Some(index) = shift_index {
+		value.replace_range(index..index + 6, "");
+	}
+	if let Some(index) = value.to_lowercase().find("control+") {
+		modifiers.push("Control");
+		value.replace_range(index..index + 8, "");
+	}
+	if let Some(index) = value.to_lowercase().find("alt+") {
+		modifiers.push("Alt");
+		value.replace_range(index..index + 4, "");
+	}
+
+	let mut key = match value.to_lowercase().as_ref() {
+		"backspace" => String::from("Backspace"),
+		"backtab" => String::from("BackTab"),
+		"delete" => String::from("Delete"),
+		"down" => String::from("Down"),
+		"end" => String::from("End"),
+		"enter" => String::from("Enter"),
+		"esc" => String::from("Esc"),
+		"home" => String::from("Home"),
+		"insert" => String::from("Insert"),
+		"left" => String::from("Left"),
+		"pagedown" => String::from("PageDown"),
+		"pageup" => String::from("PageUp"),
+		"right" => String::from("Right"),
+		"space" => String::from("Space"),
+		"tab" => String::from("Tab"),
+		"up" => String::from("Up"),
+		v => {
+			let v_len = v.chars().count();
+			// allow F{number} values
+			if v_len > 1 && v.starts_with('f') && v[1..].parse::<u8>().is_ok() {
+				v.to_uppercase()
+			}
+			else if v_len == 1 {
+				value
+			}
+			else {
+				return None;
+			}
+		},
+	};
+
+	// Shift support was partially removed, due to Shift not being universally reported, but still maintain
+	// some backwards compatibility with printable characters
+	if shift_index.is_some() {
+		if key.len() == 1 {
+			key = key.to_uppercase();
 		}
-		if let Some(index) = value.to_lowercase().find("alt+") {
-			modifiers.push("Alt");
-			value.replace_range(index..index + 4, "");
+		else {
+			modifiers.push("Shift");
 		}
+	}
 
-		let mut key = match value.to_lowercase().as_ref() {
-			"backspace" => String::from("Backspace"),
-			"backtab" => String::from("BackTab"),
-			"delete" => String::from("Delete"),
-			"down" => String::from("Down"),
-			"end" => String::from("End"),
-			"enter" => String::from("Enter"),
-			"esc" => String::from("Esc"),
-			"home" => String::from("Home"),
-			"insert" => String::from("Insert"),
-			"left" => String::from("Left"),
-			"pagedown" => String::from("PageDown"),
-			"pageup" => String::from("PageUp"),
-			"right" => String::from("Right"),
-			"tab" => String::from("Tab"),
-			"up" => String::from("Up"),
-			v => {
-				let v_len = v.chars().count();
-				// allow F{number} values
-				if v_len > 1 && v.starts_with('f') && v[1..].parse::<u8>().is_ok() {
-					v.to_uppercase()
-				}
-				else if v_len == 1 {
-					value
-				}
-				else {
-					return Err(ConfigError::new(
-						name,
-						input.as_str(),
-						ConfigErrorCause::InvalidKeyBinding,
-					));
-				}
-			},
-		};
+	Some(format!("{}{}", modifiers.join(""), key))
+}
 
-		// Shift support was partially removed, due to Shift not being universally reported, but still maintain
-		// some backwards compatibility with printable characters
-		if shift_index.is_some() {
-			if key.len() == 1 {
-				key = key.to_uppercase();
+pub(crate) fn get_input(config: Option<&Config>, name: &str, default: &str) -> Result<Vec<String>, ConfigError> {
+	let mut values = vec![];
+	let input = get_string(config, name, default)?;
+	for value in input.split_whitespace() {
+		// a sequence of keys is separated by commas, like "g,g", while a lone comma, or a comma with modifiers, is a
+		// single key
+		let keys: Vec<&str> = value.split(',').collect();
+		let binding = if keys.len() > 1 && keys.iter().all(|k| !k.is_empty()) {
+			if keys.len() > MAX_KEY_SEQUENCE_LENGTH {
+				None
 			}
 			else {
-				modifiers.push("Shift");
+				keys.into_iter()
+					.map(|k| get_key(String::from(k)))
+					.collect::<Option<Vec<String>>>()
+					.map(|k| k.join(" "))
 			}
 		}
+		else {
+			get_key(String::from(value))
+		};
 
-		values.push(format!("{}{}", modifiers.join(""), key));
+		let Some(key) = binding
+		else {
+			return Err(ConfigError::new(
+				name,
+				input.as_str(),
+				ConfigErrorCause::InvalidKeyBinding,
+			));
+		};
+		values.push(key);
 	}
 	Ok(values)
 }
@@ -99,6 +129,7 @@ mod tests {
 	#[case::pagedown("pagedown", "PageDown")]
 	#[case::pageup("pageup", "PageUp")]
 	#[case::right("right", "Right")]
+	#[case::space("space", "Space")]
 	#[case::tab("tab", "Tab")]
 	#[case::up("up", "Up")]
 	#[case::f1("f1", "F1")]
@@ -121,6 +152,11 @@ mod tests {
 	#[case::shift_with_printable_upper("Shift+A", "A")]
 	#[case::multiple("a b ẞ c d", "a,b,ẞ,c,d")]
 	#[case::multiple_with_modifiers("Control+End Control+A", "ControlEnd,ControlA")]
+	#[case::sequence("g,g", "g g")]
+	#[case::sequence_with_modifiers("Control+w,Shift+j", "Controlw J")]
+	#[case::sequence_special("g,Home", "g Home")]
+	#[case::sequence_max_length("a,b,c,d", "a b c d")]
+	#[case::multiple_sequences("g,g d,d", "g g,d d")]
 	fn read_value(#[case] binding: &str, #[case] expected: &str) {
 		with_git_config(&["[test]", format!("value = {binding}").as_str()], |git_config| {
 			assert_ok_eq!(
@@ -130,6 +166,15 @@ mod tests {
 		});
 	}
 
+	#[rstest]
+	#[case::comma(",", ",")]
+	#[case::modifier_comma("Control+,", "Control,")]
+	fn read_value_comma(#[case] binding: &str, #[case] expected: &str) {
+		with_git_config(&["[test]", format!("value = {binding}").as_str()], |git_config| {
+			assert_ok_eq!(get_input(Some(&git_config), "test.value", "x"), vec![String::from(expected)]);
+		});
+	}
+
 	#[test]
 	fn read_value_default() {
 		with_git_config(&[], |git_config| {
@@ -141,6 +186,9 @@ mod tests {
 	#[case::multiple_characters("abcd")]
 	#[case::function_key_index("F256")]
 	#[case::multiple_bindings_one_invalid("f foo")]
+	#[case::sequence_invalid_key("g,foo")]
+	#[case::sequence_too_long("a,b,c,d,e")]
+	#[case::sequence_empty_key("g,,g")]
 	fn read_value_invalid(#[case] binding: &str) {
 		with_git_config(&["[test]", format!("value = {binding}").as_str()], |git_config| {
 			assert_err_eq!(
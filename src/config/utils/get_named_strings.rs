@@ -0,0 +1,99 @@
+use crate::{
+	config::{ConfigError, ConfigErrorCause},
+	git::Config,
+};
+
+/// Get the entries of a subsection, named like `section.subsection.name`, as the name and value of each entry, in the
+/// order they are defined. A name that is defined more than once has the last defined value.
+pub(crate) fn get_named_strings(
+	config: Option<&Config>,
+	subsection: &str,
+) -> Result<Vec<(String, String)>, ConfigError> {
+	let Some(cfg) = config
+	else {
+		return Ok(vec![]);
+	};
+	let read_error = |e: git2::Error| {
+		ConfigError::new_read_error(subsection, ConfigErrorCause::UnknownError(String::from(e.message())))
+	};
+	let prefix = format!("{subsection}.");
+	let mut named_strings: Vec<(String, String)> = vec![];
+	let mut entries = cfg.entries(None).map_err(read_error)?;
+	while let Some(result) = entries.next() {
+		let entry = result.map_err(read_error)?;
+		let Some(name) = entry.name().and_then(|name| name.strip_prefix(prefix.as_str()))
+		else {
+			continue;
+		};
+		let Some(value) = entry.value()
+		else {
+			return Err(ConfigError::new_read_error(
+				format!("{prefix}{name}").as_str(),
+				ConfigErrorCause::InvalidUtf,
+			));
+		};
+		named_strings.retain(|(n, _)| n != name);
+		named_strings.push((String::from(name), String::from(value)));
+	}
+	Ok(named_strings)
+}
+
+#[cfg(test)]
+mod tests {
+	use claims::{assert_err_eq, assert_ok_eq};
+
+	use super::*;
+	use crate::test_helpers::{invalid_utf, with_git_config};
+
+	#[test]
+	fn read_values() {
+		with_git_config(
+			&[
+				"[test \"sub\"]",
+				"first = foo",
+				"second = bar baz",
+				"[test \"other\"]",
+				"third = other",
+				"[test]",
+				"fourth = section",
+			],
+			|git_config| {
+				assert_ok_eq!(get_named_strings(Some(&git_config), "test.sub"), vec![
+					(String::from("first"), String::from("foo")),
+					(String::from("second"), String::from("bar baz")),
+				]);
+			},
+		);
+	}
+
+	#[test]
+	fn read_redefined_value() {
+		with_git_config(
+			&["[test \"sub\"]", "first = foo", "second = bar", "first = baz"],
+			|git_config| {
+				assert_ok_eq!(get_named_strings(Some(&git_config), "test.sub"), vec![
+					(String::from("second"), String::from("bar")),
+					(String::from("first"), String::from("baz")),
+				]);
+			},
+		);
+	}
+
+	#[test]
+	fn read_without_config() {
+		assert_ok_eq!(get_named_strings(None, "test.sub"), vec![]);
+	}
+
+	#[test]
+	fn read_invalid_non_utf() {
+		with_git_config(
+			&["[test \"sub\"]", format!("value = {}", invalid_utf()).as_str()],
+			|git_config| {
+				assert_err_eq!(
+					get_named_strings(Some(&git_config), "test.sub"),
+					ConfigError::new_read_error("test.sub.value", ConfigErrorCause::InvalidUtf)
+				);
+			},
+		);
+	}
+}
@@ -42,6 +42,7 @@ pub(crate) struct Display<T: Tui> {
 	action_merge: (Colors, Colors),
 	action_pick: (Colors, Colors),
 	action_reset: (Colors, Colors),
+	action_revert: (Colors, Colors),
 	action_reword: (Colors, Colors),
 	action_squash: (Colors, Colors),
 	action_update_ref: (Colors, Colors),
@@ -107,6 +108,12 @@ impl<T: Tui> Display<T> {
 			theme.color_background,
 			theme.color_selected_background,
 		);
+		let action_revert = register_selectable_color_pairs(
+			color_mode,
+			theme.color_action_revert,
+			theme.color_background,
+			theme.color_selected_background,
+		);
 		let action_reword = register_selectable_color_pairs(
 			color_mode,
 			theme.color_action_reword,
@@ -184,6 +191,7 @@ impl<T: Tui> Display<T> {
 			action_merge,
 			action_pick,
 			action_reset,
+			action_revert,
 			action_reword,
 			action_squash,
 			action_update_ref,
@@ -241,6 +249,7 @@ impl<T: Tui> Display<T> {
 					DisplayColor::ActionExec => self.action_exec.1,
 					DisplayColor::ActionFixup => self.action_fixup.1,
 					DisplayColor::ActionPick => self.action_pick.1,
+					DisplayColor::ActionRevert => self.action_revert.1,
 					DisplayColor::ActionReword => self.action_reword.1,
 					DisplayColor::ActionSquash => self.action_squash.1,
 					DisplayColor::ActionLabel => self.action_label.1,
@@ -264,6 +273,7 @@ impl<T: Tui> Display<T> {
 					DisplayColor::ActionExec => self.action_exec.0,
 					DisplayColor::ActionFixup => self.action_fixup.0,
 					DisplayColor::ActionPick => self.action_pick.0,
+					DisplayColor::ActionRevert => self.action_revert.0,
 					DisplayColor::ActionReword => self.action_reword.0,
 					DisplayColor::ActionSquash => self.action_squash.0,
 					DisplayColor::ActionLabel => self.action_label.0,
@@ -434,6 +444,13 @@ mod tests {
 		CrosstermColor::Green,
 		CrosstermColor::AnsiValue(237)
 	)]
+	#[case::action_revert(DisplayColor::ActionRevert, false, CrosstermColor::DarkRed, CrosstermColor::Reset)]
+	#[case::action_revert_selected(
+		DisplayColor::ActionRevert,
+		true,
+		CrosstermColor::DarkRed,
+		CrosstermColor::AnsiValue(237)
+	)]
 	#[case::action_reword(DisplayColor::ActionReword, false, CrosstermColor::Yellow, CrosstermColor::Reset)]
 	#[case::action_reword_selected(
 		DisplayColor::ActionReword,
@@ -14,6 +14,8 @@ pub(crate) enum DisplayColor {
 	ActionFixup,
 	/// The color for the pick action.
 	ActionPick,
+	/// The color for the revert action.
+	ActionRevert,
 	/// The color for the reword action.
 	ActionReword,
 	/// The color for the squash action.
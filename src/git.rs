@@ -12,6 +12,7 @@ mod commit;
 mod commit_diff;
 mod commit_diff_loader;
 mod commit_diff_loader_options;
+mod commit_metadata;
 mod delta;
 mod diff_line;
 mod errors;
@@ -32,6 +33,7 @@ pub(crate) use self::{
 	commit_diff::CommitDiff,
 	commit_diff_loader::CommitDiffLoader,
 	commit_diff_loader_options::CommitDiffLoaderOptions,
+	commit_metadata::CommitMetadata,
 	delta::Delta,
 	diff_line::DiffLine,
 	errors::{GitError, RepositoryLoadKind},
@@ -37,7 +37,6 @@ impl Commit {
 
 	/// Get the author of the commit.
 	#[must_use]
-	#[allow(dead_code)]
 	pub(crate) const fn authored_date(&self) -> &Option<DateTime<Local>> {
 		&self.authored_date
 	}
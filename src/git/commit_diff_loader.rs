@@ -281,7 +281,7 @@ mod tests {
 
 	#[cfg(not(tarpaulin_include))]
 	fn write_normal_file(repository: &crate::git::Repository, name: &str, contents: &[&str]) {
-		let root = repository.repo_path().parent().unwrap().to_path_buf();
+		let root = repository.git_directory().parent().unwrap().to_path_buf();
 
 		let file_path = root.join(name);
 		let mut file = File::create(file_path.as_path()).unwrap();
@@ -293,7 +293,7 @@ mod tests {
 
 	#[cfg(not(tarpaulin_include))]
 	fn remove_path(repository: &crate::git::Repository, name: &str) {
-		let root = repository.repo_path().parent().unwrap().to_path_buf();
+		let root = repository.git_directory().parent().unwrap().to_path_buf();
 
 		let file_path = root.join(name);
 		_ = remove_file(file_path);
@@ -558,7 +558,7 @@ mod tests {
 	fn load_from_hash_file_mode_executable() {
 		with_temp_repository(|repo| {
 			use std::os::unix::fs::PermissionsExt;
-			let root = repo.repo_path().parent().unwrap().to_path_buf();
+			let root = repo.git_directory().parent().unwrap().to_path_buf();
 
 			write_normal_file(&repo, "a", &["line0"]);
 			create_commit(&repo);
@@ -580,7 +580,7 @@ mod tests {
 	#[test]
 	fn load_from_hash_type_changed() {
 		with_temp_repository(|repo| {
-			let root = repo.repo_path().parent().unwrap().to_path_buf();
+			let root = repo.git_directory().parent().unwrap().to_path_buf();
 
 			write_normal_file(&repo, "a", &["line0"]);
 			write_normal_file(&repo, "b", &["line0"]);
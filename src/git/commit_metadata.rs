@@ -0,0 +1,21 @@
+use crate::git::Commit;
+
+/// The details of a commit that are not part of a todo line, such as the author and the full message.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct CommitMetadata {
+	commit: Commit,
+}
+
+impl CommitMetadata {
+	/// Create a new instance
+	#[must_use]
+	pub(crate) const fn new(commit: Commit) -> Self {
+		Self { commit }
+	}
+
+	/// Get the commit
+	#[must_use]
+	pub(crate) const fn commit(&self) -> &Commit {
+		&self.commit
+	}
+}
@@ -64,6 +64,13 @@ pub(crate) enum GitError {
 		#[source]
 		cause: git2::Error,
 	},
+	/// The branches could not be loaded
+	#[error("Could not load branches")]
+	BranchLoad {
+		/// The internal cause of the load error.
+		#[source]
+		cause: git2::Error,
+	},
 }
 
 #[cfg(test)]
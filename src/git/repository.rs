@@ -1,11 +1,26 @@
 use std::{
+	collections::HashSet,
 	fmt::{Debug, Formatter},
+	fs,
+	path::PathBuf,
 	sync::Arc,
 };
 
 use parking_lot::Mutex;
 
-use crate::git::{CommitDiff, CommitDiffLoader, CommitDiffLoaderOptions, Config, GitError, RepositoryLoadKind};
+use crate::git::{
+	Commit,
+	CommitDiff,
+	CommitDiffLoader,
+	CommitDiffLoaderOptions,
+	CommitMetadata,
+	Config,
+	DiffLine,
+	GitError,
+	Origin,
+	Reference,
+	RepositoryLoadKind,
+};
 
 /// A light cloneable, simple wrapper around the `git2::Repository` struct
 #[derive(Clone)]
@@ -66,6 +81,193 @@ impl Repository {
 			.map_err(|e| GitError::CommitLoad { cause: e })?
 			.remove(0))
 	}
+
+	/// Load the metadata for a commit hash.
+	///
+	/// # Errors
+	/// Will result in an error if the commit cannot be loaded.
+	pub(crate) fn load_commit_metadata(&self, hash: &str) -> Result<CommitMetadata, GitError> {
+		let repo = self.repository.lock();
+		let commit = repo
+			.revparse_single(hash)
+			.and_then(|object| object.peel_to_commit())
+			.map_err(|e| GitError::CommitLoad { cause: e })?;
+		Ok(CommitMetadata::new(Commit::from(&commit)))
+	}
+
+	/// Load the paths of the files changed by a commit, compared to the first parent of the commit.
+	///
+	/// # Errors
+	/// Will result in an error if the commit, or the diff of the commit, cannot be loaded.
+	pub(crate) fn load_commit_paths(&self, hash: &str) -> Result<Vec<String>, GitError> {
+		let repo = self.repository.lock();
+		let commit = repo
+			.revparse_single(hash)
+			.and_then(|object| object.peel_to_commit())
+			.map_err(|e| GitError::CommitLoad { cause: e })?;
+		let diff = Self::diff_to_first_parent(&repo, &commit).map_err(|e| GitError::CommitLoad { cause: e })?;
+		let mut paths = vec![];
+		for delta in diff.deltas() {
+			for file in [delta.old_file(), delta.new_file()] {
+				if let Some(path) = file.path().map(|p| p.to_string_lossy().to_string()) {
+					if !paths.contains(&path) {
+						paths.push(path);
+					}
+				}
+			}
+		}
+		Ok(paths)
+	}
+
+	/// Load the lines added and removed by a commit, compared to the first parent of the commit.
+	///
+	/// # Errors
+	/// Will result in an error if the commit, or the diff of the commit, cannot be loaded.
+	pub(crate) fn load_commit_changed_lines(&self, hash: &str) -> Result<Vec<DiffLine>, GitError> {
+		let repo = self.repository.lock();
+		let commit = repo
+			.revparse_single(hash)
+			.and_then(|object| object.peel_to_commit())
+			.map_err(|e| GitError::CommitLoad { cause: e })?;
+		let diff = Self::diff_to_first_parent(&repo, &commit).map_err(|e| GitError::CommitLoad { cause: e })?;
+		let mut lines = vec![];
+		diff.print(git2::DiffFormat::Patch, |_, _, diff_line| {
+			let origin = Origin::from(diff_line.origin_value());
+			if matches!(origin, Origin::Addition | Origin::Deletion) {
+				lines.push(DiffLine::new(
+					origin,
+					String::from_utf8_lossy(diff_line.content()).trim_end_matches('\n'),
+					diff_line.old_lineno(),
+					diff_line.new_lineno(),
+					false,
+				));
+			}
+			true
+		})
+		.map_err(|e| GitError::CommitLoad { cause: e })?;
+		Ok(lines)
+	}
+
+	fn diff_to_first_parent<'repo>(
+		repo: &'repo git2::Repository,
+		commit: &git2::Commit<'_>,
+	) -> Result<git2::Diff<'repo>, git2::Error> {
+		let parent_tree = match commit.parent(0) {
+			Ok(parent) => Some(parent.tree()?),
+			Err(_) => None,
+		};
+		repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&commit.tree()?), None)
+	}
+
+	/// Load the local branches, other than the branch that is being rebased, sorted by name.
+	///
+	/// # Errors
+	/// Will result in an error if the branches cannot be loaded.
+	pub(crate) fn load_local_branches(&self) -> Result<Vec<Reference>, GitError> {
+		let repo = self.repository.lock();
+		let head_name = Self::rebased_branch_name(&repo);
+		let mut branches = vec![];
+		for branch in repo
+			.branches(Some(git2::BranchType::Local))
+			.map_err(|e| GitError::BranchLoad { cause: e })?
+		{
+			let reference = branch.map_err(|e| GitError::BranchLoad { cause: e })?.0.into_reference();
+			if reference.target().is_none() || reference.name() == head_name.as_deref() {
+				continue;
+			}
+			branches.push(Reference::from(&reference));
+		}
+		branches.sort_by(|a, b| a.name().cmp(b.name()));
+		Ok(branches)
+	}
+
+	/// Load the commits reachable from a revision, newest first, up to the limit. Without a revision, the commits of
+	/// the branch being rebased are loaded, or of `HEAD` when no branch is being rebased.
+	///
+	/// # Errors
+	/// Will result in an error if the revision cannot be resolved to a commit, or the commits cannot be loaded.
+	pub(crate) fn load_commits(&self, revision: Option<&str>, limit: usize) -> Result<Vec<Commit>, GitError> {
+		let repo = self.repository.lock();
+		let start = revision
+			.map(String::from)
+			.or_else(|| Self::rebased_branch_name(&repo))
+			.unwrap_or_else(|| String::from("HEAD"));
+		let oid = repo
+			.revparse_single(start.as_str())
+			.and_then(|object| object.peel_to_commit())
+			.map_err(|e| GitError::CommitLoad { cause: e })?
+			.id();
+		let mut revwalk = repo.revwalk().map_err(|e| GitError::CommitLoad { cause: e })?;
+		revwalk
+			.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)
+			.and_then(|()| revwalk.push(oid))
+			.map_err(|e| GitError::CommitLoad { cause: e })?;
+		let mut commits = vec![];
+		for walked in revwalk.take(limit) {
+			let commit = walked
+				.and_then(|id| repo.find_commit(id))
+				.map_err(|e| GitError::CommitLoad { cause: e })?;
+			commits.push(Commit::from(&commit));
+		}
+		Ok(commits)
+	}
+
+	/// Load the commits that `HEAD` has pointed to, as recorded in its reflog, most recent first and without
+	/// duplicates, up to the limit. Commits that no longer exist are skipped.
+	///
+	/// # Errors
+	/// Will result in an error if the reflog cannot be read.
+	pub(crate) fn load_reflog_commits(&self, limit: usize) -> Result<Vec<Commit>, GitError> {
+		let repo = self.repository.lock();
+		let reflog = repo.reflog("HEAD").map_err(|e| GitError::CommitLoad { cause: e })?;
+		let mut seen = HashSet::new();
+		let mut commits = vec![];
+		for entry in reflog.iter() {
+			if commits.len() >= limit {
+				break;
+			}
+			let id = entry.id_new();
+			if id.is_zero() || !seen.insert(id) {
+				continue;
+			}
+			if let Ok(commit) = repo.find_commit(id) {
+				commits.push(Commit::from(&commit));
+			}
+		}
+		Ok(commits)
+	}
+
+	/// Resolve a revision, such as a full or abbreviated hash, to the full hash of a commit.
+	///
+	/// # Errors
+	/// Will result in an error if the revision does not resolve to a commit in the repository.
+	pub(crate) fn resolve_commit(&self, revision: &str) -> Result<String, GitError> {
+		self.repository
+			.lock()
+			.revparse_single(revision)
+			.and_then(|object| object.peel_to_commit())
+			.map(|commit| commit.id().to_string())
+			.map_err(|e| GitError::CommitLoad { cause: e })
+	}
+
+	// during a rebase HEAD is detached, so Git records the name of the branch being rebased
+	fn rebased_branch_name(repo: &git2::Repository) -> Option<String> {
+		fs::read_to_string(repo.path().join("rebase-merge").join("head-name"))
+			.ok()
+			.map(|name| String::from(name.trim()))
+			.or_else(|| {
+				repo.head()
+					.ok()
+					.filter(git2::Reference::is_branch)
+					.and_then(|head| head.name().map(String::from))
+			})
+	}
+
+	/// Get the path to the Git directory of the repository.
+	#[must_use]
+	pub(crate) fn git_directory(&self) -> PathBuf {
+		self.repository.lock().path().to_path_buf()
+	}
 }
 
 impl From<git2::Repository> for Repository {
@@ -86,10 +288,7 @@ impl Debug for Repository {
 
 #[cfg(test)]
 mod tests {
-	use std::{
-		path::{Path, PathBuf},
-		sync::Arc,
-	};
+	use std::{path::Path, sync::Arc};
 
 	use git2::{Oid, Signature};
 	use parking_lot::Mutex;
@@ -137,10 +336,6 @@ mod tests {
 			Commit::try_from(&git2_reference)
 		}
 
-		pub(crate) fn repo_path(&self) -> PathBuf {
-			self.repository.lock().path().to_path_buf()
-		}
-
 		pub(crate) fn head_id(&self, head_name: &str) -> Result<Oid, git2::Error> {
 			let repo = self.repository.lock();
 			let ref_name = format!("refs/heads/{head_name}");
@@ -195,7 +390,13 @@ mod unix_tests {
 	use git2::{ErrorClass, ErrorCode};
 
 	use super::*;
-	use crate::test_helpers::{create_commit, with_git_directory, with_temp_bare_repository, with_temp_repository};
+	use crate::test_helpers::{
+		create_commit,
+		with_git_directory,
+		with_temp_bare_repository,
+		with_temp_repository,
+		CreateCommitOptions,
+	};
 
 	#[test]
 	fn open_from_env() {
@@ -288,6 +489,175 @@ mod unix_tests {
 		});
 	}
 
+	#[test]
+	fn load_commit_metadata() {
+		with_temp_repository(|repository| {
+			let workdir = repository.repository().lock().workdir().unwrap().to_path_buf();
+			fs::create_dir_all(workdir.join("src").join("db")).unwrap();
+			fs::write(workdir.join("src").join("db").join("query.rs"), "").unwrap();
+			repository.add_path_to_index(Path::new("src/db/query.rs")).unwrap();
+			create_commit(&repository, None);
+			let id = repository.commit_id_from_ref("refs/heads/main").unwrap();
+			let metadata = repository.load_commit_metadata(id.to_string().as_str()).unwrap();
+			assert_eq!(metadata.commit().hash(), id.to_string());
+			let paths = repository.load_commit_paths(id.to_string().as_str()).unwrap();
+			assert_eq!(paths, vec![String::from("src/db/query.rs")]);
+		});
+	}
+
+	#[test]
+	fn load_commit_paths_error() {
+		with_temp_repository(|repository| {
+			assert!(matches!(
+				repository.load_commit_paths("0000000000000000000000000000000000000000"),
+				Err(GitError::CommitLoad { .. })
+			));
+		});
+	}
+
+	#[test]
+	fn load_commit_metadata_error() {
+		with_temp_repository(|repository| {
+			assert!(matches!(
+				repository.load_commit_metadata("0000000000000000000000000000000000000000"),
+				Err(GitError::CommitLoad { .. })
+			));
+		});
+	}
+
+	#[test]
+	fn load_commit_changed_lines() {
+		with_temp_repository(|repository| {
+			let workdir = repository.repository().lock().workdir().unwrap().to_path_buf();
+			fs::write(workdir.join("file.txt"), "foo\nbar\n").unwrap();
+			repository.add_path_to_index(Path::new("file.txt")).unwrap();
+			create_commit(&repository, None);
+			fs::write(workdir.join("file.txt"), "foo\nbaz\n").unwrap();
+			repository.add_path_to_index(Path::new("file.txt")).unwrap();
+			create_commit(&repository, None);
+			let id = repository.commit_id_from_ref("refs/heads/main").unwrap();
+			let lines = repository.load_commit_changed_lines(id.to_string().as_str()).unwrap();
+			let changes: Vec<(Origin, &str)> = lines.iter().map(|line| (line.origin(), line.line())).collect();
+			assert_eq!(changes, vec![(Origin::Deletion, "bar"), (Origin::Addition, "baz")]);
+		});
+	}
+
+	#[test]
+	fn load_commit_changed_lines_error() {
+		with_temp_repository(|repository| {
+			assert!(matches!(
+				repository.load_commit_changed_lines("0000000000000000000000000000000000000000"),
+				Err(GitError::CommitLoad { .. })
+			));
+		});
+	}
+
+	#[test]
+	fn load_local_branches() {
+		with_temp_repository(|repository| {
+			let oid = repository.head_id("main").unwrap();
+			{
+				let git2_repository = repository.repository();
+				let git2_lock = git2_repository.lock();
+				_ = git2_lock.reference("refs/heads/feature-b", oid, false, "test").unwrap();
+				_ = git2_lock.reference("refs/heads/feature-a", oid, false, "test").unwrap();
+			}
+			let branches = repository.load_local_branches().unwrap();
+			let names: Vec<&str> = branches.iter().map(Reference::name).collect();
+			assert_eq!(names, vec!["refs/heads/feature-a", "refs/heads/feature-b"]);
+			assert_eq!(branches[0].hash(), oid.to_string());
+		});
+	}
+
+	#[test]
+	fn load_local_branches_excludes_rebased_branch() {
+		with_temp_repository(|repository| {
+			let oid = repository.head_id("main").unwrap();
+			{
+				let git2_repository = repository.repository();
+				let git2_lock = git2_repository.lock();
+				_ = git2_lock.reference("refs/heads/feature", oid, false, "test").unwrap();
+				git2_lock.set_head_detached(oid).unwrap();
+			}
+			let rebase_directory = repository.git_directory().join("rebase-merge");
+			fs::create_dir_all(&rebase_directory).unwrap();
+			fs::write(rebase_directory.join("head-name"), "refs/heads/feature\n").unwrap();
+			let branches = repository.load_local_branches().unwrap();
+			let names: Vec<&str> = branches.iter().map(Reference::name).collect();
+			assert_eq!(names, vec!["refs/heads/main"]);
+		});
+	}
+
+	#[test]
+	fn load_commits() {
+		with_temp_repository(|repository| {
+			create_commit(&repository, Some(CreateCommitOptions::new().message("second")));
+			create_commit(&repository, Some(CreateCommitOptions::new().message("third")));
+			let commits = repository.load_commits(Some("main"), 2).unwrap();
+			let summaries: Vec<Option<&str>> = commits.iter().map(Commit::summary).collect();
+			assert_eq!(summaries, vec![Some("third"), Some("second")]);
+		});
+	}
+
+	#[test]
+	fn load_commits_of_rebased_branch() {
+		with_temp_repository(|repository| {
+			let oid = repository.head_id("main").unwrap();
+			create_commit(&repository, Some(CreateCommitOptions::new().message("feature")));
+			{
+				let git2_repository = repository.repository();
+				let git2_lock = git2_repository.lock();
+				let feature = git2_lock.head().unwrap().target().unwrap();
+				_ = git2_lock.reference("refs/heads/feature", feature, false, "test").unwrap();
+				git2_lock.set_head_detached(oid).unwrap();
+			}
+			let rebase_directory = repository.git_directory().join("rebase-merge");
+			fs::create_dir_all(&rebase_directory).unwrap();
+			fs::write(rebase_directory.join("head-name"), "refs/heads/feature\n").unwrap();
+			let commits = repository.load_commits(None, 1).unwrap();
+			assert_eq!(commits[0].summary(), Some("feature"));
+		});
+	}
+
+	#[test]
+	fn load_commits_error() {
+		with_temp_repository(|repository| {
+			assert!(matches!(
+				repository.load_commits(Some("refs/heads/missing"), 10),
+				Err(GitError::CommitLoad { .. })
+			));
+		});
+	}
+
+	#[test]
+	fn load_reflog_commits() {
+		with_temp_repository(|repository| {
+			create_commit(&repository, Some(CreateCommitOptions::new().message("second")));
+			let commits = repository.load_reflog_commits(10).unwrap();
+			let summaries: Vec<Option<&str>> = commits.iter().map(Commit::summary).collect();
+			assert_eq!(summaries[0], Some("second"));
+			assert_eq!(repository.load_reflog_commits(0).unwrap(), vec![]);
+		});
+	}
+
+	#[test]
+	fn resolve_commit() {
+		with_temp_repository(|repository| {
+			let oid = repository.head_id("main").unwrap().to_string();
+			assert_eq!(repository.resolve_commit(oid.get(0..7).unwrap()).unwrap(), oid);
+		});
+	}
+
+	#[test]
+	fn resolve_commit_error() {
+		with_temp_repository(|repository| {
+			assert!(matches!(
+				repository.resolve_commit("0000000"),
+				Err(GitError::CommitLoad { .. })
+			));
+		});
+	}
+
 	#[test]
 	fn find_reference() {
 		with_temp_repository(|repository| {
@@ -338,7 +708,7 @@ mod unix_tests {
 	fn fmt() {
 		with_temp_bare_repository(|repository| {
 			let formatted = format!("{repository:?}");
-			let path = repository.repo_path().canonicalize().unwrap();
+			let path = repository.git_directory().canonicalize().unwrap();
 			assert_eq!(
 				formatted,
 				format!("Repository {{ [path]: \"{}/\" }}", path.to_str().unwrap())
@@ -6,14 +6,21 @@ Full feature terminal based sequence editor for git interactive rebase.
 
 USAGE:
   interactive-rebase-tool [FLAGS] [REBASE-TODO-FILE]
+  interactive-rebase-tool --rebase <UPSTREAM> [REBASE-FLAGS]
 
 FLAGS:
   -v, --version       Prints versioning information
   -h, --help          Prints help information
   --license           Prints Open Source Software licensing
 
+REBASE-FLAGS:
+  --rebase <UPSTREAM> Rebase the current branch on to the upstream
+  --rebase-merges     Recreate merge commits, instead of dropping them
+  --update-refs       Update branches that point to the rebased commits
+
 ARGS:
   <REBASE-TODO-FILE>  The path to the Git rebase todo file
+  <UPSTREAM>          The upstream to rebase the current branch on to
 "#;
 
 pub(crate) fn build_help(message: Option<String>) -> String {
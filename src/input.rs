@@ -14,6 +14,7 @@ mod event_provider;
 mod input_options;
 mod key_bindings;
 mod key_event;
+mod key_sequence;
 mod map_keybindings;
 mod standard_event;
 mod thread;
@@ -27,6 +28,7 @@ pub(crate) use self::{
 	input_options::InputOptions,
 	key_bindings::KeyBindings,
 	key_event::KeyEvent,
+	key_sequence::KeySequence,
 	map_keybindings::map_keybindings,
 	standard_event::StandardEvent,
 	thread::{State, Thread, THREAD_NAME},
@@ -1,4 +1,4 @@
-use crate::input::{KeyCode, KeyEvent, MouseEvent, StandardEvent};
+use crate::input::{KeyCode, KeyEvent, KeySequence, MouseEvent, StandardEvent};
 
 /// An event, either from an input device, system change or action event.
 #[derive(Debug, PartialOrd, PartialEq, Eq, Clone, Copy)]
@@ -6,6 +6,10 @@ use crate::input::{KeyCode, KeyEvent, MouseEvent, StandardEvent};
 pub(crate) enum Event {
 	/// A keyboard event.
 	Key(KeyEvent),
+	/// A completed sequence of keyboard events.
+	KeySequence(KeySequence),
+	/// The start of a sequence of keyboard events, waiting for the following keys.
+	PendingKeySequence(KeySequence),
 	/// An action event.
 	Standard(StandardEvent),
 	/// A mouse event.
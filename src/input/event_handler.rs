@@ -1,22 +1,48 @@
-use crate::input::{Event, InputOptions, KeyBindings, KeyCode, KeyEvent, KeyModifiers, StandardEvent};
+use std::{collections::VecDeque, time::Instant};
+
+use crate::input::{
+	Event,
+	InputOptions,
+	KeyBindings,
+	KeyCode,
+	KeyEvent,
+	KeyModifiers,
+	KeySequence,
+	StandardEvent,
+};
 
 /// A handler for reading and processing events.
 #[derive(Debug)]
 pub(crate) struct EventHandler {
 	key_bindings: KeyBindings,
+	pending_keys: KeySequence,
+	pending_since: Instant,
+	replay_events: VecDeque<Event>,
 }
 
 impl EventHandler {
 	/// Create a new instance of the `EventHandler`.
 	#[must_use]
-	pub(crate) const fn new(key_bindings: KeyBindings) -> Self {
-		Self { key_bindings }
+	pub(crate) fn new(key_bindings: KeyBindings) -> Self {
+		Self {
+			key_bindings,
+			pending_keys: KeySequence::new(&[]),
+			pending_since: Instant::now(),
+			replay_events: VecDeque::new(),
+		}
+	}
+
+	/// Are there events, from a key sequence that did not complete, waiting to be read.
+	#[must_use]
+	pub(crate) fn has_replay_events(&self) -> bool {
+		!self.replay_events.is_empty()
 	}
 
 	/// Read and handle an event.
 	#[allow(clippy::trivially_copy_pass_by_ref)]
-	pub(crate) fn read_event<F>(&self, event: Event, input_options: &InputOptions, callback: F) -> Event
+	pub(crate) fn read_event<F>(&mut self, input_event: Event, input_options: &InputOptions, callback: F) -> Event
 	where F: FnOnce(Event, &KeyBindings) -> Event {
+		let event = self.handle_key_sequence(input_event, *input_options);
 		if event == Event::None {
 			return event;
 		}
@@ -61,6 +87,63 @@ impl EventHandler {
 		callback(event, &self.key_bindings)
 	}
 
+	/// Move the pending keys to the replay events, so they are handled as individual keys.
+	fn replay_pending_keys(&mut self) {
+		self.replay_events
+			.extend(self.pending_keys.keys().iter().map(|key| Event::Key(*key)));
+		self.pending_keys.clear();
+	}
+
+	fn handle_key_sequence(&mut self, event: Event, input_options: InputOptions) -> Event {
+		// keys from a sequence that did not complete are handled before any new events
+		if let Some(replay_event) = self.replay_events.pop_front() {
+			if event != Event::None {
+				self.replay_events.push_back(event);
+			}
+			return replay_event;
+		}
+
+		if !input_options.contains(InputOptions::KEY_SEQUENCES) || self.key_bindings.key_sequences.is_empty() {
+			if self.pending_keys.is_empty() {
+				return event;
+			}
+			self.replay_pending_keys();
+			return self.handle_key_sequence(event, input_options);
+		}
+
+		match event {
+			Event::None => {
+				let timeout = self.key_bindings.key_sequence_timeout;
+				if !self.pending_keys.is_empty() && self.pending_since.elapsed() >= timeout {
+					self.replay_pending_keys();
+					return self.handle_key_sequence(event, input_options);
+				}
+				event
+			},
+			Event::Key(key) => {
+				let mut keys = self.pending_keys;
+				_ = keys.push(key);
+				// a complete sequence wins over any longer sequence that starts with the same keys
+				if self.key_bindings.key_sequences.contains(&keys) {
+					self.pending_keys.clear();
+					return Event::KeySequence(keys);
+				}
+				if self.key_bindings.key_sequences.iter().any(|s| s.starts_with(&keys)) {
+					self.pending_keys = keys;
+					self.pending_since = Instant::now();
+					return Event::PendingKeySequence(keys);
+				}
+				if self.pending_keys.is_empty() {
+					return event;
+				}
+				self.replay_pending_keys();
+				self.replay_events.push_back(event);
+				self.handle_key_sequence(Event::None, input_options)
+			},
+			_ => event,
+		}
+	}
+
 	#[allow(clippy::wildcard_enum_match_arm)]
 	fn handle_standard_inputs(event: Event) -> Option<Event> {
 		match event {
@@ -161,8 +244,13 @@ impl EventHandler {
 mod tests {
 	use rstest::rstest;
 
+	use std::time::Duration;
+
 	use super::*;
-	use crate::{input::map_keybindings, test_helpers::create_test_keybindings};
+	use crate::{
+		input::map_keybindings,
+		test_helpers::{create_config, create_test_keybindings},
+	};
 
 	#[rstest]
 	#[case::standard(Event::Key(KeyEvent {
@@ -177,7 +265,7 @@ mod tests {
 	}), false)]
 	#[case::other(Event::from('a'), false)]
 	fn read_event_options_disabled(#[case] event: Event, #[case] handled: bool) {
-		let event_handler = EventHandler::new(create_test_keybindings());
+		let mut event_handler = EventHandler::new(create_test_keybindings());
 		let result = event_handler.read_event(event, &InputOptions::empty(), |_, _| Event::from(KeyCode::Null));
 
 		if handled {
@@ -201,7 +289,7 @@ mod tests {
 	}), true)]
 	#[case::other(Event::from('a'), false)]
 	fn read_event_enabled(#[case] event: Event, #[case] handled: bool) {
-		let event_handler = EventHandler::new(create_test_keybindings());
+		let mut event_handler = EventHandler::new(create_test_keybindings());
 		let result = event_handler.read_event(event, &InputOptions::all(), |_, _| Event::from(KeyCode::Null));
 
 		if handled {
@@ -214,7 +302,7 @@ mod tests {
 
 	#[test]
 	fn none_event() {
-		let event_handler = EventHandler::new(create_test_keybindings());
+		let mut event_handler = EventHandler::new(create_test_keybindings());
 		let result = event_handler.read_event(Event::None, &InputOptions::empty(), |_, _| Event::from(KeyCode::Null));
 		assert_eq!(result, Event::None);
 	}
@@ -226,7 +314,7 @@ mod tests {
 	}), Event::from(StandardEvent::Kill))]
 	#[case::other(Event::from('a'), Event::from(KeyCode::Null))]
 	fn standard_inputs(#[case] event: Event, #[case] expected: Event) {
-		let event_handler = EventHandler::new(create_test_keybindings());
+		let mut event_handler = EventHandler::new(create_test_keybindings());
 		let result = event_handler.read_event(event, &InputOptions::empty(), |_, _| Event::from(KeyCode::Null));
 		assert_eq!(result, expected);
 	}
@@ -242,7 +330,7 @@ mod tests {
 	#[case::standard(Event::from(KeyCode::End), Event::from(StandardEvent::ScrollBottom))]
 	#[case::other(Event::from('a'), Event::from(KeyCode::Null))]
 	fn movement_inputs(#[case] event: Event, #[case] expected: Event) {
-		let event_handler = EventHandler::new(create_test_keybindings());
+		let mut event_handler = EventHandler::new(create_test_keybindings());
 		let result = event_handler.read_event(event, &InputOptions::MOVEMENT, |_, _| Event::from(KeyCode::Null));
 		assert_eq!(result, expected);
 	}
@@ -267,7 +355,7 @@ mod tests {
 		bindings.scroll_up = map_keybindings(&[String::from("x")]);
 		bindings.scroll_step_down = map_keybindings(&[String::from("x")]);
 		bindings.scroll_step_up = map_keybindings(&[String::from("x")]);
-		let event_handler = EventHandler::new(bindings);
+		let mut event_handler = EventHandler::new(bindings);
 		let result = event_handler.read_event(event, &InputOptions::MOVEMENT, |_, _| Event::from(KeyCode::Null));
 		assert_eq!(result, expected);
 	}
@@ -280,7 +368,7 @@ mod tests {
 	#[case::enter(Event::from(KeyCode::Enter), Event::from(StandardEvent::SearchFinish))]
 	#[case::other(Event::from('a'), Event::from(KeyCode::Null))]
 	fn search_inputs(#[case] event: Event, #[case] expected: Event) {
-		let event_handler = EventHandler::new(create_test_keybindings());
+		let mut event_handler = EventHandler::new(create_test_keybindings());
 		let result = event_handler.read_event(event, &InputOptions::SEARCH, |_, _| Event::from(KeyCode::Null));
 		assert_eq!(result, expected);
 	}
@@ -289,14 +377,14 @@ mod tests {
 	#[case::search_start(Event::from('/'), Event::from(StandardEvent::SearchStart))]
 	#[case::other(Event::from('a'), Event::from(KeyCode::Null))]
 	fn search_start(#[case] event: Event, #[case] expected: Event) {
-		let event_handler = EventHandler::new(create_test_keybindings());
+		let mut event_handler = EventHandler::new(create_test_keybindings());
 		let result = event_handler.read_event(event, &InputOptions::SEARCH_START, |_, _| Event::from(KeyCode::Null));
 		assert_eq!(result, expected);
 	}
 
 	#[test]
 	fn help_event() {
-		let event_handler = EventHandler::new(create_test_keybindings());
+		let mut event_handler = EventHandler::new(create_test_keybindings());
 		let result = event_handler.read_event(Event::from('?'), &InputOptions::HELP, |_, _| Event::from(KeyCode::Null));
 		assert_eq!(result, Event::from(StandardEvent::Help));
 	}
@@ -312,8 +400,162 @@ mod tests {
 	}), Event::from(StandardEvent::Redo))]
 	#[case::other(Event::from('a'), Event::from(KeyCode::Null))]
 	fn undo_redo_inputs(#[case] event: Event, #[case] expected: Event) {
-		let event_handler = EventHandler::new(create_test_keybindings());
+		let mut event_handler = EventHandler::new(create_test_keybindings());
 		let result = event_handler.read_event(event, &InputOptions::UNDO_REDO, |_, _| Event::from(KeyCode::Null));
 		assert_eq!(result, expected);
 	}
+
+	fn create_key_sequence_event_handler(timeout: u64) -> EventHandler {
+		let mut bindings = create_test_keybindings();
+		bindings.move_home = map_keybindings(&[String::from("g g")]);
+		bindings.move_end = map_keybindings(&[String::from("g e x")]);
+		bindings.key_sequences = vec![
+			KeySequence::new(&[KeyEvent::from(KeyCode::Char('g')), KeyEvent::from(KeyCode::Char('g'))]),
+			KeySequence::new(&[
+				KeyEvent::from(KeyCode::Char('g')),
+				KeyEvent::from(KeyCode::Char('e')),
+				KeyEvent::from(KeyCode::Char('x')),
+			]),
+		];
+		bindings.key_sequence_timeout = Duration::from_millis(timeout);
+		EventHandler::new(bindings)
+	}
+
+	fn read_events(event_handler: &mut EventHandler, events: &[Event], input_options: InputOptions) -> Vec<Event> {
+		let mut results = vec![];
+		for event in events {
+			results.push(event_handler.read_event(*event, &input_options, |e, _| e));
+		}
+		while event_handler.has_replay_events() {
+			results.push(event_handler.read_event(Event::None, &input_options, |e, _| e));
+		}
+		results
+	}
+
+	fn key_sequence(keys: &str) -> KeySequence {
+		let keys: Vec<KeyEvent> = keys.chars().map(|c| KeyEvent::from(KeyCode::Char(c))).collect();
+		KeySequence::new(&keys)
+	}
+
+	#[test]
+	fn key_sequence_complete() {
+		let mut event_handler = create_key_sequence_event_handler(1000);
+		assert_eq!(
+			read_events(
+				&mut event_handler,
+				&[Event::from('g'), Event::from('g')],
+				InputOptions::KEY_SEQUENCES
+			),
+			vec![
+				Event::PendingKeySequence(key_sequence("g")),
+				Event::KeySequence(key_sequence("gg"))
+			]
+		);
+	}
+
+	#[test]
+	fn key_sequence_mismatch_replays_keys() {
+		let mut event_handler = create_key_sequence_event_handler(1000);
+		assert_eq!(
+			read_events(
+				&mut event_handler,
+				&[Event::from('g'), Event::from('e'), Event::from('a')],
+				InputOptions::KEY_SEQUENCES
+			),
+			vec![
+				Event::PendingKeySequence(key_sequence("g")),
+				Event::PendingKeySequence(key_sequence("ge")),
+				Event::from('g'),
+				Event::from('e'),
+				Event::from('a'),
+			]
+		);
+	}
+
+	#[test]
+	fn key_sequence_replay_before_new_event() {
+		let mut event_handler = create_key_sequence_event_handler(1000);
+		let options = InputOptions::KEY_SEQUENCES;
+		_ = event_handler.read_event(Event::from('g'), &options, |e, _| e);
+		assert_eq!(event_handler.read_event(Event::from('a'), &options, |e, _| e), Event::from('g'));
+		assert_eq!(event_handler.read_event(Event::from('b'), &options, |e, _| e), Event::from('a'));
+		assert_eq!(event_handler.read_event(Event::None, &options, |e, _| e), Event::from('b'));
+		assert!(!event_handler.has_replay_events());
+	}
+
+	#[test]
+	fn key_sequence_timeout() {
+		let mut event_handler = create_key_sequence_event_handler(0);
+		assert_eq!(
+			read_events(&mut event_handler, &[Event::from('g'), Event::None], InputOptions::KEY_SEQUENCES),
+			vec![Event::PendingKeySequence(key_sequence("g")), Event::from('g')]
+		);
+	}
+
+	#[test]
+	fn key_sequence_waits_before_timeout() {
+		let mut event_handler = create_key_sequence_event_handler(100_000);
+		assert_eq!(
+			read_events(&mut event_handler, &[Event::from('g'), Event::None], InputOptions::KEY_SEQUENCES),
+			vec![Event::PendingKeySequence(key_sequence("g")), Event::None]
+		);
+	}
+
+	#[test]
+	fn key_sequence_other_events_keep_pending_keys() {
+		let mut event_handler = create_key_sequence_event_handler(1000);
+		assert_eq!(
+			read_events(
+				&mut event_handler,
+				&[Event::from('g'), Event::Resize(10, 10), Event::from('g')],
+				InputOptions::KEY_SEQUENCES | InputOptions::RESIZE
+			),
+			vec![
+				Event::PendingKeySequence(key_sequence("g")),
+				Event::Resize(10, 10),
+				Event::KeySequence(key_sequence("gg"))
+			]
+		);
+	}
+
+	#[test]
+	fn key_sequence_disabled() {
+		let mut event_handler = create_key_sequence_event_handler(1000);
+		assert_eq!(
+			read_events(&mut event_handler, &[Event::from('g'), Event::from('g')], InputOptions::empty()),
+			vec![Event::from('g'), Event::from('g')]
+		);
+	}
+
+	#[test]
+	fn key_sequence_disabled_while_pending() {
+		let mut event_handler = create_key_sequence_event_handler(1000);
+		_ = event_handler.read_event(Event::from('g'), &InputOptions::KEY_SEQUENCES, |e, _| e);
+		assert_eq!(
+			read_events(&mut event_handler, &[Event::from('a')], InputOptions::empty()),
+			vec![Event::from('g'), Event::from('a')]
+		);
+	}
+
+	#[test]
+	fn key_sequence_default_move_home() {
+		let mut event_handler = EventHandler::new(KeyBindings::new(&create_config().key_bindings));
+		assert_eq!(
+			read_events(
+				&mut event_handler,
+				&[Event::from('g'), Event::from('g')],
+				InputOptions::KEY_SEQUENCES | InputOptions::MOVEMENT
+			),
+			vec![
+				Event::PendingKeySequence(key_sequence("g")),
+				Event::KeySequence(key_sequence("gg"))
+			]
+		);
+		assert!(
+			event_handler
+				.key_bindings
+				.move_home
+				.contains(&Event::KeySequence(key_sequence("gg")))
+		);
+	}
 }
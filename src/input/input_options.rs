@@ -16,5 +16,7 @@ bitflags! {
 		const SEARCH = 0b0001_1000;
 		/// Help input handling
 		const HELP = 0b0010_0000;
+		/// Key sequence handling
+		const KEY_SEQUENCES = 0b0100_0000;
 	}
 }
@@ -1,4 +1,6 @@
-use crate::input::{map_keybindings, Event};
+use std::time::Duration;
+
+use crate::input::{map_keybindings, Event, KeySequence};
 
 /// Represents a mapping between an input event and an action.
 #[derive(Debug)]
@@ -48,6 +50,8 @@ pub(crate) struct KeyBindings {
 	pub(crate) action_fixup: Vec<Event>,
 	/// Key bindings for the pick action.
 	pub(crate) action_pick: Vec<Event>,
+	/// Key bindings for the revert action.
+	pub(crate) action_revert: Vec<Event>,
 	/// Key bindings for the reword action.
 	pub(crate) action_reword: Vec<Event>,
 	/// Key bindings for the squash action.
@@ -62,6 +66,16 @@ pub(crate) struct KeyBindings {
 	pub(crate) force_rebase: Vec<Event>,
 	/// Key bindings for inserting a line.
 	pub(crate) insert_line: Vec<Event>,
+	/// Key bindings for inserting the update-ref lines for branches.
+	pub(crate) insert_update_refs: Vec<Event>,
+	/// Key bindings for inserting exec lines after the commits.
+	pub(crate) insert_exec_lines: Vec<Event>,
+	/// Key bindings for composing a new message for the selected commit.
+	pub(crate) reword_message: Vec<Event>,
+	/// Key bindings for changing the author, committer date or trailers of the selected commits.
+	pub(crate) rewrite_metadata: Vec<Event>,
+	/// Key bindings for browsing the undo history.
+	pub(crate) show_undo_history: Vec<Event>,
 	/// Key bindings for moving down.
 	pub(crate) move_down: Vec<Event>,
 	/// Key bindings for moving down a step.
@@ -84,6 +98,8 @@ pub(crate) struct KeyBindings {
 	pub(crate) move_up_step: Vec<Event>,
 	/// Key bindings for opening the external editor.
 	pub(crate) open_in_external_editor: Vec<Event>,
+	/// Key bindings for opening the selected lines in the external editor.
+	pub(crate) open_selection_in_external_editor: Vec<Event>,
 	/// Key bindings for rebasing.
 	pub(crate) rebase: Vec<Event>,
 	/// Key bindings for removing a line.
@@ -94,63 +110,131 @@ pub(crate) struct KeyBindings {
 	pub(crate) show_diff: Vec<Event>,
 	/// Key bindings for toggling visual mode.
 	pub(crate) toggle_visual_mode: Vec<Event>,
+	/// Key bindings for toggling the stack view.
+	pub(crate) toggle_stack_view: Vec<Event>,
+	/// Key bindings for toggling the folding of fixup and squash lines.
+	pub(crate) toggle_fold_view: Vec<Event>,
+	/// Key bindings for toggling the filter of the list by the search term.
+	pub(crate) toggle_filter: Vec<Event>,
+	/// Key bindings for collapsing or expanding a section of the stack view.
+	pub(crate) toggle_section: Vec<Event>,
+	/// Key bindings for applying the next action to all search matches.
+	pub(crate) apply_to_matches: Vec<Event>,
+	/// Key bindings for marking or unmarking the selected lines.
+	pub(crate) toggle_mark: Vec<Event>,
+	/// Key bindings for moving the marked lines after the selected line.
+	pub(crate) gather_marked: Vec<Event>,
+	/// Key bindings for clearing the marked lines.
+	pub(crate) clear_marks: Vec<Event>,
+	/// Key bindings for naming a mark for the selected line.
+	pub(crate) set_named_mark: Vec<Event>,
+	/// Key bindings for jumping to the line of a named mark.
+	pub(crate) jump_to_named_mark: Vec<Event>,
+	/// Key bindings for sorting the selected lines by the author date of the commits.
+	pub(crate) sort_by_author_date: Vec<Event>,
+	/// Key bindings for sorting the selected lines by the committer date of the commits.
+	pub(crate) sort_by_commit_date: Vec<Event>,
+	/// Key bindings for reversing the order of the selected lines.
+	pub(crate) reverse_selection: Vec<Event>,
+	/// Key bindings for restoring the original order of the selected lines.
+	pub(crate) restore_order: Vec<Event>,
 	/// Key bindings for the fixup specific action to toggle the c option.
 	pub(crate) fixup_keep_message: Vec<Event>,
 	/// Key biding for the fixup specific action to toggle the C option.
 	pub(crate) fixup_keep_message_with_editor: Vec<Event>,
+
+	/// All the key sequences used by the key bindings.
+	pub(crate) key_sequences: Vec<KeySequence>,
+	/// The time to wait for the next key of a key sequence.
+	pub(crate) key_sequence_timeout: Duration,
 }
 
 impl KeyBindings {
 	/// Create a new instance from the configuration keybindings.
 	#[must_use]
 	pub(crate) fn new(key_bindings: &crate::config::KeyBindings) -> Self {
-		Self {
-			redo: map_keybindings(&key_bindings.redo),
-			undo: map_keybindings(&key_bindings.undo),
-			scroll_down: map_keybindings(&key_bindings.scroll_down),
-			scroll_end: map_keybindings(&key_bindings.scroll_end),
-			scroll_home: map_keybindings(&key_bindings.scroll_home),
-			scroll_left: map_keybindings(&key_bindings.scroll_left),
-			scroll_right: map_keybindings(&key_bindings.scroll_right),
-			scroll_up: map_keybindings(&key_bindings.scroll_up),
-			scroll_step_down: map_keybindings(&key_bindings.scroll_step_down),
-			scroll_step_up: map_keybindings(&key_bindings.scroll_step_up),
-			help: map_keybindings(&key_bindings.help),
-			search_start: map_keybindings(&key_bindings.search_start),
-			search_next: map_keybindings(&key_bindings.search_next),
-			search_previous: map_keybindings(&key_bindings.search_previous),
-			abort: map_keybindings(&key_bindings.abort),
-			action_break: map_keybindings(&key_bindings.action_break),
-			action_drop: map_keybindings(&key_bindings.action_drop),
-			action_edit: map_keybindings(&key_bindings.action_edit),
-			action_fixup: map_keybindings(&key_bindings.action_fixup),
-			action_pick: map_keybindings(&key_bindings.action_pick),
-			action_reword: map_keybindings(&key_bindings.action_reword),
-			action_squash: map_keybindings(&key_bindings.action_squash),
-			edit: map_keybindings(&key_bindings.edit),
-			force_abort: map_keybindings(&key_bindings.force_abort),
-			force_rebase: map_keybindings(&key_bindings.force_rebase),
-			insert_line: map_keybindings(&key_bindings.insert_line),
-			move_down: map_keybindings(&key_bindings.move_down),
-			move_down_step: map_keybindings(&key_bindings.move_down_step),
-			move_end: map_keybindings(&key_bindings.move_end),
-			move_home: map_keybindings(&key_bindings.move_home),
-			move_left: map_keybindings(&key_bindings.move_left),
-			move_right: map_keybindings(&key_bindings.move_right),
-			move_selection_down: map_keybindings(&key_bindings.move_selection_down),
-			move_selection_up: map_keybindings(&key_bindings.move_selection_up),
-			move_up: map_keybindings(&key_bindings.move_up),
-			move_up_step: map_keybindings(&key_bindings.move_up_step),
-			open_in_external_editor: map_keybindings(&key_bindings.open_in_external_editor),
-			rebase: map_keybindings(&key_bindings.rebase),
-			remove_line: map_keybindings(&key_bindings.remove_line),
-			show_commit: map_keybindings(&key_bindings.show_commit),
-			show_diff: map_keybindings(&key_bindings.show_diff),
-			toggle_visual_mode: map_keybindings(&key_bindings.toggle_visual_mode),
-			confirm_yes: map_keybindings(&key_bindings.confirm_yes),
-			fixup_keep_message: map_keybindings(&key_bindings.fixup_keep_message),
-			fixup_keep_message_with_editor: map_keybindings(&key_bindings.fixup_keep_message_with_editor),
-		}
+		let mut key_sequences = vec![];
+		let mut map = |bindings: &[String]| {
+			let events = map_keybindings(bindings);
+			for event in &events {
+				if let Event::KeySequence(sequence) = *event {
+					key_sequences.push(sequence);
+				}
+			}
+			events
+		};
+		let mut input_key_bindings = Self {
+			redo: map(&key_bindings.redo),
+			undo: map(&key_bindings.undo),
+			scroll_down: map(&key_bindings.scroll_down),
+			scroll_end: map(&key_bindings.scroll_end),
+			scroll_home: map(&key_bindings.scroll_home),
+			scroll_left: map(&key_bindings.scroll_left),
+			scroll_right: map(&key_bindings.scroll_right),
+			scroll_up: map(&key_bindings.scroll_up),
+			scroll_step_down: map(&key_bindings.scroll_step_down),
+			scroll_step_up: map(&key_bindings.scroll_step_up),
+			help: map(&key_bindings.help),
+			search_start: map(&key_bindings.search_start),
+			search_next: map(&key_bindings.search_next),
+			search_previous: map(&key_bindings.search_previous),
+			abort: map(&key_bindings.abort),
+			action_break: map(&key_bindings.action_break),
+			action_drop: map(&key_bindings.action_drop),
+			action_edit: map(&key_bindings.action_edit),
+			action_fixup: map(&key_bindings.action_fixup),
+			action_pick: map(&key_bindings.action_pick),
+			action_revert: map(&key_bindings.action_revert),
+			action_reword: map(&key_bindings.action_reword),
+			action_squash: map(&key_bindings.action_squash),
+			edit: map(&key_bindings.edit),
+			force_abort: map(&key_bindings.force_abort),
+			force_rebase: map(&key_bindings.force_rebase),
+			insert_line: map(&key_bindings.insert_line),
+			insert_update_refs: map(&key_bindings.insert_update_refs),
+			insert_exec_lines: map(&key_bindings.insert_exec_lines),
+			reword_message: map(&key_bindings.reword_message),
+			rewrite_metadata: map(&key_bindings.rewrite_metadata),
+			show_undo_history: map(&key_bindings.show_undo_history),
+			move_down: map(&key_bindings.move_down),
+			move_down_step: map(&key_bindings.move_down_step),
+			move_end: map(&key_bindings.move_end),
+			move_home: map(&key_bindings.move_home),
+			move_left: map(&key_bindings.move_left),
+			move_right: map(&key_bindings.move_right),
+			move_selection_down: map(&key_bindings.move_selection_down),
+			move_selection_up: map(&key_bindings.move_selection_up),
+			move_up: map(&key_bindings.move_up),
+			move_up_step: map(&key_bindings.move_up_step),
+			open_in_external_editor: map(&key_bindings.open_in_external_editor),
+			open_selection_in_external_editor: map(&key_bindings.open_selection_in_external_editor),
+			rebase: map(&key_bindings.rebase),
+			remove_line: map(&key_bindings.remove_line),
+			show_commit: map(&key_bindings.show_commit),
+			show_diff: map(&key_bindings.show_diff),
+			toggle_visual_mode: map(&key_bindings.toggle_visual_mode),
+			toggle_stack_view: map(&key_bindings.toggle_stack_view),
+			toggle_fold_view: map(&key_bindings.toggle_fold_view),
+			toggle_filter: map(&key_bindings.toggle_filter),
+			toggle_section: map(&key_bindings.toggle_section),
+			confirm_yes: map(&key_bindings.confirm_yes),
+			fixup_keep_message: map(&key_bindings.fixup_keep_message),
+			apply_to_matches: map(&key_bindings.apply_to_matches),
+			toggle_mark: map(&key_bindings.toggle_mark),
+			gather_marked: map(&key_bindings.gather_marked),
+			clear_marks: map(&key_bindings.clear_marks),
+			set_named_mark: map(&key_bindings.set_named_mark),
+			jump_to_named_mark: map(&key_bindings.jump_to_named_mark),
+			sort_by_author_date: map(&key_bindings.sort_by_author_date),
+			sort_by_commit_date: map(&key_bindings.sort_by_commit_date),
+			reverse_selection: map(&key_bindings.reverse_selection),
+			restore_order: map(&key_bindings.restore_order),
+			fixup_keep_message_with_editor: map(&key_bindings.fixup_keep_message_with_editor),
+			key_sequences: vec![],
+			key_sequence_timeout: Duration::from_millis(u64::from(key_bindings.key_sequence_timeout)),
+		};
+		input_key_bindings.key_sequences = key_sequences;
+		input_key_bindings
 	}
 }
 
@@ -160,7 +244,7 @@ mod tests {
 	use rstest::rstest;
 
 	use super::*;
-	use crate::input::KeyEvent;
+	use crate::{input::KeyEvent, test_helpers::create_config};
 
 	#[test]
 	fn map_keybindings_with_modifiers() {
@@ -172,6 +256,28 @@ mod tests {
 		)]);
 	}
 
+	#[test]
+	fn map_keybindings_key_sequence() {
+		assert_eq!(map_keybindings(&[String::from("g ControlHome")]), vec![Event::KeySequence(
+			KeySequence::new(&[
+				KeyEvent::from(KeyCode::Char('g')),
+				KeyEvent::new(KeyCode::Home, KeyModifiers::CONTROL)
+			])
+		)]);
+	}
+
+	#[test]
+	fn new_collects_key_sequences() {
+		let mut config_key_bindings = create_config().key_bindings;
+		config_key_bindings.move_home = vec![String::from("g g"), String::from("Home")];
+		let key_bindings = KeyBindings::new(&config_key_bindings);
+		assert_eq!(key_bindings.key_sequences, vec![KeySequence::new(&[
+			KeyEvent::from(KeyCode::Char('g')),
+			KeyEvent::from(KeyCode::Char('g'))
+		])]);
+		assert_eq!(key_bindings.key_sequence_timeout, Duration::from_secs(1));
+	}
+
 	#[rstest]
 	#[case::backspace("Backspace", KeyCode::Backspace)]
 	#[case::back_tab("BackTab", KeyCode::BackTab)]
@@ -186,6 +292,7 @@ mod tests {
 	#[case::page_down("PageDown", KeyCode::PageDown)]
 	#[case::page_up("PageUp", KeyCode::PageUp)]
 	#[case::right("Right", KeyCode::Right)]
+	#[case::space("Space", KeyCode::Char(' '))]
 	#[case::tab("Tab", KeyCode::Tab)]
 	#[case::up("Up", KeyCode::Up)]
 	#[case::function_in_range("F10", KeyCode::F(10))]
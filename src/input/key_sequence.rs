@@ -0,0 +1,155 @@
+use std::fmt::{Display, Formatter};
+
+use crate::{
+	config::MAX_KEY_SEQUENCE_LENGTH,
+	input::{KeyCode, KeyEvent, KeyModifiers},
+};
+
+/// Represents a sequence of key events, like `g` followed by `g`.
+#[derive(Debug, PartialOrd, PartialEq, Eq, Clone, Copy)]
+pub(crate) struct KeySequence {
+	keys: [KeyEvent; MAX_KEY_SEQUENCE_LENGTH],
+	length: usize,
+}
+
+impl KeySequence {
+	/// Create a new `KeySequence` from the keys, ignoring any keys past the maximum length of a sequence.
+	#[must_use]
+	pub(crate) fn new(keys: &[KeyEvent]) -> Self {
+		let mut sequence = Self {
+			keys: [KeyEvent::from(KeyCode::Null); MAX_KEY_SEQUENCE_LENGTH],
+			length: 0,
+		};
+		for key in keys {
+			_ = sequence.push(*key);
+		}
+		sequence
+	}
+
+	/// Add a key to the end of the sequence, returning `false` if the sequence is already at the maximum length.
+	pub(crate) fn push(&mut self, key: KeyEvent) -> bool {
+		if self.length == MAX_KEY_SEQUENCE_LENGTH {
+			return false;
+		}
+		self.keys[self.length] = key;
+		self.length += 1;
+		true
+	}
+
+	/// Remove all the keys from the sequence.
+	pub(crate) fn clear(&mut self) {
+		// unused keys are always null, so that sequences with the same keys are equal
+		self.keys = [KeyEvent::from(KeyCode::Null); MAX_KEY_SEQUENCE_LENGTH];
+		self.length = 0;
+	}
+
+	/// Get the keys of the sequence.
+	#[must_use]
+	pub(crate) fn keys(&self) -> &[KeyEvent] {
+		&self.keys[..self.length]
+	}
+
+	/// Does the sequence contain any keys.
+	#[must_use]
+	pub(crate) const fn is_empty(&self) -> bool {
+		self.length == 0
+	}
+
+	/// Does this sequence start with all the keys of the other sequence, without being equal to it.
+	#[must_use]
+	pub(crate) fn starts_with(&self, other: &Self) -> bool {
+		self.length > other.length && self.keys().starts_with(other.keys())
+	}
+}
+
+impl Display for KeySequence {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		for key in self.keys() {
+			if key.modifiers.contains(KeyModifiers::CONTROL) {
+				write!(f, "^")?;
+			}
+			if key.modifiers.contains(KeyModifiers::ALT) {
+				write!(f, "M-")?;
+			}
+			match key.code {
+				KeyCode::Char(' ') => write!(f, "<Space>")?,
+				KeyCode::Char(c) => write!(f, "{c}")?,
+				KeyCode::F(n) => write!(f, "<F{n}>")?,
+				KeyCode::Backspace => write!(f, "<Backspace>")?,
+				KeyCode::BackTab => write!(f, "<BackTab>")?,
+				KeyCode::Delete => write!(f, "<Delete>")?,
+				KeyCode::Down => write!(f, "<Down>")?,
+				KeyCode::End => write!(f, "<End>")?,
+				KeyCode::Enter => write!(f, "<Enter>")?,
+				KeyCode::Esc => write!(f, "<Esc>")?,
+				KeyCode::Home => write!(f, "<Home>")?,
+				KeyCode::Insert => write!(f, "<Insert>")?,
+				KeyCode::Left => write!(f, "<Left>")?,
+				KeyCode::PageDown => write!(f, "<PageDown>")?,
+				KeyCode::PageUp => write!(f, "<PageUp>")?,
+				KeyCode::Right => write!(f, "<Right>")?,
+				KeyCode::Tab => write!(f, "<Tab>")?,
+				KeyCode::Up => write!(f, "<Up>")?,
+				_ => write!(f, "<?>")?,
+			}
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn new() {
+		let sequence = KeySequence::new(&[KeyEvent::from(KeyCode::Char('g')), KeyEvent::from(KeyCode::Home)]);
+		assert_eq!(sequence.keys(), &[
+			KeyEvent::from(KeyCode::Char('g')),
+			KeyEvent::from(KeyCode::Home)
+		]);
+		assert!(!sequence.is_empty());
+	}
+
+	#[test]
+	fn new_too_long() {
+		let keys = [KeyEvent::from(KeyCode::Char('a')); MAX_KEY_SEQUENCE_LENGTH + 1];
+		assert_eq!(KeySequence::new(&keys).keys().len(), MAX_KEY_SEQUENCE_LENGTH);
+	}
+
+	#[test]
+	fn push_and_clear() {
+		let mut sequence = KeySequence::new(&[]);
+		assert!(sequence.is_empty());
+		assert!(sequence.push(KeyEvent::from(KeyCode::Char('a'))));
+		assert_eq!(sequence.keys(), &[KeyEvent::from(KeyCode::Char('a'))]);
+		sequence.clear();
+		assert!(sequence.is_empty());
+	}
+
+	#[test]
+	fn starts_with() {
+		let g = KeyEvent::from(KeyCode::Char('g'));
+		let sequence = KeySequence::new(&[g, g]);
+		assert!(sequence.starts_with(&KeySequence::new(&[g])));
+		assert!(!sequence.starts_with(&KeySequence::new(&[g, g])));
+		assert!(!sequence.starts_with(&KeySequence::new(&[KeyEvent::from(KeyCode::Char('a'))])));
+	}
+
+	#[test]
+	fn display() {
+		let sequence = KeySequence::new(&[
+			KeyEvent::new(KeyCode::Char('w'), KeyModifiers::CONTROL),
+			KeyEvent::new(KeyCode::Char('j'), KeyModifiers::ALT),
+			KeyEvent::from(KeyCode::F(2)),
+			KeyEvent::from(KeyCode::Home),
+		]);
+		assert_eq!(sequence.to_string(), "^wM-j<F2><Home>");
+	}
+
+	#[test]
+	fn display_space() {
+		let sequence = KeySequence::new(&[KeyEvent::from(KeyCode::Char(' ')), KeyEvent::from(KeyCode::Char('a'))]);
+		assert_eq!(sequence.to_string(), "<Space>a");
+	}
+}
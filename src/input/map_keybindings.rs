@@ -1,53 +1,64 @@
 use crossterm::event::{KeyCode, KeyModifiers};
 
-use crate::input::{Event, KeyEvent};
+use crate::input::{Event, KeyEvent, KeySequence};
 
-/// Map a keybinding to a list of events.
-#[must_use]
 #[allow(clippy::string_slice, clippy::missing_panics_doc)]
+fn map_key(binding: &str) -> KeyEvent {
+	let mut key = String::from(binding);
+	let mut modifiers = KeyModifiers::empty();
+	if key.contains("Control") {
+		key = key.replace("Control", "");
+		modifiers.insert(KeyModifiers::CONTROL);
+	}
+	if key.contains("Alt") {
+		key = key.replace("Alt", "");
+		modifiers.insert(KeyModifiers::ALT);
+	}
+	if key.contains("Shift") {
+		key = key.replace("Shift", "");
+		modifiers.insert(KeyModifiers::SHIFT);
+	}
+
+	let code = match key.as_str() {
+		"Backspace" => KeyCode::Backspace,
+		"BackTab" => KeyCode::BackTab,
+		"Delete" => KeyCode::Delete,
+		"Down" => KeyCode::Down,
+		"End" => KeyCode::End,
+		"Enter" => KeyCode::Enter,
+		"Esc" => KeyCode::Esc,
+		"Home" => KeyCode::Home,
+		"Insert" => KeyCode::Insert,
+		"Left" => KeyCode::Left,
+		"PageDown" => KeyCode::PageDown,
+		"PageUp" => KeyCode::PageUp,
+		"Right" => KeyCode::Right,
+		"Space" => KeyCode::Char(' '),
+		"Tab" => KeyCode::Tab,
+		"Up" => KeyCode::Up,
+		// assume that this is an F key
+		k if k.len() > 1 => {
+			let key_number = k[1..].parse::<u8>().unwrap_or(1);
+			KeyCode::F(key_number)
+		},
+		k => KeyCode::Char(k.chars().next().expect("Expected only one character from Char KeyCode")),
+	};
+	KeyEvent::new(code, modifiers)
+}
+
+/// Map a keybinding to a list of events, where the keys of a key sequence are separated by a space.
+#[must_use]
 pub(crate) fn map_keybindings(bindings: &[String]) -> Vec<Event> {
 	bindings
 		.iter()
 		.map(|b| {
-			let mut key = String::from(b);
-			let mut modifiers = KeyModifiers::empty();
-			if key.contains("Control") {
-				key = key.replace("Control", "");
-				modifiers.insert(KeyModifiers::CONTROL);
-			}
-			if key.contains("Alt") {
-				key = key.replace("Alt", "");
-				modifiers.insert(KeyModifiers::ALT);
+			if b.contains(' ') {
+				let keys: Vec<KeyEvent> = b.split(' ').map(map_key).collect();
+				Event::KeySequence(KeySequence::new(&keys))
 			}
-			if key.contains("Shift") {
-				key = key.replace("Shift", "");
-				modifiers.insert(KeyModifiers::SHIFT);
+			else {
+				Event::Key(map_key(b))
 			}
-
-			let code = match key.as_str() {
-				"Backspace" => KeyCode::Backspace,
-				"BackTab" => KeyCode::BackTab,
-				"Delete" => KeyCode::Delete,
-				"Down" => KeyCode::Down,
-				"End" => KeyCode::End,
-				"Enter" => KeyCode::Enter,
-				"Esc" => KeyCode::Esc,
-				"Home" => KeyCode::Home,
-				"Insert" => KeyCode::Insert,
-				"Left" => KeyCode::Left,
-				"PageDown" => KeyCode::PageDown,
-				"PageUp" => KeyCode::PageUp,
-				"Right" => KeyCode::Right,
-				"Tab" => KeyCode::Tab,
-				"Up" => KeyCode::Up,
-				// assume that this is an F key
-				k if k.len() > 1 => {
-					let key_number = k[1..].parse::<u8>().unwrap_or(1);
-					KeyCode::F(key_number)
-				},
-				k => KeyCode::Char(k.chars().next().expect("Expected only one character from Char KeyCode")),
-			};
-			Event::Key(KeyEvent::new(code, modifiers))
 		})
 		.collect()
 }
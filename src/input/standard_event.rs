@@ -57,6 +57,8 @@ pub(crate) enum StandardEvent {
 	ActionFixup,
 	/// The pick action meta event.
 	ActionPick,
+	/// The revert action meta event.
+	ActionRevert,
 	/// The reword action meta event.
 	ActionReword,
 	/// The squash action meta event.
@@ -83,6 +85,8 @@ pub(crate) enum StandardEvent {
 	Edit,
 	/// The open in editor meta event.
 	OpenInEditor,
+	/// The open selection in editor meta event.
+	OpenSelectionInEditor,
 	/// The show commit meta event.
 	ShowCommit,
 	/// The show diff meta event.
@@ -93,8 +97,46 @@ pub(crate) enum StandardEvent {
 	SwapSelectedUp,
 	/// The toggle visual mode meta event.
 	ToggleVisualMode,
+	/// The toggle stack view meta event.
+	ToggleStackView,
+	/// The toggle folding of fixup and squash lines meta event.
+	ToggleFoldView,
+	/// The toggle filter meta event.
+	ToggleFilter,
+	/// The apply next action to all search matches meta event.
+	ApplyToMatches,
+	/// The toggle mark of the selected lines meta event.
+	ToggleMark,
+	/// The gather marked lines meta event.
+	GatherMarked,
+	/// The clear marked lines meta event.
+	ClearMarks,
+	/// The name a mark for the selected line meta event.
+	SetNamedMark,
+	/// The jump to the line of a named mark meta event.
+	JumpToNamedMark,
+	/// The sort selected lines by author date meta event.
+	SortByAuthorDate,
+	/// The sort selected lines by committer date meta event.
+	SortByCommitDate,
+	/// The reverse selected lines meta event.
+	ReverseSelection,
+	/// The restore original order of the selected lines meta event.
+	RestoreOrder,
+	/// The toggle section collapse meta event.
+	ToggleSection,
 	/// The insert line meta event.
 	InsertLine,
+	/// The insert update-ref lines meta event.
+	InsertUpdateRefs,
+	/// The insert exec lines after the commits meta event.
+	InsertExecLines,
+	/// The compose a new message for the selected commit meta event.
+	RewordMessage,
+	/// The change the author, committer date or trailers of the selected commits meta event.
+	RewriteMetadata,
+	/// The browse the undo history meta event.
+	ShowUndoHistory,
 	/// Fixup specific action to toggle the c option.
 	FixupKeepMessage,
 	/// Fixup specific action to toggle the C option.
@@ -41,6 +41,7 @@ mod license;
 mod module;
 mod modules;
 mod process;
+mod rebase;
 mod runtime;
 mod search;
 #[cfg(test)]
@@ -69,6 +70,7 @@ fn run(os_args: Vec<OsString>) -> Exit {
 				Mode::Version => version::run(),
 				Mode::License => license::run(),
 				Mode::Editor => editor::run(&args),
+				Mode::Rebase => rebase::run(&args),
 			}
 		},
 	}
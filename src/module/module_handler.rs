@@ -40,9 +40,16 @@ impl<ModuleProvider: crate::module::ModuleProvider> ModuleHandler<ModuleProvider
 	) -> Option<Results> {
 		let module = self.module_provider.get_module(state);
 		let input_options = module.input_options();
+		// the keys of an incomplete key sequence are handled before waiting on any new input
+		let input_event = if self.event_handler.has_replay_events() {
+			Event::None
+		}
+		else {
+			input_state.read_event()
+		};
 		let event = self
 			.event_handler
-			.read_event(input_state.read_event(), input_options, |event, key_bindings| {
+			.read_event(input_event, input_options, |event, key_bindings| {
 				module.read_event(event, key_bindings)
 			});
 		(event != Event::None).then(|| {
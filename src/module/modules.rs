@@ -3,6 +3,7 @@ use std::sync::Arc;
 use parking_lot::Mutex;
 
 use crate::{
+	components::InputHistory,
 	config::Config,
 	git::Repository,
 	module::{Module, ModuleProvider, State},
@@ -10,6 +11,8 @@ use crate::{
 	todo_file::TodoFile,
 };
 
+const INPUT_HISTORY_FILE_NAME: &str = "interactive-rebase-tool-history";
+
 pub(crate) struct Modules {
 	confirm_abort: ConfirmAbort,
 	confirm_rebase: ConfirmRebase,
@@ -23,10 +26,22 @@ pub(crate) struct Modules {
 
 impl ModuleProvider for Modules {
 	fn new(config: &Config, repository: Repository, todo_file: &Arc<Mutex<TodoFile>>) -> Self {
+		// the branches are only used for update-ref lines, so a failure to load them should not prevent editing
+		let branches = repository.load_local_branches().unwrap_or_default();
+		let history = InputHistory::load(
+			repository.git_directory().join(INPUT_HISTORY_FILE_NAME).as_path(),
+			config.input_history_limit,
+		);
 		Self {
 			error: Error::new(),
-			list: List::new(config, Arc::clone(todo_file)),
-			show_commit: ShowCommit::new(config, repository, Arc::clone(todo_file)),
+			list: List::new(
+				config,
+				Arc::clone(todo_file),
+				branches.clone(),
+				Some(repository.clone()),
+				history.clone(),
+			),
+			show_commit: ShowCommit::new(config, repository.clone(), Arc::clone(todo_file), history.clone()),
 			window_size_error: WindowSizeError::new(),
 			confirm_abort: ConfirmAbort::new(
 				&config.key_bindings.confirm_yes,
@@ -34,8 +49,12 @@ impl ModuleProvider for Modules {
 				Arc::clone(todo_file),
 			),
 			confirm_rebase: ConfirmRebase::new(&config.key_bindings.confirm_yes, &config.key_bindings.confirm_no),
-			external_editor: ExternalEditor::new(config.git.editor.as_str(), Arc::clone(todo_file)),
-			insert: Insert::new(Arc::clone(todo_file)),
+			external_editor: ExternalEditor::new(
+				config.git.editor.as_str(),
+				config.editor_line_argument.as_deref(),
+				Arc::clone(todo_file),
+			),
+			insert: Insert::new(Arc::clone(todo_file), branches, Some(repository), history),
 		}
 	}
 
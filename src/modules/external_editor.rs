@@ -30,7 +30,9 @@ pub(crate) struct ExternalEditor {
 	empty_choice: Choice<Action>,
 	error_choice: Choice<Action>,
 	external_command: (String, Vec<String>),
+	line_argument: Option<String>,
 	lines: Vec<Line>,
+	range: Option<(usize, usize)>,
 	state: ExternalEditorState,
 	todo_file: Arc<Mutex<TodoFile>>,
 	view_data: ViewData,
@@ -38,18 +40,24 @@ pub(crate) struct ExternalEditor {
 
 impl Module for ExternalEditor {
 	fn activate(&mut self, _: State) -> Results {
-		let todo_file = self.todo_file.lock();
+		let mut todo_file = self.todo_file.lock();
 		let mut results = Results::new();
-		if let Err(err) = todo_file.write_file() {
-			results.error_with_return(err.into(), State::List);
+		self.range = todo_file.take_edit_range();
+		if let Err(err) = self.write_file(&todo_file) {
+			results.error_with_return(err, State::List);
 			return results;
 		}
 
 		if self.lines.is_empty() {
 			self.lines = todo_file.get_lines_owned();
 		}
+		// the line of the selected line within the written file, starting at one
+		let selected_line = todo_file
+			.get_selected_line_index()
+			.saturating_sub(self.range.map_or(0, |(start, _)| start))
+			+ 1;
 		drop(todo_file);
-		match self.get_command() {
+		match self.get_command(selected_line) {
 			Ok(external_command) => self.external_command = external_command,
 			Err(err) => {
 				results.error_with_return(err, State::List);
@@ -63,6 +71,7 @@ impl Module for ExternalEditor {
 
 	fn deactivate(&mut self) -> Results {
 		self.lines.clear();
+		self.range = None;
 		self.view_data.update_view_data(|updater| updater.clear());
 		Results::new()
 	}
@@ -99,7 +108,10 @@ impl Module for ExternalEditor {
 				match event {
 					Event::Standard(StandardEvent::ExternalCommandSuccess) => {
 						let mut todo_file = self.todo_file.lock();
-						let result = todo_file.load_file();
+						let result = match self.range {
+							Some((start, end)) => todo_file.reload_range(start, end),
+							None => todo_file.reload_file(),
+						};
 						let state = match result {
 							Ok(()) => {
 								if todo_file.is_empty() || todo_file.is_noop() {
@@ -152,7 +164,7 @@ impl Module for ExternalEditor {
 						Action::EditRebase => self.set_state(&mut results, ExternalEditorState::Active),
 						Action::RestoreAndAbortEdit => {
 							let mut todo_file = self.todo_file.lock();
-							todo_file.set_lines(self.lines.clone());
+							todo_file.replace_lines(self.lines.clone());
 							results.state(State::List);
 							if let Err(err) = todo_file.write_file() {
 								results.error(err.into());
@@ -170,7 +182,7 @@ impl Module for ExternalEditor {
 }
 
 impl ExternalEditor {
-	pub(crate) fn new(editor: &str, todo_file: Arc<Mutex<TodoFile>>) -> Self {
+	pub(crate) fn new(editor: &str, line_argument: Option<&str>, todo_file: Arc<Mutex<TodoFile>>) -> Self {
 		let view_data = ViewData::new(|updater| {
 			updater.set_show_title(true);
 		});
@@ -206,7 +218,9 @@ impl ExternalEditor {
 			empty_choice,
 			error_choice,
 			external_command: (String::new(), vec![]),
+			line_argument: line_argument.map(String::from),
 			lines: vec![],
+			range: None,
 			state: ExternalEditorState::Active,
 			todo_file,
 			view_data,
@@ -225,16 +239,24 @@ impl ExternalEditor {
 
 	fn undo_and_edit(&mut self, results: &mut Results) {
 		let mut todo_file = self.todo_file.lock();
-		todo_file.set_lines(self.lines.clone());
-		if let Err(err) = todo_file.write_file() {
-			results.error_with_return(err.into(), State::List);
+		todo_file.replace_lines(self.lines.clone());
+		if let Err(err) = self.write_file(&todo_file) {
+			results.error_with_return(err, State::List);
 			return;
 		}
 		drop(todo_file);
 		self.set_state(results, ExternalEditorState::Active);
 	}
 
-	fn get_command(&self) -> Result<(String, Vec<String>)> {
+	fn write_file(&self, todo_file: &TodoFile) -> Result<()> {
+		match self.range {
+			Some((start, end)) => todo_file.write_range_for_edit(start, end)?,
+			None => todo_file.write_file_for_edit()?,
+		}
+		Ok(())
+	}
+
+	fn get_command(&self, selected_line: usize) -> Result<(String, Vec<String>)> {
 		let mut parameters = tokenize(self.editor.as_str())
 			.map_or(Err(anyhow!("Invalid editor: \"{}\"", self.editor)), |args| {
 				if args.is_empty() {
@@ -247,27 +269,41 @@ impl ExternalEditor {
 			.map_err(|e| anyhow!("Please see the git \"core.editor\" configuration for details").context(e))?;
 
 		let todo_file = self.todo_file.lock();
-		let filepath = todo_file.get_filepath().to_str().ok_or_else(|| {
-			anyhow!(
-				"The file path {} is invalid",
-				todo_file.get_filepath().to_string_lossy()
-			)
-		})?;
+		// a range of lines is edited in a separate file, so the rebase file always has all the lines
+		let edited_filepath = if self.range.is_some() {
+			todo_file.get_edit_range_filepath()
+		}
+		else {
+			todo_file.get_filepath().to_path_buf()
+		};
+		drop(todo_file);
+		let filepath = edited_filepath
+			.to_str()
+			.ok_or_else(|| anyhow!("The file path {} is invalid", edited_filepath.to_string_lossy()))?;
+		// editors that support it are passed the selected line, in an argument just before the file
+		let mut file_arguments: Vec<String> = self
+			.line_argument
+			.as_deref()
+			.map(|template| template.replace('%', selected_line.to_string().as_str()))
+			.into_iter()
+			.collect();
+		file_arguments.push(String::from(filepath));
+
 		let mut file_pattern_found = false;
 		let command = parameters.next().unwrap_or_else(|| String::from("false"));
 		let mut arguments = parameters
-			.map(|a| {
+			.flat_map(|a| {
 				if a.as_str() == "%" {
 					file_pattern_found = true;
-					String::from(filepath)
+					file_arguments.clone()
 				}
 				else {
-					a
+					vec![a]
 				}
 			})
 			.collect::<Vec<String>>();
 		if !file_pattern_found {
-			arguments.push(String::from(filepath));
+			arguments.extend(file_arguments);
 		}
 		Ok((command, arguments))
 	}
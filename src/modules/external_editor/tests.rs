@@ -1,7 +1,14 @@
 use std::{fs, fs::File};
 
 use super::*;
-use crate::{assert_rendered_output, assert_results, input::KeyCode, process::Artifact, test_helpers::testers};
+use crate::{
+	assert_rendered_output,
+	assert_results,
+	input::KeyCode,
+	process::Artifact,
+	test_helpers::testers,
+	todo_file::{Action as TodoAction, EditContext},
+};
 
 fn assert_external_editor_state_eq(actual: &ExternalEditorState, expected: &ExternalEditorState) {
 	let actual_state = match *actual {
@@ -42,7 +49,7 @@ macro_rules! assert_external_editor_state_eq {
 }
 
 fn create_external_editor(editor: &str, todo_file: TodoFile) -> ExternalEditor {
-	ExternalEditor::new(editor, Arc::new(Mutex::new(todo_file)))
+	ExternalEditor::new(editor, None, Arc::new(Mutex::new(todo_file)))
 }
 
 #[test]
@@ -104,6 +111,90 @@ fn activate_file_placement_marker() {
 	});
 }
 
+#[test]
+fn activate_line_argument() {
+	testers::module(&["pick aaa comment1", "drop bbb comment2"], &[], |mut test_context| {
+		let mut todo_file = test_context.take_todo_file();
+		_ = todo_file.set_selected_line_index(1);
+		let todo_path = String::from(todo_file.get_filepath().to_str().unwrap());
+
+		let mut module = ExternalEditor::new("editor -w %", Some("+%"), Arc::new(Mutex::new(todo_file)));
+		assert_results!(
+			test_context.activate(&mut module, State::List),
+			Artifact::ExternalCommand((String::from("editor"), vec![
+				String::from("-w"),
+				String::from("+2"),
+				todo_path
+			]))
+		);
+	});
+}
+
+#[test]
+fn activate_edit_range() {
+	testers::module(
+		&["pick aaa comment1", "drop bbb comment2", "pick ccc comment3"],
+		&[],
+		|mut test_context| {
+			let mut todo_file = test_context.take_todo_file();
+			_ = todo_file.set_selected_line_index(2);
+			todo_file.set_edit_range(1, 2);
+			let todo_contents = fs::read_to_string(todo_file.get_filepath()).unwrap();
+			let range_path = todo_file.get_edit_range_filepath();
+
+			let mut module = ExternalEditor::new("editor", Some("+%"), Arc::new(Mutex::new(todo_file)));
+			assert_results!(
+				test_context.activate(&mut module, State::List),
+				Artifact::ExternalCommand((String::from("editor"), vec![
+					String::from("+2"),
+					String::from(range_path.to_str().unwrap())
+				]))
+			);
+			assert_eq!(module.range, Some((1, 2)));
+			assert_eq!(
+				fs::read_to_string(&range_path).unwrap(),
+				"drop bbb comment2\npick ccc comment3\n"
+			);
+			assert_eq!(
+				fs::read_to_string(module.todo_file.lock().get_filepath()).unwrap(),
+				todo_contents
+			);
+			fs::remove_file(range_path).unwrap();
+			_ = test_context.deactivate(&mut module);
+			assert_eq!(module.range, None);
+		},
+	);
+}
+
+#[test]
+fn edit_range_success() {
+	testers::module(
+		&["pick aaa comment1", "pick bbb comment2", "pick ccc comment3"],
+		&[Event::from(StandardEvent::ExternalCommandSuccess)],
+		|mut test_context| {
+			let mut todo_file = test_context.take_todo_file();
+			todo_file.set_edit_range(1, 1);
+			let mut module = create_external_editor("editor", todo_file);
+			_ = test_context.activate(&mut module, State::List);
+			let range_path = module.todo_file.lock().get_edit_range_filepath();
+			fs::write(range_path, "fixup bbb comment2\nexec make\n").unwrap();
+			assert_results!(
+				test_context.handle_event(&mut module),
+				Artifact::Event(Event::from(StandardEvent::ExternalCommandSuccess)),
+				Artifact::ChangeState(State::List)
+			);
+			let todo_file = module.todo_file.lock();
+			assert_eq!(todo_file.get_lines_owned().iter().map(Line::to_text).collect::<Vec<String>>(), vec![
+				"pick aaa comment1",
+				"fixup bbb comment2",
+				"exec make",
+				"pick ccc comment3"
+			]);
+			assert!(todo_file.get_line(1).unwrap().is_modified());
+		},
+	);
+}
+
 #[test]
 fn deactivate() {
 	testers::module(&["pick aaa comment", "drop bbb comment2"], &[], |mut test_context| {
@@ -133,6 +224,32 @@ fn edit_success() {
 	);
 }
 
+#[test]
+fn edit_success_undoable() {
+	testers::module(
+		&["pick aaa comment1", "pick bbb comment2"],
+		&[Event::from(StandardEvent::ExternalCommandSuccess)],
+		|mut test_context| {
+			let mut todo_file = test_context.take_todo_file();
+			todo_file.update_range(0, 0, &EditContext::new().action(TodoAction::Reword));
+			let mut module = create_external_editor("editor", todo_file);
+			_ = test_context.activate(&mut module, State::List);
+			let todo_path = module.todo_file.lock().get_filepath().to_path_buf();
+			fs::write(todo_path, "pick bbb comment2\nreword aaa comment1\n").unwrap();
+			_ = test_context.handle_event(&mut module);
+
+			let mut todo_file = module.todo_file.lock();
+			assert!(todo_file.get_line(1).unwrap().is_modified());
+			assert!(!todo_file.get_line(0).unwrap().is_modified());
+			assert_eq!(todo_file.undo(), Some((0, 1)));
+			assert_eq!(todo_file.get_lines_owned().iter().map(Line::to_text).collect::<Vec<String>>(), vec![
+				"reword aaa comment1",
+				"pick bbb comment2"
+			]);
+		},
+	);
+}
+
 #[test]
 fn empty_edit_error() {
 	testers::module(
@@ -1,3 +1,5 @@
+mod commit_picker;
+mod commit_source;
 mod insert_state;
 mod line_type;
 
@@ -6,15 +8,19 @@ mod tests;
 
 use std::sync::Arc;
 
+use anyhow::Error;
 use parking_lot::Mutex;
 
-use self::{insert_state::InsertState, line_type::LineType};
+use self::{commit_picker::CommitPicker, insert_state::InsertState, line_type::LineType};
 use crate::{
 	components::{
 		choice::{Choice, INPUT_OPTIONS as CHOICE_INPUT_OPTIONS},
 		edit::{Edit, INPUT_OPTIONS as EDIT_INPUT_OPTIONS},
+		HistoryKind,
+		InputHistory,
 	},
 	display::DisplayColor,
+	git::{Reference, Repository},
 	input::{Event, InputOptions},
 	module::{Module, State},
 	process::Results,
@@ -24,8 +30,13 @@ use crate::{
 
 pub(crate) struct Insert {
 	action_choices: Choice<LineType>,
+	branches: Vec<Reference>,
+	commit_picker: Option<CommitPicker>,
 	edit: Edit,
+	error: Option<String>,
+	history: InputHistory,
 	line_type: LineType,
+	repository: Option<Repository>,
 	state: InsertState,
 	todo_file: Arc<Mutex<TodoFile>>,
 }
@@ -34,6 +45,7 @@ impl Module for Insert {
 	fn activate(&mut self, _: State) -> Results {
 		self.state = InsertState::Prompt;
 		self.edit.reset();
+		self.error = None;
 		Results::new()
 	}
 
@@ -41,24 +53,44 @@ impl Module for Insert {
 		match self.state {
 			InsertState::Prompt => self.action_choices.get_view_data(),
 			InsertState::Edit => {
+				let completion_hint = self.completion_hint();
+				let error = self.error.as_deref();
 				self.edit.build_view_data(
 					|updater: &mut ViewDataUpdater<'_>| {
 						updater.push_leading_line(ViewLine::from(vec![LineSegment::new_with_color(
 							"Enter contents of the new line. Empty content cancels creation of a new line.",
 							DisplayColor::IndicatorColor,
 						)]));
+						if let Some(message) = error {
+							updater.push_leading_line(ViewLine::from(vec![LineSegment::new_with_color(
+								message,
+								DisplayColor::IndicatorColor,
+							)]));
+						}
+						if let Some(hint) = completion_hint {
+							updater.push_leading_line(ViewLine::from(vec![LineSegment::new_with_color(
+								hint,
+								DisplayColor::IndicatorColor,
+							)]));
+						}
 						updater.push_leading_line(ViewLine::new_empty_line());
 					},
 					|_| {},
 				)
 			},
+			InsertState::Picker => {
+				match self.commit_picker.as_mut() {
+					Some(commit_picker) => commit_picker.build_view_data(),
+					None => self.action_choices.get_view_data(),
+				}
+			},
 		}
 	}
 
 	fn input_options(&self) -> &InputOptions {
 		match self.state {
 			InsertState::Prompt => &CHOICE_INPUT_OPTIONS,
-			InsertState::Edit => &EDIT_INPUT_OPTIONS,
+			InsertState::Edit | InsertState::Picker => &EDIT_INPUT_OPTIONS,
 		}
 	}
 
@@ -72,9 +104,21 @@ impl Module for Insert {
 					if action == &LineType::Cancel {
 						results.state(State::List);
 					}
+					else if action == &LineType::PickCommits {
+						if let Some(commit_picker) = self.commit_picker.as_mut() {
+							commit_picker.reset();
+							self.state = InsertState::Picker;
+						}
+					}
 					else {
 						self.line_type = action.clone();
 						self.edit.set_label(format!("{action} ").as_str());
+						match *action {
+							LineType::Exec => self.edit.set_history_kind(HistoryKind::Exec),
+							LineType::Label | LineType::Reset => self.edit.set_history_kind(HistoryKind::Label),
+							_ => {},
+						}
+						self.edit.set_completions(self.completions());
 						self.state = InsertState::Edit;
 					}
 				}
@@ -82,18 +126,32 @@ impl Module for Insert {
 			InsertState::Edit => {
 				self.edit.handle_event(event);
 				if self.edit.is_finished() {
-					let content = self.edit.get_content();
+					let input = String::from(self.edit.get_content());
+					let Some(content) = self.resolve_content(input.as_str())
+					else {
+						self.error = Some(format!("{input} does not resolve to a commit in the repository"));
+						self.edit.reset();
+						self.edit.set_content(input.as_str());
+						return results;
+					};
 					results.state(State::List);
+					if let Err(err) = self.history.save() {
+						results.error_with_return(
+							Error::from(err).context("Unable to write input history"),
+							State::List,
+						);
+					}
 					if !content.is_empty() {
 						let line = match self.line_type {
-							LineType::Exec => Line::new_exec(content),
-							LineType::Pick => Line::new_pick(content),
-							LineType::Label => Line::new_label(content),
-							LineType::Reset => Line::new_reset(content),
-							LineType::Merge => Line::new_merge(content),
-							LineType::UpdateRef => Line::new_update_ref(content),
-							// this should exit in the prompt state and never get here
-							LineType::Cancel => unreachable!(),
+							LineType::Exec => Line::new_exec(content.as_str()),
+							LineType::Pick => Line::new_pick(content.as_str()),
+							LineType::Label => Line::new_label(content.as_str()),
+							LineType::Reset => Line::new_reset(content.as_str()),
+							LineType::Revert => Line::new_revert(content.as_str()),
+							LineType::Merge => Line::new_merge(content.as_str()),
+							LineType::UpdateRef => Line::new_update_ref(content.as_str()),
+							// these should exit in the prompt state and never get here
+							LineType::Cancel | LineType::PickCommits => unreachable!(),
 						};
 						let mut todo_file = self.todo_file.lock();
 						let new_line_index = todo_file.get_selected_line_index() + 1;
@@ -102,34 +160,137 @@ impl Module for Insert {
 					}
 				}
 			},
+			InsertState::Picker => {
+				let Some(commit_picker) = self.commit_picker.as_mut()
+				else {
+					results.state(State::List);
+					return results;
+				};
+				commit_picker.handle_event(event);
+				if commit_picker.is_finished() {
+					results.state(State::List);
+					let lines: Vec<Line> = commit_picker
+						.picked()
+						.iter()
+						.filter_map(|(hash, summary)| Line::parse(format!("pick {hash} {summary}").trim_end()).ok())
+						.collect();
+					if !lines.is_empty() {
+						let mut todo_file = self.todo_file.lock();
+						let new_line_index = todo_file.get_selected_line_index() + 1;
+						let last_line_index = new_line_index + lines.len() - 1;
+						todo_file.add_lines(new_line_index, lines);
+						_ = todo_file.set_selected_line_index(last_line_index);
+					}
+				}
+			},
 		}
 		results
 	}
 }
 
 impl Insert {
-	pub(crate) fn new(todo_file: Arc<Mutex<TodoFile>>) -> Self {
-		let mut action_choices = Choice::new(vec![
-			(LineType::Exec, 'e', String::from("exec <command>")),
-			(LineType::Pick, 'p', String::from("pick <hash>")),
-			(LineType::Label, 'l', String::from("label <label>")),
-			(LineType::Reset, 'r', String::from("reset <label>")),
-			(
-				LineType::Merge,
-				'm',
-				String::from("merge [-C <commit> | -c <commit>] <label> [# <oneline>]"),
-			),
-			(LineType::UpdateRef, 'u', String::from("update-ref <reference>")),
-			(LineType::Cancel, 'q', String::from("Cancel add line")),
-		]);
+	pub(crate) fn new(
+		todo_file: Arc<Mutex<TodoFile>>,
+		branches: Vec<Reference>,
+		repository: Option<Repository>,
+		history: InputHistory,
+	) -> Self {
+		// commits can only be picked from a list when there is a repository to load them from
+		let commit_picker = repository
+			.as_ref()
+			.map(|repo| CommitPicker::new(repo.clone(), branches.as_slice()));
+		let pick_choices = if commit_picker.is_some() {
+			vec![
+				(LineType::Pick, 'p', String::from("pick <hash>")),
+				(
+					LineType::PickCommits,
+					'c',
+					String::from("pick <commits from a branch or the reflog>"),
+				),
+			]
+		}
+		else {
+			vec![(LineType::Pick, 'p', String::from("pick <hash>"))]
+		};
+		// the sequencer, used for a multiple commit cherry-pick or revert, only accepts pick and revert lines
+		let mut action_choices = Choice::new(if todo_file.lock().is_sequencer() {
+			let mut choices = pick_choices;
+			choices.extend([
+				(LineType::Revert, 'v', String::from("revert <hash>")),
+				(LineType::Cancel, 'q', String::from("Cancel add line")),
+			]);
+			choices
+		}
+		else {
+			let mut choices = vec![(LineType::Exec, 'e', String::from("exec <command>"))];
+			choices.extend(pick_choices);
+			choices.extend([
+				(LineType::Label, 'l', String::from("label <label>")),
+				(LineType::Reset, 'r', String::from("reset <label>")),
+				(
+					LineType::Merge,
+					'm',
+					String::from("merge [-C <commit> | -c <commit>] <label> [# <oneline>]"),
+				),
+				(LineType::UpdateRef, 'u', String::from("update-ref <reference>")),
+				(LineType::Cancel, 'q', String::from("Cancel add line")),
+			]);
+			choices
+		});
 		action_choices.set_prompt(vec![ViewLine::from("Select the type of line to insert:")]);
+		let mut edit = Edit::new();
+		edit.set_history(history.clone());
 
 		Self {
 			action_choices,
-			edit: Edit::new(),
+			branches,
+			commit_picker,
+			edit,
+			error: None,
+			history,
 			line_type: LineType::Exec,
+			repository,
 			state: InsertState::Prompt,
 			todo_file,
 		}
 	}
+
+	/// Resolve the content of a `pick` or `revert` line to the full hash of its commit, when there is a repository to
+	/// resolve it with, returning `None` when the content does not resolve to a commit.
+	fn resolve_content(&self, content: &str) -> Option<String> {
+		if content.is_empty() || !matches!(self.line_type, LineType::Pick | LineType::Revert) {
+			return Some(String::from(content));
+		}
+		self.repository.as_ref().map_or_else(
+			|| Some(String::from(content)),
+			|repository| repository.resolve_commit(content).ok(),
+		)
+	}
+
+	/// The candidates for completing the content of the new line: the labels defined before the new line for `reset`
+	/// and `merge` lines, the local branches for `update-ref` lines, and the previous commands for `exec` lines.
+	fn completions(&self) -> Vec<String> {
+		match self.line_type {
+			LineType::Reset | LineType::Merge => {
+				let todo_file = self.todo_file.lock();
+				todo_file.labels_before(todo_file.get_selected_line_index() + 1)
+			},
+			LineType::UpdateRef => self.branches.iter().map(|branch| String::from(branch.name())).collect(),
+			LineType::Exec => self.history.entries(HistoryKind::Exec).into_iter().rev().collect(),
+			_ => vec![],
+		}
+	}
+
+	fn completion_hint(&self) -> Option<&'static str> {
+		if self.completions().is_empty() {
+			return None;
+		}
+		match self.line_type {
+			LineType::Reset | LineType::Merge => Some("Press Tab to complete a label defined before the new line."),
+			LineType::UpdateRef => Some("Press Tab to complete the name of a branch."),
+			LineType::Exec => Some("Press Tab to complete a previous command."),
+			_ => None,
+		}
+	}
+
 }
@@ -0,0 +1,237 @@
+use crate::{
+	components::EditableLine,
+	display::DisplayColor,
+	git::{Commit, Reference, Repository},
+	input::{Event, KeyCode, KeyEvent, KeyModifiers},
+	modules::insert::commit_source::CommitSource,
+	view::{LineSegment, LineSegmentOptions, ViewData, ViewLine},
+};
+
+const COMMIT_LIMIT: usize = 500;
+const PREVIEW_MESSAGE_LINES: usize = 5;
+const PAGE_SIZE: usize = 10;
+
+/// Browses the commits of a branch, or of the reflog, to select one or more commits to insert as `pick` lines.
+pub(crate) struct CommitPicker {
+	commits: Vec<Commit>,
+	cursor: usize,
+	error: Option<String>,
+	filter: EditableLine,
+	finished: bool,
+	repository: Repository,
+	selected: Vec<String>,
+	source_index: usize,
+	sources: Vec<CommitSource>,
+	view_data: ViewData,
+}
+
+impl CommitPicker {
+	pub(crate) fn new(repository: Repository, branches: &[Reference]) -> Self {
+		let mut sources = vec![CommitSource::Current];
+		sources.extend(branches.iter().cloned().map(CommitSource::Branch));
+		sources.push(CommitSource::Reflog);
+		let mut filter = EditableLine::new();
+		filter.set_label(LineSegment::new_with_color_and_style(
+			"Search: ",
+			DisplayColor::Normal,
+			LineSegmentOptions::DIMMED,
+		));
+
+		Self {
+			commits: vec![],
+			cursor: 0,
+			error: None,
+			filter,
+			finished: false,
+			repository,
+			selected: vec![],
+			source_index: 0,
+			sources,
+			view_data: ViewData::new(|updater| {
+				updater.set_show_title(true);
+			}),
+		}
+	}
+
+	/// Start picking from the commits of the current branch.
+	pub(crate) fn reset(&mut self) {
+		self.filter.clear();
+		self.finished = false;
+		self.selected.clear();
+		self.source_index = 0;
+		self.load_commits();
+	}
+
+	pub(crate) const fn is_finished(&self) -> bool {
+		self.finished
+	}
+
+	/// The hashes and summaries of the picked commits, oldest first, so that inserting them in order applies them in
+	/// the order they were committed. Empty when picking was cancelled.
+	pub(crate) fn picked(&self) -> Vec<(String, String)> {
+		self.commits
+			.iter()
+			.rev()
+			.filter(|commit| self.selected.iter().any(|hash| hash == commit.hash()))
+			.map(|commit| (String::from(commit.hash()), String::from(commit.summary().unwrap_or(""))))
+			.collect()
+	}
+
+	pub(crate) fn handle_event(&mut self, event: Event) {
+		let Event::Key(KeyEvent { code, modifiers }) = event
+		else {
+			return;
+		};
+		match (code, modifiers) {
+			(KeyCode::Enter, KeyModifiers::NONE) => {
+				if self.selected.is_empty() {
+					if let Some(hash) = self.cursor_commit().map(|commit| String::from(commit.hash())) {
+						self.selected.push(hash);
+					}
+				}
+				self.finished = true;
+			},
+			(KeyCode::Esc, KeyModifiers::NONE) => {
+				self.selected.clear();
+				self.finished = true;
+			},
+			(KeyCode::Tab, KeyModifiers::NONE) => {
+				if let Some(hash) = self.cursor_commit().map(|commit| String::from(commit.hash())) {
+					if self.selected.contains(&hash) {
+						self.selected.retain(|h| *h != hash);
+					}
+					else {
+						self.selected.push(hash);
+					}
+					self.move_cursor_down(1);
+				}
+			},
+			(KeyCode::Char('b'), KeyModifiers::CONTROL) => {
+				self.source_index = (self.source_index + 1) % self.sources.len();
+				self.load_commits();
+			},
+			(KeyCode::Up, KeyModifiers::NONE) => self.cursor = self.cursor.saturating_sub(1),
+			(KeyCode::Down, KeyModifiers::NONE) => self.move_cursor_down(1),
+			(KeyCode::PageUp, KeyModifiers::NONE) => self.cursor = self.cursor.saturating_sub(PAGE_SIZE),
+			(KeyCode::PageDown, KeyModifiers::NONE) => self.move_cursor_down(PAGE_SIZE),
+			_ => {
+				_ = self.filter.handle_event(event);
+				self.cursor = 0;
+			},
+		}
+	}
+
+	pub(crate) fn build_view_data(&mut self) -> &ViewData {
+		let matches = matching_commits(&self.commits, self.filter.get_content());
+		let cursor_commit = matches.get(self.cursor).copied();
+		self.view_data.update_view_data(|updater| {
+			updater.clear();
+			updater.push_leading_line(ViewLine::from(LineSegment::new_with_color(
+				format!(
+					"Commits of {}, Ctrl+B to show another branch or the reflog",
+					self.sources[self.source_index]
+				)
+				.as_str(),
+				DisplayColor::IndicatorColor,
+			)));
+			updater.push_leading_line(ViewLine::from(LineSegment::new_with_color(
+				"Tab to select, Enter to insert the selected commits, Esc to cancel",
+				DisplayColor::IndicatorColor,
+			)));
+			updater.push_leading_line(ViewLine::from(self.filter.line_segments()));
+			if let Some(error) = self.error.as_deref() {
+				updater.push_leading_line(ViewLine::from(LineSegment::new_with_color(
+					error,
+					DisplayColor::IndicatorColor,
+				)));
+			}
+			updater.push_leading_line(ViewLine::new_empty_line());
+
+			for (index, commit) in matches.iter().enumerate() {
+				let is_selected = self.selected.iter().any(|hash| hash == commit.hash());
+				let is_cursor = index == self.cursor;
+				let line = ViewLine::from(vec![
+					LineSegment::new(if is_selected { "* " } else { "  " }),
+					LineSegment::new_with_color(
+						commit.hash().chars().take(8).collect::<String>().as_str(),
+						DisplayColor::IndicatorColor,
+					),
+					LineSegment::new(format!(" {}", commit.summary().unwrap_or("")).as_str()),
+				]);
+				updater.push_line(if is_cursor {
+					line.set_selected(true).set_padding(' ')
+				}
+				else {
+					line
+				});
+			}
+
+			if let Some(commit) = cursor_commit {
+				updater.push_trailing_line(ViewLine::new_empty_line());
+				updater.push_trailing_line(ViewLine::from(vec![
+					LineSegment::new_with_color("Author: ", DisplayColor::IndicatorColor),
+					LineSegment::new(commit.author().to_string().as_str()),
+				]));
+				updater.push_trailing_line(ViewLine::from(vec![
+					LineSegment::new_with_color("Date: ", DisplayColor::IndicatorColor),
+					LineSegment::new(commit.committed_date().format("%c %z").to_string().as_str()),
+				]));
+				for line in commit.message().unwrap_or("").lines().take(PREVIEW_MESSAGE_LINES) {
+					updater.push_trailing_line(ViewLine::from(line));
+				}
+			}
+			if !matches.is_empty() {
+				updater.ensure_line_visible(self.cursor);
+			}
+		});
+		&self.view_data
+	}
+
+	fn load_commits(&mut self) {
+		let result = match self.sources[self.source_index] {
+			CommitSource::Current => self.repository.load_commits(None, COMMIT_LIMIT),
+			CommitSource::Branch(ref reference) => self.repository.load_commits(Some(reference.name()), COMMIT_LIMIT),
+			CommitSource::Reflog => self.repository.load_reflog_commits(COMMIT_LIMIT),
+		};
+		self.cursor = 0;
+		match result {
+			Ok(commits) => {
+				self.commits = commits;
+				self.error = None;
+			},
+			Err(err) => {
+				self.commits.clear();
+				self.error = Some(format!("Unable to load the commits of {}: {err}", self.sources[self.source_index]));
+			},
+		}
+	}
+
+	fn cursor_commit(&self) -> Option<&Commit> {
+		matching_commits(&self.commits, self.filter.get_content())
+			.get(self.cursor)
+			.copied()
+	}
+
+	fn move_cursor_down(&mut self, amount: usize) {
+		let last = matching_commits(&self.commits, self.filter.get_content())
+			.len()
+			.saturating_sub(1);
+		self.cursor = (self.cursor + amount).min(last);
+	}
+}
+
+/// The commits that match the search term, by the start of the hash, or by the summary or author, ignoring case.
+fn matching_commits<'commits>(commits: &'commits [Commit], term: &str) -> Vec<&'commits Commit> {
+	let lower_term = term.to_lowercase();
+	commits
+		.iter()
+		.filter(|commit| {
+			lower_term.is_empty()
+				|| commit.hash().starts_with(lower_term.as_str())
+				|| commit
+					.summary()
+					.is_some_and(|summary| summary.to_lowercase().contains(lower_term.as_str()))
+				|| commit.author().to_string().to_lowercase().contains(lower_term.as_str())
+		})
+		.collect()
+}
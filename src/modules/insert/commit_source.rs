@@ -0,0 +1,48 @@
+use std::fmt::Display;
+
+use crate::git::Reference;
+
+/// Where the commits shown in the commit picker come from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum CommitSource {
+	/// The branch being rebased, or `HEAD` when no branch is being rebased.
+	Current,
+	/// Another local branch.
+	Branch(Reference),
+	/// The commits `HEAD` has pointed to.
+	Reflog,
+}
+
+impl Display for CommitSource {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match *self {
+			Self::Current => write!(f, "the current branch"),
+			Self::Branch(ref reference) => write!(f, "{}", reference.shortname()),
+			Self::Reflog => write!(f, "the reflog"),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use rstest::rstest;
+
+	use super::*;
+	use crate::git::ReferenceKind;
+
+	#[rstest]
+	#[case::current(CommitSource::Current, "the current branch")]
+	#[case::branch(
+		CommitSource::Branch(Reference::new(
+			String::from("aaa"),
+			String::from("refs/heads/feature"),
+			String::from("feature"),
+			ReferenceKind::Branch,
+		)),
+		"feature"
+	)]
+	#[case::reflog(CommitSource::Reflog, "the reflog")]
+	fn to_string(#[case] source: CommitSource, #[case] expected: &str) {
+		assert_eq!(source.to_string(), String::from(expected));
+	}
+}
@@ -2,4 +2,5 @@
 pub(crate) enum InsertState {
 	Prompt,
 	Edit,
+	Picker,
 }
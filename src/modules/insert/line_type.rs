@@ -4,10 +4,12 @@ use std::fmt::Display;
 pub(crate) enum LineType {
 	Cancel,
 	Pick,
+	PickCommits,
 	Exec,
 	Label,
 	Merge,
 	Reset,
+	Revert,
 	UpdateRef,
 }
 
@@ -15,11 +17,12 @@ impl Display for LineType {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 		match *self {
 			Self::Cancel => write!(f, "<cancel>"),
-			Self::Pick => write!(f, "pick"),
+			Self::Pick | Self::PickCommits => write!(f, "pick"),
 			Self::Exec => write!(f, "exec"),
 			Self::Label => write!(f, "label"),
 			Self::Merge => write!(f, "merge"),
 			Self::Reset => write!(f, "reset"),
+			Self::Revert => write!(f, "revert"),
 			Self::UpdateRef => write!(f, "update-ref"),
 		}
 	}
@@ -34,10 +37,12 @@ mod tests {
 	#[rstest]
 	#[case::cancel(&LineType::Cancel, "<cancel>")]
 	#[case::pick(&LineType::Pick, "pick")]
+	#[case::pick_commits(&LineType::PickCommits, "pick")]
 	#[case::exec(&LineType::Exec, "exec")]
 	#[case::label(&LineType::Label, "label")]
 	#[case::merge(&LineType::Merge, "merge")]
 	#[case::reset(&LineType::Reset, "reset")]
+	#[case::revert(&LineType::Revert, "revert")]
 	#[case::update_ref(&LineType::UpdateRef, "update-ref")]
 	fn to_string(#[case] line_type: &LineType, #[case] expected: &str) {
 		assert_eq!(line_type.to_string(), String::from(expected));
@@ -1,8 +1,61 @@
 use super::*;
-use crate::{assert_rendered_output, assert_results, input::KeyCode, process::Artifact, test_helpers::testers};
+use crate::{
+	assert_rendered_output,
+	assert_results,
+	git::ReferenceKind,
+	input::{KeyCode, KeyEvent, KeyModifiers},
+	process::Artifact,
+	render_line,
+	test_helpers::{create_commit, testers, with_sequencer_todo_file, with_temp_repository, CreateCommitOptions},
+};
 
 fn create_insert(todo_file: TodoFile) -> Insert {
-	Insert::new(Arc::new(Mutex::new(todo_file)))
+	Insert::new(Arc::new(Mutex::new(todo_file)), vec![], None, InputHistory::default())
+}
+
+fn create_insert_with_branches(todo_file: TodoFile) -> Insert {
+	Insert::new(
+		Arc::new(Mutex::new(todo_file)),
+		vec![
+			Reference::new(
+				String::from("aaaaaaaa"),
+				String::from("refs/heads/feature-a"),
+				String::from("feature-a"),
+				ReferenceKind::Branch,
+			),
+			Reference::new(
+				String::from("bbbbbbbb"),
+				String::from("refs/heads/feature-b"),
+				String::from("feature-b"),
+				ReferenceKind::Branch,
+			),
+			Reference::new(
+				String::from("cccccccc"),
+				String::from("refs/heads/main"),
+				String::from("main"),
+				ReferenceKind::Branch,
+			),
+		],
+		None,
+		InputHistory::default(),
+	)
+}
+
+fn create_insert_with_repository(todo_file: TodoFile, repository: Repository) -> Insert {
+	Insert::new(
+		Arc::new(Mutex::new(todo_file)),
+		vec![],
+		Some(repository),
+		InputHistory::default(),
+	)
+}
+
+fn create_commits(repository: &Repository) -> (String, String) {
+	create_commit(repository, Some(CreateCommitOptions::new().message("first")));
+	let first = repository.head_id("main").unwrap().to_string();
+	create_commit(repository, Some(CreateCommitOptions::new().message("second")));
+	let second = repository.head_id("main").unwrap().to_string();
+	(first, second)
 }
 
 #[test]
@@ -38,6 +91,29 @@ fn render_prompt() {
 	});
 }
 
+#[test]
+fn render_prompt_sequencer() {
+	with_sequencer_todo_file(&[], |todo_file| {
+		testers::module(&[], &[], |test_context| {
+			let mut module = create_insert(todo_file);
+			let view_data = test_context.build_view_data(&mut module);
+			assert_rendered_output!(
+				view_data,
+				"{TITLE}",
+				"{LEADING}",
+				"Select the type of line to insert:",
+				"",
+				"{BODY}",
+				"p) pick <hash>",
+				"v) revert <hash>",
+				"q) Cancel add line",
+				"",
+				"Please choose an option."
+			);
+		});
+	});
+}
+
 #[test]
 fn prompt_cancel() {
 	testers::module(&[], &[Event::from('q')], |mut test_context| {
@@ -266,6 +342,184 @@ fn update_ref_render_merge() {
 	);
 }
 
+#[test]
+fn update_ref_complete_branch() {
+	testers::module(
+		&[],
+		&[
+			Event::from('u'),
+			Event::from('f'),
+			Event::from(KeyCode::Tab),
+			Event::from(KeyCode::Enter),
+		],
+		|mut test_context| {
+			let mut module = create_insert_with_branches(test_context.take_todo_file());
+			_ = test_context.handle_n_events(&mut module, 3);
+			let view_data = test_context.build_view_data(&mut module);
+			assert_rendered_output!(
+				view_data,
+				"{TITLE}",
+				"{LEADING}",
+				"Enter contents of the new line. Empty content cancels creation of a new line.",
+				"Press Tab to complete the name of a branch.",
+				"",
+				"{BODY}",
+				"update-ref refs/heads/feature-a",
+				"  refs/heads/feature-a",
+				"  refs/heads/feature-b",
+				"{TRAILING}",
+				"Enter to finish"
+			);
+			_ = test_context.handle_event(&mut module);
+			assert_eq!(
+				module.todo_file.lock().get_line(0).unwrap().to_text(),
+				"update-ref refs/heads/feature-a"
+			);
+		},
+	);
+}
+
+#[test]
+fn update_ref_complete_branch_cycles_matches() {
+	testers::module(
+		&[],
+		&[
+			Event::from('u'),
+			Event::from('f'),
+			Event::from(KeyCode::Tab),
+			Event::from(KeyCode::Tab),
+			Event::from(KeyCode::Tab),
+		],
+		|mut test_context| {
+			let mut module = create_insert_with_branches(test_context.take_todo_file());
+			_ = test_context.handle_n_events(&mut module, 4);
+			assert_eq!(module.edit.get_content(), "refs/heads/feature-b");
+			_ = test_context.handle_event(&mut module);
+			assert_eq!(module.edit.get_content(), "refs/heads/feature-a");
+		},
+	);
+}
+
+#[test]
+fn update_ref_complete_branch_restarts_after_edit() {
+	testers::module(
+		&[],
+		&[
+			Event::from('u'),
+			Event::from('f'),
+			Event::from(KeyCode::Tab),
+			Event::from(KeyCode::Backspace),
+			Event::from(KeyCode::Tab),
+		],
+		|mut test_context| {
+			let mut module = create_insert_with_branches(test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.edit.get_content(), "refs/heads/feature-a");
+		},
+	);
+}
+
+#[test]
+fn update_ref_complete_branch_no_match() {
+	testers::module(
+		&[],
+		&[Event::from('u'), Event::from('x'), Event::from(KeyCode::Tab)],
+		|mut test_context| {
+			let mut module = create_insert_with_branches(test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.edit.get_content(), "x");
+		},
+	);
+}
+
+#[test]
+fn reset_complete_label_defined_before() {
+	testers::module(
+		&["label onto", "pick aaa c1", "label later"],
+		&[Event::from('r'), Event::from(KeyCode::Tab)],
+		|mut test_context| {
+			let mut module = create_insert(test_context.take_todo_file());
+			_ = module.todo_file.lock().set_selected_line_index(1);
+			_ = test_context.handle_all_events(&mut module);
+			let view_data = test_context.build_view_data(&mut module);
+			assert_rendered_output!(
+				view_data,
+				"{TITLE}",
+				"{LEADING}",
+				"Enter contents of the new line. Empty content cancels creation of a new line.",
+				"Press Tab to complete a label defined before the new line.",
+				"",
+				"{BODY}",
+				"reset onto",
+				"  onto",
+				"{TRAILING}",
+				"Enter to finish"
+			);
+		},
+	);
+}
+
+#[test]
+fn merge_complete_last_word() {
+	testers::module(
+		&["label feature", "pick aaa c1"],
+		&[
+			Event::from('m'),
+			Event::from('-'),
+			Event::from('C'),
+			Event::from(' '),
+			Event::from('a'),
+			Event::from(' '),
+			Event::from('f'),
+			Event::from(KeyCode::Tab),
+		],
+		|mut test_context| {
+			let mut module = create_insert(test_context.take_todo_file());
+			_ = module.todo_file.lock().set_selected_line_index(1);
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.edit.get_content(), "-C a feature");
+		},
+	);
+}
+
+#[test]
+fn exec_complete_previous_command() {
+	testers::module(
+		&[],
+		&[
+			Event::from('e'),
+			Event::from('c'),
+			Event::from('a'),
+			Event::from(KeyCode::Tab),
+			Event::from(KeyCode::Tab),
+		],
+		|mut test_context| {
+			let history = InputHistory::new(10);
+			history.push(HistoryKind::Exec, "cargo test");
+			history.push(HistoryKind::Exec, "make");
+			history.push(HistoryKind::Exec, "cargo build");
+			let mut module = Insert::new(Arc::new(Mutex::new(test_context.take_todo_file())), vec![], None, history);
+			_ = test_context.handle_n_events(&mut module, 4);
+			assert_eq!(module.edit.get_content(), "cargo build");
+			_ = test_context.handle_event(&mut module);
+			assert_eq!(module.edit.get_content(), "cargo test");
+		},
+	);
+}
+
+#[test]
+fn exec_does_not_complete_branch() {
+	testers::module(
+		&[],
+		&[Event::from('e'), Event::from('f'), Event::from(KeyCode::Tab)],
+		|mut test_context| {
+			let mut module = create_insert_with_branches(test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.edit.get_content(), "f");
+		},
+	);
+}
+
 #[test]
 fn edit_select_next_index() {
 	testers::module(
@@ -297,3 +551,308 @@ fn cancel_edit() {
 		},
 	);
 }
+
+#[test]
+fn edit_revert_sequencer() {
+	with_sequencer_todo_file(&[], |todo_file| {
+		testers::module(
+			&[],
+			&[
+				Event::from('v'),
+				Event::from('a'),
+				Event::from('b'),
+				Event::from('c'),
+				Event::from(KeyCode::Enter),
+			],
+			|mut test_context| {
+				let mut module = create_insert(todo_file);
+				_ = test_context.handle_all_events(&mut module);
+				assert_eq!(module.todo_file.lock().get_line(0).unwrap().to_text(), "revert abc ");
+			},
+		);
+	});
+}
+
+#[test]
+fn exec_recalls_history() {
+	testers::module(
+		&[],
+		&[Event::from('e'), Event::from(KeyCode::Up)],
+		|mut test_context| {
+			let history = InputHistory::new(10);
+			history.push(HistoryKind::Exec, "cargo test");
+			history.push(HistoryKind::Label, "onto");
+			let mut module = Insert::new(Arc::new(Mutex::new(test_context.take_todo_file())), vec![], None, history);
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.edit.get_content(), "cargo test");
+		},
+	);
+}
+
+#[test]
+fn pick_does_not_recall_history() {
+	testers::module(
+		&[],
+		&[Event::from('p'), Event::from(KeyCode::Up)],
+		|mut test_context| {
+			let history = InputHistory::new(10);
+			history.push(HistoryKind::Exec, "cargo test");
+			let mut module = Insert::new(Arc::new(Mutex::new(test_context.take_todo_file())), vec![], None, history);
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.edit.get_content(), "");
+		},
+	);
+}
+
+#[test]
+fn render_prompt_with_repository() {
+	with_temp_repository(|repository| {
+		testers::module(&[], &[], |mut test_context| {
+			let mut module = create_insert_with_repository(test_context.take_todo_file(), repository);
+			let view_data = test_context.build_view_data(&mut module);
+			assert_rendered_output!(
+				view_data,
+				"{TITLE}",
+				"{LEADING}",
+				"Select the type of line to insert:",
+				"",
+				"{BODY}",
+				"e) exec <command>",
+				"p) pick <hash>",
+				"c) pick <commits from a branch or the reflog>",
+				"l) label <label>",
+				"r) reset <label>",
+				"m) merge [-C <commit> | -c <commit>] <label> [# <oneline>]",
+				"u) update-ref <reference>",
+				"q) Cancel add line",
+				"",
+				"Please choose an option."
+			);
+		});
+	});
+}
+
+#[test]
+fn pick_commits_render() {
+	with_temp_repository(|repository| {
+		let (_, second) = create_commits(&repository);
+		testers::module(&[], &[Event::from('c')], |mut test_context| {
+			let mut module = create_insert_with_repository(test_context.take_todo_file(), repository);
+			_ = test_context.handle_all_events(&mut module);
+			let view_data = test_context.build_view_data(&mut module);
+			assert_rendered_output!(
+								view_data,
+				"{TITLE}",
+				"{LEADING}",
+				"Commits of the current branch, Ctrl+B to show another branch or the reflog",
+				"Tab to select, Enter to insert the selected commits, Esc to cancel",
+				"Search:",
+				"",
+				"{BODY}",
+				format!("{{Selected}}  {} second{{Pad( )}}", second.get(0..8).unwrap()),
+				render_line!(EndsWith " first"),
+				render_line!(EndsWith " initial commit"),
+				"{TRAILING}",
+				"",
+				render_line!(Contains "Author: "),
+				render_line!(Contains "Date: "),
+				"second"
+			);
+		});
+	});
+}
+
+#[test]
+fn pick_commits_cursor_commit() {
+	with_temp_repository(|repository| {
+		let (_, second) = create_commits(&repository);
+		testers::module(
+			&["pick aaa comment"],
+			&[Event::from('c'), Event::from(KeyCode::Enter)],
+			|mut test_context| {
+				let mut module = create_insert_with_repository(test_context.take_todo_file(), repository);
+				_ = test_context.handle_event(&mut module);
+				assert_results!(
+					test_context.handle_event(&mut module),
+					Artifact::Event(Event::from(KeyCode::Enter)),
+					Artifact::ChangeState(State::List)
+				);
+				let todo_file = module.todo_file.lock();
+				assert_eq!(todo_file.get_line(1).unwrap().to_text(), format!("pick {second} second"));
+				assert_eq!(todo_file.get_selected_line_index(), 1);
+			},
+		);
+	});
+}
+
+#[test]
+fn pick_commits_selected_in_commit_order() {
+	with_temp_repository(|repository| {
+		let (first, second) = create_commits(&repository);
+		testers::module(
+			&["pick aaa comment"],
+			&[
+				Event::from('c'),
+				Event::from(KeyCode::Tab),
+				Event::from(KeyCode::Tab),
+				Event::from(KeyCode::Enter),
+			],
+			|mut test_context| {
+				let mut module = create_insert_with_repository(test_context.take_todo_file(), repository);
+				_ = test_context.handle_all_events(&mut module);
+				let todo_file = module.todo_file.lock();
+				assert_eq!(todo_file.get_max_selected_line_index(), 2);
+				assert_eq!(todo_file.get_line(1).unwrap().to_text(), format!("pick {first} first"));
+				assert_eq!(todo_file.get_line(2).unwrap().to_text(), format!("pick {second} second"));
+				assert_eq!(todo_file.get_selected_line_index(), 2);
+			},
+		);
+	});
+}
+
+#[test]
+fn pick_commits_filter() {
+	with_temp_repository(|repository| {
+		let (first, _) = create_commits(&repository);
+		testers::module(
+			&[],
+			&[
+				Event::from('c'),
+				Event::from('F'),
+				Event::from('i'),
+				Event::from('r'),
+				Event::from(KeyCode::Enter),
+			],
+			|mut test_context| {
+				let mut module = create_insert_with_repository(test_context.take_todo_file(), repository);
+				_ = test_context.handle_all_events(&mut module);
+				assert_eq!(
+					module.todo_file.lock().get_line(0).unwrap().to_text(),
+					format!("pick {first} first")
+				);
+			},
+		);
+	});
+}
+
+#[test]
+fn pick_commits_cancel() {
+	with_temp_repository(|repository| {
+		_ = create_commits(&repository);
+		testers::module(
+			&[],
+			&[Event::from('c'), Event::from(KeyCode::Esc)],
+			|mut test_context| {
+				let mut module = create_insert_with_repository(test_context.take_todo_file(), repository);
+				_ = test_context.handle_event(&mut module);
+				assert_results!(
+					test_context.handle_event(&mut module),
+					Artifact::Event(Event::from(KeyCode::Esc)),
+					Artifact::ChangeState(State::List)
+				);
+				assert!(module.todo_file.lock().is_empty());
+			},
+		);
+	});
+}
+
+#[test]
+fn pick_commits_change_source() {
+	with_temp_repository(|repository| {
+		testers::module(
+			&[],
+			&[Event::from('c'), Event::from(KeyEvent::new(KeyCode::Char('b'), KeyModifiers::CONTROL))],
+			|mut test_context| {
+				let mut module = create_insert_with_repository(test_context.take_todo_file(), repository);
+				_ = test_context.handle_all_events(&mut module);
+				let view_data = test_context.build_view_data(&mut module);
+				assert_rendered_output!(
+										view_data,
+					"{TITLE}",
+					"{LEADING}",
+					"Commits of the reflog, Ctrl+B to show another branch or the reflog",
+					"Tab to select, Enter to insert the selected commits, Esc to cancel",
+					"Search:",
+					"",
+					"{BODY}",
+					render_line!(EndsWith " initial commit{Pad( )}"),
+					"{TRAILING}",
+					"",
+					render_line!(Contains "Author: "),
+					render_line!(Contains "Date: "),
+					"initial commit"
+				);
+			},
+		);
+	});
+}
+
+#[test]
+fn pick_rejects_unknown_hash() {
+	with_temp_repository(|repository| {
+		testers::module(
+			&[],
+			&[
+				Event::from('p'),
+				Event::from('z'),
+				Event::from('z'),
+				Event::from('z'),
+				Event::from(KeyCode::Enter),
+			],
+			|mut test_context| {
+				let mut module = create_insert_with_repository(test_context.take_todo_file(), repository);
+				_ = test_context.handle_n_events(&mut module, 4);
+				assert_results!(
+					test_context.handle_event(&mut module),
+					Artifact::Event(Event::from(KeyCode::Enter))
+				);
+				assert!(module.todo_file.lock().is_empty());
+				let view_data = test_context.build_view_data(&mut module);
+				assert_rendered_output!(
+										view_data,
+					"{TITLE}",
+					"{LEADING}",
+					"Enter contents of the new line. Empty content cancels creation of a new line.",
+					"zzz does not resolve to a commit in the repository",
+					"",
+					"{BODY}",
+					"pick zzz",
+					"{TRAILING}",
+					"Enter to finish"
+				);
+			},
+		);
+	});
+}
+
+#[test]
+fn pick_accepts_known_hash() {
+	with_temp_repository(|repository| {
+		let (first, _) = create_commits(&repository);
+		let mut events = vec![Event::from('p')];
+		events.extend(first.chars().take(8).map(Event::from));
+		events.push(Event::from(KeyCode::Enter));
+		testers::module(&[], &events, |mut test_context| {
+			let mut module = create_insert_with_repository(test_context.take_todo_file(), repository);
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(
+				module.todo_file.lock().get_line(0).unwrap().to_text(),
+				format!("pick {first} ")
+			);
+		});
+	});
+}
+
+#[test]
+fn pick_rejects_non_commit() {
+	with_temp_repository(|repository| {
+		let mut events = vec![Event::from('p')];
+		events.extend("HEAD^{tree}".chars().map(Event::from));
+		events.push(Event::from(KeyCode::Enter));
+		testers::module(&[], &events, |mut test_context| {
+			let mut module = create_insert_with_repository(test_context.take_todo_file(), repository);
+			_ = test_context.handle_all_events(&mut module);
+			assert!(module.todo_file.lock().is_empty());
+		});
+	});
+}
@@ -1,32 +1,79 @@
+mod branches;
+mod edit_line;
+mod exec_lines;
+mod filter;
+mod fold;
+mod marks;
+mod named_marks;
+mod reword;
+mod rewrite_metadata;
 mod search;
+mod sort;
+mod stack;
 #[cfg(all(unix, test))]
 mod tests;
+mod undo_history;
+mod update_refs;
 mod utils;
 
-use std::{cmp::min, sync::Arc};
+use std::{
+	cmp::{max, min},
+	collections::BTreeSet,
+	mem,
+	sync::Arc,
+};
 
-use captur::capture;
+use anyhow::Error;
 use parking_lot::Mutex;
 
 use self::{
-	search::Search,
+	branches::Branches,
+	edit_line::EditLine,
+	exec_lines::{ExecLines, ExecTarget},
+	filter::Filter,
+	fold::{FixupGroup, Fold},
+	marks::{gather_marked_lines, remove_marked_lines, toggle_marks},
+	named_marks::{NamedMarkCommand, NamedMarks},
+	reword::Reword,
+	rewrite_metadata::RewriteMetadata,
+	search::{MetadataIndex, Search},
+	sort::sort_lines_by_date,
+	stack::{get_sections, Stack},
+	undo_history::UndoHistory,
+	update_refs::UpdateRefs,
 	utils::{
+		get_branch_segments,
 		get_list_normal_mode_help_lines,
 		get_list_visual_mode_help_lines,
+		get_section_header_segments,
 		get_todo_line_segments,
 		TodoLineSegmentsOptions,
 	},
 };
 use crate::{
 	components::{
-		edit::Edit,
+		choice::INPUT_OPTIONS as CHOICE_INPUT_OPTIONS,
+		edit::INPUT_OPTIONS as EDIT_INPUT_OPTIONS,
 		help::Help,
 		search_bar::{SearchBar, SearchBarAction},
 		spin_indicator::SpinIndicator,
+		text_editor::INPUT_OPTIONS as TEXT_EDITOR_INPUT_OPTIONS,
+		InputHistory,
 	},
 	config::Config,
 	display::DisplayColor,
-	input::{Event, InputOptions, KeyBindings, MouseEventKind, StandardEvent},
+	git::{Reference, Repository},
+	input::{
+		Event,
+		InputOptions,
+		KeyBindings,
+		KeyCode,
+		KeyEvent,
+		KeyModifiers,
+		KeySequence,
+		MouseEventKind,
+		StandardEvent,
+	},
 	module::{ExitStatus, Module, State},
 	modules::list::utils::get_line_action_maximum_width,
 	process::Results,
@@ -40,13 +87,37 @@ use crate::{
 const INPUT_OPTIONS: InputOptions = InputOptions::UNDO_REDO
 	.union(InputOptions::RESIZE)
 	.union(InputOptions::HELP)
-	.union(InputOptions::SEARCH_START);
+	.union(InputOptions::SEARCH_START)
+	.union(InputOptions::KEY_SEQUENCES);
+
+const NAMED_MARK_INPUT_OPTIONS: InputOptions = InputOptions::RESIZE;
+
+const UNDO_HISTORY_INPUT_OPTIONS: InputOptions = InputOptions::RESIZE;
+
+// the largest count that can be typed before a movement or swap event
+const MAX_COUNT: usize = 9999;
 
 #[derive(Debug, PartialEq, Eq)]
 enum ListState {
 	Normal,
 	Visual,
 	Edit,
+	ExecLines,
+	Reword,
+	RewriteMetadata,
+	UndoHistory,
+	UpdateRefs,
+}
+
+/// The rows that the todo lines are shown in.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum ListView {
+	/// A row for each line, after the done lines.
+	Lines,
+	/// A row for each section of a stack of branches, and for each line of the sections that are not collapsed.
+	Stack,
+	/// A row for each line, except the `fixup` and `squash` lines of the collapsed fixup groups.
+	Fold,
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -58,23 +129,47 @@ enum CursorUpdate {
 }
 
 pub(crate) struct List {
+	apply_to_matches: bool,
 	auto_select_next: bool,
-	edit: Edit,
+	branches: Branches,
+	count: usize,
+	edit_line: EditLine,
+	exec_lines: ExecLines,
+	filter: Option<Filter>,
+	fold: Fold,
 	height: usize,
+	history: InputHistory,
+	is_sequencer: bool,
+	marked_lines: BTreeSet<usize>,
+	metadata_index: MetadataIndex,
+	named_marks: NamedMarks,
 	normal_mode_help: Help,
+	pending_count: usize,
+	pending_keys: Option<KeySequence>,
+	pending_named_mark: Option<NamedMarkCommand>,
+	reword: Reword,
+	rewrite_metadata: RewriteMetadata,
 	search: Search,
 	search_bar: SearchBar,
 	selected_line_action: Option<Action>,
 	spin_indicator: SpinIndicator,
+	stack: Stack,
 	state: ListState,
 	todo_file: Arc<Mutex<TodoFile>>,
+	undo_history: UndoHistory,
+	update_refs: UpdateRefs,
+	view: ListView,
 	view_data: ViewData,
 	visual_index_start: Option<usize>,
 	visual_mode_help: Help,
 }
 
 impl Module for List {
-	fn activate(&mut self, _: State) -> Results {
+	fn activate(&mut self, previous_state: State) -> Results {
+		// lines may have been added or changed in any way, so the indexes of the marked lines are no longer valid
+		if matches!(previous_state, State::Insert | State::ExternalEditor) {
+			self.marked_lines.clear();
+		}
 		self.selected_line_action = self.todo_file.lock().get_selected_line().map(|line| *line.get_action());
 		let searchable: Box<dyn Searchable> = Box::new(self.search.clone());
 		let mut results = Results::from(searchable);
@@ -88,23 +183,12 @@ impl Module for List {
 		match self.state {
 			ListState::Normal => self.get_normal_mode_view_data(context),
 			ListState::Visual => self.get_visual_mode_view_data(context),
-			ListState::Edit => {
-				if let Some(selected_line) = self.todo_file.lock().get_selected_line() {
-					if selected_line.is_editable() {
-						return self.edit.build_view_data(
-							|updater| {
-								updater.push_leading_line(ViewLine::from(LineSegment::new_with_color(
-									format!("Modifying line: {}", selected_line.to_text()).as_str(),
-									DisplayColor::IndicatorColor,
-								)));
-								updater.push_leading_line(ViewLine::new_empty_line());
-							},
-							|_| {},
-						);
-					}
-				}
-				self.edit.get_view_data()
-			},
+			ListState::Edit => self.edit_line.build_view_data(&self.todo_file.lock()),
+			ListState::ExecLines => self.exec_lines.build_view_data(),
+			ListState::Reword => self.reword.build_view_data(&self.todo_file.lock()),
+			ListState::RewriteMetadata => self.rewrite_metadata.build_view_data(),
+			ListState::UndoHistory => self.undo_history.build_view_data(&self.todo_file.lock()),
+			ListState::UpdateRefs => self.update_refs.build_view_data(),
 		}
 	}
 
@@ -114,19 +198,26 @@ impl Module for List {
 				match self.state {
 					ListState::Normal => self.handle_normal_mode_event(event, view_state),
 					ListState::Visual => self.handle_visual_mode_input(event, view_state),
-					ListState::Edit => self.handle_edit_mode_input(event),
+					ListState::Edit
+					| ListState::ExecLines
+					| ListState::Reword
+					| ListState::RewriteMetadata
+					| ListState::UndoHistory
+					| ListState::UpdateRefs => self.handle_mode_input(event, view_state),
 				}
 			},
 			self.normal_mode_help.handle_event(event, view_state),
 			self.visual_mode_help.handle_event(event, view_state),
-			self.handle_search_input(event)
+			self.handle_search_input(event),
+			self.handle_key_prefix_input(event)
 		)
 	}
 
 	fn input_options(&self) -> &InputOptions {
 		select!(
 			default & INPUT_OPTIONS,
-			(self.state == ListState::Edit).then(|| self.edit.input_options()),
+			self.mode_input_options(),
+			self.pending_named_mark.is_some().then_some(&NAMED_MARK_INPUT_OPTIONS),
 			self.normal_mode_help.input_options(),
 			self.visual_mode_help.input_options(),
 			self.search_bar.input_options()
@@ -136,7 +227,8 @@ impl Module for List {
 	fn read_event(&self, event: Event, key_bindings: &KeyBindings) -> Event {
 		select!(
 			default self.read_event_default(event, key_bindings),
-			(self.state == ListState::Edit).then_some(event),
+			self.mode_input_options().is_some().then_some(event),
+			self.pending_named_mark.is_some().then_some(event),
 			self.normal_mode_help.read_event(event),
 			self.visual_mode_help.read_event(event),
 			self.search_bar.read_event(event)
@@ -145,45 +237,211 @@ impl Module for List {
 }
 
 impl List {
-	pub(crate) fn new(config: &Config, todo_file: Arc<Mutex<TodoFile>>) -> Self {
+	pub(crate) fn new(
+		config: &Config,
+		todo_file: Arc<Mutex<TodoFile>>,
+		branches: Vec<Reference>,
+		repository: Option<Repository>,
+		history: InputHistory,
+	) -> Self {
 		let view_data = ViewData::new(|updater| {
 			updater.set_show_title(true);
 			updater.set_show_help(true);
 		});
 
-		let search = Search::new(Arc::clone(&todo_file));
+		let metadata_index = repository.map_or_else(MetadataIndex::default, MetadataIndex::new);
+		let search = Search::new(Arc::clone(&todo_file), metadata_index.clone());
+		let is_sequencer = todo_file.lock().is_sequencer();
+		let mut search_bar = SearchBar::new();
+		search_bar.set_history(history.clone());
 
 		Self {
+			apply_to_matches: false,
 			auto_select_next: config.auto_select_next,
-			edit: Edit::new(),
+			branches: Branches::new(branches),
+			count: 0,
+			edit_line: EditLine::new(history.clone()),
+			exec_lines: ExecLines::new(config.exec_templates.clone(), history.clone()),
+			filter: None,
+			fold: Fold::new(),
 			height: 0,
-			normal_mode_help: Help::new_from_keybindings(&get_list_normal_mode_help_lines(&config.key_bindings)),
+			history,
+			is_sequencer,
+			marked_lines: BTreeSet::new(),
+			metadata_index,
+			named_marks: NamedMarks::new(),
+			normal_mode_help: Help::new_from_keybindings(&get_list_normal_mode_help_lines(
+				&config.key_bindings,
+				is_sequencer,
+			)),
+			pending_count: 0,
+			pending_keys: None,
+			pending_named_mark: None,
+			reword: Reword::new(),
+			rewrite_metadata: RewriteMetadata::new(),
 			search,
-			search_bar: SearchBar::new(),
+			search_bar,
 			selected_line_action: None,
 			spin_indicator: SpinIndicator::new(),
+			stack: Stack::new(),
 			state: ListState::Normal,
 			todo_file,
+			undo_history: UndoHistory::new(),
+			update_refs: UpdateRefs::new(),
+			view: ListView::Lines,
 			view_data,
 			visual_index_start: None,
-			visual_mode_help: Help::new_from_keybindings(&get_list_visual_mode_help_lines(&config.key_bindings)),
+			visual_mode_help: Help::new_from_keybindings(&get_list_visual_mode_help_lines(
+				&config.key_bindings,
+				is_sequencer,
+			)),
 		}
 	}
 
 	fn update_cursor(&mut self, cursor_update: CursorUpdate) -> usize {
+		if self.view != ListView::Lines || self.filter.is_some() {
+			return self.update_row_cursor(cursor_update);
+		}
+
 		let mut todo_file = self.todo_file.lock();
-		let new_selected_line_index = match cursor_update {
-			CursorUpdate::Down(amount) => todo_file.get_selected_line_index().saturating_add(amount),
-			CursorUpdate::Up(amount) => todo_file.get_selected_line_index().saturating_sub(amount),
-			CursorUpdate::Set(value) => value,
-			CursorUpdate::End => todo_file.get_max_selected_line_index(),
+		// done lines are rendered above the todo lines, and can only be selected outside of visual mode
+		let done_lines_count = if self.state == ListState::Visual {
+			0
+		}
+		else {
+			todo_file.done_lines_count()
 		};
-		let selected_line_index = todo_file.set_selected_line_index(new_selected_line_index);
+		let position = todo_file
+			.get_selected_done_line_index()
+			.unwrap_or_else(|| done_lines_count + todo_file.get_selected_line_index());
+		let new_position = match cursor_update {
+			CursorUpdate::Down(amount) => position.saturating_add(amount),
+			CursorUpdate::Up(amount) => position.saturating_sub(amount),
+			CursorUpdate::Set(value) => done_lines_count + value,
+			CursorUpdate::End => done_lines_count + todo_file.get_max_selected_line_index(),
+		};
+
+		if new_position < done_lines_count {
+			_ = todo_file.set_selected_done_line_index(new_position);
+			self.selected_line_action = None;
+			return todo_file.get_selected_line_index();
+		}
+
+		let selected_line_index = todo_file.set_selected_line_index(new_position - done_lines_count);
 		self.selected_line_action = todo_file.get_selected_line().map(|line| *line.get_action());
 		self.search.set_search_start_hint(selected_line_index);
 		selected_line_index
 	}
 
+	/// Update the cursor in the stack view, where the done lines are hidden and a collapsed section is a single row, in
+	/// the fold view, where a collapsed fixup group is a single row, or in the filtered list, where only the lines that
+	/// match the filter are shown.
+	fn update_row_cursor(&mut self, cursor_update: CursorUpdate) -> usize {
+		self.update_filter_commit_matches();
+		let mut todo_file = self.todo_file.lock();
+		let rows = self.filter_rows(&todo_file).unwrap_or_else(|| {
+			if self.view == ListView::Fold {
+				self.fold.rows(&todo_file)
+			}
+			else {
+				self.stack.rows(&todo_file)
+			}
+		});
+		let current_row = rows
+			.iter()
+			.rposition(|&index| index <= todo_file.get_selected_line_index())
+			.unwrap_or(0);
+		let new_row = match cursor_update {
+			CursorUpdate::Down(amount) => current_row.saturating_add(amount),
+			CursorUpdate::Up(amount) => current_row.saturating_sub(amount),
+			CursorUpdate::Set(value) => rows.iter().rposition(|&index| index <= value).unwrap_or(0),
+			CursorUpdate::End => rows.len().saturating_sub(1),
+		};
+		let index = rows.get(new_row).or_else(|| rows.last()).copied().unwrap_or(0);
+
+		let selected_line_index = todo_file.set_selected_line_index(index);
+		self.selected_line_action = todo_file.get_selected_line().map(|line| *line.get_action());
+		self.search.set_search_start_hint(selected_line_index);
+		selected_line_index
+	}
+
+	/// The indexes of the selected lines, without the lines hidden by the filter.
+	fn selected_indexes(&self, todo_file: &TodoFile) -> Vec<usize> {
+		self.filtered_selected_indexes(todo_file).unwrap_or_else(|| {
+			let (start_index, end_index) = self.selected_range(todo_file);
+			(min(start_index, end_index)..=max(start_index, end_index)).collect()
+		})
+	}
+
+	/// The indexes of the lines that match the filter, when a filter is active.
+	fn filter_rows(&self, todo_file: &TodoFile) -> Option<Vec<usize>> {
+		self.filter.as_ref().map(|filter| filter.rows(todo_file))
+	}
+
+	/// Get the indexes of the lines in the selected range that match the filter, when a filter is active. The lines
+	/// hidden by the filter are never changed by an action.
+	fn filtered_selected_indexes(&self, todo_file: &TodoFile) -> Option<Vec<usize>> {
+		let filter = self.filter.as_ref()?;
+		let (start_index, end_index) = self.selected_range(todo_file);
+		Some(filter.rows_in_range(todo_file, start_index, end_index))
+	}
+
+	/// Is the selected line hidden by the filter, which only happens when no lines match the filter.
+	fn is_selected_line_hidden(&self) -> bool {
+		let todo_file = self.todo_file.lock();
+		self.filter_rows(&todo_file)
+			.map_or(false, |rows| !rows.contains(&todo_file.get_selected_line_index()))
+	}
+
+	/// Update the filter with the commits found by the search since the last update.
+	fn update_filter_commit_matches(&mut self) {
+		if let Some(filter) = self.filter.as_mut() {
+			filter.update_commit_matches(&self.search);
+		}
+	}
+
+	/// The fixup groups that are collapsed into a single row, when the fold view is active.
+	fn collapsed_fixup_groups(&self, todo_file: &TodoFile) -> Vec<FixupGroup> {
+		if self.view == ListView::Fold {
+			self.fold.collapsed_groups(todo_file)
+		}
+		else {
+			vec![]
+		}
+	}
+
+	/// Get the range of lines of the selected row, when it is a collapsed section in the stack view, or a collapsed
+	/// fixup group in the fold view.
+	fn selected_collapsed_range(&self, todo_file: &TodoFile) -> Option<(usize, usize)> {
+		match self.view {
+			ListView::Lines => None,
+			ListView::Stack => {
+				self.stack
+					.selected_collapsed_section(todo_file)
+					.map(|section| (section.start(), section.end()))
+			},
+			ListView::Fold => {
+				self.fold
+					.selected_collapsed_group(todo_file)
+					.map(|group| (group.start(), group.end()))
+			},
+		}
+	}
+
+	/// Is the selected row a collapsed section in the stack view, or a collapsed fixup group in the fold view.
+	fn is_collapsed_row_selected(&self) -> bool {
+		self.selected_collapsed_range(&self.todo_file.lock()).is_some()
+	}
+
+	/// Get the range of lines that are changed by an action, which is either the visual selection, or all the lines of
+	/// a collapsed section in the stack view, or of a collapsed fixup group in the fold view.
+	fn selected_range(&self, todo_file: &TodoFile) -> (usize, usize) {
+		self.selected_collapsed_range(todo_file).unwrap_or_else(|| {
+			let start_index = todo_file.get_selected_line_index();
+			(start_index, self.visual_index_start.unwrap_or(start_index))
+		})
+	}
+
 	#[allow(clippy::unused_self)]
 	fn move_cursor_left(&self, view_state: &crate::view::State) {
 		view_state.scroll_left();
@@ -217,58 +475,130 @@ impl List {
 	}
 
 	fn swap_selected_up(&mut self) {
+		self.marked_lines.clear();
 		let mut todo_file = self.todo_file.lock();
-		let start_index = todo_file.get_selected_line_index();
-		let end_index = self.visual_index_start.unwrap_or(start_index);
-
-		let swapped = todo_file.swap_range_up(start_index, end_index);
+		let selected_index = todo_file.get_selected_line_index();
+		let (start_index, end_index) = self.selected_range(&todo_file);
+		let new_index = match self.view {
+			ListView::Stack => self.stack.swap_selected_up(&mut todo_file),
+			ListView::Fold => self.fold.swap_range_up(&mut todo_file, start_index, end_index),
+			ListView::Lines => {
+				if let Some(filter) = self.filter.as_ref() {
+					filter
+						.swap_range_up(&mut todo_file, start_index, end_index)
+						.map(|distance| selected_index - distance)
+				}
+				else {
+					todo_file
+						.swap_range_up(start_index, end_index)
+						.then(|| selected_index - 1)
+				}
+			},
+		};
 		drop(todo_file);
 
-		if swapped {
+		if let Some(index) = new_index {
 			if let Some(visual_index_start) = self.visual_index_start {
-				self.visual_index_start = Some(visual_index_start - 1);
+				self.visual_index_start = Some(visual_index_start - (selected_index - index));
 			}
-			_ = self.update_cursor(CursorUpdate::Up(1));
+			_ = self.update_cursor(CursorUpdate::Set(index));
 		}
 	}
 
 	fn swap_selected_down(&mut self) {
+		self.marked_lines.clear();
 		let mut todo_file = self.todo_file.lock();
-		let start_index = todo_file.get_selected_line_index();
-		let end_index = self.visual_index_start.unwrap_or(start_index);
-
-		let swapped = todo_file.swap_range_down(start_index, end_index);
+		let selected_index = todo_file.get_selected_line_index();
+		let (start_index, end_index) = self.selected_range(&todo_file);
+		let new_index = match self.view {
+			ListView::Stack => self.stack.swap_selected_down(&mut todo_file),
+			ListView::Fold => self.fold.swap_range_down(&mut todo_file, start_index, end_index),
+			ListView::Lines => {
+				if let Some(filter) = self.filter.as_ref() {
+					filter
+						.swap_range_down(&mut todo_file, start_index, end_index)
+						.map(|distance| selected_index + distance)
+				}
+				else {
+					todo_file
+						.swap_range_down(start_index, end_index)
+						.then(|| selected_index + 1)
+				}
+			},
+		};
 		drop(todo_file);
 
-		if swapped {
+		if let Some(index) = new_index {
 			if let Some(visual_index_start) = self.visual_index_start {
-				self.visual_index_start = Some(visual_index_start + 1);
+				self.visual_index_start = Some(visual_index_start + (index - selected_index));
 			}
-			_ = self.update_cursor(CursorUpdate::Down(1));
+			_ = self.update_cursor(CursorUpdate::Set(index));
+		}
+	}
+
+	fn is_action_allowed(&self, action: Action) -> bool {
+		// the sequencer, used for a multiple commit cherry-pick or revert, only accepts the pick and revert actions
+		if self.is_sequencer {
+			matches!(action, Action::Pick | Action::Revert)
+		}
+		else {
+			action != Action::Revert
 		}
 	}
 
 	fn set_selected_line_action(&mut self, action: Action) {
+		if !self.is_action_allowed(action) {
+			return;
+		}
+
 		let mut todo_file = self.todo_file.lock();
-		let start_index = todo_file.get_selected_line_index();
-		let end_index = self.visual_index_start.unwrap_or(start_index);
+		let (start_index, end_index) = self.selected_range(&todo_file);
+		let edit_context = EditContext::new().action(action);
 
-		todo_file.update_range(start_index, end_index, &EditContext::new().action(action));
+		if !self.marked_lines.is_empty() {
+			let indexes: Vec<usize> = self.marked_lines.iter().copied().collect();
+			todo_file.update_lines(&indexes, &edit_context);
+			return;
+		}
+
+		if let Some(indexes) = self.filtered_selected_indexes(&todo_file) {
+			todo_file.update_lines(&indexes, &edit_context);
+		}
+		else {
+			todo_file.update_range(start_index, end_index, &edit_context);
+		}
 		drop(todo_file);
 
 		if self.state == ListState::Normal && self.auto_select_next {
 			_ = self.update_cursor(CursorUpdate::Down(1));
 		}
+		else if self.filter.is_some() {
+			// the new action may no longer match the filter, which hides the selected line
+			_ = self.update_cursor(CursorUpdate::Set(start_index));
+		}
+	}
+
+	/// Set the action of all the lines that match the search, as a single change.
+	fn set_search_matches_action(&mut self, action: Action) {
+		if !self.is_action_allowed(action) {
+			return;
+		}
+
+		let indexes = self.search.match_indexes();
+		self.todo_file
+			.lock()
+			.update_lines(&indexes, &EditContext::new().action(action));
 	}
 
 	fn undo(&mut self) {
+		self.marked_lines.clear();
 		let mut todo_file = self.todo_file.lock();
 		let undo_result = todo_file.undo();
 		drop(todo_file);
 
 		if let Some((start_index, end_index)) = undo_result {
 			let new_start_index = self.update_cursor(CursorUpdate::Set(start_index));
-			if new_start_index == end_index {
+			if new_start_index == end_index || self.view != ListView::Lines || self.filter.is_some() {
 				self.state = ListState::Normal;
 				self.visual_index_start = None;
 			}
@@ -280,13 +610,14 @@ impl List {
 	}
 
 	fn redo(&mut self) {
+		self.marked_lines.clear();
 		let mut todo_file = self.todo_file.lock();
 		let redo_result = todo_file.redo();
 		drop(todo_file);
 
 		if let Some((start_index, end_index)) = redo_result {
 			let new_start_index = self.update_cursor(CursorUpdate::Set(start_index));
-			if new_start_index == end_index {
+			if new_start_index == end_index || self.view != ListView::Lines || self.filter.is_some() {
 				self.state = ListState::Normal;
 				self.visual_index_start = None;
 			}
@@ -298,11 +629,33 @@ impl List {
 	}
 
 	fn delete(&mut self) {
+		if !self.marked_lines.is_empty() {
+			self.delete_marked();
+			return;
+		}
+
 		let mut todo_file = self.todo_file.lock();
-		let start_index = todo_file.get_selected_line_index();
-		let end_index = self.visual_index_start.unwrap_or(start_index);
+		let (start_index, end_index) = self.selected_range(&todo_file);
 
-		todo_file.remove_lines(start_index, end_index);
+		if let Some(indexes) = self.filtered_selected_indexes(&todo_file) {
+			if indexes.is_empty() {
+				return;
+			}
+			// the lines in the range that are hidden by the filter are kept
+			let hidden_lines: Vec<Line> = (min(start_index, end_index)..=max(start_index, end_index))
+				.filter(|index| !indexes.contains(index))
+				.filter_map(|index| todo_file.get_line(index).cloned())
+				.collect();
+			if hidden_lines.is_empty() {
+				todo_file.remove_lines(start_index, end_index);
+			}
+			else {
+				todo_file.replace_range(start_index, end_index, hidden_lines);
+			}
+		}
+		else {
+			todo_file.remove_lines(start_index, end_index);
+		}
 		drop(todo_file);
 
 		let new_index = min(start_index, end_index);
@@ -319,7 +672,23 @@ impl List {
 		results.state(State::ExternalEditor);
 	}
 
+	fn open_selection_in_editor(&mut self, results: &mut Results) {
+		let mut todo_file = self.todo_file.lock();
+		if todo_file.is_empty() {
+			return;
+		}
+		let (start, end) = self.selected_range(&todo_file);
+		todo_file.set_edit_range(min(start, end), max(start, end));
+		drop(todo_file);
+		self.open_in_editor(results);
+	}
+
 	fn toggle_visual_mode(&mut self) {
+		// a collapsed section, or collapsed fixup group, acts as the selection in the stack and fold views
+		if self.view != ListView::Lines {
+			return;
+		}
+
 		if self.state == ListState::Visual {
 			self.state = ListState::Normal;
 			self.visual_index_start = None;
@@ -361,6 +730,9 @@ impl List {
 	}
 
 	fn show_commit(&mut self, results: &mut Results) {
+		if self.is_selected_line_hidden() {
+			return;
+		}
 		let todo_file = self.todo_file.lock();
 		if let Some(selected_line) = todo_file.get_selected_line() {
 			if selected_line.has_reference() {
@@ -370,6 +742,10 @@ impl List {
 	}
 
 	fn action_break(&mut self) {
+		if !self.is_action_allowed(Action::Break) {
+			return;
+		}
+
 		let mut todo_file = self.todo_file.lock();
 		let selected_line_index = todo_file.get_selected_line_index();
 		let next_action_is_break = todo_file
@@ -385,6 +761,7 @@ impl List {
 			.get_line(selected_line_index)
 			.map_or(false, |line| line.get_action() == &Action::Break);
 
+		self.marked_lines.clear();
 		let cursor_update = if selected_action_is_break {
 			todo_file.remove_lines(selected_line_index, selected_line_index);
 			CursorUpdate::Up(1)
@@ -411,15 +788,95 @@ impl List {
 	}
 
 	fn edit(&mut self) {
+		if self.edit_line.start(&self.todo_file.lock(), &self.branches) {
+			self.state = ListState::Edit;
+		}
+	}
+
+	/// Start inserting exec lines, after the selected commits in visual mode, or otherwise after every commit, or the
+	/// last commit of each branch when there are `update-ref` lines to choose from.
+	fn insert_exec_lines(&mut self) {
+		if !self.is_action_allowed(Action::Exec) {
+			return;
+		}
 		let todo_file = self.todo_file.lock();
-		if let Some(selected_line) = todo_file.get_selected_line() {
-			if selected_line.is_editable() {
-				self.state = ListState::Edit;
-				self.edit.reset();
-				self.edit.set_content(selected_line.get_content());
-				self.edit.set_label(format!("{} ", selected_line.get_action()).as_str());
-			}
+		if todo_file.is_empty() {
+			return;
+		}
+		let target = if self.state == ListState::Visual {
+			let (start, end) = self.selected_range(&todo_file);
+			Some(ExecTarget::Selection(start, end))
+		}
+		else if todo_file.lines_iter().any(|line| *line.get_action() == Action::UpdateRef) {
+			None
 		}
+		else {
+			Some(ExecTarget::Commits)
+		};
+		drop(todo_file);
+
+		self.exec_lines.start(target);
+		self.state = ListState::ExecLines;
+	}
+
+	/// Start composing a new message for the commit of the selected line, starting from the message composed before, or
+	/// the full message of the commit.
+	fn reword_message(&mut self) {
+		// the message is amended with an exec line, which the sequencer does not support
+		if !self.is_action_allowed(Action::Exec) || self.is_selected_line_hidden() {
+			return;
+		}
+		let todo_file = self.todo_file.lock();
+		let Some(selected_line) = todo_file.get_selected_line()
+		else {
+			return;
+		};
+		if !matches!(
+			*selected_line.get_action(),
+			Action::Pick | Action::Reword | Action::Edit
+		) {
+			return;
+		}
+		let content = selected_line.message().map_or_else(
+			|| {
+				self.metadata_index
+					.get(selected_line.get_hash())
+					.and_then(|metadata| metadata.commit().message().map(|message| String::from(message.trim_end())))
+					.unwrap_or_else(|| String::from(selected_line.get_content()))
+			},
+			String::from,
+		);
+		self.reword.start(todo_file.get_selected_line_index(), content.as_str());
+		self.state = ListState::Reword;
+	}
+
+	/// Start changing the author, committer date or trailers of the commits of the selected lines.
+	fn rewrite_metadata(&mut self) {
+		// the commits are amended with an exec line, which the sequencer does not support
+		if !self.is_action_allowed(Action::Exec) || self.is_selected_line_hidden() {
+			return;
+		}
+		let todo_file = self.todo_file.lock();
+		let (start, end) = self.selected_range(&todo_file);
+		let has_commit = todo_file
+			.lines_iter()
+			.skip(min(start, end))
+			.take(max(start, end) - min(start, end) + 1)
+			.any(|line| {
+				line.has_reference() && matches!(*line.get_action(), Action::Pick | Action::Reword | Action::Edit)
+			});
+		drop(todo_file);
+
+		if has_commit {
+			self.rewrite_metadata.start(start, end);
+			self.state = ListState::RewriteMetadata;
+		}
+	}
+
+	/// Start browsing the undo history, to restore a previous state.
+	fn show_undo_history(&mut self) {
+		self.undo_history.reset(&self.todo_file.lock());
+		self.state = ListState::UndoHistory;
 	}
 
 	#[allow(clippy::unused_self)]
@@ -427,95 +884,472 @@ impl List {
 		results.state(State::Insert);
 	}
 
+	/// Insert `update-ref` lines for the branches that do not have one, choosing the branches when there is more than
+	/// one.
+	fn insert_update_refs(&mut self) {
+		if !self.is_action_allowed(Action::UpdateRef) {
+			return;
+		}
+
+		let missing_update_refs = self.branches.missing_update_refs(&self.todo_file.lock());
+		if missing_update_refs.len() > 1 {
+			self.update_refs.start(missing_update_refs);
+			self.state = ListState::UpdateRefs;
+		}
+		else {
+			self.add_update_refs(missing_update_refs);
+		}
+	}
+
+	fn add_update_refs(&mut self, update_refs: Vec<(usize, String)>) {
+		let mut todo_file = self.todo_file.lock();
+		let selected_line_index = todo_file.get_selected_line_index();
+		// keep the cursor on the same line, when lines are added above it
+		let added_above = update_refs
+			.iter()
+			.filter(|update_ref| update_ref.0 < selected_line_index)
+			.count();
+		let added = todo_file.add_lines_after(
+			update_refs
+				.into_iter()
+				.map(|(index, name)| (index, Line::new_update_ref(name.as_str())))
+				.collect(),
+		);
+		drop(todo_file);
+
+		if added > 0 {
+			self.marked_lines.clear();
+		}
+		if added_above > 0 {
+			_ = self.update_cursor(CursorUpdate::Set(selected_line_index + added_above));
+		}
+	}
+
+	/// Mark the selected lines, or unmark them when they are all already marked.
+	fn toggle_mark(&mut self) {
+		let indexes = self.selected_indexes(&self.todo_file.lock());
+		toggle_marks(&mut self.marked_lines, indexes);
+	}
+
+	fn delete_marked(&mut self) {
+		let selected_index = remove_marked_lines(&mut self.marked_lines, &mut self.todo_file.lock());
+		_ = self.update_cursor(CursorUpdate::Set(selected_index));
+		if self.state == ListState::Visual {
+			self.state = ListState::Normal;
+			self.visual_index_start = None;
+		}
+	}
+
+	/// Move the marked lines after the selected line, keeping their order, and select the first of the moved lines.
+	fn gather_marked(&mut self) {
+		let gathered = gather_marked_lines(&mut self.marked_lines, &mut self.todo_file.lock());
+		if let Some(start_index) = gathered {
+			self.state = ListState::Normal;
+			self.visual_index_start = None;
+			_ = self.update_cursor(CursorUpdate::Set(start_index));
+		}
+	}
+
+	fn reorder_selected<F: FnOnce(&mut TodoFile, &[usize]) -> bool>(&mut self, reorder: F) {
+		let mut todo_file = self.todo_file.lock();
+		let indexes = self.selected_indexes(&todo_file);
+		let reordered = reorder(&mut todo_file, &indexes);
+		drop(todo_file);
+
+		if reordered {
+			self.marked_lines.clear();
+		}
+	}
+
+	/// Sort the selected lines that have a commit by the author or committer date of the commit, oldest first.
+	fn sort_selected_by_date(&mut self, committer_date: bool) {
+		let metadata_index = self.metadata_index.clone();
+		self.reorder_selected(|todo_file, indexes| {
+			sort_lines_by_date(todo_file, indexes, &metadata_index, committer_date)
+		});
+	}
+
+	/// Set the rows of the list, leaving visual mode and keeping the cursor on the selected line. The stack view, the
+	/// fold view and the filter all change the rows of the list, so only one can be active.
+	fn set_view(&mut self, view: ListView, filter: Option<Filter>) {
+		self.view = view;
+		self.filter = filter;
+		self.state = ListState::Normal;
+		self.visual_index_start = None;
+		let selected_line_index = self.todo_file.lock().get_selected_line_index();
+		_ = self.update_cursor(CursorUpdate::Set(selected_line_index));
+	}
+
+	/// Toggle a filter for the current search term, that hides the lines that do not match the term.
+	fn toggle_filter(&mut self) {
+		if self.filter.is_some() {
+			self.set_view(self.view, None);
+		}
+		else if let Some(term) = self.search_bar.search_value() {
+			let filter = Filter::new(term);
+			self.set_view(ListView::Lines, Some(filter));
+		}
+	}
+
+	fn toggle_stack_view(&mut self) {
+		// the sequencer does not support update-ref lines, so the todo is never a stack of branches
+		if self.is_sequencer {
+			return;
+		}
+		self.set_view(
+			if self.view == ListView::Stack {
+				ListView::Lines
+			}
+			else {
+				ListView::Stack
+			},
+			None,
+		);
+	}
+
+	/// Toggle folding the `fixup` and `squash` lines into the row of the commit they are folded into.
+	fn toggle_fold_view(&mut self) {
+		// the sequencer only supports the pick and revert actions, so there are never lines to fold
+		if self.is_sequencer {
+			return;
+		}
+		self.set_view(
+			if self.view == ListView::Fold {
+				ListView::Lines
+			}
+			else {
+				ListView::Fold
+			},
+			None,
+		);
+	}
+
+	/// Collapse or expand the selected section in the stack view, or the selected fixup group in the fold view.
+	fn toggle_section(&mut self) {
+		let todo_file = self.todo_file.lock();
+		let selected_line_index = todo_file.get_selected_line_index();
+		let toggled = match self.view {
+			ListView::Lines => false,
+			ListView::Stack => self.stack.toggle_section(&todo_file),
+			ListView::Fold => self.fold.toggle_group(&todo_file),
+		};
+		drop(todo_file);
+
+		if toggled {
+			_ = self.update_cursor(CursorUpdate::Set(selected_line_index));
+		}
+	}
+
 	fn update_list_view_data(&mut self, context: &RenderContext) -> &ViewData {
+		self.update_filter_commit_matches();
 		let todo_file = self.todo_file.lock();
+		let is_empty = todo_file.is_empty();
 		let is_visual_mode = self.state == ListState::Visual;
+		let is_full_width = context.is_full_width();
+		let selected_done_index = todo_file.get_selected_done_line_index();
+		let done_lines_count = todo_file.done_lines_count();
 		let selected_index = todo_file.get_selected_line_index();
 		let visual_index = self.visual_index_start.unwrap_or(selected_index);
-		let search_view_line = self.search_bar.is_editing().then(|| self.search_bar.build_view_line());
-		let search_results_total = self.search.total_results();
-		let search_results_current = self.search.current_result_selected().unwrap_or(0);
-		let search_term = self.search_bar.search_value();
+		let search_query = self.search.query();
 		let search_index = self.search.current_match();
-		let search_active = self.search.is_active();
-		let spin_indicator = self.spin_indicator.indicator();
+		let visible_rows = self
+			.filter_rows(&todo_file)
+			.or_else(|| (self.view == ListView::Fold).then(|| self.fold.rows(&todo_file)));
+		let collapsed_groups = self.collapsed_fixup_groups(&todo_file);
+		let maximum_action_width = get_line_action_maximum_width(&todo_file);
+		let leading_lines = self.get_status_lines(&todo_file, visible_rows.as_deref());
+		let trailing_line = self.get_search_line();
+
+		let todo_view_line = |index: usize, line: &Line| {
+			let search_match = self.search.match_at_index(index);
+			let selected_line = is_visual_mode
+				&& ((visual_index <= selected_index && index >= visual_index && index <= selected_index)
+					|| (visual_index > selected_index && index >= selected_index && index <= visual_index));
+			let is_cursor_line = selected_done_index.is_none() && selected_index == index;
+			let mut todo_line_segment_options = TodoLineSegmentsOptions::empty();
+			todo_line_segment_options.set(TodoLineSegmentsOptions::CURSOR_LINE, is_cursor_line);
+			todo_line_segment_options.set(TodoLineSegmentsOptions::SELECTED, selected_line);
+			todo_line_segment_options.set(TodoLineSegmentsOptions::FULL_WIDTH, is_full_width);
+			todo_line_segment_options.set(
+				TodoLineSegmentsOptions::SEARCH_LINE,
+				search_index.map_or(false, |v| v.index() == index),
+			);
+			todo_line_segment_options.set(TodoLineSegmentsOptions::MARKED, self.marked_lines.contains(&index));
+			let mut segments = get_todo_line_segments(
+				line,
+				Some(&search_query),
+				search_match,
+				todo_line_segment_options,
+				maximum_action_width,
+			);
+			segments.extend(get_branch_segments(
+				&self.branches.for_line(line),
+				self.branches.unexpected_update_ref_target(&todo_file, index),
+				false,
+			));
+			if let Some(group) = collapsed_groups.iter().find(|group| group.start() == index) {
+				segments.push(LineSegment::new_with_color(
+					format!(" [+{}]", group.fixups()).as_str(),
+					DisplayColor::IndicatorColor,
+				));
+			}
+			let view_line = ViewLine::new_with_pinned_segments(segments, if line.has_reference() { 2 } else { 3 });
+			if is_cursor_line || selected_line {
+				view_line.set_selected(true).set_padding(' ')
+			}
+			else {
+				view_line
+			}
+		};
+
+		let (lines, visible_lines) = if let Some(rows) = visible_rows {
+			// the done lines can not be changed, so they are not shown in the filtered list or the fold view
+			let (lines, cursor_row) = Self::get_row_lines(&todo_file, &rows, selected_index, todo_view_line);
+			(lines, vec![cursor_row])
+		}
+		else if self.view == ListView::Stack {
+			// the done lines are not part of any section, so they are not shown in the stack view
+			let (lines, cursor_row) =
+				Self::get_stack_lines(&todo_file, &self.stack, selected_index, is_full_width, todo_view_line);
+			(lines, vec![cursor_row])
+		}
+		else {
+			let mut lines = Self::get_done_lines(
+				&todo_file,
+				&self.branches,
+				selected_done_index,
+				is_full_width,
+				maximum_action_width,
+			);
+			lines.extend(
+				todo_file
+					.lines_iter()
+					.enumerate()
+					.map(|(index, line)| todo_view_line(index, line)),
+			);
+			let mut visible_lines = vec![];
+			if visual_index != selected_index {
+				visible_lines.push(done_lines_count + visual_index);
+			}
+			visible_lines.push(selected_done_index.unwrap_or(done_lines_count + selected_index));
+			(lines, visible_lines)
+		};
+		drop(todo_file);
 
 		self.view_data.update_view_data(|updater| {
-			capture!(todo_file);
 			updater.clear();
-			if todo_file.is_empty() {
+			if is_empty {
 				updater.push_leading_line(ViewLine::from(LineSegment::new_with_color(
 					"Rebase todo file is empty",
 					DisplayColor::IndicatorColor,
 				)));
+				return;
 			}
-			else {
-				let maximum_action_width = get_line_action_maximum_width(&todo_file);
-				for (index, line) in todo_file.lines_iter().enumerate() {
-					let search_match = self.search.match_at_index(index);
-					let selected_line = is_visual_mode
-						&& ((visual_index <= selected_index && index >= visual_index && index <= selected_index)
-							|| (visual_index > selected_index && index >= selected_index && index <= visual_index));
-					let mut todo_line_segment_options = TodoLineSegmentsOptions::empty();
-					if selected_index == index {
-						todo_line_segment_options.insert(TodoLineSegmentsOptions::CURSOR_LINE);
-					}
-					if selected_line {
-						todo_line_segment_options.insert(TodoLineSegmentsOptions::SELECTED);
-					}
-					if context.is_full_width() {
-						todo_line_segment_options.insert(TodoLineSegmentsOptions::FULL_WIDTH);
-					}
-					if search_index.map_or(false, |v| v.index() == index) {
-						todo_line_segment_options.insert(TodoLineSegmentsOptions::SEARCH_LINE);
-					}
-					let mut view_line = ViewLine::new_with_pinned_segments(
-						get_todo_line_segments(
-							line,
-							search_term,
-							search_match,
-							todo_line_segment_options,
-							maximum_action_width,
-						),
-						if line.has_reference() { 2 } else { 3 },
-					)
-					.set_selected(selected_index == index || selected_line);
-
-					if selected_index == index || selected_line {
-						view_line = view_line.set_selected(true).set_padding(' ');
-					}
-
-					updater.push_line(view_line);
-				}
-				if let Some(search) = search_view_line {
-					updater.push_trailing_line(search);
-				}
-				else if let Some(s_term) = search_term {
-					let mut search_line_segments = vec![];
-					search_line_segments.push(LineSegment::new(format!("[{s_term}]: ").as_str()));
-					if search_results_total == 0 && !search_active {
-						search_line_segments.push(LineSegment::new("No Results"));
-					}
-					else {
-						search_line_segments.push(LineSegment::new(
-							format!("{}/{search_results_total}", search_results_current + 1).as_str(),
-						));
-					}
-
-					if search_active {
-						search_line_segments.push(LineSegment::new(format!(" Searching [{spin_indicator}]").as_str()));
-					}
-					updater.push_trailing_line(ViewLine::from(search_line_segments));
-				}
+			for line in leading_lines {
+				updater.push_leading_line(line);
 			}
-			if visual_index != selected_index {
-				updater.ensure_line_visible(visual_index);
+			for line in lines {
+				updater.push_line(line);
+			}
+			for index in visible_lines {
+				updater.ensure_line_visible(index);
+			}
+			if let Some(line) = trailing_line {
+				updater.push_trailing_line(line);
 			}
-			updater.ensure_line_visible(selected_index);
 		});
 		&self.view_data
 	}
 
+	/// Get the lines that show the pending input, the marked lines and the filter, above the todo lines.
+	fn get_status_lines(&self, todo_file: &TodoFile, visible_rows: Option<&[usize]>) -> Vec<ViewLine> {
+		let mut lines = vec![];
+		if let Some(command) = self.pending_named_mark {
+			lines.push(ViewLine::from(LineSegment::new_with_color(
+				match command {
+					NamedMarkCommand::Set => "Press a key to name a mark for the selected line",
+					NamedMarkCommand::Jump => "Press the key of the mark to jump to",
+				},
+				DisplayColor::IndicatorColor,
+			)));
+		}
+		else if self.pending_count > 0 || self.pending_keys.is_some() {
+			let count = if self.pending_count > 0 {
+				self.pending_count.to_string()
+			}
+			else {
+				String::new()
+			};
+			let keys = self.pending_keys.map(|keys| keys.to_string()).unwrap_or_default();
+			lines.push(ViewLine::from(LineSegment::new_with_color(
+				format!("Pending: {count}{keys}").as_str(),
+				DisplayColor::IndicatorColor,
+			)));
+		}
+
+		if !self.marked_lines.is_empty() {
+			lines.push(ViewLine::from(LineSegment::new_with_color(
+				format!(
+					"Marked: {} {}",
+					self.marked_lines.len(),
+					if self.marked_lines.len() == 1 { "line" } else { "lines" }
+				)
+				.as_str(),
+				DisplayColor::IndicatorColor,
+			)));
+		}
+
+		if let Some(filter) = self.filter.as_ref() {
+			lines.push(ViewLine::from(LineSegment::new_with_color(
+				format!(
+					"Filter [{}]: {}/{} lines",
+					filter.term(),
+					visible_rows.map_or(0, <[usize]>::len),
+					todo_file.lines_iter().len()
+				)
+				.as_str(),
+				DisplayColor::IndicatorColor,
+			)));
+		}
+		lines
+	}
+
+	/// Get the line that shows the search bar while editing the search term, or the search results, below the todo
+	/// lines.
+	fn get_search_line(&self) -> Option<ViewLine> {
+		if self.search_bar.is_editing() {
+			return Some(self.search_bar.build_view_line());
+		}
+
+		let term = self.search_bar.search_value()?;
+		let search_results_total = self.search.total_results();
+		let search_active = self.search.is_active();
+		let mut search_line_segments = vec![LineSegment::new(format!("[{term}]: ").as_str())];
+		if search_results_total == 0 && !search_active {
+			search_line_segments.push(LineSegment::new("No Results"));
+		}
+		else {
+			search_line_segments.push(LineSegment::new(
+				format!(
+					"{}/{search_results_total}",
+					self.search.current_result_selected().unwrap_or(0) + 1
+				)
+				.as_str(),
+			));
+		}
+		if search_active {
+			search_line_segments.push(LineSegment::new(
+				format!(" Searching [{}]", self.spin_indicator.indicator()).as_str(),
+			));
+		}
+		if self.apply_to_matches {
+			search_line_segments.push(LineSegment::new_with_color(
+				" Select an action for all matches",
+				DisplayColor::IndicatorColor,
+			));
+		}
+		Some(ViewLine::from(search_line_segments))
+	}
+
+	/// Get the lines of the rows, with the row of the selected line, or a line that tells no lines match the filter.
+	fn get_row_lines<F: Fn(usize, &Line) -> ViewLine>(
+		todo_file: &TodoFile,
+		rows: &[usize],
+		selected_index: usize,
+		todo_view_line: F,
+	) -> (Vec<ViewLine>, usize) {
+		if rows.is_empty() {
+			let line = ViewLine::from(LineSegment::new_with_color(
+				"No lines match the filter",
+				DisplayColor::IndicatorColor,
+			));
+			return (vec![line], 0);
+		}
+		let cursor_row = rows.iter().rposition(|&index| index == selected_index).unwrap_or(0);
+		let lines = rows
+			.iter()
+			.filter_map(|&index| todo_file.get_line(index).map(|line| todo_view_line(index, line)))
+			.collect();
+		(lines, cursor_row)
+	}
+
+	/// Get the lines of the stack view, with a header for each section, and the lines of the sections that are not
+	/// collapsed, with the row of the selected line.
+	fn get_stack_lines<F: Fn(usize, &Line) -> ViewLine>(
+		todo_file: &TodoFile,
+		stack: &Stack,
+		selected_index: usize,
+		is_full_width: bool,
+		todo_view_line: F,
+	) -> (Vec<ViewLine>, usize) {
+		let mut lines = vec![];
+		let mut cursor_row = 0;
+		for section in get_sections(todo_file) {
+			let is_collapsed = stack.is_collapsed(&section);
+			let is_cursor_section = is_collapsed && section.contains(selected_index);
+			if is_cursor_section {
+				cursor_row = lines.len();
+			}
+			let header = ViewLine::from(get_section_header_segments(
+				&section,
+				is_collapsed,
+				is_cursor_section,
+				is_full_width,
+			));
+			lines.push(if is_cursor_section {
+				header.set_selected(true).set_padding(' ')
+			}
+			else {
+				header
+			});
+			if is_collapsed {
+				continue;
+			}
+			for (index, line) in todo_file
+				.lines_iter()
+				.enumerate()
+				.skip(section.start())
+				.take(section.len())
+			{
+				if index == selected_index {
+					cursor_row = lines.len();
+				}
+				lines.push(todo_view_line(index, line));
+			}
+		}
+		(lines, cursor_row)
+	}
+
+	/// Get the lines of the done lines, which are shown dimmed above the todo lines.
+	fn get_done_lines(
+		todo_file: &TodoFile,
+		branches: &Branches,
+		selected_done_index: Option<usize>,
+		is_full_width: bool,
+		maximum_action_width: usize,
+	) -> Vec<ViewLine> {
+		todo_file
+			.done_lines_iter()
+			.enumerate()
+			.map(|(index, line)| {
+				let is_cursor_line = selected_done_index == Some(index);
+				let mut todo_line_segment_options = TodoLineSegmentsOptions::DONE;
+				todo_line_segment_options.set(TodoLineSegmentsOptions::CURSOR_LINE, is_cursor_line);
+				todo_line_segment_options.set(TodoLineSegmentsOptions::FULL_WIDTH, is_full_width);
+				let mut segments =
+					get_todo_line_segments(line, None, None, todo_line_segment_options, maximum_action_width);
+				segments.extend(get_branch_segments(&branches.for_line(line), None, true));
+				let view_line = ViewLine::new_with_pinned_segments(segments, if line.has_reference() { 2 } else { 3 });
+				if is_cursor_line {
+					view_line.set_selected(true).set_padding(' ')
+				}
+				else {
+					view_line
+				}
+			})
+			.collect()
+	}
+
 	fn get_visual_mode_view_data(&mut self, context: &RenderContext) -> &ViewData {
 		if self.visual_mode_help.is_active() {
 			self.visual_mode_help.get_view_data()
@@ -558,12 +1392,18 @@ impl List {
 			e if key_bindings.action_edit.contains(&e) => Event::from(StandardEvent::ActionEdit),
 			e if key_bindings.action_fixup.contains(&e) => Event::from(StandardEvent::ActionFixup),
 			e if key_bindings.action_pick.contains(&e) => Event::from(StandardEvent::ActionPick),
+			e if key_bindings.action_revert.contains(&e) => Event::from(StandardEvent::ActionRevert),
 			e if key_bindings.action_reword.contains(&e) => Event::from(StandardEvent::ActionReword),
 			e if key_bindings.action_squash.contains(&e) => Event::from(StandardEvent::ActionSquash),
 			e if key_bindings.edit.contains(&e) => Event::from(StandardEvent::Edit),
 			e if key_bindings.force_abort.contains(&e) => Event::from(StandardEvent::ForceAbort),
 			e if key_bindings.force_rebase.contains(&e) => Event::from(StandardEvent::ForceRebase),
 			e if key_bindings.insert_line.contains(&e) => Event::from(StandardEvent::InsertLine),
+			e if key_bindings.insert_update_refs.contains(&e) => Event::from(StandardEvent::InsertUpdateRefs),
+			e if key_bindings.insert_exec_lines.contains(&e) => Event::from(StandardEvent::InsertExecLines),
+			e if key_bindings.reword_message.contains(&e) => Event::from(StandardEvent::RewordMessage),
+			e if key_bindings.rewrite_metadata.contains(&e) => Event::from(StandardEvent::RewriteMetadata),
+			e if key_bindings.show_undo_history.contains(&e) => Event::from(StandardEvent::ShowUndoHistory),
 			e if key_bindings.move_down.contains(&e) => Event::from(StandardEvent::MoveCursorDown),
 			e if key_bindings.move_down_step.contains(&e) => Event::from(StandardEvent::MoveCursorPageDown),
 			e if key_bindings.move_end.contains(&e) => Event::from(StandardEvent::MoveCursorEnd),
@@ -575,10 +1415,27 @@ impl List {
 			e if key_bindings.move_up.contains(&e) => Event::from(StandardEvent::MoveCursorUp),
 			e if key_bindings.move_up_step.contains(&e) => Event::from(StandardEvent::MoveCursorPageUp),
 			e if key_bindings.open_in_external_editor.contains(&e) => Event::from(StandardEvent::OpenInEditor),
+			e if key_bindings.open_selection_in_external_editor.contains(&e) => {
+				Event::from(StandardEvent::OpenSelectionInEditor)
+			},
 			e if key_bindings.rebase.contains(&e) => Event::from(StandardEvent::Rebase),
 			e if key_bindings.remove_line.contains(&e) => Event::from(StandardEvent::Delete),
 			e if key_bindings.show_commit.contains(&e) => Event::from(StandardEvent::ShowCommit),
 			e if key_bindings.toggle_visual_mode.contains(&e) => Event::from(StandardEvent::ToggleVisualMode),
+			e if key_bindings.toggle_stack_view.contains(&e) => Event::from(StandardEvent::ToggleStackView),
+			e if key_bindings.toggle_fold_view.contains(&e) => Event::from(StandardEvent::ToggleFoldView),
+			e if key_bindings.toggle_filter.contains(&e) => Event::from(StandardEvent::ToggleFilter),
+			e if key_bindings.apply_to_matches.contains(&e) => Event::from(StandardEvent::ApplyToMatches),
+			e if key_bindings.toggle_mark.contains(&e) => Event::from(StandardEvent::ToggleMark),
+			e if key_bindings.gather_marked.contains(&e) => Event::from(StandardEvent::GatherMarked),
+			e if key_bindings.clear_marks.contains(&e) => Event::from(StandardEvent::ClearMarks),
+			e if key_bindings.set_named_mark.contains(&e) => Event::from(StandardEvent::SetNamedMark),
+			e if key_bindings.jump_to_named_mark.contains(&e) => Event::from(StandardEvent::JumpToNamedMark),
+			e if key_bindings.sort_by_author_date.contains(&e) => Event::from(StandardEvent::SortByAuthorDate),
+			e if key_bindings.sort_by_commit_date.contains(&e) => Event::from(StandardEvent::SortByCommitDate),
+			e if key_bindings.reverse_selection.contains(&e) => Event::from(StandardEvent::ReverseSelection),
+			e if key_bindings.restore_order.contains(&e) => Event::from(StandardEvent::RestoreOrder),
+			e if key_bindings.toggle_section.contains(&e) => Event::from(StandardEvent::ToggleSection),
 			Event::Mouse(mouse_event) => {
 				match mouse_event.kind {
 					MouseEventKind::ScrollDown => Event::from(StandardEvent::MoveCursorDown),
@@ -614,6 +1471,7 @@ impl List {
 				else {
 					results.search_term(term.as_str());
 				}
+				self.save_history(&mut results);
 			},
 			SearchBarAction::Next(term) => {
 				results.search_term(term.as_str());
@@ -638,6 +1496,8 @@ impl List {
 	#[allow(clippy::integer_division)]
 	fn handle_common_list_input(&mut self, event: Event, view_state: &crate::view::State) -> Option<Results> {
 		let mut results = Results::new();
+		// the movement and swap events are repeated by the count typed before the event
+		let count = max(self.count, 1);
 		match event {
 			Event::Standard(standard_event) => {
 				match standard_event {
@@ -646,13 +1506,14 @@ impl List {
 					StandardEvent::ActionEdit => self.set_selected_line_action(Action::Edit),
 					StandardEvent::ActionFixup => self.set_selected_line_action(Action::Fixup),
 					StandardEvent::ActionPick => self.set_selected_line_action(Action::Pick),
+					StandardEvent::ActionRevert => self.set_selected_line_action(Action::Revert),
 					StandardEvent::ActionReword => self.set_selected_line_action(Action::Reword),
 					StandardEvent::ActionSquash => self.set_selected_line_action(Action::Squash),
 					StandardEvent::Delete => self.delete(),
 					StandardEvent::ForceAbort => self.force_abort(&mut results),
 					StandardEvent::ForceRebase => self.force_rebase(&mut results),
 					StandardEvent::MoveCursorDown => {
-						_ = self.update_cursor(CursorUpdate::Down(1));
+						_ = self.update_cursor(CursorUpdate::Down(count));
 					},
 					StandardEvent::MoveCursorEnd => {
 						_ = self.update_cursor(CursorUpdate::End);
@@ -662,20 +1523,43 @@ impl List {
 					},
 					StandardEvent::MoveCursorLeft => self.move_cursor_left(view_state),
 					StandardEvent::MoveCursorPageDown => {
-						_ = self.update_cursor(CursorUpdate::Down(self.height / 2));
+						_ = self.update_cursor(CursorUpdate::Down((self.height / 2).saturating_mul(count)));
 					},
 					StandardEvent::MoveCursorPageUp => {
-						_ = self.update_cursor(CursorUpdate::Up(self.height / 2));
+						_ = self.update_cursor(CursorUpdate::Up((self.height / 2).saturating_mul(count)));
 					},
 					StandardEvent::MoveCursorRight => self.move_cursor_right(view_state),
 					StandardEvent::MoveCursorUp => {
-						_ = self.update_cursor(CursorUpdate::Up(1));
+						_ = self.update_cursor(CursorUpdate::Up(count));
 					},
 					StandardEvent::OpenInEditor => self.open_in_editor(&mut results),
+					StandardEvent::OpenSelectionInEditor => self.open_selection_in_editor(&mut results),
+					StandardEvent::InsertExecLines => self.insert_exec_lines(),
+					StandardEvent::RewordMessage => self.reword_message(),
+					StandardEvent::RewriteMetadata => self.rewrite_metadata(),
+					StandardEvent::ShowUndoHistory => self.show_undo_history(),
 					StandardEvent::Rebase => self.rebase(&mut results),
-					StandardEvent::SwapSelectedDown => self.swap_selected_down(),
-					StandardEvent::SwapSelectedUp => self.swap_selected_up(),
+					StandardEvent::SwapSelectedDown => {
+						for _ in 0..count {
+							self.swap_selected_down();
+						}
+					},
+					StandardEvent::SwapSelectedUp => {
+						for _ in 0..count {
+							self.swap_selected_up();
+						}
+					},
 					StandardEvent::ToggleVisualMode => self.toggle_visual_mode(),
+					StandardEvent::ToggleStackView => self.toggle_stack_view(),
+					StandardEvent::ToggleFoldView => self.toggle_fold_view(),
+					StandardEvent::ToggleFilter => self.toggle_filter(),
+					StandardEvent::ApplyToMatches => self.apply_to_matches = self.search.total_results() > 0,
+					StandardEvent::ToggleMark => self.toggle_mark(),
+					StandardEvent::GatherMarked => self.gather_marked(),
+					StandardEvent::ClearMarks => self.marked_lines.clear(),
+					StandardEvent::SetNamedMark => self.pending_named_mark = Some(NamedMarkCommand::Set),
+					StandardEvent::JumpToNamedMark => self.pending_named_mark = Some(NamedMarkCommand::Jump),
+					StandardEvent::ToggleSection => self.toggle_section(),
 					StandardEvent::Help => self.help(),
 					StandardEvent::Redo => self.redo(),
 					StandardEvent::Undo => self.undo(),
@@ -690,17 +1574,159 @@ impl List {
 		Some(results)
 	}
 
+	fn handle_done_line_input(&mut self, event: Event, view_state: &crate::view::State) -> Results {
+		// done lines, and the hidden line selected when no lines match the filter, are read-only, so only allow events
+		// that move the cursor, search, show help or the commit, undo or redo, or leave the list, and the filter toggle
+		// so the hidden line can be shown again
+		match event {
+			Event::Standard(StandardEvent::ShowCommit) => {
+				let mut results = Results::new();
+				self.show_commit(&mut results);
+				results
+			},
+			Event::Standard(
+				StandardEvent::Abort
+				| StandardEvent::ForceAbort
+				| StandardEvent::ForceRebase
+				| StandardEvent::Help
+				| StandardEvent::JumpToNamedMark
+				| StandardEvent::MoveCursorDown
+				| StandardEvent::MoveCursorEnd
+				| StandardEvent::MoveCursorHome
+				| StandardEvent::MoveCursorLeft
+				| StandardEvent::MoveCursorPageDown
+				| StandardEvent::MoveCursorPageUp
+				| StandardEvent::MoveCursorRight
+				| StandardEvent::MoveCursorUp
+				| StandardEvent::Rebase
+				| StandardEvent::Redo
+				| StandardEvent::SearchStart
+				| StandardEvent::ToggleFilter
+				| StandardEvent::Undo,
+			)
+			| Event::Resize(..) => {
+				self.handle_common_list_input(event, view_state)
+					.unwrap_or_else(Results::new)
+			},
+			_ => Results::new(),
+		}
+	}
+
+	/// Handle the keys typed before an event: the digits of a count that repeats the next movement or swap event, the
+	/// start of a key sequence, and the name of the mark that follows the set or jump to named mark events.
+	fn handle_key_prefix_input(&mut self, event: Event) -> Option<Results> {
+		if !matches!(self.state, ListState::Normal | ListState::Visual) {
+			return None;
+		}
+
+		if let Some(command) = self.pending_named_mark {
+			let Event::Key(KeyEvent { code, .. }) = event
+			else {
+				return None;
+			};
+			self.pending_named_mark = None;
+			// any key that is not a character, like Esc, cancels the mark
+			if let KeyCode::Char(name) = code {
+				self.handle_named_mark(command, name);
+			}
+			return Some(Results::new());
+		}
+
+		match event {
+			Event::PendingKeySequence(keys) => {
+				self.pending_keys = Some(keys);
+				return Some(Results::new());
+			},
+			Event::Key(KeyEvent {
+				code: KeyCode::Char(digit @ '0'..='9'),
+				modifiers: KeyModifiers::NONE,
+			}) if digit != '0' || self.pending_count > 0 => {
+				let value = digit.to_digit(10).map_or(0, |d| d as usize);
+				self.pending_count = min(self.pending_count.saturating_mul(10).saturating_add(value), MAX_COUNT);
+				return Some(Results::new());
+			},
+			Event::Resize(..) => return None,
+			_ => {},
+		}
+
+		self.pending_keys = None;
+		self.count = mem::take(&mut self.pending_count);
+		None
+	}
+
+	fn handle_named_mark(&mut self, command: NamedMarkCommand, name: char) {
+		let is_selected_line_hidden = self.is_selected_line_hidden();
+		let todo_file = self.todo_file.lock();
+		match command {
+			NamedMarkCommand::Set => {
+				// done lines, and the hidden line selected when no lines match the filter, can not be marked
+				if !todo_file.is_done_line_selected() && !is_selected_line_hidden {
+					self.named_marks.set(name, &todo_file);
+				}
+			},
+			NamedMarkCommand::Jump => {
+				if let Some(index) = self.named_marks.find(name, &todo_file) {
+					drop(todo_file);
+					_ = self.update_cursor(CursorUpdate::Set(index));
+				}
+			},
+		}
+	}
+
+	/// Handle the event that follows the apply to matches event, where an action event sets the action of all the lines
+	/// that match the search. Any other event cancels applying to the matches.
+	fn handle_matches_action_input(&mut self, event: Event) -> Option<Results> {
+		if !mem::take(&mut self.apply_to_matches) {
+			return None;
+		}
+
+		let action = match event {
+			Event::Standard(StandardEvent::ActionDrop) => Action::Drop,
+			Event::Standard(StandardEvent::ActionEdit) => Action::Edit,
+			Event::Standard(StandardEvent::ActionFixup) => Action::Fixup,
+			Event::Standard(StandardEvent::ActionPick) => Action::Pick,
+			Event::Standard(StandardEvent::ActionRevert) => Action::Revert,
+			Event::Standard(StandardEvent::ActionReword) => Action::Reword,
+			Event::Standard(StandardEvent::ActionSquash) => Action::Squash,
+			_ => return None,
+		};
+		self.set_search_matches_action(action);
+		Some(Results::new())
+	}
+
 	fn handle_normal_mode_event(&mut self, event: Event, view_state: &crate::view::State) -> Results {
+		if let Some(results) = self.handle_matches_action_input(event) {
+			return results;
+		}
+
+		if self.todo_file.lock().is_done_line_selected() || self.is_selected_line_hidden() {
+			return self.handle_done_line_input(event, view_state);
+		}
+
 		if let Some(results) = self.handle_common_list_input(event, view_state) {
 			results
 		}
 		else {
 			let mut results = Results::new();
 			if let Event::Standard(standard_event) = event {
+				// a collapsed section or fixup group contains many lines, so only allow events that can change all the
+				// lines
+				if self.is_collapsed_row_selected()
+					&& matches!(
+						standard_event,
+						StandardEvent::ActionBreak
+							| StandardEvent::Edit
+							| StandardEvent::FixupKeepMessage
+							| StandardEvent::FixupKeepMessageWithEditor
+							| StandardEvent::ShowCommit
+					) {
+					return results;
+				}
 				match standard_event {
 					StandardEvent::ActionBreak => self.action_break(),
 					StandardEvent::Edit => self.edit(),
 					StandardEvent::InsertLine => self.insert_line(&mut results),
+					StandardEvent::InsertUpdateRefs => self.insert_update_refs(),
 					StandardEvent::ShowCommit => self.show_commit(&mut results),
 					StandardEvent::FixupKeepMessage => self.toggle_option("-C"),
 					StandardEvent::FixupKeepMessageWithEditor => self.toggle_option("-c"),
@@ -712,23 +1738,107 @@ impl List {
 	}
 
 	fn handle_visual_mode_input(&mut self, event: Event, view_state: &crate::view::State) -> Results {
-		self.handle_common_list_input(event, view_state)
-			.unwrap_or_else(Results::new)
+		if let Some(results) = self.handle_matches_action_input(event) {
+			return results;
+		}
+
+		if let Some(results) = self.handle_common_list_input(event, view_state) {
+			return results;
+		}
+
+		match event {
+			Event::Standard(StandardEvent::SortByAuthorDate) => self.sort_selected_by_date(false),
+			Event::Standard(StandardEvent::SortByCommitDate) => self.sort_selected_by_date(true),
+			Event::Standard(StandardEvent::ReverseSelection) => {
+				self.reorder_selected(|todo_file, indexes| todo_file.reverse_lines(indexes));
+			},
+			Event::Standard(StandardEvent::RestoreOrder) => {
+				self.reorder_selected(|todo_file, indexes| todo_file.restore_lines_order(indexes));
+			},
+			_ => {},
+		}
+		Results::new()
 	}
 
-	fn handle_edit_mode_input(&mut self, event: Event) -> Results {
-		self.edit.handle_event(event);
-		if self.edit.is_finished() {
-			let mut todo_file = self.todo_file.lock();
-			let selected_index = todo_file.get_selected_line_index();
-			todo_file.update_range(
-				selected_index,
-				selected_index,
-				&EditContext::new().content(self.edit.get_content()),
-			);
+	/// The input options of the mode that replaces the list, when a mode is active.
+	fn mode_input_options(&self) -> Option<&InputOptions> {
+		match self.state {
+			ListState::Normal | ListState::Visual => None,
+			ListState::Edit => Some(&*EDIT_INPUT_OPTIONS),
+			ListState::ExecLines => Some(self.exec_lines.input_options()),
+			ListState::Reword => Some(&*TEXT_EDITOR_INPUT_OPTIONS),
+			ListState::RewriteMetadata => Some(self.rewrite_metadata.input_options()),
+			ListState::UndoHistory => Some(&UNDO_HISTORY_INPUT_OPTIONS),
+			ListState::UpdateRefs => Some(&*CHOICE_INPUT_OPTIONS),
+		}
+	}
+
+	/// Handle the input of the mode that replaces the list, returning to the list once the mode is finished.
+	fn handle_mode_input(&mut self, event: Event, view_state: &crate::view::State) -> Results {
+		let finished = match self.state {
+			ListState::Normal | ListState::Visual => false,
+			ListState::Edit => {
+				self.edit_line.handle_event(event, &mut self.todo_file.lock());
+				self.edit_line.is_finished()
+			},
+			ListState::ExecLines => {
+				self.exec_lines
+					.handle_event(event, view_state, &mut self.todo_file.lock());
+				self.exec_lines.is_finished()
+			},
+			ListState::Reword => {
+				self.reword.handle_event(event, &mut self.todo_file.lock());
+				self.reword.is_finished()
+			},
+			ListState::RewriteMetadata => {
+				self.rewrite_metadata
+					.handle_event(event, view_state, &mut self.todo_file.lock());
+				self.rewrite_metadata.is_finished()
+			},
+			ListState::UndoHistory => {
+				self.undo_history.handle_event(event);
+				if self.undo_history.is_finished() {
+					self.restore_undo_history();
+				}
+				self.undo_history.is_finished()
+			},
+			ListState::UpdateRefs => {
+				self.update_refs.handle_event(event, view_state);
+				if self.update_refs.is_finished() {
+					let update_refs = self.update_refs.take_chosen_update_refs();
+					self.add_update_refs(update_refs);
+				}
+				self.update_refs.is_finished()
+			},
+		};
+
+		let mut results = Results::new();
+		if finished {
 			self.visual_index_start = None;
 			self.state = ListState::Normal;
+			self.save_history(&mut results);
+		}
+		results
+	}
+
+	/// Write the inputs added to the history, reporting a failure without leaving the list.
+	fn save_history(&self, results: &mut Results) {
+		if let Err(err) = self.history.save() {
+			results.error_with_return(Error::from(err).context("Unable to write input history"), State::List);
+		}
+	}
+
+	fn restore_undo_history(&mut self) {
+		let Some(position) = self.undo_history.restore_position()
+		else {
+			return;
+		};
+		self.marked_lines.clear();
+		let mut todo_file = self.todo_file.lock();
+		if todo_file.restore_history(position) {
+			let selected_index = todo_file.get_selected_line_index();
+			drop(todo_file);
+			_ = self.update_cursor(CursorUpdate::Set(selected_index));
 		}
-		Results::new()
 	}
 }
@@ -0,0 +1,210 @@
+use crate::{
+	git::Reference,
+	todo_file::{Action, Line, TodoFile},
+};
+
+/// Tracks the local branches that point to commits in the rebase, for managing `update-ref` lines.
+#[derive(Debug, Default)]
+pub(crate) struct Branches {
+	references: Vec<Reference>,
+}
+
+impl Branches {
+	pub(crate) const fn new(references: Vec<Reference>) -> Self {
+		Self { references }
+	}
+
+	/// Get the full names of the branches.
+	pub(crate) fn names(&self) -> Vec<String> {
+		self.references
+			.iter()
+			.map(|reference| String::from(reference.name()))
+			.collect()
+	}
+
+	/// Does the line create a commit that is the commit referenced by the line hash.
+	const fn is_commit_line(line: &Line) -> bool {
+		matches!(
+			*line.get_action(),
+			Action::Edit | Action::Fixup | Action::Pick | Action::Reword | Action::Squash
+		)
+	}
+
+	fn is_hash_match(reference: &Reference, hash: &str) -> bool {
+		!hash.is_empty() && reference.hash().starts_with(hash)
+	}
+
+	/// Get the branches that point to the commit of the line, including dropped commits.
+	pub(crate) fn for_line(&self, line: &Line) -> Vec<&Reference> {
+		if !Self::is_commit_line(line) && *line.get_action() != Action::Drop {
+			return vec![];
+		}
+		self.references
+			.iter()
+			.filter(|reference| Self::is_hash_match(reference, line.get_hash()))
+			.collect()
+	}
+
+	/// Find the branches that do not have an `update-ref` line, returning the index of the line after which the new
+	/// `update-ref` line should be added, and the name of the branch, in the order of the lines.
+	pub(crate) fn missing_update_refs(&self, todo_file: &TodoFile) -> Vec<(usize, String)> {
+		let mut missing = vec![];
+		for (index, line) in todo_file.lines_iter().enumerate() {
+			if !Self::is_commit_line(line) {
+				continue;
+			}
+			for reference in self.for_line(line) {
+				let has_update_ref = todo_file
+					.lines_iter()
+					.any(|l| *l.get_action() == Action::UpdateRef && l.get_content() == reference.name());
+				if !has_update_ref {
+					missing.push((index, String::from(reference.name())));
+				}
+			}
+		}
+		missing
+	}
+
+	/// Get the commit hash of the branch, if the `update-ref` line at the index will result in the branch pointing to
+	/// a different commit than it does now. This happens when the commit was dropped, removed or moved away from the
+	/// `update-ref` line.
+	pub(crate) fn unexpected_update_ref_target(&self, todo_file: &TodoFile, index: usize) -> Option<&str> {
+		let line = todo_file.get_line(index)?;
+		if *line.get_action() != Action::UpdateRef {
+			return None;
+		}
+		let hash = self
+			.references
+			.iter()
+			.find(|reference| reference.name() == line.get_content())?
+			.hash();
+
+		let preceding_lines = todo_file
+			.done_lines_iter()
+			.chain(todo_file.lines_iter().take(index))
+			.collect::<Vec<&Line>>();
+		for preceding in preceding_lines.into_iter().rev() {
+			match *preceding.get_action() {
+				Action::Edit | Action::Pick | Action::Reword => {
+					return (!hash.starts_with(preceding.get_hash()) || preceding.get_hash().is_empty()).then_some(hash);
+				},
+				// the commit is folded into an earlier commit, which is where the branch will point
+				Action::Fixup | Action::Squash => {
+					if !preceding.get_hash().is_empty() && hash.starts_with(preceding.get_hash()) {
+						return None;
+					}
+				},
+				Action::Break | Action::Drop | Action::Exec | Action::Label | Action::Noop | Action::UpdateRef => {},
+				Action::Merge | Action::Reset | Action::Revert => return Some(hash),
+			}
+		}
+		Some(hash)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use rstest::rstest;
+
+	use super::*;
+	use crate::{git::ReferenceKind, test_helpers::with_todo_file};
+
+	fn create_branches() -> Branches {
+		Branches::new(vec![
+			Reference::new(
+				String::from("aaaaaaaa"),
+				String::from("refs/heads/feature-a"),
+				String::from("feature-a"),
+				ReferenceKind::Branch,
+			),
+			Reference::new(
+				String::from("bbbbbbbb"),
+				String::from("refs/heads/feature-b"),
+				String::from("feature-b"),
+				ReferenceKind::Branch,
+			),
+		])
+	}
+
+	#[test]
+	fn names() {
+		assert_eq!(create_branches().names(), vec!["refs/heads/feature-a", "refs/heads/feature-b"]);
+	}
+
+	#[test]
+	fn for_line() {
+		let branches = create_branches();
+		let names: Vec<&str> = branches
+			.for_line(&Line::new_pick("aaa"))
+			.into_iter()
+			.map(Reference::shortname)
+			.collect();
+		assert_eq!(names, vec!["feature-a"]);
+	}
+
+	#[test]
+	fn for_line_non_commit_line() {
+		assert!(create_branches().for_line(&Line::new_exec("aaa")).is_empty());
+	}
+
+	#[test]
+	fn missing_update_refs() {
+		with_todo_file(
+			&["pick aaa c1", "update-ref refs/heads/feature-a", "pick bbb c2", "pick ccc c3"],
+			|context| {
+				assert_eq!(create_branches().missing_update_refs(context.todo_file()), vec![(
+					2,
+					String::from("refs/heads/feature-b")
+				)]);
+			},
+		);
+	}
+
+	#[test]
+	fn missing_update_refs_in_line_order() {
+		with_todo_file(&["pick aaa c1", "pick bbb c2"], |context| {
+			assert_eq!(create_branches().missing_update_refs(context.todo_file()), vec![
+				(0, String::from("refs/heads/feature-a")),
+				(1, String::from("refs/heads/feature-b"))
+			]);
+		});
+	}
+
+	#[test]
+	fn missing_update_refs_skips_dropped() {
+		with_todo_file(&["drop aaa c1", "pick ccc c2"], |context| {
+			assert!(create_branches().missing_update_refs(context.todo_file()).is_empty());
+		});
+	}
+
+	#[rstest]
+	#[case::expected(&["pick aaa c1", "update-ref refs/heads/feature-a"], 1, None)]
+	#[case::after_fixup(&["pick aaa c1", "fixup ccc c2", "update-ref refs/heads/feature-a"], 2, None)]
+	#[case::after_fixup_target(&["pick ccc c1", "fixup aaa c2", "update-ref refs/heads/feature-a"], 2, None)]
+	#[case::after_exec(&["pick aaa c1", "exec make", "update-ref refs/heads/feature-a"], 2, None)]
+	#[case::unknown_branch(&["pick ccc c1", "update-ref refs/heads/unknown"], 1, None)]
+	#[case::not_update_ref(&["pick ccc c1"], 0, None)]
+	#[case::moved(&["pick ccc c1", "update-ref refs/heads/feature-a", "pick aaa c2"], 1, Some("aaaaaaaa"))]
+	#[case::dropped(&["pick ccc c1", "drop aaa c2", "update-ref refs/heads/feature-a"], 2, Some("aaaaaaaa"))]
+	#[case::removed(&["update-ref refs/heads/feature-a", "pick ccc c1"], 0, Some("aaaaaaaa"))]
+	#[case::after_reset(&["pick aaa c1", "reset onto", "update-ref refs/heads/feature-a"], 2, Some("aaaaaaaa"))]
+	fn unexpected_update_ref_target(#[case] lines: &[&str], #[case] index: usize, #[case] expected: Option<&str>) {
+		with_todo_file(lines, |context| {
+			assert_eq!(
+				create_branches().unexpected_update_ref_target(context.todo_file(), index),
+				expected
+			);
+		});
+	}
+
+	#[test]
+	fn unexpected_update_ref_target_with_done_lines() {
+		with_todo_file(&["update-ref refs/heads/feature-a", "pick ccc c1"], |mut context| {
+			context.todo_file_mut().set_done_lines(vec![Line::parse("pick aaa c1").unwrap()]);
+			assert_eq!(
+				create_branches().unexpected_update_ref_target(context.todo_file(), 0),
+				None
+			);
+		});
+	}
+}
@@ -0,0 +1,84 @@
+use super::branches::Branches;
+use crate::{
+	components::{edit::Edit, HistoryKind, InputHistory},
+	display::DisplayColor,
+	input::Event,
+	todo_file::{Action, EditContext, TodoFile},
+	view::{LineSegment, ViewData, ViewLine},
+};
+
+/// Edits the content of the selected line, like the command of an `exec` line or the name of a `label` line.
+pub(crate) struct EditLine {
+	edit: Edit,
+	history: InputHistory,
+}
+
+impl EditLine {
+	pub(crate) fn new(history: InputHistory) -> Self {
+		let mut edit = Edit::new();
+		edit.set_history(history.clone());
+		Self { edit, history }
+	}
+
+	/// Start editing the content of the selected line, returning `false` when the content of the line can not be
+	/// edited.
+	pub(crate) fn start(&mut self, todo_file: &TodoFile, branches: &Branches) -> bool {
+		let Some(selected_line) = todo_file.get_selected_line()
+		else {
+			return false;
+		};
+		if !selected_line.is_editable() {
+			return false;
+		}
+
+		self.edit.reset();
+		self.edit.set_content(selected_line.get_content());
+		self.edit.set_label(format!("{} ", selected_line.get_action()).as_str());
+		match *selected_line.get_action() {
+			Action::Exec => self.edit.set_history_kind(HistoryKind::Exec),
+			Action::Label | Action::Reset => self.edit.set_history_kind(HistoryKind::Label),
+			_ => {},
+		}
+		self.edit.set_completions(match *selected_line.get_action() {
+			Action::Reset | Action::Merge => todo_file.labels_before(todo_file.get_selected_line_index()),
+			Action::UpdateRef => branches.names(),
+			Action::Exec => self.history.entries(HistoryKind::Exec).into_iter().rev().collect(),
+			_ => vec![],
+		});
+		true
+	}
+
+	pub(crate) const fn is_finished(&self) -> bool {
+		self.edit.is_finished()
+	}
+
+	pub(crate) fn handle_event(&mut self, event: Event, todo_file: &mut TodoFile) {
+		self.edit.handle_event(event);
+		if self.edit.is_finished() {
+			let selected_index = todo_file.get_selected_line_index();
+			todo_file.update_range(
+				selected_index,
+				selected_index,
+				&EditContext::new().content(self.edit.get_content()),
+			);
+		}
+	}
+
+	pub(crate) fn build_view_data(&mut self, todo_file: &TodoFile) -> &ViewData {
+		if let Some(selected_line) = todo_file.get_selected_line() {
+			if selected_line.is_editable() {
+				return self.edit.build_view_data(
+					|updater| {
+						updater.push_leading_line(ViewLine::from(LineSegment::new_with_color(
+							format!("Modifying line: {}", selected_line.to_text()).as_str(),
+							DisplayColor::IndicatorColor,
+						)));
+						updater.push_leading_line(ViewLine::new_empty_line());
+					},
+					|_| {},
+				);
+			}
+		}
+		self.edit.get_view_data()
+	}
+}
@@ -0,0 +1,270 @@
+use std::cmp::{max, min};
+
+use crate::{
+	components::{
+		choice::{Choice, INPUT_OPTIONS as CHOICE_INPUT_OPTIONS},
+		edit::Edit,
+		HistoryKind,
+		InputHistory,
+	},
+	display::DisplayColor,
+	input::{Event, InputOptions},
+	todo_file::{Action, Line, TodoFile},
+	view::{LineSegment, LineSegmentOptions, ViewData, ViewLine},
+};
+
+/// The commits that `exec` lines are inserted after.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum ExecTarget {
+	/// Every commit.
+	Commits,
+	/// The last commit of each branch that is updated by an `update-ref` line.
+	Branches,
+	/// Every commit within the range of lines.
+	Selection(usize, usize),
+}
+
+/// Does the line create a commit.
+const fn is_commit_line(line: &Line) -> bool {
+	matches!(
+		*line.get_action(),
+		Action::Edit | Action::Fixup | Action::Merge | Action::Pick | Action::Revert | Action::Reword | Action::Squash
+	)
+}
+
+/// Find the indexes of the lines to insert an `exec` line with the command after. Like `git rebase --exec`, a commit
+/// followed by `fixup` or `squash` lines is only followed by an `exec` line after the last of those lines. Commits that
+/// are already followed by an `exec` line with the command are skipped, so inserting again does not add duplicates.
+pub(crate) fn get_exec_line_indexes(todo_file: &TodoFile, target: ExecTarget, command: &str) -> Vec<usize> {
+	let lines: Vec<&Line> = todo_file.lines_iter().collect();
+	let candidates: Vec<usize> = match target {
+		ExecTarget::Commits => (0..lines.len()).collect(),
+		ExecTarget::Selection(start, end) => (min(start, end)..=max(start, end)).collect(),
+		ExecTarget::Branches => {
+			lines
+				.iter()
+				.enumerate()
+				.filter(|&(_, line)| *line.get_action() == Action::UpdateRef)
+				.filter_map(|(index, _)| (0..index).rev().find(|&i| is_commit_line(lines[i])))
+				.collect()
+		},
+	};
+
+	let mut indexes: Vec<usize> = candidates
+		.into_iter()
+		.filter(|&index| {
+			lines.get(index).is_some_and(|line| is_commit_line(line))
+				&& !lines.get(index + 1).is_some_and(|next| {
+					matches!(*next.get_action(), Action::Fixup | Action::Squash)
+						|| (*next.get_action() == Action::Exec && next.get_content() == command)
+				})
+		})
+		.collect();
+	indexes.dedup();
+	indexes
+}
+
+/// Inserts `exec` lines, first choosing the commits to insert the lines after when there is a choice, and then editing
+/// the command of the lines.
+pub(crate) struct ExecLines {
+	choices: Choice<Option<ExecTarget>>,
+	edit: Edit,
+	finished: bool,
+	history: InputHistory,
+	target: Option<ExecTarget>,
+	templates: Vec<(String, String)>,
+}
+
+impl ExecLines {
+	pub(crate) fn new(templates: Vec<(String, String)>, history: InputHistory) -> Self {
+		let mut choices = Choice::new(vec![
+			(Some(ExecTarget::Commits), 'c', String::from("After every commit")),
+			(Some(ExecTarget::Branches), 'b', String::from("After the last commit of each branch")),
+			(None, 'q', String::from("Cancel")),
+		]);
+		choices.set_prompt(vec![ViewLine::from(LineSegment::new_with_color(
+			"Select where to insert the exec lines:",
+			DisplayColor::IndicatorColor,
+		))]);
+		let mut edit = Edit::new();
+		edit.set_history(history.clone());
+
+		Self {
+			choices,
+			edit,
+			finished: false,
+			history,
+			target: None,
+			templates,
+		}
+	}
+
+	/// Start inserting exec lines after the commits of the target, or start choosing the target when there is none.
+	pub(crate) fn start(&mut self, target: Option<ExecTarget>) {
+		self.finished = false;
+		self.target = None;
+		if let Some(exec_target) = target {
+			self.edit_command(exec_target);
+		}
+	}
+
+	fn edit_command(&mut self, target: ExecTarget) {
+		self.target = Some(target);
+		self.edit.reset();
+		self.edit.set_label("exec ");
+		self.edit.set_history_kind(HistoryKind::Exec);
+		let mut completions: Vec<String> = self.templates.iter().map(|(_, command)| command.clone()).collect();
+		for command in self.history.entries(HistoryKind::Exec).into_iter().rev() {
+			if !completions.contains(&command) {
+				completions.push(command);
+			}
+		}
+		self.edit.set_completions(completions);
+	}
+
+	pub(crate) const fn is_finished(&self) -> bool {
+		self.finished
+	}
+
+	pub(crate) fn input_options(&self) -> &InputOptions {
+		if self.target.is_some() {
+			self.edit.input_options()
+		}
+		else {
+			&CHOICE_INPUT_OPTIONS
+		}
+	}
+
+	pub(crate) fn handle_event(&mut self, event: Event, view_state: &crate::view::State, todo_file: &mut TodoFile) {
+		let Some(target) = self.target
+		else {
+			if let Some(choice) = self.choices.handle_event(event, view_state).copied() {
+				if let Some(exec_target) = choice {
+					self.edit_command(exec_target);
+				}
+				else {
+					self.finished = true;
+				}
+			}
+			return;
+		};
+
+		self.edit.handle_event(event);
+		if self.edit.is_finished() {
+			let content = self.edit.get_content();
+			let command = self
+				.templates
+				.iter()
+				.find(|&(name, _)| name == content)
+				.map_or(content, |(_, template_command)| template_command.as_str());
+			if !command.is_empty() {
+				let indexes = get_exec_line_indexes(todo_file, target, command);
+				let selected_index = todo_file.get_selected_line_index();
+				let inserted_before_selected = indexes.iter().filter(|&&index| index < selected_index).count();
+				if todo_file.add_line_after_lines(&indexes, &Line::new_exec(command)) > 0 {
+					_ = todo_file.set_selected_line_index(selected_index + inserted_before_selected);
+				}
+			}
+			self.finished = true;
+		}
+	}
+
+	pub(crate) fn build_view_data(&mut self) -> &ViewData {
+		let Some(target) = self.target
+		else {
+			return self.choices.get_view_data();
+		};
+
+		let prompt = match target {
+			ExecTarget::Commits => "Enter the command to run after every commit.",
+			ExecTarget::Branches => "Enter the command to run after the last commit of each branch.",
+			ExecTarget::Selection(..) => "Enter the command to run after every selected commit.",
+		};
+		let templates = &self.templates;
+		self.edit.build_view_data(
+			|updater| {
+				updater.push_leading_line(ViewLine::from(LineSegment::new_with_color(
+					format!("{prompt} Empty content cancels the insertion.").as_str(),
+					DisplayColor::IndicatorColor,
+				)));
+				if !templates.is_empty() {
+					updater.push_leading_line(ViewLine::from(LineSegment::new_with_color(
+						"Enter the name of a template to use its command:",
+						DisplayColor::IndicatorColor,
+					)));
+					for (name, command) in templates {
+						updater.push_leading_line(ViewLine::from(vec![
+							LineSegment::new(format!("  {name}: ").as_str()),
+							LineSegment::new_with_color_and_style(
+								command,
+								DisplayColor::Normal,
+								LineSegmentOptions::DIMMED,
+							),
+						]));
+					}
+				}
+				updater.push_leading_line(ViewLine::new_empty_line());
+			},
+			|_| {},
+		)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use rstest::rstest;
+
+	use super::*;
+	use crate::test_helpers::with_todo_file;
+
+	#[rstest]
+	#[case::commits(
+		&["pick aaa c1", "exec make", "reword bbb c2", "break", "edit ccc c3"],
+		ExecTarget::Commits,
+		&[0, 2, 4]
+	)]
+	#[case::commits_after_fixups(
+		&["pick aaa c1", "fixup bbb c2", "squash ccc c3", "pick ddd c4"],
+		ExecTarget::Commits,
+		&[2, 3]
+	)]
+	#[case::commits_skip_existing_exec(
+		&["pick aaa c1", "exec cargo test", "pick bbb c2"],
+		ExecTarget::Commits,
+		&[2]
+	)]
+	#[case::commits_merge(
+		&["label onto", "merge -C aaa feature", "reset onto"],
+		ExecTarget::Commits,
+		&[1]
+	)]
+	#[case::selection(
+		&["pick aaa c1", "pick bbb c2", "exec make", "pick ccc c3", "pick ddd c4"],
+		ExecTarget::Selection(3, 1),
+		&[1, 3]
+	)]
+	#[case::branches(
+		&[
+			"pick aaa c1",
+			"pick bbb c2",
+			"update-ref refs/heads/a",
+			"update-ref refs/heads/b",
+			"pick ccc c3",
+			"fixup ddd c4",
+			"label x",
+			"update-ref refs/heads/c",
+			"pick eee c5",
+		],
+		ExecTarget::Branches,
+		&[1, 5]
+	)]
+	#[case::branches_without_update_refs(&["pick aaa c1"], ExecTarget::Branches, &[])]
+	fn exec_line_indexes(#[case] lines: &[&str], #[case] target: ExecTarget, #[case] expected: &[usize]) {
+		with_todo_file(lines, |context| {
+			assert_eq!(
+				get_exec_line_indexes(context.todo_file(), target, "cargo test"),
+				expected.to_vec()
+			);
+		});
+	}
+}
@@ -0,0 +1,99 @@
+use std::{
+	cmp::{max, min},
+	collections::HashSet,
+};
+
+use crate::{
+	modules::list::search::{Query, Search},
+	todo_file::{Line, TodoFile},
+};
+
+/// A filter that limits the todo lines shown in the list to the lines that match a search term. The fields of the
+/// lines are matched directly, while the matches of the commits of the lines are taken from the background search of
+/// the same term, so the commits are never loaded from the repository while rendering.
+#[derive(Debug, Clone)]
+pub(crate) struct Filter {
+	commit_matches: HashSet<String>,
+	term: String,
+	query: Query,
+}
+
+impl Filter {
+	pub(crate) fn new(term: &str) -> Self {
+		Self {
+			commit_matches: HashSet::new(),
+			term: String::from(term),
+			query: Query::new(term),
+		}
+	}
+
+	/// The search term of the filter, shown in the filter indicator.
+	pub(crate) fn term(&self) -> &str {
+		self.term.as_str()
+	}
+
+	/// Add the commits found by the search to the matched commits, when the search is for the term of the filter.
+	pub(crate) fn update_commit_matches(&mut self, search: &Search) {
+		self.commit_matches.extend(search.commit_matches(self.term.as_str()));
+	}
+
+	/// Does the line, or the commit of the line, match the filter.
+	pub(crate) fn is_line_match(&self, line: &Line) -> bool {
+		self.query.is_hash_match(line)
+			|| self.query.is_action_match(line)
+			|| self.query.is_content_match(line)
+			|| (!line.get_hash().is_empty() && self.commit_matches.contains(line.get_hash()))
+	}
+
+	/// The indexes of the lines that match the filter.
+	pub(crate) fn rows(&self, todo_file: &TodoFile) -> Vec<usize> {
+		todo_file
+			.lines_iter()
+			.enumerate()
+			.filter(|&(_, line)| self.is_line_match(line))
+			.map(|(index, _)| index)
+			.collect()
+	}
+
+	/// Get the indexes of the lines in the range that match the filter.
+	pub(crate) fn rows_in_range(&self, todo_file: &TodoFile, start_index: usize, end_index: usize) -> Vec<usize> {
+		let range = min(start_index, end_index)..=max(start_index, end_index);
+		self.rows(todo_file)
+			.into_iter()
+			.filter(|index| range.contains(index))
+			.collect()
+	}
+
+	/// Move the lines in the range above the previous line that matches the filter, skipping over the hidden lines,
+	/// returning the distance the lines were moved.
+	pub(crate) fn swap_range_up(
+		&self,
+		todo_file: &mut TodoFile,
+		start_index: usize,
+		end_index: usize,
+	) -> Option<usize> {
+		let start = min(start_index, end_index);
+		let end = max(start_index, end_index);
+		let destination_index = self.rows(todo_file).into_iter().rev().find(|&index| index < start)?;
+
+		todo_file
+			.move_range(start, end, destination_index)
+			.then_some(start - destination_index)
+	}
+
+	/// Move the lines in the range below the next line that matches the filter, skipping over the hidden lines,
+	/// returning the distance the lines were moved.
+	pub(crate) fn swap_range_down(
+		&self,
+		todo_file: &mut TodoFile,
+		start_index: usize,
+		end_index: usize,
+	) -> Option<usize> {
+		let start = min(start_index, end_index);
+		let end = max(start_index, end_index);
+		let next_index = self.rows(todo_file).into_iter().find(|&index| index > end)?;
+		let distance = next_index - end;
+
+		todo_file.move_range(start, end, start + distance).then_some(distance)
+	}
+}
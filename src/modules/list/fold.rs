@@ -0,0 +1,224 @@
+use std::collections::HashSet;
+
+use crate::todo_file::{Action, TodoFile};
+
+/// A commit followed by the `fixup` and `squash` lines that are folded into it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FixupGroup {
+	start: usize,
+	end: usize,
+	hash: String,
+}
+
+impl FixupGroup {
+	/// The index of the commit line of the group.
+	pub(crate) const fn start(&self) -> usize {
+		self.start
+	}
+
+	/// The index of the last `fixup` or `squash` line of the group.
+	pub(crate) const fn end(&self) -> usize {
+		self.end
+	}
+
+	/// The number of `fixup` and `squash` lines in the group.
+	pub(crate) const fn fixups(&self) -> usize {
+		self.end - self.start
+	}
+
+	pub(crate) fn contains(&self, index: usize) -> bool {
+		(self.start..=self.end).contains(&index)
+	}
+
+	/// A key that identifies the group, that remains the same when the group is moved.
+	pub(crate) fn key(&self) -> &str {
+		self.hash.as_str()
+	}
+}
+
+/// Find each commit that is directly followed by one or more `fixup` or `squash` lines.
+pub(crate) fn get_fixup_groups(todo_file: &TodoFile) -> Vec<FixupGroup> {
+	let mut groups: Vec<FixupGroup> = vec![];
+	let mut target = None;
+
+	for (index, line) in todo_file.lines_iter().enumerate() {
+		if matches!(line.get_action(), Action::Fixup | Action::Squash) {
+			if let Some((start, hash)) = target.as_ref() {
+				match groups.last_mut() {
+					Some(group) if group.start == *start => group.end = index,
+					_ => {
+						groups.push(FixupGroup {
+							start: *start,
+							end: index,
+							hash: String::clone(hash),
+						});
+					},
+				}
+			}
+			continue;
+		}
+		target = line.has_reference().then(|| (index, String::from(line.get_hash())));
+	}
+	groups
+}
+
+/// The fixup groups that are expanded in the fold view, where all the other fixup groups are collapsed into a single
+/// row.
+#[derive(Debug)]
+pub(crate) struct Fold {
+	expanded_groups: HashSet<String>,
+}
+
+impl Fold {
+	pub(crate) fn new() -> Self {
+		Self {
+			expanded_groups: HashSet::new(),
+		}
+	}
+
+	/// The index of the first line of each row of the fold view.
+	pub(crate) fn rows(&self, todo_file: &TodoFile) -> Vec<usize> {
+		let groups = self.collapsed_groups(todo_file);
+		(0..todo_file.lines_iter().len())
+			.filter(|&index| {
+				!groups
+					.iter()
+					.any(|group| group.contains(index) && group.start() != index)
+			})
+			.collect()
+	}
+
+	/// The fixup groups that are collapsed into a single row.
+	pub(crate) fn collapsed_groups(&self, todo_file: &TodoFile) -> Vec<FixupGroup> {
+		get_fixup_groups(todo_file)
+			.into_iter()
+			.filter(|group| !self.expanded_groups.contains(group.key()))
+			.collect()
+	}
+
+	/// Get the fixup group that contains the selected line, when it is collapsed.
+	pub(crate) fn selected_collapsed_group(&self, todo_file: &TodoFile) -> Option<FixupGroup> {
+		let selected_index = todo_file.get_selected_line_index();
+		self.collapsed_groups(todo_file)
+			.into_iter()
+			.find(|group| group.contains(selected_index))
+	}
+
+	/// Move the range of lines of the selected row up, returning the index the lines were moved to. A collapsed fixup
+	/// group is moved as a whole, and is moved above a collapsed fixup group in a single step, so the lines are never
+	/// moved into a collapsed group.
+	pub(crate) fn swap_range_up(
+		&self,
+		todo_file: &mut TodoFile,
+		start_index: usize,
+		end_index: usize,
+	) -> Option<usize> {
+		let previous_index = start_index.checked_sub(1)?;
+		let destination_index = self
+			.collapsed_groups(todo_file)
+			.into_iter()
+			.find(|group| group.contains(previous_index))
+			.map_or(previous_index, |group| group.start());
+
+		let moved = if destination_index == previous_index {
+			todo_file.swap_range_up(start_index, end_index)
+		}
+		else {
+			todo_file.move_range(start_index, end_index, destination_index)
+		};
+		moved.then_some(destination_index)
+	}
+
+	/// Move the range of lines of the selected row down, returning the index the lines were moved to. A collapsed fixup
+	/// group is moved as a whole, and is moved below a collapsed fixup group in a single step, so the lines are never
+	/// moved into a collapsed group.
+	pub(crate) fn swap_range_down(
+		&self,
+		todo_file: &mut TodoFile,
+		start_index: usize,
+		end_index: usize,
+	) -> Option<usize> {
+		let next_index = end_index + 1;
+		if next_index >= todo_file.lines_iter().len() {
+			return None;
+		}
+		let next_end_index = self
+			.collapsed_groups(todo_file)
+			.into_iter()
+			.find(|group| group.contains(next_index))
+			.map_or(next_index, |group| group.end());
+		let destination_index = start_index + (next_end_index - end_index);
+
+		let moved = if next_end_index == next_index {
+			todo_file.swap_range_down(start_index, end_index)
+		}
+		else {
+			todo_file.move_range(start_index, end_index, destination_index)
+		};
+		moved.then_some(destination_index)
+	}
+
+	/// Expand or collapse the fixup group that contains the selected line, returning `false` when the selected line is
+	/// not part of a fixup group.
+	pub(crate) fn toggle_group(&mut self, todo_file: &TodoFile) -> bool {
+		let selected_line_index = todo_file.get_selected_line_index();
+		let Some(group) = get_fixup_groups(todo_file)
+			.into_iter()
+			.find(|group| group.contains(selected_line_index))
+		else {
+			return false;
+		};
+
+		if !self.expanded_groups.remove(group.key()) {
+			_ = self.expanded_groups.insert(String::from(group.key()));
+		}
+		true
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test_helpers::with_todo_file;
+
+	#[test]
+	fn get_fixup_groups_without_fixups() {
+		with_todo_file(&["pick aaa c1", "pick bbb c2"], |context| {
+			assert!(get_fixup_groups(context.todo_file()).is_empty());
+		});
+	}
+
+	#[test]
+	fn get_fixup_groups_with_fixups() {
+		with_todo_file(
+			&[
+				"pick aaa c1",
+				"fixup bbb c2",
+				"squash ccc c3",
+				"reword ddd c4",
+				"exec make",
+				"fixup eee c5",
+				"edit fff c6",
+				"fixup ggg c7",
+			],
+			|context| {
+				let groups = get_fixup_groups(context.todo_file());
+				assert_eq!(groups.len(), 2);
+				assert_eq!((groups[0].start(), groups[0].end()), (0, 2));
+				assert_eq!(groups[0].fixups(), 2);
+				assert_eq!(groups[0].key(), "aaa");
+				assert!(groups[0].contains(1));
+				assert!(!groups[0].contains(3));
+				assert_eq!((groups[1].start(), groups[1].end()), (6, 7));
+				assert_eq!(groups[1].key(), "fff");
+			},
+		);
+	}
+
+	#[test]
+	fn get_fixup_groups_leading_fixup() {
+		with_todo_file(&["fixup aaa c1", "pick bbb c2"], |context| {
+			assert!(get_fixup_groups(context.todo_file()).is_empty());
+		});
+	}
+}
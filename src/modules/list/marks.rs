@@ -0,0 +1,35 @@
+use std::{collections::BTreeSet, mem};
+
+use crate::todo_file::TodoFile;
+
+/// Mark the lines at the indexes, or unmark them when they are all already marked.
+pub(crate) fn toggle_marks(marked_lines: &mut BTreeSet<usize>, indexes: Vec<usize>) {
+	if indexes.iter().all(|index| marked_lines.contains(index)) {
+		for index in &indexes {
+			_ = marked_lines.remove(index);
+		}
+	}
+	else {
+		marked_lines.extend(indexes);
+	}
+}
+
+/// Remove the marked lines, returning the index of the selected line, or the line that follows it when it was removed.
+pub(crate) fn remove_marked_lines(marked_lines: &mut BTreeSet<usize>, todo_file: &mut TodoFile) -> usize {
+	let indexes: Vec<usize> = mem::take(marked_lines).into_iter().collect();
+	let selected_index = todo_file.get_selected_line_index();
+	todo_file.remove_lines_at(&indexes);
+
+	let removed_above = indexes.iter().filter(|&&index| index < selected_index).count();
+	selected_index - removed_above
+}
+
+/// Move the marked lines after the selected line, keeping their order, returning the index of the first of the moved
+/// lines.
+pub(crate) fn gather_marked_lines(marked_lines: &mut BTreeSet<usize>, todo_file: &mut TodoFile) -> Option<usize> {
+	let indexes: Vec<usize> = mem::take(marked_lines).into_iter().collect();
+	let selected_index = todo_file.get_selected_line_index();
+	todo_file
+		.gather_lines(&indexes, selected_index)
+		.map(|(start_index, _)| start_index)
+}
@@ -0,0 +1,47 @@
+use std::{cmp::min, collections::HashMap};
+
+use crate::todo_file::TodoFile;
+
+/// The use of the key that follows the set or jump to named mark events.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum NamedMarkCommand {
+	Jump,
+	Set,
+}
+
+/// Lines marked with a single character name, to jump back to the line later. A line with a commit is found by the
+/// hash of the commit, so the mark follows the line when lines are moved, while any other line is found by its index.
+pub(crate) struct NamedMarks {
+	marks: HashMap<char, (String, usize)>,
+}
+
+impl NamedMarks {
+	pub(crate) fn new() -> Self {
+		Self { marks: HashMap::new() }
+	}
+
+	/// Name a mark for the selected line of the todo file, replacing any existing mark with the same name.
+	pub(crate) fn set(&mut self, name: char, todo_file: &TodoFile) {
+		let index = todo_file.get_selected_line_index();
+		let hash = todo_file
+			.get_line(index)
+			.filter(|line| line.has_reference())
+			.map(|line| String::from(line.get_hash()))
+			.unwrap_or_default();
+		let _previous_mark = self.marks.insert(name, (hash, index));
+	}
+
+	/// The index of the line of the mark, or `None` if there is no mark with the name.
+	pub(crate) fn find(&self, name: char, todo_file: &TodoFile) -> Option<usize> {
+		let (hash, index) = self.marks.get(&name)?;
+		if !hash.is_empty() {
+			if let Some(line_index) = todo_file
+				.lines_iter()
+				.position(|line| line.has_reference() && line.get_hash() == hash)
+			{
+				return Some(line_index);
+			}
+		}
+		Some(min(*index, todo_file.get_max_selected_line_index()))
+	}
+}
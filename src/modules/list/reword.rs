@@ -0,0 +1,70 @@
+use super::utils::get_message_hint_lines;
+use crate::{
+	components::text_editor::TextEditor,
+	display::DisplayColor,
+	input::Event,
+	todo_file::TodoFile,
+	view::{LineSegment, ViewData, ViewLine},
+};
+
+/// Composes a new message for the commit of a line, where an empty message keeps the original message.
+pub(crate) struct Reword {
+	index: usize,
+	text_editor: TextEditor,
+}
+
+impl Reword {
+	pub(crate) fn new() -> Self {
+		Self {
+			index: 0,
+			text_editor: TextEditor::new(),
+		}
+	}
+
+	/// Start composing the message of the commit of the line at the index, from the content.
+	pub(crate) fn start(&mut self, index: usize, content: &str) {
+		self.index = index;
+		self.text_editor.reset();
+		self.text_editor.set_content(content);
+	}
+
+	pub(crate) const fn is_finished(&self) -> bool {
+		self.text_editor.is_finished()
+	}
+
+	pub(crate) fn handle_event(&mut self, event: Event, todo_file: &mut TodoFile) {
+		self.text_editor.handle_event(event);
+		if self.text_editor.is_finished() && !self.text_editor.is_cancelled() {
+			let content = self.text_editor.get_content();
+			let message = content.trim_end();
+			_ = todo_file.set_message(self.index, (!message.is_empty()).then_some(message));
+		}
+	}
+
+	pub(crate) fn build_view_data(&mut self, todo_file: &TodoFile) -> &ViewData {
+		let hash = todo_file
+			.get_line(self.index)
+			.map(|line| String::from(line.get_hash()))
+			.unwrap_or_default();
+		let hint_lines = get_message_hint_lines(&self.text_editor.lines().collect::<Vec<&str>>());
+		self.text_editor.build_view_data(
+			|updater| {
+				updater.push_leading_line(ViewLine::from(LineSegment::new_with_color(
+					format!("Composing the message of commit {hash:.8}. An empty message keeps the original message.")
+						.as_str(),
+					DisplayColor::IndicatorColor,
+				)));
+				for hint_line in hint_lines {
+					updater.push_leading_line(hint_line);
+				}
+				updater.push_leading_line(ViewLine::new_empty_line());
+			},
+			|_| {},
+		)
+	}
+
+	#[cfg(test)]
+	pub(crate) fn get_content(&self) -> String {
+		self.text_editor.get_content()
+	}
+}
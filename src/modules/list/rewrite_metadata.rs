@@ -0,0 +1,292 @@
+use std::cmp::{max, min};
+
+use crate::{
+	components::{
+		choice::{Choice, INPUT_OPTIONS as CHOICE_INPUT_OPTIONS},
+		edit::Edit,
+	},
+	display::DisplayColor,
+	input::{Event, InputOptions},
+	todo_file::{Line, MetadataChanges, TodoFile},
+	view::{LineSegment, ViewData, ViewLine},
+};
+
+/// The common trailer tokens, offered as completions when adding a trailer.
+const TRAILER_TOKENS: [&str; 5] = [
+	"Signed-off-by: ",
+	"Reviewed-by: ",
+	"Acked-by: ",
+	"Tested-by: ",
+	"Co-authored-by: ",
+];
+
+/// The metadata of the selected commits to change.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum MetadataField {
+	/// The author of the commits.
+	Author,
+	/// The committer date of the commits.
+	CommitterDate,
+	/// A trailer added to the message of the commits.
+	Trailer,
+	/// Remove all the changes to the metadata of the commits.
+	Clear,
+}
+
+impl MetadataField {
+	/// The label of the field, shown before the value that is edited.
+	pub(crate) const fn label(self) -> &'static str {
+		match self {
+			Self::Author => "author: ",
+			Self::CommitterDate => "committer date: ",
+			Self::Trailer | Self::Clear => "trailer: ",
+		}
+	}
+
+	/// Get the value of the field, from the changes of a commit.
+	fn value(self, metadata_changes: &MetadataChanges) -> Option<&str> {
+		match self {
+			Self::Author => metadata_changes.author(),
+			Self::CommitterDate => metadata_changes.committer_date(),
+			Self::Trailer | Self::Clear => None,
+		}
+	}
+
+	/// Apply a value of the field to the changes of a commit, where an empty value removes the change.
+	pub(crate) fn apply(self, metadata_changes: &mut MetadataChanges, value: &str) {
+		let new_value = (!value.is_empty()).then_some(value);
+		match self {
+			Self::Author => metadata_changes.set_author(new_value),
+			Self::CommitterDate => metadata_changes.set_committer_date(new_value),
+			Self::Trailer => {
+				if let Some(trailer) = new_value {
+					metadata_changes.add_trailer(trailer);
+				}
+			},
+			Self::Clear => *metadata_changes = MetadataChanges::default(),
+		}
+	}
+}
+
+fn range_lines(todo_file: &TodoFile, start: usize, end: usize) -> impl Iterator<Item = &Line> {
+	todo_file
+		.lines_iter()
+		.skip(min(start, end))
+		.take(max(start, end) - min(start, end) + 1)
+}
+
+/// Get the value of the field that all the commits within the range of lines share, to start editing from.
+pub(crate) fn get_shared_value(todo_file: &TodoFile, start: usize, end: usize, field: MetadataField) -> String {
+	let mut values = range_lines(todo_file, start, end)
+		.filter(|line| line.has_reference())
+		.map(|line| field.value(line.metadata_changes()));
+	let first = values.next().flatten();
+	if values.all(|value| value == first) {
+		first.map(String::from).unwrap_or_default()
+	}
+	else {
+		String::new()
+	}
+}
+
+/// Get the completions for the field, with the values already used for any commit, and the common trailer tokens.
+pub(crate) fn get_completions(todo_file: &TodoFile, field: MetadataField) -> Vec<String> {
+	let mut completions: Vec<String> = vec![];
+	for line in todo_file.lines_iter() {
+		let metadata_changes = line.metadata_changes();
+		let values: Vec<&str> = if field == MetadataField::Trailer {
+			metadata_changes.trailers().iter().map(String::as_str).collect()
+		}
+		else {
+			field.value(metadata_changes).into_iter().collect()
+		};
+		for value in values {
+			if !completions.iter().any(|completion| completion == value) {
+				completions.push(String::from(value));
+			}
+		}
+	}
+	if field == MetadataField::Trailer {
+		completions.extend(TRAILER_TOKENS.iter().map(|&token| String::from(token)));
+	}
+	completions
+}
+
+/// Changes the author, committer date or trailers of the commits within a range of lines, first choosing the change,
+/// and then editing the value of the field.
+pub(crate) struct RewriteMetadata {
+	choices: Choice<Option<MetadataField>>,
+	edit: Edit,
+	field: Option<MetadataField>,
+	finished: bool,
+	range: (usize, usize),
+}
+
+impl RewriteMetadata {
+	pub(crate) fn new() -> Self {
+		let mut choices = Choice::new(vec![
+			(Some(MetadataField::Author), 'a', String::from("Set the author")),
+			(Some(MetadataField::CommitterDate), 'd', String::from("Set the committer date")),
+			(Some(MetadataField::Trailer), 't', String::from("Add a trailer")),
+			(Some(MetadataField::Clear), 'r', String::from("Remove all changes")),
+			(None, 'q', String::from("Cancel")),
+		]);
+		choices.set_prompt(vec![ViewLine::from(LineSegment::new_with_color(
+			"Select the change to the selected commits:",
+			DisplayColor::IndicatorColor,
+		))]);
+
+		Self {
+			choices,
+			edit: Edit::new(),
+			field: None,
+			finished: false,
+			range: (0, 0),
+		}
+	}
+
+	/// Start choosing the change to the commits within the range of lines.
+	pub(crate) fn start(&mut self, start: usize, end: usize) {
+		self.field = None;
+		self.finished = false;
+		self.range = (start, end);
+	}
+
+	pub(crate) const fn is_finished(&self) -> bool {
+		self.finished
+	}
+
+	pub(crate) fn input_options(&self) -> &InputOptions {
+		if self.field.is_some() {
+			self.edit.input_options()
+		}
+		else {
+			&CHOICE_INPUT_OPTIONS
+		}
+	}
+
+	pub(crate) fn handle_event(&mut self, event: Event, view_state: &crate::view::State, todo_file: &mut TodoFile) {
+		let (start, end) = self.range;
+		let Some(field) = self.field
+		else {
+			match self.choices.handle_event(event, view_state).copied() {
+				Some(Some(MetadataField::Clear)) => {
+					_ = todo_file.update_metadata_changes(start, end, |changes| {
+						MetadataField::Clear.apply(changes, "");
+					});
+					self.finished = true;
+				},
+				Some(Some(chosen_field)) => {
+					self.field = Some(chosen_field);
+					self.edit.reset();
+					self.edit.set_label(chosen_field.label());
+					self.edit
+						.set_content(get_shared_value(todo_file, start, end, chosen_field).as_str());
+					self.edit.set_completions(get_completions(todo_file, chosen_field));
+				},
+				Some(None) => self.finished = true,
+				None => {},
+			}
+			return;
+		};
+
+		self.edit.handle_event(event);
+		if self.edit.is_finished() {
+			let value = self.edit.get_content().trim();
+			// an empty trailer is not a change, so is not recorded in the history
+			if field != MetadataField::Trailer || !value.is_empty() {
+				_ = todo_file.update_metadata_changes(start, end, |changes| field.apply(changes, value));
+			}
+			self.finished = true;
+		}
+	}
+
+	pub(crate) fn build_view_data(&mut self) -> &ViewData {
+		let Some(field) = self.field
+		else {
+			return self.choices.get_view_data();
+		};
+
+		let prompt = match field {
+			MetadataField::Author => {
+				"Enter the author of the selected commits, as `Name <email>`. Empty content removes the change."
+			},
+			MetadataField::CommitterDate => {
+				"Enter the committer date of the selected commits. Empty content removes the change."
+			},
+			MetadataField::Trailer | MetadataField::Clear => {
+				"Enter a trailer to add to the selected commits, as `Token: value`. Empty content cancels."
+			},
+		};
+		self.edit.build_view_data(
+			|updater| {
+				updater.push_leading_line(ViewLine::from(LineSegment::new_with_color(
+					prompt,
+					DisplayColor::IndicatorColor,
+				)));
+				updater.push_leading_line(ViewLine::new_empty_line());
+			},
+			|_| {},
+		)
+	}
+
+	#[cfg(test)]
+	pub(crate) fn get_content(&self) -> &str {
+		self.edit.get_content()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test_helpers::with_todo_file;
+
+	#[test]
+	fn apply_and_clear() {
+		let mut metadata_changes = MetadataChanges::default();
+		MetadataField::Author.apply(&mut metadata_changes, "Jane <jane@example.com>");
+		MetadataField::CommitterDate.apply(&mut metadata_changes, "yesterday");
+		MetadataField::Trailer.apply(&mut metadata_changes, "Acked-by: John");
+		MetadataField::Trailer.apply(&mut metadata_changes, "");
+		assert_eq!(metadata_changes.author(), Some("Jane <jane@example.com>"));
+		assert_eq!(metadata_changes.committer_date(), Some("yesterday"));
+		assert_eq!(metadata_changes.trailers(), &[String::from("Acked-by: John")]);
+		MetadataField::Author.apply(&mut metadata_changes, "");
+		assert_eq!(metadata_changes.author(), None);
+		MetadataField::Clear.apply(&mut metadata_changes, "");
+		assert!(metadata_changes.is_empty());
+	}
+
+	#[test]
+	fn shared_value() {
+		with_todo_file(&["pick aaa c1", "exec make", "pick bbb c2", "pick ccc c3"], |mut context| {
+			_ = context
+				.todo_file_mut()
+				.update_metadata_changes(0, 2, |changes| changes.set_author(Some("Jane")));
+			assert_eq!(get_shared_value(context.todo_file(), 2, 0, MetadataField::Author), "Jane");
+			assert_eq!(get_shared_value(context.todo_file(), 0, 3, MetadataField::Author), "");
+			assert_eq!(get_shared_value(context.todo_file(), 0, 2, MetadataField::CommitterDate), "");
+		});
+	}
+
+	#[test]
+	fn completions() {
+		with_todo_file(&["pick aaa c1", "pick bbb c2"], |mut context| {
+			_ = context.todo_file_mut().update_metadata_changes(0, 1, |changes| {
+				changes.set_author(Some("Jane"));
+				changes.add_trailer("Reviewed-by: John");
+			});
+			assert_eq!(get_completions(context.todo_file(), MetadataField::Author), vec![
+				"Jane"
+			]);
+			assert_eq!(get_completions(context.todo_file(), MetadataField::Trailer), vec![
+				"Reviewed-by: John",
+				"Signed-off-by: ",
+				"Reviewed-by: ",
+				"Acked-by: ",
+				"Tested-by: ",
+				"Co-authored-by: ",
+			]);
+		});
+	}
+}
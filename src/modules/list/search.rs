@@ -1,4 +1,6 @@
 mod line_match;
+mod metadata_index;
+mod query;
 mod state;
 
 use std::{
@@ -11,10 +13,15 @@ use std::{
 
 use parking_lot::{Mutex, RwLock};
 
-pub(crate) use self::{line_match::LineMatch, state::State};
+pub(crate) use self::{
+	line_match::{LineMatch, MatchedFields},
+	metadata_index::MetadataIndex,
+	query::Query,
+	state::State,
+};
 use crate::{
 	search::{Interrupter, SearchResult, Searchable, Status},
-	todo_file::{Action, TodoFile},
+	todo_file::{Line, TodoFile},
 };
 
 const LOCK_DURATION: Duration = Duration::from_millis(100);
@@ -22,6 +29,7 @@ const LOCK_DURATION: Duration = Duration::from_millis(100);
 #[derive(Clone, Debug)]
 pub(crate) struct Search {
 	cursor: Arc<AtomicUsize>,
+	metadata_index: MetadataIndex,
 	state: Arc<RwLock<State>>,
 	todo_file: Arc<Mutex<TodoFile>>,
 }
@@ -32,57 +40,65 @@ impl Searchable for Search {
 	}
 
 	fn search(&mut self, interrupter: Interrupter, term: &str) -> SearchResult {
-		let Some(todo_file) = self.todo_file.try_lock_for(LOCK_DURATION)
+		let Some((version, line_count)) = self
+			.todo_file
+			.try_lock_for(LOCK_DURATION)
+			.map(|todo_file| (*todo_file.version(), todo_file.lines_iter().len()))
 		else {
 			return SearchResult::None;
 		};
-		let Some(mut state) = self.state.try_write_for(LOCK_DURATION)
-		else {
-			return SearchResult::None;
+		self.metadata_index.reserve(line_count);
+		let query = {
+			let Some(mut state) = self.state.try_write_for(LOCK_DURATION)
+			else {
+				return SearchResult::None;
+			};
+			if state.try_invalidate_search(&version, term) {
+				self.cursor.store(0, Ordering::Release);
+			}
+			state.set_status(Status::Active);
+			state.query().clone()
 		};
-		if state.try_invalidate_search(todo_file.version(), term) {
-			self.cursor.store(0, Ordering::Release);
-		}
+
 		let mut has_matches = false;
 		let mut complete = false;
-
-		state.set_status(Status::Active);
 		let mut cursor = self.cursor.load(Ordering::Acquire);
 		while interrupter.should_continue() {
-			let Some(line) = todo_file.get_line(cursor)
+			// only the searched line is copied, so neither the todo file nor the state are locked while the commit of
+			// the line is loaded from the repository
+			let Some(todo_file) = self.todo_file.try_lock_for(LOCK_DURATION)
 			else {
-				complete = true;
 				break;
 			};
-
-			let action = *line.get_action();
-
-			let has_hash_match = match action {
-				Action::Break | Action::Noop | Action::Label | Action::Reset | Action::Merge | Action::Exec => false,
-				Action::Drop
-				| Action::Edit
-				| Action::Fixup
-				| Action::Pick
-				| Action::Reword
-				| Action::Squash
-				| Action::UpdateRef => line.get_hash().starts_with(term),
+			if todo_file.version() != &version {
+				break;
+			}
+			let Some(line) = todo_file.get_line(cursor).cloned()
+			else {
+				complete = true;
+				break;
 			};
-			let has_content_match = match action {
-				Action::Break | Action::Noop => false,
-				Action::Drop
-				| Action::Edit
-				| Action::Fixup
-				| Action::Pick
-				| Action::Reword
-				| Action::Squash
-				| Action::UpdateRef
-				| Action::Label
-				| Action::Reset
-				| Action::Merge
-				| Action::Exec => line.get_content().contains(term),
+			drop(todo_file);
+
+			let is_commit_match = self.is_commit_match(&query, &line);
+			let mut fields = MatchedFields::empty();
+			fields.set(MatchedFields::HASH, query.is_hash_match(&line));
+			fields.set(MatchedFields::ACTION, query.is_action_match(&line));
+			fields.set(MatchedFields::CONTENT, query.is_content_match(&line));
+			fields.set(MatchedFields::METADATA, is_commit_match);
+			let line_match = LineMatch::new(cursor, fields);
+			let Some(mut state) = self.state.try_write_for(LOCK_DURATION)
+			else {
+				break;
 			};
-
-			has_matches = state.push_match(LineMatch::new(cursor, has_hash_match, has_content_match)) || has_matches;
+			if !state.is_current(&version, term) {
+				break;
+			}
+			if is_commit_match {
+				state.push_commit_match(line.get_hash());
+			}
+			has_matches = state.push_match(line_match) || has_matches;
+			drop(state);
 
 			cursor += 1;
 		}
@@ -93,7 +109,7 @@ impl Searchable for Search {
 			SearchResult::Updated
 		}
 		else if complete {
-			state.set_status(Status::Complete);
+			self.state.write().set_status(Status::Complete);
 			SearchResult::Complete
 		}
 		else {
@@ -103,12 +119,39 @@ impl Searchable for Search {
 }
 
 impl Search {
+	/// Does the commit of the line match the query, loading the commit details from the metadata index as needed.
+	fn is_commit_match(&self, query: &Query, line: &Line) -> bool {
+		let hash = line.get_hash();
+		if hash.is_empty() {
+			return false;
+		}
+		if query.is_metadata_query() {
+			self.metadata_index
+				.get(hash)
+				.map_or(false, |metadata| query.is_metadata_match(&metadata))
+		}
+		else if query.is_path_query() {
+			self.metadata_index
+				.get_paths(hash)
+				.map_or(false, |paths| query.is_path_match(&paths))
+		}
+		else if query.is_diff_query() {
+			self.metadata_index
+				.get_changed_lines(hash)
+				.map_or(false, |changed_lines| query.is_diff_match(&changed_lines))
+		}
+		else {
+			false
+		}
+	}
+
 	/// Create a new instance
 	#[inline]
 	#[must_use]
-	pub(crate) fn new(todo_file: Arc<Mutex<TodoFile>>) -> Self {
+	pub(crate) fn new(todo_file: Arc<Mutex<TodoFile>>, metadata_index: MetadataIndex) -> Self {
 		Self {
 			cursor: Arc::new(AtomicUsize::new(0)),
+			metadata_index,
 			state: Arc::new(RwLock::new(State::new())),
 			todo_file,
 		}
@@ -210,6 +253,13 @@ impl Search {
 		self.state.read().selected()
 	}
 
+	/// Get the line indexes of all the results
+	#[inline]
+	#[must_use]
+	pub(crate) fn match_indexes(&self) -> Vec<usize> {
+		self.state.read().matches().iter().map(LineMatch::index).collect()
+	}
+
 	/// Get the total number of results
 	#[inline]
 	#[must_use]
@@ -217,6 +267,25 @@ impl Search {
 		self.state.read().number_matches()
 	}
 
+	/// Get the hashes of the commits that match the search term, found so far by the search. There are none when the
+	/// term is not the term of the current search.
+	#[inline]
+	#[must_use]
+	pub(crate) fn commit_matches(&self, term: &str) -> Vec<String> {
+		let state = self.state.read();
+		if state.search_term() != term {
+			return vec![];
+		}
+		state.commit_matches().iter().cloned().collect()
+	}
+
+	/// Get the query of the current search
+	#[inline]
+	#[must_use]
+	pub(crate) fn query(&self) -> Query {
+		self.state.read().query().clone()
+	}
+
 	/// Is search active
 	#[inline]
 	#[must_use]
@@ -227,18 +296,20 @@ impl Search {
 
 #[cfg(test)]
 mod tests {
+	use std::{fs, path::Path};
+
 	use claims::{assert_none, assert_some_eq};
 	use rstest::rstest;
 
 	use super::*;
-	use crate::test_helpers::{testers, with_todo_file};
+	use crate::test_helpers::{create_commit, testers, with_temp_repository, with_todo_file};
 
 	pub(crate) fn create_search(todo_file: TodoFile) -> Search {
-		Search::new(Arc::new(Mutex::new(todo_file)))
+		Search::new(Arc::new(Mutex::new(todo_file)), MetadataIndex::default())
 	}
 
 	pub(crate) fn create_and_run_search(todo_file: TodoFile, term: &str, result: SearchResult) -> Search {
-		let search = Search::new(Arc::new(Mutex::new(todo_file)));
+		let search = Search::new(Arc::new(Mutex::new(todo_file)), MetadataIndex::default());
 		assert_eq!(testers::SearchableRunner::new(&search).run_search(term), result);
 		search
 	}
@@ -291,7 +362,7 @@ mod tests {
 			let (_todo_file_path, todo_file) = context.to_owned();
 			let search = create_and_run_search(todo_file, "foo", SearchResult::Updated);
 			assert_eq!(search.total_results(), 1);
-			assert_some_eq!(search.match_at_index(0), LineMatch::new(0, false, true));
+			assert_some_eq!(search.match_at_index(0), LineMatch::new(0, MatchedFields::CONTENT));
 		});
 	}
 
@@ -312,11 +383,11 @@ mod tests {
 				let search = create_and_run_search(todo_file, "abcd", SearchResult::Updated);
 				assert_eq!(search.total_results(), 6);
 				assert_none!(search.match_at_index(0));
-				assert_some_eq!(search.match_at_index(1), LineMatch::new(1, true, false));
-				assert_some_eq!(search.match_at_index(2), LineMatch::new(2, true, false));
-				assert_some_eq!(search.match_at_index(3), LineMatch::new(3, true, false));
-				assert_some_eq!(search.match_at_index(4), LineMatch::new(4, true, false));
-				assert_some_eq!(search.match_at_index(5), LineMatch::new(5, true, false));
+				assert_some_eq!(search.match_at_index(1), LineMatch::new(1, MatchedFields::HASH));
+				assert_some_eq!(search.match_at_index(2), LineMatch::new(2, MatchedFields::HASH));
+				assert_some_eq!(search.match_at_index(3), LineMatch::new(3, MatchedFields::HASH));
+				assert_some_eq!(search.match_at_index(4), LineMatch::new(4, MatchedFields::HASH));
+				assert_some_eq!(search.match_at_index(5), LineMatch::new(5, MatchedFields::HASH));
 			},
 		);
 	}
@@ -343,16 +414,16 @@ mod tests {
 				let search = create_and_run_search(todo_file, "ooba", SearchResult::Updated);
 				assert_eq!(search.total_results(), 11);
 				assert_none!(search.match_at_index(0));
-				assert_some_eq!(search.match_at_index(1), LineMatch::new(1, false, true));
-				assert_some_eq!(search.match_at_index(2), LineMatch::new(2, false, true));
-				assert_some_eq!(search.match_at_index(3), LineMatch::new(3, false, true));
-				assert_some_eq!(search.match_at_index(4), LineMatch::new(4, false, true));
-				assert_some_eq!(search.match_at_index(5), LineMatch::new(5, false, true));
-				assert_some_eq!(search.match_at_index(6), LineMatch::new(6, false, true));
-				assert_some_eq!(search.match_at_index(7), LineMatch::new(7, false, true));
-				assert_some_eq!(search.match_at_index(8), LineMatch::new(8, false, true));
-				assert_some_eq!(search.match_at_index(9), LineMatch::new(9, false, true));
-				assert_some_eq!(search.match_at_index(10), LineMatch::new(10, false, true));
+				assert_some_eq!(search.match_at_index(1), LineMatch::new(1, MatchedFields::CONTENT));
+				assert_some_eq!(search.match_at_index(2), LineMatch::new(2, MatchedFields::CONTENT));
+				assert_some_eq!(search.match_at_index(3), LineMatch::new(3, MatchedFields::CONTENT));
+				assert_some_eq!(search.match_at_index(4), LineMatch::new(4, MatchedFields::CONTENT));
+				assert_some_eq!(search.match_at_index(5), LineMatch::new(5, MatchedFields::CONTENT));
+				assert_some_eq!(search.match_at_index(6), LineMatch::new(6, MatchedFields::CONTENT));
+				assert_some_eq!(search.match_at_index(7), LineMatch::new(7, MatchedFields::CONTENT));
+				assert_some_eq!(search.match_at_index(8), LineMatch::new(8, MatchedFields::CONTENT));
+				assert_some_eq!(search.match_at_index(9), LineMatch::new(9, MatchedFields::CONTENT));
+				assert_some_eq!(search.match_at_index(10), LineMatch::new(10, MatchedFields::CONTENT));
 			},
 		);
 	}
@@ -366,6 +437,96 @@ mod tests {
 		});
 	}
 
+	#[rstest]
+	#[case::lowercase("^wip", 2)]
+	#[case::uppercase("^WIP", 1)]
+	fn search_regex_smart_case(#[case] term: &str, #[case] expected: usize) {
+		with_todo_file(&["pick aaa WIP foo", "pick bbb wip bar", "pick ccc done"], |context| {
+			let (_todo_file_path, todo_file) = context.to_owned();
+			let search = create_and_run_search(todo_file, term, SearchResult::Updated);
+			assert_eq!(search.total_results(), expected);
+			assert_some_eq!(search.match_at_index(0), LineMatch::new(0, MatchedFields::CONTENT));
+		});
+	}
+
+	#[test]
+	fn search_action_field() {
+		with_todo_file(
+			&["pick aaa foo", "fixup bbb foo", "squash ccc foo", "exec fixup"],
+			|context| {
+				let (_todo_file_path, todo_file) = context.to_owned();
+				let search = create_and_run_search(todo_file, "action:fixup|squash", SearchResult::Updated);
+				assert_eq!(search.total_results(), 2);
+				assert_some_eq!(search.match_at_index(1), LineMatch::new(1, MatchedFields::ACTION));
+				assert_some_eq!(search.match_at_index(2), LineMatch::new(2, MatchedFields::ACTION));
+			},
+		);
+	}
+
+	#[test]
+	fn search_hash_field() {
+		with_todo_file(&["pick abc foo", "pick def abc"], |context| {
+			let (_todo_file_path, todo_file) = context.to_owned();
+			let search = create_and_run_search(todo_file, "hash:abc", SearchResult::Updated);
+			assert_eq!(search.total_results(), 1);
+			assert_some_eq!(search.match_at_index(0), LineMatch::new(0, MatchedFields::HASH));
+		});
+	}
+
+	#[test]
+	fn search_message_field() {
+		with_todo_file(&["pick abc foo", "pick def abc"], |context| {
+			let (_todo_file_path, todo_file) = context.to_owned();
+			let search = create_and_run_search(todo_file, "msg:abc", SearchResult::Updated);
+			assert_eq!(search.total_results(), 1);
+			assert_some_eq!(search.match_at_index(1), LineMatch::new(1, MatchedFields::CONTENT));
+		});
+	}
+
+	#[test]
+	fn search_metadata() {
+		with_temp_repository(|repository| {
+			let hash = repository.commit_id_from_ref("refs/heads/main").unwrap().to_string();
+			let lines = [format!("pick {hash} foo"), String::from("pick 0000000 foo")];
+			with_todo_file(&[lines[0].as_str(), lines[1].as_str()], |context| {
+				let (_todo_file_path, todo_file) = context.to_owned();
+				let search = Search::new(Arc::new(Mutex::new(todo_file)), MetadataIndex::new(repository));
+				assert_eq!(
+					testers::SearchableRunner::new(&search).run_search("author:^name <"),
+					SearchResult::Updated
+				);
+				assert_eq!(search.total_results(), 1);
+				assert_some_eq!(search.match_at_index(0), LineMatch::new(0, MatchedFields::METADATA));
+				assert_eq!(search.commit_matches("author:^name <"), vec![hash.clone()]);
+				assert!(search.commit_matches("author:other").is_empty());
+			});
+		});
+	}
+
+	#[rstest]
+	#[case::pickaxe("pickaxe:connect(")]
+	#[case::diff("diff:^conn")]
+	fn search_diff(#[case] term: &str) {
+		with_temp_repository(|repository| {
+			let workdir = repository.repository().lock().workdir().unwrap().to_path_buf();
+			fs::write(workdir.join("file.txt"), "connect(url);\n").unwrap();
+			repository.add_path_to_index(Path::new("file.txt")).unwrap();
+			create_commit(&repository, None);
+			let hash = repository.commit_id_from_ref("refs/heads/main").unwrap().to_string();
+			let line = format!("pick {hash} connect");
+			with_todo_file(&["pick 0000000 missing", line.as_str()], |context| {
+				let (_todo_file_path, todo_file) = context.to_owned();
+				let search = Search::new(Arc::new(Mutex::new(todo_file)), MetadataIndex::new(repository));
+				assert_eq!(
+					testers::SearchableRunner::new(&search).run_search(term),
+					SearchResult::Updated
+				);
+				assert_eq!(search.total_results(), 1);
+				assert_some_eq!(search.match_at_index(1), LineMatch::new(1, MatchedFields::METADATA));
+			});
+		});
+	}
+
 	#[rstest]
 	#[case::pick("noop")]
 	#[case::pick("break")]
@@ -405,7 +566,7 @@ mod tests {
 			let (_todo_file_path, todo_file) = context.to_owned();
 			let mut search = create_and_run_search(todo_file, "foo", SearchResult::Updated);
 			assert_some_eq!(search.next(), 0);
-			assert_some_eq!(search.current_match(), LineMatch::new(0, false, true));
+			assert_some_eq!(search.current_match(), LineMatch::new(0, MatchedFields::CONTENT));
 		});
 	}
 
@@ -420,7 +581,7 @@ mod tests {
 				SearchResult::Updated
 			);
 			assert_some_eq!(search.next(), 1);
-			assert_some_eq!(search.current_match(), LineMatch::new(1, false, true));
+			assert_some_eq!(search.current_match(), LineMatch::new(1, MatchedFields::CONTENT));
 		});
 	}
 
@@ -435,7 +596,7 @@ mod tests {
 				SearchResult::Updated
 			);
 			assert_some_eq!(search.next(), 2);
-			assert_some_eq!(search.current_match(), LineMatch::new(2, false, true));
+			assert_some_eq!(search.current_match(), LineMatch::new(2, MatchedFields::CONTENT));
 		});
 	}
 
@@ -452,7 +613,7 @@ mod tests {
 					SearchResult::Updated
 				);
 				assert_some_eq!(search.next(), 1);
-				assert_some_eq!(search.current_match(), LineMatch::new(1, false, true));
+				assert_some_eq!(search.current_match(), LineMatch::new(1, MatchedFields::CONTENT));
 			},
 		);
 	}
@@ -470,7 +631,7 @@ mod tests {
 					SearchResult::Updated
 				);
 				assert_some_eq!(search.next(), 1);
-				assert_some_eq!(search.current_match(), LineMatch::new(1, false, true));
+				assert_some_eq!(search.current_match(), LineMatch::new(1, MatchedFields::CONTENT));
 			},
 		);
 	}
@@ -485,9 +646,9 @@ mod tests {
 				SearchResult::Updated
 			);
 			assert_some_eq!(search.next(), 0);
-			assert_some_eq!(search.current_match(), LineMatch::new(0, false, true));
+			assert_some_eq!(search.current_match(), LineMatch::new(0, MatchedFields::CONTENT));
 			assert_some_eq!(search.next(), 1);
-			assert_some_eq!(search.current_match(), LineMatch::new(1, false, true));
+			assert_some_eq!(search.current_match(), LineMatch::new(1, MatchedFields::CONTENT));
 		});
 	}
 
@@ -501,9 +662,9 @@ mod tests {
 				SearchResult::Updated
 			);
 			assert_some_eq!(search.next(), 0);
-			assert_some_eq!(search.current_match(), LineMatch::new(0, false, true));
+			assert_some_eq!(search.current_match(), LineMatch::new(0, MatchedFields::CONTENT));
 			assert_some_eq!(search.next(), 0);
-			assert_some_eq!(search.current_match(), LineMatch::new(0, false, true));
+			assert_some_eq!(search.current_match(), LineMatch::new(0, MatchedFields::CONTENT));
 		});
 	}
 
@@ -517,11 +678,11 @@ mod tests {
 				SearchResult::Updated
 			);
 			assert_some_eq!(search.next(), 0);
-			assert_some_eq!(search.current_match(), LineMatch::new(0, false, true));
+			assert_some_eq!(search.current_match(), LineMatch::new(0, MatchedFields::CONTENT));
 			assert_some_eq!(search.next(), 1);
-			assert_some_eq!(search.current_match(), LineMatch::new(1, false, true));
+			assert_some_eq!(search.current_match(), LineMatch::new(1, MatchedFields::CONTENT));
 			assert_some_eq!(search.next(), 0);
-			assert_some_eq!(search.current_match(), LineMatch::new(0, false, true));
+			assert_some_eq!(search.current_match(), LineMatch::new(0, MatchedFields::CONTENT));
 		});
 	}
 
@@ -564,7 +725,7 @@ mod tests {
 				SearchResult::Updated
 			);
 			assert_some_eq!(search.previous(), 0);
-			assert_some_eq!(search.current_match(), LineMatch::new(0, false, true));
+			assert_some_eq!(search.current_match(), LineMatch::new(0, MatchedFields::CONTENT));
 		});
 	}
 
@@ -579,7 +740,7 @@ mod tests {
 				SearchResult::Updated
 			);
 			assert_some_eq!(search.previous(), 1);
-			assert_some_eq!(search.current_match(), LineMatch::new(1, false, true));
+			assert_some_eq!(search.current_match(), LineMatch::new(1, MatchedFields::CONTENT));
 		});
 	}
 
@@ -596,7 +757,7 @@ mod tests {
 					SearchResult::Updated
 				);
 				assert_some_eq!(search.previous(), 1);
-				assert_some_eq!(search.current_match(), LineMatch::new(1, false, true));
+				assert_some_eq!(search.current_match(), LineMatch::new(1, MatchedFields::CONTENT));
 			},
 		);
 	}
@@ -613,7 +774,7 @@ mod tests {
 					SearchResult::Updated
 				);
 				assert_some_eq!(search.previous(), 3);
-				assert_some_eq!(search.current_match(), LineMatch::new(3, false, true));
+				assert_some_eq!(search.current_match(), LineMatch::new(3, MatchedFields::CONTENT));
 			},
 		);
 	}
@@ -631,7 +792,7 @@ mod tests {
 					SearchResult::Updated
 				);
 				assert_some_eq!(search.previous(), 2);
-				assert_some_eq!(search.current_match(), LineMatch::new(2, false, true));
+				assert_some_eq!(search.current_match(), LineMatch::new(2, MatchedFields::CONTENT));
 			},
 		);
 	}
@@ -647,9 +808,9 @@ mod tests {
 				SearchResult::Updated
 			);
 			assert_some_eq!(search.previous(), 2);
-			assert_some_eq!(search.current_match(), LineMatch::new(2, false, true));
+			assert_some_eq!(search.current_match(), LineMatch::new(2, MatchedFields::CONTENT));
 			assert_some_eq!(search.previous(), 1);
-			assert_some_eq!(search.current_match(), LineMatch::new(1, false, true));
+			assert_some_eq!(search.current_match(), LineMatch::new(1, MatchedFields::CONTENT));
 		});
 	}
 
@@ -663,9 +824,9 @@ mod tests {
 				SearchResult::Updated
 			);
 			assert_some_eq!(search.previous(), 0);
-			assert_some_eq!(search.current_match(), LineMatch::new(0, false, true));
+			assert_some_eq!(search.current_match(), LineMatch::new(0, MatchedFields::CONTENT));
 			assert_some_eq!(search.previous(), 0);
-			assert_some_eq!(search.current_match(), LineMatch::new(0, false, true));
+			assert_some_eq!(search.current_match(), LineMatch::new(0, MatchedFields::CONTENT));
 		});
 	}
 
@@ -679,9 +840,9 @@ mod tests {
 				SearchResult::Updated
 			);
 			assert_some_eq!(search.previous(), 0);
-			assert_some_eq!(search.current_match(), LineMatch::new(0, false, true));
+			assert_some_eq!(search.current_match(), LineMatch::new(0, MatchedFields::CONTENT));
 			assert_some_eq!(search.previous(), 1);
-			assert_some_eq!(search.current_match(), LineMatch::new(1, false, true));
+			assert_some_eq!(search.current_match(), LineMatch::new(1, MatchedFields::CONTENT));
 		});
 	}
 
@@ -732,7 +893,7 @@ mod tests {
 				SearchResult::Updated
 			);
 			_ = search.next();
-			assert_some_eq!(search.current_match(), LineMatch::new(0, false, true));
+			assert_some_eq!(search.current_match(), LineMatch::new(0, MatchedFields::CONTENT));
 		});
 	}
 
@@ -759,7 +920,7 @@ mod tests {
 				SearchResult::Updated
 			);
 			_ = search.next();
-			assert_some_eq!(search.match_at_index(0), LineMatch::new(0, false, true));
+			assert_some_eq!(search.match_at_index(0), LineMatch::new(0, MatchedFields::CONTENT));
 		});
 	}
 
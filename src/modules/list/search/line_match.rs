@@ -1,13 +1,25 @@
+use bitflags::bitflags;
+
+bitflags! {
+	/// The fields of a line that matched the search.
+	#[derive(Default, PartialEq, Eq, Debug, Clone, Copy)]
+	pub(crate) struct MatchedFields: u8 {
+		const HASH = 0b0000_0001;
+		const ACTION = 0b0000_0010;
+		const CONTENT = 0b0000_0100;
+		const METADATA = 0b0000_1000;
+	}
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub(crate) struct LineMatch {
 	index: usize,
-	hash: bool,
-	content: bool,
+	fields: MatchedFields,
 }
 
 impl LineMatch {
-	pub(crate) const fn new(index: usize, hash: bool, content: bool) -> Self {
-		Self { index, hash, content }
+	pub(crate) const fn new(index: usize, fields: MatchedFields) -> Self {
+		Self { index, fields }
 	}
 
 	pub(crate) const fn index(&self) -> usize {
@@ -15,10 +27,18 @@ impl LineMatch {
 	}
 
 	pub(crate) const fn hash(&self) -> bool {
-		self.hash
+		self.fields.contains(MatchedFields::HASH)
+	}
+
+	pub(crate) const fn action(&self) -> bool {
+		self.fields.contains(MatchedFields::ACTION)
 	}
 
 	pub(crate) const fn content(&self) -> bool {
-		self.content
+		self.fields.contains(MatchedFields::CONTENT)
+	}
+
+	pub(crate) const fn metadata(&self) -> bool {
+		self.fields.contains(MatchedFields::METADATA)
 	}
 }
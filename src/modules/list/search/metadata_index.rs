@@ -0,0 +1,230 @@
+use std::{
+	collections::{HashMap, VecDeque},
+	sync::Arc,
+};
+
+use parking_lot::RwLock;
+
+use crate::git::{CommitMetadata, DiffLine, GitError, Repository};
+
+/// The most commits kept in the index, which is more than the commits of most todo files.
+const COMMITS_LIMIT: usize = 1000;
+/// The most commit changes kept in the index, which is lower, since the changes of a commit can be large. The limit is
+/// raised to the number of lines of the todo file when searched, so a repeated search never loads a commit again.
+const CHANGED_LINES_LIMIT: usize = 100;
+
+/// Values loaded from the repository, keyed by commit hash. Once the limit is reached, the oldest value is removed
+/// before a new value is added.
+#[derive(Debug)]
+struct Cache<T: ?Sized> {
+	limit: usize,
+	values: HashMap<String, Option<Arc<T>>>,
+	order: VecDeque<String>,
+}
+
+impl<T: ?Sized> Cache<T> {
+	fn new(limit: usize) -> Self {
+		Self {
+			limit,
+			values: HashMap::new(),
+			order: VecDeque::new(),
+		}
+	}
+
+	fn get(&self, hash: &str) -> Option<&Option<Arc<T>>> {
+		self.values.get(hash)
+	}
+
+	/// Raise the limit, so at least the number of values are kept.
+	fn reserve(&mut self, limit: usize) {
+		self.limit = self.limit.max(limit);
+	}
+
+	fn insert(&mut self, hash: &str, value: Option<Arc<T>>) {
+		if self.values.contains_key(hash) {
+			return;
+		}
+		while self.order.len() >= self.limit {
+			let Some(oldest) = self.order.pop_front()
+			else {
+				break;
+			};
+			let _removed = self.values.remove(&oldest);
+		}
+		self.order.push_back(String::from(hash));
+		let _previous = self.values.insert(String::from(hash), value);
+	}
+}
+
+type SharedCache<T> = Arc<RwLock<Cache<T>>>;
+
+/// An index of the metadata and changes of the commits in the todo file, keyed by the hash on the line. The index is
+/// shared between searches, so each commit is only loaded from the repository once, while it is in the index.
+#[derive(Clone, Debug)]
+pub(crate) struct MetadataIndex {
+	repository: Option<Repository>,
+	commits: SharedCache<CommitMetadata>,
+	paths: SharedCache<[String]>,
+	changed_lines: SharedCache<[DiffLine]>,
+}
+
+impl Default for MetadataIndex {
+	fn default() -> Self {
+		Self::with_repository(None)
+	}
+}
+
+impl MetadataIndex {
+	/// Create a new instance
+	#[must_use]
+	pub(crate) fn new(repository: Repository) -> Self {
+		Self::with_repository(Some(repository))
+	}
+
+	fn with_repository(repository: Option<Repository>) -> Self {
+		Self {
+			repository,
+			commits: Arc::new(RwLock::new(Cache::new(COMMITS_LIMIT))),
+			paths: Arc::new(RwLock::new(Cache::new(CHANGED_LINES_LIMIT))),
+			changed_lines: Arc::new(RwLock::new(Cache::new(CHANGED_LINES_LIMIT))),
+		}
+	}
+
+	/// Get the metadata for a commit hash, loading it from the repository if it is not in the index. Commits that
+	/// cannot be loaded are also recorded in the index, so they are not loaded again.
+	pub(crate) fn get(&self, hash: &str) -> Option<Arc<CommitMetadata>> {
+		self.load(&self.commits, hash, Repository::load_commit_metadata)
+	}
+
+	/// Keep at least the number of commits in the index, such as the number of lines of the todo file, so searching
+	/// all the lines again does not load any commit again.
+	pub(crate) fn reserve(&self, commits: usize) {
+		self.commits.write().reserve(commits);
+		self.paths.write().reserve(commits);
+		self.changed_lines.write().reserve(commits);
+	}
+
+	/// Get the paths of the files changed by a commit hash, loading them from the repository if they are not in the
+	/// index. The paths are only loaded when needed, since they require a diff of the commit.
+	pub(crate) fn get_paths(&self, hash: &str) -> Option<Arc<[String]>> {
+		self.load(&self.paths, hash, Repository::load_commit_paths)
+	}
+
+	/// Get the lines added and removed by a commit hash, loading them from the repository if they are not in the
+	/// index.
+	pub(crate) fn get_changed_lines(&self, hash: &str) -> Option<Arc<[DiffLine]>> {
+		self.load(&self.changed_lines, hash, Repository::load_commit_changed_lines)
+	}
+
+	fn load<T, U, F>(&self, cache: &SharedCache<T>, hash: &str, loader: F) -> Option<Arc<T>>
+	where
+		T: ?Sized,
+		Arc<T>: From<U>,
+		F: FnOnce(&Repository, &str) -> Result<U, GitError>,
+	{
+		if let Some(value) = cache.read().get(hash) {
+			return value.clone();
+		}
+		let value = loader(self.repository.as_ref()?, hash).ok().map(Arc::from);
+		cache.write().insert(hash, value.clone());
+		value
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use claims::{assert_none, assert_some};
+
+	use super::*;
+	use crate::test_helpers::with_temp_repository;
+
+	#[test]
+	fn cache_removes_oldest_value_past_limit() {
+		let mut cache = Cache::new(2);
+		cache.insert("a", Some(Arc::new(1)));
+		cache.insert("b", None);
+		cache.insert("a", Some(Arc::new(2)));
+		cache.insert("c", Some(Arc::new(3)));
+		assert_none!(cache.get("a"));
+		assert_some!(cache.get("b"));
+		assert_eq!(cache.get("c").cloned().flatten().as_deref(), Some(&3));
+		assert_eq!(cache.order.len(), 2);
+	}
+
+	#[test]
+	fn cache_reserve_raises_limit() {
+		let mut cache = Cache::new(1);
+		cache.reserve(2);
+		cache.reserve(0);
+		cache.insert("a", Some(Arc::new(1)));
+		cache.insert("b", Some(Arc::new(2)));
+		assert_some!(cache.get("a"));
+		assert_some!(cache.get("b"));
+		assert_eq!(cache.limit, 2);
+	}
+
+	#[test]
+	fn get_without_repository() {
+		assert_none!(MetadataIndex::default().get("abc"));
+	}
+
+	#[test]
+	fn get_loads_and_caches() {
+		with_temp_repository(|repository| {
+			let hash = repository.commit_id_from_ref("refs/heads/main").unwrap().to_string();
+			let index = MetadataIndex::new(repository);
+			let metadata = index.get(hash.as_str()).unwrap();
+			assert!(Arc::ptr_eq(&metadata, &index.get(hash.as_str()).unwrap()));
+			assert_some!(index.commits.read().get(hash.as_str()));
+		});
+	}
+
+	#[test]
+	fn get_caches_missing_commit() {
+		with_temp_repository(|repository| {
+			let index = MetadataIndex::new(repository);
+			assert_none!(index.get("0000000"));
+			assert_some!(index.commits.read().get("0000000"));
+		});
+	}
+
+	#[test]
+	fn get_changed_lines_loads_and_caches() {
+		with_temp_repository(|repository| {
+			let hash = repository.commit_id_from_ref("refs/heads/main").unwrap().to_string();
+			let index = MetadataIndex::new(repository);
+			let lines = index.get_changed_lines(hash.as_str()).unwrap();
+			assert!(Arc::ptr_eq(&lines, &index.get_changed_lines(hash.as_str()).unwrap()));
+		});
+	}
+
+	#[test]
+	fn get_paths_loads_and_caches() {
+		with_temp_repository(|repository| {
+			let hash = repository.commit_id_from_ref("refs/heads/main").unwrap().to_string();
+			let index = MetadataIndex::new(repository);
+			let paths = index.get_paths(hash.as_str()).unwrap();
+			assert!(Arc::ptr_eq(&paths, &index.get_paths(hash.as_str()).unwrap()));
+			assert_none!(index.changed_lines.read().get(hash.as_str()));
+		});
+	}
+
+	#[test]
+	fn get_does_not_load_paths() {
+		with_temp_repository(|repository| {
+			let hash = repository.commit_id_from_ref("refs/heads/main").unwrap().to_string();
+			let index = MetadataIndex::new(repository);
+			_ = index.get(hash.as_str()).unwrap();
+			assert_none!(index.paths.read().get(hash.as_str()));
+		});
+	}
+
+	#[test]
+	fn reserve() {
+		let index = MetadataIndex::default();
+		index.reserve(5000);
+		assert_eq!(index.commits.read().limit, 5000);
+		assert_eq!(index.paths.read().limit, 5000);
+		assert_eq!(index.changed_lines.read().limit, 5000);
+	}
+}
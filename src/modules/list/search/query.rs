@@ -0,0 +1,344 @@
+use std::ops::Range;
+
+use regex::Regex;
+
+use crate::{
+	git::{CommitMetadata, DiffLine, Origin},
+	search::build_regex,
+	todo_file::Line,
+};
+
+/// The field of the todo line, or of the commit of the line, that a search query is limited to.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Field {
+	Any,
+	Action,
+	Hash,
+	Message,
+	Author,
+	Date,
+	Body,
+	Path,
+	Pickaxe,
+	Diff,
+}
+
+const FIELD_PREFIXES: [(&str, Field); 9] = [
+	("hash:", Field::Hash),
+	("action:", Field::Action),
+	("msg:", Field::Message),
+	("author:", Field::Author),
+	("date:", Field::Date),
+	("body:", Field::Body),
+	("path:", Field::Path),
+	("pickaxe:", Field::Pickaxe),
+	("diff:", Field::Diff),
+];
+
+const DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S %z";
+
+/// A parsed search term. The term is a regular expression, optionally prefixed with `hash:`, `action:` or `msg:` to
+/// limit the search to a single field of the line, or with `author:`, `date:`, `body:` or `path:` to search the
+/// commit of the line instead. Similar to `git log -S` and `git log -G`, `pickaxe:` finds commits that change the
+/// number of occurrences of a literal string, and `diff:` finds commits with an added or removed line that matches.
+/// Matching is case-insensitive, unless the pattern contains an uppercase character. A pattern that is not a valid
+/// regular expression is matched literally.
+#[derive(Debug, Clone)]
+pub(crate) struct Query {
+	field: Field,
+	regex: Option<Regex>,
+}
+
+impl Query {
+	pub(crate) fn new(term: &str) -> Self {
+		let (field, pattern) = FIELD_PREFIXES
+			.iter()
+			.find_map(|&(prefix, field)| term.strip_prefix(prefix).map(|pattern| (field, pattern)))
+			.unwrap_or((Field::Any, term));
+
+		// a pickaxe pattern is always a literal string, and actions are matched in full, so that `action:e` matches
+		// `edit` lines, but not `exec` lines
+		let regex = build_regex(pattern, field == Field::Pickaxe, field == Field::Action);
+		Self { field, regex }
+	}
+
+	/// Does the start of the hash of the line match the query.
+	pub(crate) fn is_hash_match(&self, line: &Line) -> bool {
+		if !matches!(self.field, Field::Any | Field::Hash) || line.get_hash().is_empty() {
+			return false;
+		}
+		self.regex
+			.as_ref()
+			.map_or(false, |regex| regex.find(line.get_hash()).map_or(false, |m| m.start() == 0))
+	}
+
+	/// Does the action of the line, or its abbreviation, match the query.
+	pub(crate) fn is_action_match(&self, line: &Line) -> bool {
+		if self.field != Field::Action {
+			return false;
+		}
+		let action = *line.get_action();
+		self.regex.as_ref().map_or(false, |regex| {
+			regex.is_match(action.to_string().as_str()) || regex.is_match(action.to_abbreviation().as_str())
+		})
+	}
+
+	/// Does the content of the line match the query.
+	pub(crate) fn is_content_match(&self, line: &Line) -> bool {
+		!self.content_matches(line.get_content()).is_empty()
+	}
+
+	/// Does the query search the metadata of the commit of the line, instead of the line itself.
+	pub(crate) const fn is_metadata_query(&self) -> bool {
+		matches!(self.field, Field::Author | Field::Date | Field::Body)
+	}
+
+	/// Does the metadata of the commit match the query.
+	pub(crate) fn is_metadata_match(&self, metadata: &CommitMetadata) -> bool {
+		let Some(regex) = self.regex.as_ref()
+		else {
+			return false;
+		};
+		let commit = metadata.commit();
+		match self.field {
+			Field::Any
+			| Field::Action
+			| Field::Hash
+			| Field::Message
+			| Field::Path
+			| Field::Pickaxe
+			| Field::Diff => false,
+			Field::Author => regex.is_match(commit.author().to_string().as_str()),
+			Field::Date => {
+				commit
+					.authored_date()
+					.iter()
+					.chain([commit.committed_date()])
+					.any(|date| regex.is_match(date.format(DATE_FORMAT).to_string().as_str()))
+			},
+			Field::Body => commit.message().map_or(false, |message| regex.is_match(message)),
+		}
+	}
+
+	/// Does the query search the paths of the files changed by the commit of the line.
+	pub(crate) fn is_path_query(&self) -> bool {
+		self.field == Field::Path
+	}
+
+	/// Do the paths of the files changed by the commit match the query.
+	pub(crate) fn is_path_match(&self, paths: &[String]) -> bool {
+		self.field == Field::Path
+			&& self
+				.regex
+				.as_ref()
+				.map_or(false, |regex| paths.iter().any(|path| regex.is_match(path)))
+	}
+
+	/// Does the query search the changes made by the commit of the line.
+	pub(crate) const fn is_diff_query(&self) -> bool {
+		matches!(self.field, Field::Pickaxe | Field::Diff)
+	}
+
+	/// Do the lines added and removed by the commit match the query.
+	pub(crate) fn is_diff_match(&self, changed_lines: &[DiffLine]) -> bool {
+		let Some(regex) = self.regex.as_ref()
+		else {
+			return false;
+		};
+		match self.field {
+			Field::Pickaxe => {
+				let count = |origin: Origin| -> usize {
+					changed_lines
+						.iter()
+						.filter(|line| line.origin() == origin)
+						.map(|line| regex.find_iter(line.line()).count())
+						.sum()
+				};
+				count(Origin::Addition) != count(Origin::Deletion)
+			},
+			Field::Diff => changed_lines.iter().any(|line| regex.is_match(line.line())),
+			Field::Any
+			| Field::Action
+			| Field::Hash
+			| Field::Message
+			| Field::Author
+			| Field::Date
+			| Field::Body
+			| Field::Path => false,
+		}
+	}
+
+	/// Get the ranges of the content that match the query, ignoring empty matches.
+	pub(crate) fn content_matches(&self, content: &str) -> Vec<Range<usize>> {
+		if !matches!(self.field, Field::Any | Field::Message) {
+			return vec![];
+		}
+		self.regex.as_ref().map_or_else(Vec::new, |regex| {
+			regex
+				.find_iter(content)
+				.filter(|m| !m.is_empty())
+				.map(|m| m.range())
+				.collect()
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use rstest::rstest;
+
+	use super::*;
+	use crate::{git::User, test_helpers::builders::CommitBuilder};
+
+	fn line(input: &str) -> Line {
+		Line::parse(input).unwrap()
+	}
+
+	#[rstest]
+	#[case::literal("foo", "pick aaa foobar", true)]
+	#[case::regex("^fo+b", "pick aaa foobar", true)]
+	#[case::regex_miss("^bar", "pick aaa foobar", false)]
+	#[case::smart_case_lower("foo", "pick aaa FooBar", true)]
+	#[case::smart_case_upper("Foo", "pick aaa foobar", false)]
+	#[case::smart_case_upper_match("Foo", "pick aaa Foobar", true)]
+	#[case::invalid_regex("foo(", "pick aaa call foo(bar)", true)]
+	#[case::message_field("msg:^WIP", "pick aaa WIP: foo", true)]
+	#[case::message_field_miss("msg:aaa", "pick aaa foo", false)]
+	#[case::hash_field("hash:foo", "pick aaa foo", false)]
+	#[case::action_field("action:pick", "pick aaa pick", false)]
+	#[case::exec("make", "exec make test", true)]
+	fn is_content_match(#[case] term: &str, #[case] input: &str, #[case] expected: bool) {
+		assert_eq!(Query::new(term).is_content_match(&line(input)), expected);
+	}
+
+	#[rstest]
+	#[case::prefix("abc", "pick abcdef foo", true)]
+	#[case::not_prefix("def", "pick abcdef foo", false)]
+	#[case::regex("a.c", "pick abcdef foo", true)]
+	#[case::hash_field("hash:abc", "pick abcdef foo", true)]
+	#[case::message_field("msg:abc", "pick abcdef foo", false)]
+	#[case::no_hash("exec", "exec make", false)]
+	fn is_hash_match(#[case] term: &str, #[case] input: &str, #[case] expected: bool) {
+		assert_eq!(Query::new(term).is_hash_match(&line(input)), expected);
+	}
+
+	#[rstest]
+	#[case::action("action:squash", "squash aaa foo", true)]
+	#[case::abbreviation("action:s", "squash aaa foo", true)]
+	#[case::alternation("action:fixup|squash", "fixup aaa foo", true)]
+	#[case::partial("action:e", "exec make", false)]
+	#[case::invalid_regex("action:(", "pick aaa foo", false)]
+	#[case::smart_case("action:PICK", "pick aaa foo", false)]
+	#[case::without_field("pick", "pick aaa foo", false)]
+	fn is_action_match(#[case] term: &str, #[case] input: &str, #[case] expected: bool) {
+		assert_eq!(Query::new(term).is_action_match(&line(input)), expected);
+	}
+
+	#[test]
+	fn empty_pattern() {
+		let query = Query::new("msg:");
+		assert!(!query.is_content_match(&line("pick aaa foo")));
+		assert!(!query.is_hash_match(&line("pick aaa foo")));
+	}
+
+	#[test]
+	fn content_matches() {
+		assert_eq!(Query::new("o+").content_matches("foo boo bar"), vec![1..3, 5..7]);
+	}
+
+	#[test]
+	fn content_matches_ignores_empty_matches() {
+		assert!(Query::new("x*").content_matches("foo").is_empty());
+	}
+
+	fn create_metadata() -> CommitMetadata {
+		CommitMetadata::new(
+			CommitBuilder::new("abc")
+				.author(User::new(Some("Alice"), Some("alice@example.com")))
+				.authored_time(1_625_000_000)
+				.message("Add query\n\nFixes: #42")
+				.build(),
+		)
+	}
+
+	#[rstest]
+	#[case::author_name("author:alice", true)]
+	#[case::author_email("author:@example\\.com", true)]
+	#[case::author_miss("author:bob", false)]
+	#[case::date("date:^2021-06-", true)]
+	#[case::date_miss("date:^1999", false)]
+	#[case::body("body:fixes: #\\d+", true)]
+	#[case::body_miss("body:closes", false)]
+	#[case::path("path:^src/db/", false)]
+	#[case::line_field("msg:query", false)]
+	#[case::empty("author:", false)]
+	fn is_metadata_match(#[case] term: &str, #[case] expected: bool) {
+		assert_eq!(Query::new(term).is_metadata_match(&create_metadata()), expected);
+	}
+
+	#[rstest]
+	#[case::author("author:alice", true)]
+	#[case::date("date:2021", true)]
+	#[case::body("body:fixes", true)]
+	#[case::path("path:src", false)]
+	#[case::message("msg:foo", false)]
+	#[case::any("foo", false)]
+	fn is_metadata_query(#[case] term: &str, #[case] expected: bool) {
+		assert_eq!(Query::new(term).is_metadata_query(), expected);
+	}
+
+	#[rstest]
+	#[case::path("path:^src/db/", true)]
+	#[case::path_miss("path:^test/", false)]
+	#[case::other_field("author:src", false)]
+	#[case::empty("path:", false)]
+	fn is_path_match(#[case] term: &str, #[case] expected: bool) {
+		let paths = [String::from("src/db/query.rs"), String::from("README.md")];
+		assert_eq!(Query::new(term).is_path_match(&paths), expected);
+	}
+
+	#[rstest]
+	#[case::path("path:foo", true)]
+	#[case::author("author:foo", false)]
+	#[case::any("foo", false)]
+	fn is_path_query(#[case] term: &str, #[case] expected: bool) {
+		assert_eq!(Query::new(term).is_path_query(), expected);
+	}
+
+	fn create_changed_lines() -> Vec<DiffLine> {
+		vec![
+			DiffLine::new(Origin::Deletion, "let rows = db.query(sql);", Some(1), None, false),
+			DiffLine::new(Origin::Addition, "let rows = db.query(sql).await;", None, Some(1), false),
+			DiffLine::new(Origin::Addition, "connect(url);", None, Some(2), false),
+		]
+	}
+
+	#[rstest]
+	#[case::pickaxe_added("pickaxe:connect(", true)]
+	#[case::pickaxe_moved("pickaxe:db.query(sql)", false)]
+	#[case::pickaxe_literal("pickaxe:db.query(sql).*", false)]
+	#[case::pickaxe_miss("pickaxe:disconnect", false)]
+	#[case::diff_regex("diff:\\.await;$", true)]
+	#[case::diff_moved("diff:db\\.query", true)]
+	#[case::diff_miss("diff:disconnect", false)]
+	#[case::other_field("path:connect", false)]
+	#[case::empty("diff:", false)]
+	fn is_diff_match(#[case] term: &str, #[case] expected: bool) {
+		assert_eq!(Query::new(term).is_diff_match(&create_changed_lines()), expected);
+	}
+
+	#[rstest]
+	#[case::pickaxe("pickaxe:foo", true)]
+	#[case::diff("diff:foo", true)]
+	#[case::path("path:foo", false)]
+	fn is_diff_query(#[case] term: &str, #[case] expected: bool) {
+		assert_eq!(Query::new(term).is_diff_query(), expected);
+	}
+
+	#[test]
+	fn metadata_query_does_not_match_line() {
+		let query = Query::new("path:foo");
+		assert!(!query.is_content_match(&line("pick aaa foo")));
+		assert!(!query.is_hash_match(&line("pick aaa foo")));
+	}
+}
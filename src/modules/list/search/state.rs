@@ -1,16 +1,18 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use version_track::Version;
 
-use super::line_match::LineMatch;
+use super::{line_match::LineMatch, query::Query};
 use crate::search::Status;
 
 /// Input thread state.
 #[derive(Clone, Debug)]
 pub(crate) struct State {
+	commit_matches: HashSet<String>,
 	match_indexes: HashMap<usize, usize>,
 	match_start_hint: usize,
 	matches: Vec<LineMatch>,
+	query: Query,
 	search_term: String,
 	selected: Option<usize>,
 	status: Status,
@@ -20,9 +22,11 @@ pub(crate) struct State {
 impl State {
 	pub(crate) fn new() -> Self {
 		Self {
+			commit_matches: HashSet::new(),
 			match_indexes: HashMap::new(),
 			match_start_hint: 0,
 			matches: vec![],
+			query: Query::new(""),
 			search_term: String::new(),
 			selected: None,
 			status: Status::Inactive,
@@ -31,9 +35,11 @@ impl State {
 	}
 
 	pub(crate) fn reset(&mut self) {
+		self.commit_matches.clear();
 		self.match_indexes.clear();
 		self.matches.clear();
 		self.search_term.clear();
+		self.query = Query::new("");
 		self.selected = None;
 		self.status = Status::Inactive;
 		self.todo_file_version = Version::sentinel();
@@ -41,7 +47,12 @@ impl State {
 
 	pub(crate) fn try_invalidate_search(&mut self, version: &Version, search_term: &str) -> bool {
 		if &self.todo_file_version != version || self.search_term != search_term {
+			// the commit of a hash never changes, so the matched commits are kept until the term changes
+			if self.search_term != search_term {
+				self.commit_matches.clear();
+			}
 			self.search_term = String::from(search_term);
+			self.query = Query::new(search_term);
 			self.matches.clear();
 			self.match_indexes.clear();
 			self.todo_file_version = *version;
@@ -52,6 +63,11 @@ impl State {
 		}
 	}
 
+	/// Is the search for the version of the todo file and the search term, so matches found for them can be added.
+	pub(crate) fn is_current(&self, version: &Version, search_term: &str) -> bool {
+		&self.todo_file_version == version && self.search_term == search_term
+	}
+
 	pub(crate) const fn status(&self) -> Status {
 		self.status
 	}
@@ -61,7 +77,7 @@ impl State {
 	}
 
 	pub(crate) fn push_match(&mut self, line_match: LineMatch) -> bool {
-		if line_match.hash() || line_match.content() {
+		if line_match.hash() || line_match.action() || line_match.content() || line_match.metadata() {
 			_ = self.match_indexes.insert(line_match.index(), self.matches.len());
 			self.matches.push(line_match);
 			true
@@ -71,6 +87,26 @@ impl State {
 		}
 	}
 
+	/// Record the hash of a commit that matches the query.
+	pub(crate) fn push_commit_match(&mut self, hash: &str) {
+		if !self.commit_matches.contains(hash) {
+			_ = self.commit_matches.insert(String::from(hash));
+		}
+	}
+
+	/// The hashes of the commits that match the query, searched so far.
+	pub(crate) const fn commit_matches(&self) -> &HashSet<String> {
+		&self.commit_matches
+	}
+
+	pub(crate) fn search_term(&self) -> &str {
+		self.search_term.as_str()
+	}
+
+	pub(crate) const fn query(&self) -> &Query {
+		&self.query
+	}
+
 	pub(crate) const fn matches(&self) -> &Vec<LineMatch> {
 		&self.matches
 	}
@@ -112,6 +148,7 @@ mod tests {
 	use claims::{assert_none, assert_some_eq};
 
 	use super::*;
+	use crate::modules::list::search::line_match::MatchedFields;
 
 	#[test]
 	fn try_invalidate_search_with_no_change() {
@@ -131,10 +168,22 @@ mod tests {
 		assert!(state.try_invalidate_search(&Version::new(), ""));
 	}
 
+	#[test]
+	fn is_current() {
+		let mut state = State::new();
+		let version = Version::new();
+		_ = state.try_invalidate_search(&version, "foo");
+		assert!(state.is_current(&version, "foo"));
+		assert!(!state.is_current(&version, "bar"));
+		assert!(!state.is_current(&Version::sentinel(), "foo"));
+		state.reset();
+		assert!(!state.is_current(&version, "foo"));
+	}
+
 	#[test]
 	fn try_invalidate_search_resets_state() {
 		let mut state = State::new();
-		state.matches.push(LineMatch::new(1, false, false));
+		state.matches.push(LineMatch::new(1, MatchedFields::empty()));
 		_ = state.match_indexes.insert(1, 1);
 		let version = Version::new();
 		assert!(state.try_invalidate_search(&version, "foo"));
@@ -144,6 +193,17 @@ mod tests {
 		assert_eq!(state.todo_file_version, version);
 	}
 
+	#[test]
+	fn try_invalidate_search_keeps_commit_matches_for_term() {
+		let mut state = State::new();
+		_ = state.try_invalidate_search(&Version::sentinel(), "foo");
+		state.push_commit_match("aaa");
+		assert!(state.try_invalidate_search(&Version::new(), "foo"));
+		assert!(state.commit_matches().contains("aaa"));
+		assert!(state.try_invalidate_search(&Version::new(), "bar"));
+		assert!(state.commit_matches().is_empty());
+	}
+
 	#[test]
 	fn search_status() {
 		let mut state = State::new();
@@ -154,7 +214,7 @@ mod tests {
 	#[test]
 	fn push_match_with_hash_match() {
 		let mut state = State::new();
-		assert!(state.push_match(LineMatch::new(1, true, false)));
+		assert!(state.push_match(LineMatch::new(1, MatchedFields::HASH)));
 		assert!(!state.matches().is_empty());
 		assert_eq!(state.number_matches(), 1);
 	}
@@ -162,7 +222,7 @@ mod tests {
 	#[test]
 	fn push_match_with_content_match() {
 		let mut state = State::new();
-		assert!(state.push_match(LineMatch::new(1, false, true)));
+		assert!(state.push_match(LineMatch::new(1, MatchedFields::CONTENT)));
 		assert!(!state.matches().is_empty());
 		assert_eq!(state.number_matches(), 1);
 	}
@@ -170,7 +230,7 @@ mod tests {
 	#[test]
 	fn push_match_with_hash_and_content_match() {
 		let mut state = State::new();
-		assert!(state.push_match(LineMatch::new(1, true, true)));
+		assert!(state.push_match(LineMatch::new(1, MatchedFields::HASH | MatchedFields::CONTENT)));
 		assert!(!state.matches().is_empty());
 		assert_eq!(state.number_matches(), 1);
 	}
@@ -178,7 +238,7 @@ mod tests {
 	#[test]
 	fn push_match_with_no_hash_and_no_content_match() {
 		let mut state = State::new();
-		assert!(!state.push_match(LineMatch::new(1, false, false)));
+		assert!(!state.push_match(LineMatch::new(1, MatchedFields::empty())));
 		assert!(state.matches().is_empty());
 		assert_eq!(state.number_matches(), 0);
 	}
@@ -186,14 +246,14 @@ mod tests {
 	#[test]
 	fn match_value_for_line_index_miss() {
 		let mut state = State::new();
-		assert!(state.push_match(LineMatch::new(1, false, true)));
+		assert!(state.push_match(LineMatch::new(1, MatchedFields::CONTENT)));
 		assert_none!(state.match_value_for_line(99));
 	}
 
 	#[test]
 	fn match_value_for_line_index_hit() {
 		let mut state = State::new();
-		let line_match = LineMatch::new(1, false, true);
+		let line_match = LineMatch::new(1, MatchedFields::CONTENT);
 		assert!(state.push_match(line_match));
 		assert_some_eq!(state.match_value_for_line(1), line_match);
 	}
@@ -201,14 +261,14 @@ mod tests {
 	#[test]
 	fn match_value_miss() {
 		let mut state = State::new();
-		assert!(state.push_match(LineMatch::new(1, false, true)));
+		assert!(state.push_match(LineMatch::new(1, MatchedFields::CONTENT)));
 		assert_none!(state.match_value(99));
 	}
 
 	#[test]
 	fn match_value_hit() {
 		let mut state = State::new();
-		let line_match = LineMatch::new(1, false, true);
+		let line_match = LineMatch::new(1, MatchedFields::CONTENT);
 		assert!(state.push_match(line_match));
 		assert_some_eq!(state.match_value(0), line_match);
 	}
@@ -0,0 +1,25 @@
+use super::search::MetadataIndex;
+use crate::todo_file::TodoFile;
+
+/// Sort the lines at the indexes that have a commit by the author or committer date of the commit, oldest first. The
+/// lines without a commit, and with a commit that cannot be loaded, are left in their position.
+pub(crate) fn sort_lines_by_date(
+	todo_file: &mut TodoFile,
+	indexes: &[usize],
+	metadata_index: &MetadataIndex,
+	committer_date: bool,
+) -> bool {
+	todo_file.sort_lines_by_key(indexes, |line| {
+		if !line.has_reference() {
+			return None;
+		}
+		let metadata = metadata_index.get(line.get_hash())?;
+		let commit = metadata.commit();
+		Some(if committer_date {
+			*commit.committed_date()
+		}
+		else {
+			commit.authored_date().unwrap_or(*commit.committed_date())
+		})
+	})
+}
@@ -0,0 +1,284 @@
+use std::collections::HashSet;
+
+use crate::todo_file::{Action, TodoFile};
+
+/// A section of the todo file, for a single branch in a stack of branches. Each section ends with the `update-ref`
+/// lines of the branches that point to the last commit of the section, except for the final section, which contains
+/// the commits of the branch being rebased.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Section {
+	start: usize,
+	end: usize,
+	update_ref_start: usize,
+	branches: Vec<String>,
+	commits: usize,
+}
+
+impl Section {
+	/// The index of the first line of the section.
+	pub(crate) const fn start(&self) -> usize {
+		self.start
+	}
+
+	/// The index of the last line of the section.
+	pub(crate) const fn end(&self) -> usize {
+		self.end
+	}
+
+	/// The index of the first `update-ref` line that ends the section, or the index after the end of the section when
+	/// the section does not end with an `update-ref` line.
+	pub(crate) const fn update_ref_start(&self) -> usize {
+		self.update_ref_start
+	}
+
+	/// The number of lines in the section.
+	pub(crate) const fn len(&self) -> usize {
+		self.end - self.start + 1
+	}
+
+	pub(crate) fn contains(&self, index: usize) -> bool {
+		(self.start..=self.end).contains(&index)
+	}
+
+	/// A key that identifies the section, that remains the same when lines in the section change.
+	pub(crate) fn key(&self) -> String {
+		self.branches.join(" ")
+	}
+
+	/// The title of the section, used for the section header.
+	pub(crate) fn title(&self) -> String {
+		let name = if self.branches.is_empty() {
+			String::from("HEAD")
+		}
+		else {
+			self.branches
+				.iter()
+				.map(|branch| branch.strip_prefix("refs/heads/").unwrap_or(branch))
+				.collect::<Vec<&str>>()
+				.join(", ")
+		};
+		format!(
+			"{name} ({} {})",
+			self.commits,
+			if self.commits == 1 { "commit" } else { "commits" }
+		)
+	}
+}
+
+/// Split the todo lines into sections, at each group of `update-ref` lines.
+pub(crate) fn get_sections(todo_file: &TodoFile) -> Vec<Section> {
+	let mut sections = vec![];
+	let mut start = 0;
+	let mut update_ref_start = None;
+	let mut branches = vec![];
+	let mut commits = 0;
+
+	for (index, line) in todo_file.lines_iter().enumerate() {
+		if *line.get_action() == Action::UpdateRef {
+			_ = update_ref_start.get_or_insert(index);
+			branches.push(String::from(line.get_content()));
+			continue;
+		}
+		if let Some(ref_start) = update_ref_start.take() {
+			sections.push(Section {
+				start,
+				end: index - 1,
+				update_ref_start: ref_start,
+				branches: branches.drain(..).collect(),
+				commits,
+			});
+			start = index;
+			commits = 0;
+		}
+		if line.has_reference() && *line.get_action() != Action::Drop {
+			commits += 1;
+		}
+	}
+
+	let len = todo_file.lines_iter().len();
+	if start < len {
+		sections.push(Section {
+			start,
+			end: len - 1,
+			update_ref_start: update_ref_start.unwrap_or(len),
+			branches,
+			commits,
+		});
+	}
+	sections
+}
+
+/// The sections that are collapsed in the stack view, where a collapsed section is shown as a single row.
+#[derive(Debug)]
+pub(crate) struct Stack {
+	collapsed_sections: HashSet<String>,
+}
+
+impl Stack {
+	pub(crate) fn new() -> Self {
+		Self {
+			collapsed_sections: HashSet::new(),
+		}
+	}
+
+	pub(crate) fn is_collapsed(&self, section: &Section) -> bool {
+		self.collapsed_sections.contains(&section.key())
+	}
+
+	/// The index of the first line of each row of the stack view.
+	pub(crate) fn rows(&self, todo_file: &TodoFile) -> Vec<usize> {
+		let mut rows = vec![];
+		for section in get_sections(todo_file) {
+			if self.is_collapsed(&section) {
+				rows.push(section.start());
+			}
+			else {
+				rows.extend(section.start()..=section.end());
+			}
+		}
+		rows
+	}
+
+	/// Get the section that contains the selected line, when it is collapsed.
+	pub(crate) fn selected_collapsed_section(&self, todo_file: &TodoFile) -> Option<Section> {
+		let selected_index = todo_file.get_selected_line_index();
+		get_sections(todo_file)
+			.into_iter()
+			.find(|section| section.contains(selected_index))
+			.filter(|section| self.is_collapsed(section))
+	}
+
+	/// Move the selected line up, returning the index the lines were moved to. A collapsed section is moved above the
+	/// previous section, and the first line of a section is moved to the end of the previous section.
+	pub(crate) fn swap_selected_up(&self, todo_file: &mut TodoFile) -> Option<usize> {
+		let selected_index = todo_file.get_selected_line_index();
+		let sections = get_sections(todo_file);
+		let position = sections.iter().position(|section| section.contains(selected_index))?;
+		let section = &sections[position];
+		let previous_section = position.checked_sub(1).map(|p| &sections[p]);
+
+		let (start_index, end_index, destination_index) = if self.is_collapsed(section) {
+			(section.start(), section.end(), previous_section?.start())
+		}
+		else if let Some(previous) = previous_section.filter(|_| selected_index == section.start()) {
+			(selected_index, selected_index, previous.update_ref_start())
+		}
+		else if selected_index > 0 {
+			(selected_index, selected_index, selected_index - 1)
+		}
+		else {
+			return None;
+		};
+
+		todo_file
+			.move_range(start_index, end_index, destination_index)
+			.then_some(destination_index)
+	}
+
+	/// Move the selected line down, returning the index the lines were moved to. A collapsed section is moved below the
+	/// next section, and the last commit of a section is moved to the start of the next section.
+	pub(crate) fn swap_selected_down(&self, todo_file: &mut TodoFile) -> Option<usize> {
+		let selected_index = todo_file.get_selected_line_index();
+		let sections = get_sections(todo_file);
+		let position = sections.iter().position(|section| section.contains(selected_index))?;
+		let section = &sections[position];
+		let next_section = sections.get(position + 1);
+
+		let (start_index, end_index, destination_index) = if self.is_collapsed(section) {
+			(section.start(), section.end(), section.start() + next_section?.len())
+		}
+		else if next_section.is_some() && selected_index + 1 == section.update_ref_start() {
+			(selected_index, selected_index, section.end())
+		}
+		else {
+			(selected_index, selected_index, selected_index + 1)
+		};
+
+		todo_file
+			.move_range(start_index, end_index, destination_index)
+			.then_some(destination_index)
+	}
+
+	/// Collapse or expand the section that contains the selected line, returning `false` when there is no section.
+	pub(crate) fn toggle_section(&mut self, todo_file: &TodoFile) -> bool {
+		let selected_line_index = todo_file.get_selected_line_index();
+		let Some(section) = get_sections(todo_file)
+			.into_iter()
+			.find(|section| section.contains(selected_line_index))
+		else {
+			return false;
+		};
+
+		let key = section.key();
+		if !self.collapsed_sections.remove(&key) {
+			_ = self.collapsed_sections.insert(key);
+		}
+		true
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test_helpers::with_todo_file;
+
+	#[test]
+	fn get_sections_without_update_refs() {
+		with_todo_file(&["pick aaa c1", "pick bbb c2"], |context| {
+			let sections = get_sections(context.todo_file());
+			assert_eq!(sections.len(), 1);
+			assert_eq!(sections[0].start(), 0);
+			assert_eq!(sections[0].end(), 1);
+			assert_eq!(sections[0].update_ref_start(), 2);
+			assert_eq!(sections[0].title(), "HEAD (2 commits)");
+		});
+	}
+
+	#[test]
+	fn get_sections_with_update_refs() {
+		with_todo_file(
+			&[
+				"pick aaa c1",
+				"update-ref refs/heads/one",
+				"update-ref refs/heads/two",
+				"pick bbb c2",
+				"drop ccc c3",
+				"exec make",
+				"update-ref refs/heads/three",
+				"pick ddd c4",
+			],
+			|context| {
+				let sections = get_sections(context.todo_file());
+				assert_eq!(sections.len(), 3);
+				assert_eq!((sections[0].start(), sections[0].end()), (0, 2));
+				assert_eq!(sections[0].update_ref_start(), 1);
+				assert_eq!(sections[0].title(), "one, two (1 commit)");
+				assert_eq!(sections[0].key(), "refs/heads/one refs/heads/two");
+				assert_eq!((sections[1].start(), sections[1].end()), (3, 6));
+				assert_eq!(sections[1].update_ref_start(), 6);
+				assert_eq!(sections[1].title(), "three (1 commit)");
+				assert_eq!((sections[2].start(), sections[2].end()), (7, 7));
+				assert_eq!(sections[2].title(), "HEAD (1 commit)");
+			},
+		);
+	}
+
+	#[test]
+	fn get_sections_ending_with_update_ref() {
+		with_todo_file(&["pick aaa c1", "update-ref refs/heads/one"], |context| {
+			let sections = get_sections(context.todo_file());
+			assert_eq!(sections.len(), 1);
+			assert_eq!((sections[0].start(), sections[0].end()), (0, 1));
+			assert_eq!(sections[0].len(), 2);
+			assert!(sections[0].contains(1));
+			assert!(!sections[0].contains(2));
+		});
+	}
+
+	#[test]
+	fn get_sections_empty() {
+		with_todo_file(&[], |context| {
+			assert!(get_sections(context.todo_file()).is_empty());
+		});
+	}
+}
@@ -1,28 +1,48 @@
 mod abort_and_rebase;
 mod activate;
 mod change_action;
+mod count;
+mod done_lines;
 mod edit_mode;
+mod exec_lines;
 mod external_editor;
+mod filter;
+mod fold_view;
 mod help;
 mod insert_line;
+mod marks;
 mod movement;
+mod named_marks;
 mod normal_mode;
 mod read_event;
 mod remove_lines;
+mod reorder;
+mod reword_message;
+mod rewrite_metadata;
 mod render;
 mod search;
+mod sequencer;
 mod show_commit;
+mod stack_view;
 mod swap_lines;
 mod toggle_break;
 mod toggle_option;
 mod undo_redo;
+mod undo_history;
+mod update_refs;
 mod visual_mode;
 
 use super::*;
 use crate::test_helpers::{create_config, testers};
 
 pub(crate) fn create_list(config: &Config, todo_file: TodoFile) -> List {
-	List::new(config, Arc::new(Mutex::new(todo_file)))
+	List::new(
+		config,
+		Arc::new(Mutex::new(todo_file)),
+		vec![],
+		None,
+		InputHistory::default(),
+	)
 }
 
 #[test]
@@ -0,0 +1,184 @@
+use super::*;
+use crate::{
+	action_line,
+	assert_rendered_output,
+	input::{KeyCode, KeyEvent, KeySequence},
+};
+
+const LINES: &[&str] = &["pick aaa c1", "pick bbb c2", "pick ccc c3", "pick ddd c4", "pick eee c5"];
+
+#[test]
+fn move_down_with_count() {
+	testers::module(
+		LINES,
+		&[Event::from('3'), Event::from(StandardEvent::MoveCursorDown)],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.todo_file.lock().get_selected_line_index(), 3);
+		},
+	);
+}
+
+#[test]
+fn move_up_with_multiple_digit_count() {
+	testers::module(
+		LINES,
+		&[
+			Event::from(StandardEvent::MoveCursorEnd),
+			Event::from('1'),
+			Event::from('0'),
+			Event::from(StandardEvent::MoveCursorUp),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.todo_file.lock().get_selected_line_index(), 0);
+		},
+	);
+}
+
+#[test]
+fn count_applies_to_next_event_only() {
+	testers::module(
+		LINES,
+		&[
+			Event::from('2'),
+			Event::from(StandardEvent::MoveCursorDown),
+			Event::from(StandardEvent::MoveCursorDown),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.todo_file.lock().get_selected_line_index(), 3);
+		},
+	);
+}
+
+#[test]
+fn count_cancelled_by_other_event() {
+	testers::module(
+		LINES,
+		&[
+			Event::from('4'),
+			Event::from(KeyCode::Esc),
+			Event::from(StandardEvent::MoveCursorDown),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.todo_file.lock().get_selected_line_index(), 1);
+		},
+	);
+}
+
+#[test]
+fn zero_without_count_is_not_a_count() {
+	testers::module(
+		LINES,
+		&[Event::from('0'), Event::from(StandardEvent::MoveCursorDown)],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.todo_file.lock().get_selected_line_index(), 1);
+		},
+	);
+}
+
+#[test]
+fn swap_down_with_count() {
+	testers::module(
+		LINES,
+		&[Event::from('2'), Event::from(StandardEvent::SwapSelectedDown)],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_rendered_output!(
+				Body test_context.build_view_data(&mut module),
+				action_line!(Pick "bbb", "c2"),
+				action_line!(Pick "ccc", "c3"),
+				action_line!(Selected Pick "aaa", "c1"),
+				action_line!(Pick "ddd", "c4"),
+				action_line!(Pick "eee", "c5")
+			);
+		},
+	);
+}
+
+#[test]
+fn swap_up_with_count_in_visual_mode() {
+	testers::module(
+		LINES,
+		&[
+			Event::from(StandardEvent::MoveCursorEnd),
+			Event::from(StandardEvent::ToggleVisualMode),
+			Event::from(StandardEvent::MoveCursorUp),
+			Event::from('9'),
+			Event::from(StandardEvent::SwapSelectedUp),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_rendered_output!(
+				Body test_context.build_view_data(&mut module),
+				action_line!(Selected Pick "ddd", "c4"),
+				action_line!(Selected Pick "eee", "c5"),
+				action_line!(Pick "aaa", "c1"),
+				action_line!(Pick "bbb", "c2"),
+				action_line!(Pick "ccc", "c3")
+			);
+		},
+	);
+}
+
+#[test]
+fn render_pending_count() {
+	testers::module(&["pick aaa c1"], &[Event::from('1'), Event::from('2')], |mut test_context| {
+		let mut module = create_list(&create_config(), test_context.take_todo_file());
+		_ = test_context.handle_all_events(&mut module);
+		assert_rendered_output!(
+			Style test_context.build_view_data(&mut module),
+			"{TITLE}{HELP}",
+			"{LEADING}",
+			"{IndicatorColor}Pending: 12",
+			"{BODY}",
+			"{Selected}{Normal} > {ActionPick}pick   {Normal}aaa      c1{Pad( )}"
+		);
+	});
+}
+
+#[test]
+fn render_pending_key_sequence() {
+	let keys = KeySequence::new(&[KeyEvent::from(KeyCode::Char('g'))]);
+	testers::module(
+		&["pick aaa c1"],
+		&[Event::from('5'), Event::PendingKeySequence(keys)],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_rendered_output!(
+				Style test_context.build_view_data(&mut module),
+				"{TITLE}{HELP}",
+				"{LEADING}",
+				"{IndicatorColor}Pending: 5g",
+				"{BODY}",
+				"{Selected}{Normal} > {ActionPick}pick   {Normal}aaa      c1{Pad( )}"
+			);
+		},
+	);
+}
+
+#[test]
+fn pending_key_sequence_cleared_by_next_event() {
+	let keys = KeySequence::new(&[KeyEvent::from(KeyCode::Char('g'))]);
+	testers::module(
+		LINES,
+		&[Event::PendingKeySequence(keys), Event::from(StandardEvent::MoveCursorDown)],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert!(module.pending_keys.is_none());
+			assert_eq!(module.todo_file.lock().get_selected_line_index(), 1);
+		},
+	);
+}
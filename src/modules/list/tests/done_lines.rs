@@ -0,0 +1,229 @@
+use claims::assert_some_eq;
+
+use super::*;
+use crate::{action_line, assert_rendered_output, assert_results, process::Artifact};
+
+fn create_list_with_done_lines(mut todo_file: TodoFile, done_lines: &[&str]) -> List {
+	todo_file.set_done_lines(done_lines.iter().map(|l| Line::parse(l).unwrap()).collect());
+	create_list(&create_config(), todo_file)
+}
+
+#[test]
+fn render_done_lines_before_todo_lines() {
+	testers::module(&["pick ccc c3"], &[], |mut test_context| {
+		let mut module = create_list_with_done_lines(test_context.take_todo_file(), &["pick aaa c1", "exec echo foo"]);
+		let view_data = test_context.build_view_data(&mut module);
+		assert_rendered_output!(
+			Style view_data,
+			"{TITLE}{HELP}",
+			"{BODY}",
+			"{Normal,Dimmed}   {ActionPick,Dimmed}pick   {Normal,Dimmed}aaa     {Normal} {Normal,Dimmed}c1",
+			"{Normal,Dimmed}   {ActionExec,Dimmed}exec   {Normal,Dimmed}echo foo",
+			"{Selected}{Normal} > {ActionPick}pick   {Normal}ccc      c3{Pad( )}"
+		);
+	});
+}
+
+#[test]
+fn move_cursor_up_into_done_lines() {
+	testers::module(
+		&["pick ccc c3"],
+		&[Event::from(StandardEvent::MoveCursorUp)],
+		|mut test_context| {
+			let mut module =
+				create_list_with_done_lines(test_context.take_todo_file(), &["pick aaa c1", "pick bbb c2"]);
+			_ = test_context.handle_all_events(&mut module);
+			assert!(module.todo_file.lock().is_done_line_selected());
+			assert_some_eq!(module.todo_file.lock().get_selected_done_line_index(), 1);
+			let view_data = test_context.build_view_data(&mut module);
+			assert_rendered_output!(
+				Style view_data,
+				"{TITLE}{HELP}",
+				"{BODY}",
+				"{Normal,Dimmed}   {ActionPick,Dimmed}pick   {Normal,Dimmed}aaa     {Normal} {Normal,Dimmed}c1",
+				"{Selected}{Normal} > {ActionPick,Dimmed}pick   {Normal,Dimmed}bbb     {Normal} {Normal,Dimmed}c2{Normal}{Pad( )}",
+				"{Normal}   {ActionPick}pick   {Normal}ccc      c3"
+			);
+		},
+	);
+}
+
+#[test]
+fn move_cursor_up_past_done_lines() {
+	testers::module(
+		&["pick ccc c3"],
+		&[Event::from(StandardEvent::MoveCursorUp); 3],
+		|mut test_context| {
+			let mut module =
+				create_list_with_done_lines(test_context.take_todo_file(), &["pick aaa c1", "pick bbb c2"]);
+			_ = test_context.handle_all_events(&mut module);
+			assert_some_eq!(module.todo_file.lock().get_selected_done_line_index(), 0);
+		},
+	);
+}
+
+#[test]
+fn move_cursor_down_out_of_done_lines() {
+	testers::module(
+		&["pick ccc c3", "pick ddd c4"],
+		&[
+			Event::from(StandardEvent::MoveCursorUp),
+			Event::from(StandardEvent::MoveCursorDown),
+		],
+		|mut test_context| {
+			let mut module = create_list_with_done_lines(test_context.take_todo_file(), &["pick aaa c1"]);
+			_ = test_context.handle_all_events(&mut module);
+			assert!(!module.todo_file.lock().is_done_line_selected());
+			assert_eq!(module.todo_file.lock().get_selected_line_index(), 0);
+		},
+	);
+}
+
+#[test]
+fn move_cursor_home_selects_first_todo_line() {
+	testers::module(
+		&["pick ccc c3", "pick ddd c4"],
+		&[
+			Event::from(StandardEvent::MoveCursorUp),
+			Event::from(StandardEvent::MoveCursorHome),
+		],
+		|mut test_context| {
+			let mut module = create_list_with_done_lines(test_context.take_todo_file(), &["pick aaa c1"]);
+			_ = test_context.handle_all_events(&mut module);
+			assert!(!module.todo_file.lock().is_done_line_selected());
+			assert_eq!(module.todo_file.lock().get_selected_line_index(), 0);
+		},
+	);
+}
+
+#[test]
+fn visual_mode_does_not_select_done_lines() {
+	testers::module(
+		&["pick ccc c3", "pick ddd c4"],
+		&[
+			Event::from(StandardEvent::ToggleVisualMode),
+			Event::from(StandardEvent::MoveCursorUp),
+		],
+		|mut test_context| {
+			let mut module = create_list_with_done_lines(test_context.take_todo_file(), &["pick aaa c1"]);
+			_ = test_context.handle_all_events(&mut module);
+			assert!(!module.todo_file.lock().is_done_line_selected());
+		},
+	);
+}
+
+#[test]
+fn done_lines_are_not_modified() {
+	testers::module(
+		&["pick ccc c3"],
+		&[
+			Event::from(StandardEvent::MoveCursorUp),
+			Event::from(StandardEvent::ActionDrop),
+			Event::from(StandardEvent::Delete),
+			Event::from(StandardEvent::SwapSelectedDown),
+			Event::from(StandardEvent::ActionBreak),
+			Event::from(StandardEvent::ToggleVisualMode),
+			Event::from(StandardEvent::ToggleMark),
+			Event::from(StandardEvent::GatherMarked),
+			Event::from(StandardEvent::SortByAuthorDate),
+			Event::from(StandardEvent::SortByCommitDate),
+			Event::from(StandardEvent::ReverseSelection),
+			Event::from(StandardEvent::RestoreOrder),
+			Event::from(StandardEvent::ToggleSection),
+			Event::from(StandardEvent::ApplyToMatches),
+			Event::from(StandardEvent::MoveCursorDown),
+		],
+		|mut test_context| {
+			let mut module = create_list_with_done_lines(test_context.take_todo_file(), &["pick aaa c1"]);
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.state, ListState::Normal);
+			let view_data = test_context.build_view_data(&mut module);
+			assert_rendered_output!(
+				view_data,
+				"{TITLE}{HELP}",
+				"{BODY}",
+				"   pick   aaa      c1",
+				"{Selected} > pick   ccc      c3{Pad( )}"
+			);
+		},
+	);
+}
+
+#[test]
+fn done_lines_are_not_written() {
+	testers::module(&["pick ccc c3"], &[], |mut test_context| {
+		let module = create_list_with_done_lines(test_context.take_todo_file(), &["pick aaa c1"]);
+		let todo_file = module.todo_file.lock();
+		assert_eq!(todo_file.get_lines_owned().len(), 1);
+		todo_file.write_file().unwrap();
+		assert_eq!(
+			std::fs::read_to_string(todo_file.get_filepath()).unwrap(),
+			"pick ccc c3\n"
+		);
+	});
+}
+
+#[test]
+fn show_commit_for_done_line() {
+	testers::module(
+		&["exec echo foo"],
+		&[
+			Event::from(StandardEvent::MoveCursorUp),
+			Event::from(StandardEvent::ShowCommit),
+		],
+		|mut test_context| {
+			let mut module = create_list_with_done_lines(test_context.take_todo_file(), &["pick aaa c1"]);
+			_ = test_context.handle_event(&mut module);
+			assert_results!(
+				test_context.handle_event(&mut module),
+				Artifact::Event(Event::from(StandardEvent::ShowCommit)),
+				Artifact::ChangeState(State::ShowCommit)
+			);
+			assert_some_eq!(module.todo_file.lock().get_selected_line().map(Line::get_hash), "aaa");
+		},
+	);
+}
+
+#[test]
+fn edit_todo_line_after_leaving_done_lines() {
+	testers::module(
+		&["pick ccc c3"],
+		&[
+			Event::from(StandardEvent::MoveCursorUp),
+			Event::from(StandardEvent::MoveCursorDown),
+			Event::from(StandardEvent::ActionDrop),
+		],
+		|mut test_context| {
+			let mut module = create_list_with_done_lines(test_context.take_todo_file(), &["pick aaa c1"]);
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.todo_file.lock().get_line(0).unwrap().get_action(), &Action::Drop);
+			let view_data = test_context.build_view_data(&mut module);
+			assert_rendered_output!(
+				Body, Skip 1,
+				view_data,
+				action_line!(Selected Drop "ccc", "c3")
+			);
+		},
+	);
+}
+
+#[test]
+fn undo_and_redo_from_done_lines() {
+	testers::module(
+		&["pick ccc c3"],
+		&[
+			Event::from(StandardEvent::ActionDrop),
+			Event::from(StandardEvent::MoveCursorUp),
+			Event::from(StandardEvent::Undo),
+			Event::from(StandardEvent::MoveCursorUp),
+			Event::from(StandardEvent::Redo),
+		],
+		|mut test_context| {
+			let mut module = create_list_with_done_lines(test_context.take_todo_file(), &["pick aaa c1"]);
+			_ = test_context.handle_n_events(&mut module, 3);
+			assert_eq!(module.todo_file.lock().get_line(0).unwrap().get_action(), &Action::Pick);
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.todo_file.lock().get_line(0).unwrap().get_action(), &Action::Drop);
+		},
+	);
+}
@@ -1,5 +1,14 @@
+use anyhow::anyhow;
+
 use super::*;
-use crate::{assert_rendered_output, assert_results, input::KeyCode, process::Artifact};
+use crate::{
+	assert_rendered_output,
+	assert_results,
+	components::HistoryKind,
+	input::KeyCode,
+	process::Artifact,
+	test_helpers::with_temporary_path,
+};
 
 #[test]
 fn edit_with_edit_content() {
@@ -87,3 +96,140 @@ fn render() {
 		},
 	);
 }
+
+#[test]
+fn edit_exec_recalls_history() {
+	testers::module(
+		&["exec foo", "exec bar"],
+		&[
+			Event::from(StandardEvent::Edit),
+			Event::from(KeyCode::Char('x')),
+			Event::from(KeyCode::Enter),
+			Event::from(StandardEvent::MoveCursorDown),
+			Event::from(StandardEvent::Edit),
+			Event::from(KeyCode::Up),
+			Event::from(KeyCode::Enter),
+		],
+		|mut test_context| {
+			let history = InputHistory::new(10);
+			let mut module = List::new(
+				&create_config(),
+				Arc::new(Mutex::new(test_context.take_todo_file())),
+				vec![],
+				None,
+				history.clone(),
+			);
+			_ = test_context.build_view_data(&mut module);
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(history.entries(HistoryKind::Exec), vec!["foox"]);
+			assert_eq!(module.todo_file.lock().get_line(1).unwrap().get_content(), "foox");
+		},
+	);
+}
+
+#[test]
+fn edit_label_uses_label_history() {
+	testers::module(
+		&["label foo"],
+		&[Event::from(StandardEvent::Edit), Event::from(KeyCode::Enter)],
+		|mut test_context| {
+			let history = InputHistory::new(10);
+			let mut module = List::new(
+				&create_config(),
+				Arc::new(Mutex::new(test_context.take_todo_file())),
+				vec![],
+				None,
+				history.clone(),
+			);
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(history.entries(HistoryKind::Label), vec!["foo"]);
+			assert!(history.entries(HistoryKind::Exec).is_empty());
+		},
+	);
+}
+
+#[test]
+fn edit_reset_completes_earlier_label() {
+	testers::module(
+		&["label onto", "pick aaa c1", "label other", "reset o", "label later"],
+		&[
+			Event::from(StandardEvent::MoveCursorDown),
+			Event::from(StandardEvent::MoveCursorDown),
+			Event::from(StandardEvent::MoveCursorDown),
+			Event::from(StandardEvent::Edit),
+			Event::from(KeyCode::Tab),
+			Event::from(KeyCode::Tab),
+			Event::from(KeyCode::Tab),
+			Event::from(KeyCode::Enter),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_n_events(&mut module, 5);
+			let view_data = test_context.build_view_data(&mut module);
+			assert_rendered_output!(
+				Body view_data,
+				"reset onto",
+				"  onto",
+				"  other"
+			);
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.todo_file.lock().get_line(3).unwrap().get_content(), "onto");
+		},
+	);
+}
+
+#[test]
+fn edit_exec_completes_from_history() {
+	testers::module(
+		&["exec c"],
+		&[
+			Event::from(StandardEvent::Edit),
+			Event::from(KeyCode::Tab),
+			Event::from(KeyCode::Enter),
+		],
+		|mut test_context| {
+			let history = InputHistory::new(10);
+			history.push(HistoryKind::Exec, "cargo test");
+			history.push(HistoryKind::Exec, "cargo build");
+			let mut module = List::new(
+				&create_config(),
+				Arc::new(Mutex::new(test_context.take_todo_file())),
+				vec![],
+				None,
+				history,
+			);
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.todo_file.lock().get_line(0).unwrap().get_content(), "cargo build");
+		},
+	);
+}
+
+#[test]
+fn edit_history_write_error() {
+	testers::module(
+		&["exec foo"],
+		&[Event::from(StandardEvent::Edit), Event::from(KeyCode::Enter)],
+		|mut test_context| {
+			with_temporary_path(|path| {
+				let history = InputHistory::load(path.join("missing").join("history").as_path(), 10);
+				let mut module = List::new(
+					&create_config(),
+					Arc::new(Mutex::new(test_context.take_todo_file())),
+					vec![],
+					None,
+					history,
+				);
+				_ = test_context.handle_event(&mut module);
+				assert_results!(
+					test_context.handle_event(&mut module),
+					Artifact::Event(Event::from(KeyCode::Enter)),
+					Artifact::Error(
+						anyhow!("Unable to write input history: No such file or directory (os error 2)"),
+						Some(State::List)
+					)
+				);
+				assert_eq!(module.state, ListState::Normal);
+			});
+		},
+	);
+}
@@ -0,0 +1,225 @@
+use super::*;
+use crate::{assert_rendered_output, input::KeyCode, test_helpers::with_sequencer_todo_file};
+
+fn type_command(command: &str) -> Vec<Event> {
+	let mut events: Vec<Event> = command.chars().map(|c| Event::from(KeyCode::Char(c))).collect();
+	events.push(Event::from(KeyCode::Enter));
+	events
+}
+
+fn create_events(before: &[Event], command: &str) -> Vec<Event> {
+	let mut events = before.to_vec();
+	events.extend(type_command(command));
+	events
+}
+
+fn todo_lines(module: &List) -> Vec<String> {
+	module.todo_file.lock().lines_iter().map(Line::to_text).collect()
+}
+
+#[test]
+fn after_every_commit() {
+	testers::module(
+		&["pick aaa c1", "fixup bbb c2", "break", "pick ccc c3"],
+		&create_events(&[Event::from(StandardEvent::InsertExecLines)], "make"),
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.state, ListState::Normal);
+			assert_eq!(todo_lines(&module), vec![
+				"pick aaa c1",
+				"fixup bbb c2",
+				"exec make",
+				"break",
+				"pick ccc c3",
+				"exec make",
+			]);
+		},
+	);
+}
+
+#[test]
+fn after_selected_commits() {
+	testers::module(
+		&["pick aaa c1", "pick bbb c2", "pick ccc c3", "pick ddd c4"],
+		&create_events(
+			&[
+				Event::from(StandardEvent::MoveCursorDown),
+				Event::from(StandardEvent::ToggleVisualMode),
+				Event::from(StandardEvent::MoveCursorDown),
+				Event::from(StandardEvent::InsertExecLines),
+			],
+			"make",
+		),
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.state, ListState::Normal);
+			assert_eq!(todo_lines(&module), vec![
+				"pick aaa c1",
+				"pick bbb c2",
+				"exec make",
+				"pick ccc c3",
+				"exec make",
+				"pick ddd c4",
+			]);
+			assert_eq!(module.todo_file.lock().get_selected_line_index(), 3);
+		},
+	);
+}
+
+#[test]
+fn choose_target_with_update_refs() {
+	testers::module(
+		&["pick aaa c1", "pick bbb c2", "update-ref refs/heads/a", "pick ccc c3"],
+		&[Event::from(StandardEvent::InsertExecLines)],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.state, ListState::ExecLines);
+			let view_data = test_context.build_view_data(&mut module);
+			assert_rendered_output!(
+				view_data,
+				"{TITLE}",
+				"{LEADING}",
+				"Select where to insert the exec lines:",
+				"",
+				"{BODY}",
+				"c) After every commit",
+				"b) After the last commit of each branch",
+				"q) Cancel",
+				"",
+				"Please choose an option."
+			);
+		},
+	);
+}
+
+#[test]
+fn after_every_branch() {
+	testers::module(
+		&["pick aaa c1", "pick bbb c2", "update-ref refs/heads/a", "pick ccc c3"],
+		&create_events(
+			&[
+				Event::from(StandardEvent::InsertExecLines),
+				Event::from(KeyCode::Char('b')),
+			],
+			"make",
+		),
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(todo_lines(&module), vec![
+				"pick aaa c1",
+				"pick bbb c2",
+				"exec make",
+				"update-ref refs/heads/a",
+				"pick ccc c3",
+			]);
+		},
+	);
+}
+
+#[test]
+fn cancel_target_choice() {
+	testers::module(
+		&["pick aaa c1", "update-ref refs/heads/a"],
+		&[
+			Event::from(StandardEvent::InsertExecLines),
+			Event::from(KeyCode::Char('q')),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.state, ListState::Normal);
+			assert_eq!(todo_lines(&module), vec!["pick aaa c1", "update-ref refs/heads/a"]);
+		},
+	);
+}
+
+#[test]
+fn empty_command_cancels() {
+	testers::module(
+		&["pick aaa c1"],
+		&create_events(&[Event::from(StandardEvent::InsertExecLines)], ""),
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.state, ListState::Normal);
+			assert_eq!(todo_lines(&module), vec!["pick aaa c1"]);
+		},
+	);
+}
+
+#[test]
+fn template_name_uses_command() {
+	testers::module(
+		&["pick aaa c1"],
+		&create_events(&[Event::from(StandardEvent::InsertExecLines)], "test"),
+		|mut test_context| {
+			let mut config = create_config();
+			config.exec_templates = vec![(String::from("test"), String::from("cargo test --workspace"))];
+			let mut module = create_list(&config, test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(todo_lines(&module), vec!["pick aaa c1", "exec cargo test --workspace"]);
+		},
+	);
+}
+
+#[test]
+fn render_command_edit() {
+	testers::module(
+		&["pick aaa c1"],
+		&[Event::from(StandardEvent::InsertExecLines)],
+		|mut test_context| {
+			let mut config = create_config();
+			config.exec_templates = vec![(String::from("test"), String::from("cargo test"))];
+			let mut module = create_list(&config, test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			let view_data = test_context.build_view_data(&mut module);
+			assert_rendered_output!(
+				Style view_data,
+				"{TITLE}",
+				"{LEADING}",
+				"{IndicatorColor}Enter the command to run after every commit. Empty content cancels the insertion.",
+				"{IndicatorColor}Enter the name of a template to use its command:",
+				"{Normal}  test: {Normal,Dimmed}cargo test",
+				"",
+				"{BODY}",
+				"{Normal,Dimmed}exec {Normal,Underline}",
+				"{TRAILING}",
+				"{IndicatorColor}Enter to finish"
+			);
+		},
+	);
+}
+
+#[test]
+fn undo_removes_all_inserted_lines() {
+	testers::module(
+		&["pick aaa c1", "pick bbb c2"],
+		&create_events(&[Event::from(StandardEvent::InsertExecLines)], "make"),
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(todo_lines(&module).len(), 4);
+			_ = module.todo_file.lock().undo();
+			assert_eq!(todo_lines(&module), vec!["pick aaa c1", "pick bbb c2"]);
+		},
+	);
+}
+
+#[test]
+fn not_allowed_in_sequencer() {
+	with_sequencer_todo_file(&["pick aaa c1"], |todo_file| {
+		testers::module(
+			&[],
+			&[Event::from(StandardEvent::InsertExecLines)],
+			|mut test_context| {
+				let mut module = create_list(&create_config(), todo_file);
+				_ = test_context.handle_all_events(&mut module);
+				assert_eq!(module.state, ListState::Normal);
+			},
+		);
+	});
+}
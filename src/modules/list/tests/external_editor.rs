@@ -38,3 +38,66 @@ fn visual_mode_open_external_editor() {
 		},
 	);
 }
+
+#[test]
+fn normal_mode_open_selection_in_external_editor() {
+	testers::module(
+		&["pick aaa c1", "pick bbb c2", "pick ccc c3"],
+		&[
+			Event::from(StandardEvent::MoveCursorDown),
+			Event::from(StandardEvent::OpenSelectionInEditor),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_event(&mut module);
+			assert_results!(
+				test_context.handle_event(&mut module),
+				Artifact::Event(Event::from(StandardEvent::OpenSelectionInEditor)),
+				Artifact::SearchCancel,
+				Artifact::ChangeState(State::ExternalEditor)
+			);
+			assert_eq!(module.todo_file.lock().take_edit_range(), Some((1, 1)));
+		},
+	);
+}
+
+#[test]
+fn visual_mode_open_selection_in_external_editor() {
+	testers::module(
+		&["pick aaa c1", "pick bbb c2", "pick ccc c3"],
+		&[
+			Event::from(StandardEvent::MoveCursorDown),
+			Event::from(StandardEvent::MoveCursorDown),
+			Event::from(StandardEvent::ToggleVisualMode),
+			Event::from(StandardEvent::MoveCursorUp),
+			Event::from(StandardEvent::OpenSelectionInEditor),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_n_events(&mut module, 4);
+			assert_results!(
+				test_context.handle_event(&mut module),
+				Artifact::Event(Event::from(StandardEvent::OpenSelectionInEditor)),
+				Artifact::SearchCancel,
+				Artifact::ChangeState(State::ExternalEditor)
+			);
+			assert_eq!(module.todo_file.lock().take_edit_range(), Some((1, 2)));
+		},
+	);
+}
+
+#[test]
+fn open_selection_in_external_editor_empty_list() {
+	testers::module(
+		&[],
+		&[Event::from(StandardEvent::OpenSelectionInEditor)],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			assert_results!(
+				test_context.handle_event(&mut module),
+				Artifact::Event(Event::from(StandardEvent::OpenSelectionInEditor))
+			);
+			assert_eq!(module.todo_file.lock().take_edit_range(), None);
+		},
+	);
+}
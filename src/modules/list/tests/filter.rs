@@ -0,0 +1,307 @@
+use claims::assert_some_eq;
+
+use super::*;
+use crate::{
+	assert_rendered_output,
+	search::SearchResult,
+	test_helpers::{testers::ModuleTestContext, with_temp_repository},
+};
+
+const LINES: &[&str] = &["pick aaa c1", "pick bbb c2", "pick ccc c3", "pick ddd c4"];
+
+fn create_filtered_list(test_context: &mut ModuleTestContext, term: &str) -> List {
+	let mut module = create_list(&create_config(), test_context.take_todo_file());
+	module.filter = Some(Filter::new(term));
+	module
+}
+
+fn lines(module: &List) -> Vec<String> {
+	module
+		.todo_file
+		.lock()
+		.get_lines_owned()
+		.iter()
+		.map(Line::to_text)
+		.collect()
+}
+
+#[test]
+fn render() {
+	testers::module(LINES, &[], |mut test_context| {
+		let mut module = create_filtered_list(&mut test_context, "c1|c3");
+		let view_data = test_context.build_view_data(&mut module);
+		assert_rendered_output!(
+			Style view_data,
+			"{TITLE}{HELP}",
+			"{LEADING}",
+			"{IndicatorColor}Filter [c1|c3]: 2/4 lines",
+			"{BODY}",
+			"{Selected}{Normal} > {ActionPick}pick   {Normal}aaa      c1{Pad( )}",
+			"{Normal}   {ActionPick}pick   {Normal}ccc      c3"
+		);
+	});
+}
+
+#[test]
+fn render_no_matches() {
+	testers::module(LINES, &[], |mut test_context| {
+		let mut module = create_filtered_list(&mut test_context, "missing");
+		let view_data = test_context.build_view_data(&mut module);
+		assert_rendered_output!(
+			Style view_data,
+			"{TITLE}{HELP}",
+			"{LEADING}",
+			"{IndicatorColor}Filter [missing]: 0/4 lines",
+			"{BODY}",
+			"{IndicatorColor}No lines match the filter"
+		);
+	});
+}
+
+#[test]
+fn render_hides_done_lines() {
+	testers::module(LINES, &[], |mut test_context| {
+		let mut todo_file = test_context.take_todo_file();
+		todo_file.set_done_lines(vec![Line::parse("pick eee c1").unwrap()]);
+		let mut module = create_list(&create_config(), todo_file);
+		module.filter = Some(Filter::new("c1"));
+		let view_data = test_context.build_view_data(&mut module);
+		assert_rendered_output!(
+			Body view_data,
+			"{Selected} > pick   aaa      c1{Pad( )}"
+		);
+	});
+}
+
+#[test]
+fn toggle_filter_without_search() {
+	testers::module(LINES, &[Event::from(StandardEvent::ToggleFilter)], |mut test_context| {
+		let mut module = create_list(&create_config(), test_context.take_todo_file());
+		_ = test_context.handle_all_events(&mut module);
+		assert!(module.filter.is_none());
+	});
+}
+
+#[test]
+fn toggle_filter_with_search() {
+	testers::module(LINES, &[Event::from(StandardEvent::ToggleFilter)], |mut test_context| {
+		let mut module = create_list(&create_config(), test_context.take_todo_file());
+		module.search_bar.start_search(Some("c3"));
+		_ = module.search_bar.handle_event(Event::from(StandardEvent::SearchFinish));
+		_ = test_context.handle_all_events(&mut module);
+		assert_eq!(module.filter.as_ref().map(Filter::term), Some("c3"));
+		assert_eq!(module.todo_file.lock().get_selected_line_index(), 2);
+	});
+}
+
+#[test]
+fn toggle_filter_off() {
+	testers::module(LINES, &[Event::from(StandardEvent::ToggleFilter)], |mut test_context| {
+		let mut module = create_filtered_list(&mut test_context, "c1");
+		_ = test_context.handle_all_events(&mut module);
+		assert!(module.filter.is_none());
+	});
+}
+
+#[test]
+fn toggle_stack_view_removes_filter() {
+	testers::module(LINES, &[Event::from(StandardEvent::ToggleStackView)], |mut test_context| {
+		let mut module = create_filtered_list(&mut test_context, "c1");
+		_ = test_context.handle_all_events(&mut module);
+		assert_eq!(module.view, ListView::Stack);
+		assert!(module.filter.is_none());
+	});
+}
+
+#[test]
+fn move_cursor_skips_hidden_lines() {
+	testers::module(LINES, &[Event::from(StandardEvent::MoveCursorDown)], |mut test_context| {
+		let mut module = create_filtered_list(&mut test_context, "c1|c4");
+		_ = test_context.handle_all_events(&mut module);
+		assert_eq!(module.todo_file.lock().get_selected_line_index(), 3);
+	});
+}
+
+#[test]
+fn action_only_changes_visible_lines() {
+	testers::module(
+		LINES,
+		&[
+			Event::from(StandardEvent::ToggleVisualMode),
+			Event::from(StandardEvent::MoveCursorDown),
+			Event::from(StandardEvent::ActionDrop),
+		],
+		|mut test_context| {
+			let mut module = create_filtered_list(&mut test_context, "c1|c3");
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(lines(&module), vec![
+				"drop aaa c1",
+				"pick bbb c2",
+				"drop ccc c3",
+				"pick ddd c4"
+			]);
+		},
+	);
+}
+
+#[test]
+fn action_hiding_selected_line() {
+	testers::module(LINES, &[Event::from(StandardEvent::ActionDrop)], |mut test_context| {
+		let mut module = create_filtered_list(&mut test_context, "action:pick");
+		_ = test_context.handle_all_events(&mut module);
+		assert_eq!(module.todo_file.lock().get_selected_line_index(), 1);
+	});
+}
+
+#[test]
+fn action_without_matches() {
+	testers::module(LINES, &[Event::from(StandardEvent::ActionDrop)], |mut test_context| {
+		let mut module = create_filtered_list(&mut test_context, "missing");
+		_ = test_context.handle_all_events(&mut module);
+		assert_eq!(lines(&module), LINES);
+	});
+}
+
+#[test]
+fn delete_keeps_hidden_lines() {
+	testers::module(
+		LINES,
+		&[
+			Event::from(StandardEvent::ToggleVisualMode),
+			Event::from(StandardEvent::MoveCursorDown),
+			Event::from(StandardEvent::Delete),
+		],
+		|mut test_context| {
+			let mut module = create_filtered_list(&mut test_context, "c1|c3");
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(lines(&module), vec!["pick bbb c2", "pick ddd c4"]);
+		},
+	);
+}
+
+#[test]
+fn delete_and_undo() {
+	testers::module(
+		LINES,
+		&[
+			Event::from(StandardEvent::ToggleVisualMode),
+			Event::from(StandardEvent::MoveCursorDown),
+			Event::from(StandardEvent::Delete),
+			Event::from(StandardEvent::Undo),
+		],
+		|mut test_context| {
+			let mut module = create_filtered_list(&mut test_context, "c1|c3");
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(lines(&module), LINES);
+			assert_eq!(module.state, ListState::Normal);
+		},
+	);
+}
+
+#[test]
+fn delete_without_hidden_lines() {
+	testers::module(LINES, &[Event::from(StandardEvent::Delete)], |mut test_context| {
+		let mut module = create_filtered_list(&mut test_context, "c1|c3");
+		_ = test_context.handle_all_events(&mut module);
+		assert_eq!(lines(&module), vec!["pick bbb c2", "pick ccc c3", "pick ddd c4"]);
+		assert_eq!(module.todo_file.lock().get_selected_line_index(), 1);
+	});
+}
+
+#[test]
+fn swap_selected_down_skips_hidden_lines() {
+	testers::module(LINES, &[Event::from(StandardEvent::SwapSelectedDown)], |mut test_context| {
+		let mut module = create_filtered_list(&mut test_context, "c1|c3");
+		_ = test_context.handle_all_events(&mut module);
+		assert_eq!(lines(&module), vec![
+			"pick bbb c2",
+			"pick ccc c3",
+			"pick aaa c1",
+			"pick ddd c4"
+		]);
+		assert_eq!(module.todo_file.lock().get_selected_line_index(), 2);
+	});
+}
+
+#[test]
+fn swap_selected_up_skips_hidden_lines() {
+	testers::module(
+		LINES,
+		&[
+			Event::from(StandardEvent::MoveCursorDown),
+			Event::from(StandardEvent::SwapSelectedUp),
+		],
+		|mut test_context| {
+			let mut module = create_filtered_list(&mut test_context, "c1|c3");
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(lines(&module), vec![
+				"pick ccc c3",
+				"pick aaa c1",
+				"pick bbb c2",
+				"pick ddd c4"
+			]);
+			assert_eq!(module.todo_file.lock().get_selected_line_index(), 0);
+		},
+	);
+}
+
+#[test]
+fn swap_selected_up_first_visible_line() {
+	testers::module(LINES, &[Event::from(StandardEvent::SwapSelectedUp)], |mut test_context| {
+		let mut module = create_filtered_list(&mut test_context, "c2|c3");
+		_ = module.todo_file.lock().set_selected_line_index(1);
+		_ = test_context.handle_all_events(&mut module);
+		assert_eq!(lines(&module), LINES);
+	});
+}
+
+#[test]
+fn swap_selected_down_visual_range() {
+	testers::module(
+		LINES,
+		&[
+			Event::from(StandardEvent::ToggleVisualMode),
+			Event::from(StandardEvent::MoveCursorDown),
+			Event::from(StandardEvent::SwapSelectedDown),
+		],
+		|mut test_context| {
+			let mut module = create_filtered_list(&mut test_context, "c1|c2|c4");
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(lines(&module), vec![
+				"pick ccc c3",
+				"pick ddd c4",
+				"pick aaa c1",
+				"pick bbb c2"
+			]);
+			assert_eq!(module.todo_file.lock().get_selected_line_index(), 3);
+			assert_eq!(module.visual_index_start, Some(2));
+		},
+	);
+}
+
+#[test]
+fn commit_filter_uses_search_matches() {
+	with_temp_repository(|repository| {
+		let hash = repository.commit_id_from_ref("refs/heads/main").unwrap().to_string();
+		let line = format!("pick {hash} c1");
+		testers::module(&[line.as_str(), "pick bbb c2"], &[], |mut test_context| {
+			let mut module = List::new(
+				&create_config(),
+				Arc::new(Mutex::new(test_context.take_todo_file())),
+				vec![],
+				Some(repository),
+				InputHistory::default(),
+			);
+			module.filter = Some(Filter::new("author:^name <"));
+			module.update_filter_commit_matches();
+			assert_some_eq!(module.filter_rows(&module.todo_file.lock()), vec![]);
+
+			assert_eq!(
+				testers::SearchableRunner::new(&module.search).run_search("author:^name <"),
+				SearchResult::Updated
+			);
+			module.update_filter_commit_matches();
+			assert_some_eq!(module.filter_rows(&module.todo_file.lock()), vec![0]);
+		});
+	});
+}
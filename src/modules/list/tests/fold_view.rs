@@ -0,0 +1,353 @@
+use super::*;
+use crate::{
+	assert_rendered_output,
+	assert_results,
+	process::Artifact,
+	test_helpers::{assertions::assert_rendered_output::AssertRenderOptions, with_sequencer_todo_file},
+};
+
+const LINES: &[&str] = &[
+	"pick aaa c1",
+	"fixup bbb c2",
+	"squash ccc c3",
+	"pick ddd c4",
+	"pick eee c5",
+	"fixup fff c6",
+];
+
+fn lines(module: &List) -> Vec<String> {
+	module
+		.todo_file
+		.lock()
+		.get_lines_owned()
+		.iter()
+		.map(Line::to_text)
+		.collect()
+}
+
+fn selected_index(module: &List) -> usize {
+	module.todo_file.lock().get_selected_line_index()
+}
+
+#[test]
+fn render() {
+	testers::module(LINES, &[Event::from(StandardEvent::ToggleFoldView)], |mut test_context| {
+		let mut module = create_list(&create_config(), test_context.take_todo_file());
+		_ = test_context.handle_all_events(&mut module);
+		let view_data = test_context.build_view_data(&mut module);
+		assert_rendered_output!(
+			Options AssertRenderOptions::INCLUDE_STYLE | AssertRenderOptions::BODY_ONLY,
+			view_data,
+			"{Selected}{Normal} > {ActionPick}pick   {Normal}aaa      c1{IndicatorColor} [+2]{Normal}{Pad( )}",
+			"{Normal}   {ActionPick}pick   {Normal}ddd      c4",
+			"{Normal}   {ActionPick}pick   {Normal}eee      c5{IndicatorColor} [+1]"
+		);
+	});
+}
+
+#[test]
+fn render_expanded_group() {
+	testers::module(
+		LINES,
+		&[
+			Event::from(StandardEvent::ToggleFoldView),
+			Event::from(StandardEvent::ToggleSection),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			let view_data = test_context.build_view_data(&mut module);
+			assert_rendered_output!(
+				Body view_data,
+				"{Selected} > pick   aaa      c1{Pad( )}",
+				"   fixup  bbb      c2",
+				"   squash ccc      c3",
+				"   pick   ddd      c4",
+				"   pick   eee      c5 [+1]"
+			);
+		},
+	);
+}
+
+#[test]
+fn toggle_fold_view_off() {
+	testers::module(
+		LINES,
+		&[
+			Event::from(StandardEvent::ToggleFoldView),
+			Event::from(StandardEvent::ToggleFoldView),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.view, ListView::Lines);
+		},
+	);
+}
+
+#[test]
+fn toggle_fold_view_sequencer() {
+	with_sequencer_todo_file(&["pick aaa c1"], |todo_file| {
+		testers::module(&[], &[Event::from(StandardEvent::ToggleFoldView)], |mut test_context| {
+			let mut module = create_list(&create_config(), todo_file);
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.view, ListView::Lines);
+		});
+	});
+}
+
+#[test]
+fn toggle_fold_view_removes_stack_view() {
+	testers::module(
+		LINES,
+		&[
+			Event::from(StandardEvent::ToggleStackView),
+			Event::from(StandardEvent::ToggleFoldView),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.view, ListView::Fold);
+		},
+	);
+}
+
+#[test]
+fn toggle_stack_view_removes_fold_view() {
+	testers::module(
+		LINES,
+		&[
+			Event::from(StandardEvent::ToggleFoldView),
+			Event::from(StandardEvent::ToggleStackView),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.view, ListView::Stack);
+		},
+	);
+}
+
+#[test]
+fn toggle_fold_view_moves_cursor_to_group() {
+	testers::module(
+		LINES,
+		&[
+			Event::from(StandardEvent::MoveCursorDown),
+			Event::from(StandardEvent::MoveCursorDown),
+			Event::from(StandardEvent::ToggleFoldView),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(selected_index(&module), 0);
+		},
+	);
+}
+
+#[test]
+fn move_cursor_skips_folded_lines() {
+	testers::module(
+		LINES,
+		&[
+			Event::from(StandardEvent::ToggleFoldView),
+			Event::from(StandardEvent::MoveCursorDown),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(selected_index(&module), 3);
+		},
+	);
+}
+
+#[test]
+fn toggle_visual_mode_in_fold_view() {
+	testers::module(
+		LINES,
+		&[
+			Event::from(StandardEvent::ToggleFoldView),
+			Event::from(StandardEvent::ToggleVisualMode),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.state, ListState::Normal);
+		},
+	);
+}
+
+#[test]
+fn collapse_from_folded_line() {
+	testers::module(
+		LINES,
+		&[
+			Event::from(StandardEvent::ToggleFoldView),
+			Event::from(StandardEvent::ToggleSection),
+			Event::from(StandardEvent::MoveCursorDown),
+			Event::from(StandardEvent::MoveCursorDown),
+			Event::from(StandardEvent::ToggleSection),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(selected_index(&module), 0);
+			let todo_file = module.todo_file.lock();
+			assert_eq!(module.fold.collapsed_groups(&todo_file), fold::get_fixup_groups(&todo_file));
+		},
+	);
+}
+
+#[test]
+fn action_on_collapsed_group() {
+	testers::module(
+		LINES,
+		&[
+			Event::from(StandardEvent::ToggleFoldView),
+			Event::from(StandardEvent::ActionDrop),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(lines(&module), vec![
+				"drop aaa c1",
+				"drop bbb c2",
+				"drop ccc c3",
+				"pick ddd c4",
+				"pick eee c5",
+				"fixup fff c6"
+			]);
+		},
+	);
+}
+
+#[test]
+fn show_commit_on_collapsed_group() {
+	testers::module(
+		LINES,
+		&[
+			Event::from(StandardEvent::ToggleFoldView),
+			Event::from(StandardEvent::ShowCommit),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_event(&mut module);
+			assert_results!(
+				test_context.handle_event(&mut module),
+				Artifact::Event(Event::from(StandardEvent::ShowCommit))
+			);
+		},
+	);
+}
+
+#[test]
+fn swap_collapsed_group_down() {
+	testers::module(
+		LINES,
+		&[
+			Event::from(StandardEvent::ToggleFoldView),
+			Event::from(StandardEvent::SwapSelectedDown),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(lines(&module), vec![
+				"pick ddd c4",
+				"pick aaa c1",
+				"fixup bbb c2",
+				"squash ccc c3",
+				"pick eee c5",
+				"fixup fff c6"
+			]);
+			assert_eq!(selected_index(&module), 1);
+		},
+	);
+}
+
+#[test]
+fn swap_collapsed_group_over_collapsed_group() {
+	testers::module(
+		LINES,
+		&[
+			Event::from(StandardEvent::ToggleFoldView),
+			Event::from(StandardEvent::MoveCursorEnd),
+			Event::from(StandardEvent::SwapSelectedUp),
+			Event::from(StandardEvent::SwapSelectedUp),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(lines(&module), vec![
+				"pick eee c5",
+				"fixup fff c6",
+				"pick aaa c1",
+				"fixup bbb c2",
+				"squash ccc c3",
+				"pick ddd c4"
+			]);
+			assert_eq!(selected_index(&module), 0);
+		},
+	);
+}
+
+#[test]
+fn swap_line_down_over_collapsed_group() {
+	testers::module(
+		&["pick aaa c1", "pick bbb c2", "fixup ccc c3", "pick ddd c4"],
+		&[
+			Event::from(StandardEvent::ToggleFoldView),
+			Event::from(StandardEvent::SwapSelectedDown),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(lines(&module), vec!["pick bbb c2", "fixup ccc c3", "pick aaa c1", "pick ddd c4"]);
+			assert_eq!(selected_index(&module), 2);
+		},
+	);
+}
+
+#[test]
+fn swap_collapsed_group_undo() {
+	testers::module(
+		LINES,
+		&[
+			Event::from(StandardEvent::ToggleFoldView),
+			Event::from(StandardEvent::MoveCursorEnd),
+			Event::from(StandardEvent::SwapSelectedUp),
+			Event::from(StandardEvent::SwapSelectedUp),
+			Event::from(StandardEvent::Undo),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(lines(&module), vec![
+				"pick aaa c1",
+				"fixup bbb c2",
+				"squash ccc c3",
+				"pick eee c5",
+				"fixup fff c6",
+				"pick ddd c4"
+			]);
+			assert_eq!(module.state, ListState::Normal);
+		},
+	);
+}
+
+#[test]
+fn swap_collapsed_group_at_end() {
+	testers::module(
+		LINES,
+		&[
+			Event::from(StandardEvent::ToggleFoldView),
+			Event::from(StandardEvent::MoveCursorEnd),
+			Event::from(StandardEvent::SwapSelectedDown),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(lines(&module), LINES);
+		},
+	);
+}
+
@@ -15,38 +15,54 @@ fn normal_mode_help() {
 				view_data,
 				"{TITLE}",
 				"{LEADING}",
-				" Key      Action{Pad( )}",
+				" Key       Action{Pad( )}",
 				"{BODY}",
-				" Up      |Move selection up",
-				" Down    |Move selection down",
-				" PageUp  |Move selection up half a page",
-				" PageDown|Move selection down half a page",
-				" Home    |Move selection to top of the list",
-				" End     |Move selection to end of the list",
-				" Left    |Scroll content to the left",
-				" Right   |Scroll content to the right",
-				" q       |Abort interactive rebase",
-				" Q       |Immediately abort interactive rebase",
-				" w       |Write interactive rebase file",
-				" W       |Immediately write interactive rebase file",
-				" ?       |Show help",
-				" j       |Move selected lines down",
-				" k       |Move selected lines up",
-				" c       |Show commit information",
-				" b       |Toggle break action",
-				" p       |Set selected commits to be picked",
-				" r       |Set selected commits to be reworded",
-				" e       |Set selected commits to be edited",
-				" s       |Set selected commits to be squashed",
-				" f       |Set selected commits to be fixed-up",
-				" d       |Set selected commits to be dropped",
-				" E       |Edit an exec, label, reset or merge action's content",
-				" I       |Insert a new line",
-				" Delete  |Completely remove the selected lines",
-				" Controlz|Undo the last change",
-				" Controly|Redo the previous undone change",
-				" !       |Open the todo file in the default editor",
-				" v       |Enter visual selection mode",
+				" Up       |Move selection up",
+				" Down     |Move selection down",
+				" PageUp   |Move selection up half a page",
+				" PageDown |Move selection down half a page",
+				" Home, g g|Move selection to top of the list",
+				" End      |Move selection to end of the list",
+				" Left     |Scroll content to the left",
+				" Right    |Scroll content to the right",
+				" q        |Abort interactive rebase",
+				" Q        |Immediately abort interactive rebase",
+				" w        |Write interactive rebase file",
+				" W        |Immediately write interactive rebase file",
+				" ?        |Show help",
+				" j        |Move selected lines down",
+				" k        |Move selected lines up",
+				" c        |Show commit information",
+				" b        |Toggle break action",
+				" p        |Set selected commits to be picked",
+				" r        |Set selected commits to be reworded",
+				" e        |Set selected commits to be edited",
+				" s        |Set selected commits to be squashed",
+				" f        |Set selected commits to be fixed-up",
+				" d        |Set selected commits to be dropped",
+				" E        |Edit an exec, label, reset or merge action's content",
+				" A        |Set the next action for all search matches",
+				" Space    |Mark or unmark the selected lines",
+				" G        |Move the marked lines after the selected line",
+				" M        |Clear the marked lines",
+				" m        |Name a mark for the selected line with the next key",
+				" '        |Jump to the line of the mark named by the next key",
+				" I        |Insert a new line",
+				" B        |Insert update-ref lines for branches of the commits",
+				" x        |Insert exec lines after every commit",
+				" C        |Compose a new message for the selected commit",
+				" T        |Change the author, committer date or trailers of the selected commit",
+				" S        |Toggle the stack view of branches",
+				" z        |Collapse or expand the selected section or fixup group",
+				" Z        |Toggle folding fixup and squash lines into their commit",
+				" Delete   |Completely remove the selected lines",
+				" Controlz |Undo the last change",
+				" Controly |Redo the previous undone change",
+				" H        |Browse the undo history and restore a previous state",
+				" !        |Open the todo file in the default editor",
+				" @        |Open the selected lines in the default editor",
+				" v        |Enter visual selection mode",
+				" F        |Show only the lines matching the search",
 				"{TRAILING}",
 				"Press any key to close"
 			);
@@ -82,34 +98,48 @@ fn visual_mode_help() {
 				view_data,
 				"{TITLE}",
 				"{LEADING}",
-				" Key      Action{Pad( )}",
+				" Key       Action{Pad( )}",
 				"{BODY}",
-				" Up      |Move selection up",
-				" Down    |Move selection down",
-				" PageUp  |Move selection up half a page",
-				" PageDown|Move selection down half a page",
-				" Home    |Move selection to top of the list",
-				" End     |Move selection to end of the list",
-				" Left    |Scroll content to the left",
-				" Right   |Scroll content to the right",
-				" q       |Abort interactive rebase",
-				" Q       |Immediately abort interactive rebase",
-				" w       |Write interactive rebase file",
-				" W       |Immediately write interactive rebase file",
-				" ?       |Show help",
-				" j       |Move selected lines down",
-				" k       |Move selected lines up",
-				" p       |Set selected commits to be picked",
-				" r       |Set selected commits to be reworded",
-				" e       |Set selected commits to be edited",
-				" s       |Set selected commits to be squashed",
-				" f       |Set selected commits to be fixed-up",
-				" d       |Set selected commits to be dropped",
-				" Delete  |Completely remove the selected lines",
-				" Controlz|Undo the last change",
-				" Controly|Redo the previous undone change",
-				" !       |Open the todo file in the default editor",
-				" v       |Exit visual selection mode",
+				" Up       |Move selection up",
+				" Down     |Move selection down",
+				" PageUp   |Move selection up half a page",
+				" PageDown |Move selection down half a page",
+				" Home, g g|Move selection to top of the list",
+				" End      |Move selection to end of the list",
+				" Left     |Scroll content to the left",
+				" Right    |Scroll content to the right",
+				" q        |Abort interactive rebase",
+				" Q        |Immediately abort interactive rebase",
+				" w        |Write interactive rebase file",
+				" W        |Immediately write interactive rebase file",
+				" ?        |Show help",
+				" j        |Move selected lines down",
+				" k        |Move selected lines up",
+				" p        |Set selected commits to be picked",
+				" r        |Set selected commits to be reworded",
+				" e        |Set selected commits to be edited",
+				" s        |Set selected commits to be squashed",
+				" f        |Set selected commits to be fixed-up",
+				" d        |Set selected commits to be dropped",
+				" A        |Set the next action for all search matches",
+				" Space    |Mark or unmark the selected lines",
+				" G        |Move the marked lines after the selected line",
+				" M        |Clear the marked lines",
+				" m        |Name a mark for the selected line with the next key",
+				" '        |Jump to the line of the mark named by the next key",
+				" o        |Sort selected commits by author date",
+				" O        |Sort selected commits by committer date",
+				" V        |Reverse the order of the selected lines",
+				" X        |Restore the original order of the selected lines",
+				" x        |Insert exec lines after the selected commits",
+				" T        |Change the author, committer date or trailers of the selected commits",
+				" Delete   |Completely remove the selected lines",
+				" Controlz |Undo the last change",
+				" Controly |Redo the previous undone change",
+				" H        |Browse the undo history and restore a previous state",
+				" !        |Open the todo file in the default editor",
+				" @        |Open the selected lines in the default editor",
+				" v        |Exit visual selection mode",
 				"{TRAILING}",
 				"Press any key to close"
 			);
@@ -0,0 +1,241 @@
+use super::*;
+use crate::{assert_rendered_output, test_helpers::testers::ModuleTestContext};
+
+const LINES: &[&str] = &["pick aaa c1", "pick bbb c2", "pick ccc c3", "pick ddd c4", "pick eee c5"];
+
+fn create_marked_list(test_context: &mut ModuleTestContext, marked: &[usize]) -> List {
+	let mut module = create_list(&create_config(), test_context.take_todo_file());
+	module.marked_lines.extend(marked.iter().copied());
+	module
+}
+
+fn lines(module: &List) -> Vec<String> {
+	module
+		.todo_file
+		.lock()
+		.get_lines_owned()
+		.iter()
+		.map(Line::to_text)
+		.collect()
+}
+
+fn marked(module: &List) -> Vec<usize> {
+	module.marked_lines.iter().copied().collect()
+}
+
+#[test]
+fn render() {
+	testers::module(LINES, &[Event::from(StandardEvent::MoveCursorDown)], |mut test_context| {
+		let mut module = create_marked_list(&mut test_context, &[1, 3]);
+		_ = test_context.handle_all_events(&mut module);
+		let view_data = test_context.build_view_data(&mut module);
+		assert_rendered_output!(
+			Style view_data,
+			"{TITLE}{HELP}",
+			"{LEADING}",
+			"{IndicatorColor}Marked: 2 lines",
+			"{BODY}",
+			"{Normal}   {ActionPick}pick   {Normal}aaa      c1",
+			"{Selected}{Normal} >*{ActionPick}pick   {Normal}bbb      c2{Pad( )}",
+			"{Normal}   {ActionPick}pick   {Normal}ccc      c3",
+			"{Normal}  *{ActionPick}pick   {Normal}ddd      c4",
+			"{Normal}   {ActionPick}pick   {Normal}eee      c5"
+		);
+	});
+}
+
+#[test]
+fn toggle_mark_normal_mode() {
+	testers::module(
+		LINES,
+		&[
+			Event::from(StandardEvent::ToggleMark),
+			Event::from(StandardEvent::MoveCursorDown),
+			Event::from(StandardEvent::MoveCursorDown),
+			Event::from(StandardEvent::ToggleMark),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(marked(&module), vec![0, 2]);
+		},
+	);
+}
+
+#[test]
+fn toggle_mark_unmarks_marked_line() {
+	testers::module(LINES, &[Event::from(StandardEvent::ToggleMark)], |mut test_context| {
+		let mut module = create_marked_list(&mut test_context, &[0, 2]);
+		_ = test_context.handle_all_events(&mut module);
+		assert_eq!(marked(&module), vec![2]);
+	});
+}
+
+#[test]
+fn toggle_mark_visual_mode() {
+	testers::module(
+		LINES,
+		&[
+			Event::from(StandardEvent::ToggleVisualMode),
+			Event::from(StandardEvent::MoveCursorDown),
+			Event::from(StandardEvent::ToggleMark),
+		],
+		|mut test_context| {
+			let mut module = create_marked_list(&mut test_context, &[1, 4]);
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(marked(&module), vec![0, 1, 4]);
+		},
+	);
+}
+
+#[test]
+fn toggle_mark_visual_mode_all_marked() {
+	testers::module(
+		LINES,
+		&[
+			Event::from(StandardEvent::ToggleVisualMode),
+			Event::from(StandardEvent::MoveCursorDown),
+			Event::from(StandardEvent::ToggleMark),
+		],
+		|mut test_context| {
+			let mut module = create_marked_list(&mut test_context, &[0, 1, 4]);
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(marked(&module), vec![4]);
+		},
+	);
+}
+
+#[test]
+fn clear_marks() {
+	testers::module(LINES, &[Event::from(StandardEvent::ClearMarks)], |mut test_context| {
+		let mut module = create_marked_list(&mut test_context, &[0, 2]);
+		_ = test_context.handle_all_events(&mut module);
+		assert!(module.marked_lines.is_empty());
+	});
+}
+
+#[test]
+fn action_changes_marked_lines() {
+	testers::module(LINES, &[Event::from(StandardEvent::ActionFixup)], |mut test_context| {
+		let mut module = create_marked_list(&mut test_context, &[1, 3]);
+		_ = test_context.handle_all_events(&mut module);
+		assert_eq!(lines(&module), vec![
+			"pick aaa c1",
+			"fixup bbb c2",
+			"pick ccc c3",
+			"fixup ddd c4",
+			"pick eee c5"
+		]);
+		assert_eq!(marked(&module), vec![1, 3]);
+	});
+}
+
+#[test]
+fn action_on_marked_lines_undo() {
+	testers::module(
+		LINES,
+		&[Event::from(StandardEvent::ActionDrop), Event::from(StandardEvent::Undo)],
+		|mut test_context| {
+			let mut module = create_marked_list(&mut test_context, &[0, 4]);
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(lines(&module), LINES);
+		},
+	);
+}
+
+#[test]
+fn delete_marked_lines() {
+	testers::module(
+		LINES,
+		&[
+			Event::from(StandardEvent::MoveCursorDown),
+			Event::from(StandardEvent::MoveCursorDown),
+			Event::from(StandardEvent::Delete),
+		],
+		|mut test_context| {
+			let mut module = create_marked_list(&mut test_context, &[0, 3]);
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(lines(&module), vec!["pick bbb c2", "pick ccc c3", "pick eee c5"]);
+			assert_eq!(module.todo_file.lock().get_selected_line_index(), 1);
+			assert!(module.marked_lines.is_empty());
+		},
+	);
+}
+
+#[test]
+fn delete_marked_lines_undo() {
+	testers::module(
+		LINES,
+		&[Event::from(StandardEvent::Delete), Event::from(StandardEvent::Undo)],
+		|mut test_context| {
+			let mut module = create_marked_list(&mut test_context, &[0, 2, 4]);
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(lines(&module), LINES);
+		},
+	);
+}
+
+#[test]
+fn gather_marked_lines() {
+	testers::module(
+		LINES,
+		&[
+			Event::from(StandardEvent::MoveCursorDown),
+			Event::from(StandardEvent::MoveCursorDown),
+			Event::from(StandardEvent::GatherMarked),
+		],
+		|mut test_context| {
+			let mut module = create_marked_list(&mut test_context, &[0, 4]);
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(lines(&module), vec![
+				"pick bbb c2",
+				"pick ccc c3",
+				"pick aaa c1",
+				"pick eee c5",
+				"pick ddd c4"
+			]);
+			assert_eq!(module.todo_file.lock().get_selected_line_index(), 2);
+			assert!(module.marked_lines.is_empty());
+		},
+	);
+}
+
+#[test]
+fn gather_marked_lines_undo() {
+	testers::module(
+		LINES,
+		&[Event::from(StandardEvent::GatherMarked), Event::from(StandardEvent::Undo)],
+		|mut test_context| {
+			let mut module = create_marked_list(&mut test_context, &[1, 3]);
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(lines(&module), LINES);
+		},
+	);
+}
+
+#[test]
+fn gather_without_marks() {
+	testers::module(LINES, &[Event::from(StandardEvent::GatherMarked)], |mut test_context| {
+		let mut module = create_list(&create_config(), test_context.take_todo_file());
+		_ = test_context.handle_all_events(&mut module);
+		assert_eq!(lines(&module), LINES);
+	});
+}
+
+#[test]
+fn swap_clears_marks() {
+	testers::module(LINES, &[Event::from(StandardEvent::SwapSelectedDown)], |mut test_context| {
+		let mut module = create_marked_list(&mut test_context, &[0, 2]);
+		_ = test_context.handle_all_events(&mut module);
+		assert!(module.marked_lines.is_empty());
+	});
+}
+
+#[test]
+fn activate_from_external_editor_clears_marks() {
+	testers::module(LINES, &[], |mut test_context| {
+		let mut module = create_marked_list(&mut test_context, &[0, 2]);
+		_ = test_context.activate(&mut module, State::ExternalEditor);
+		assert!(module.marked_lines.is_empty());
+	});
+}
@@ -0,0 +1,160 @@
+use super::*;
+use crate::{assert_rendered_output, input::KeyCode};
+
+const LINES: &[&str] = &["pick aaa c1", "pick bbb c2", "exec make", "pick ddd c4", "pick eee c5"];
+
+#[test]
+fn jump_to_named_mark() {
+	testers::module(
+		LINES,
+		&[
+			Event::from(StandardEvent::MoveCursorDown),
+			Event::from(StandardEvent::SetNamedMark),
+			Event::from('a'),
+			Event::from(StandardEvent::MoveCursorEnd),
+			Event::from(StandardEvent::JumpToNamedMark),
+			Event::from('a'),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.todo_file.lock().get_selected_line_index(), 1);
+			assert!(module.pending_named_mark.is_none());
+		},
+	);
+}
+
+#[test]
+fn named_mark_follows_moved_commit() {
+	testers::module(
+		LINES,
+		&[
+			Event::from(StandardEvent::MoveCursorDown),
+			Event::from(StandardEvent::SetNamedMark),
+			Event::from('b'),
+			Event::from('2'),
+			Event::from(StandardEvent::SwapSelectedDown),
+			Event::from(StandardEvent::MoveCursorHome),
+			Event::from(StandardEvent::JumpToNamedMark),
+			Event::from('b'),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.todo_file.lock().get_selected_line_index(), 3);
+		},
+	);
+}
+
+#[test]
+fn named_mark_on_line_without_commit_uses_index() {
+	testers::module(
+		LINES,
+		&[
+			Event::from(StandardEvent::MoveCursorDown),
+			Event::from(StandardEvent::MoveCursorDown),
+			Event::from(StandardEvent::SetNamedMark),
+			Event::from('x'),
+			Event::from(StandardEvent::SwapSelectedUp),
+			Event::from(StandardEvent::MoveCursorEnd),
+			Event::from(StandardEvent::JumpToNamedMark),
+			Event::from('x'),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.todo_file.lock().get_selected_line_index(), 2);
+		},
+	);
+}
+
+#[test]
+fn jump_to_unknown_named_mark() {
+	testers::module(
+		LINES,
+		&[
+			Event::from(StandardEvent::MoveCursorEnd),
+			Event::from(StandardEvent::JumpToNamedMark),
+			Event::from('z'),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.todo_file.lock().get_selected_line_index(), 4);
+		},
+	);
+}
+
+#[test]
+fn named_mark_cancelled() {
+	testers::module(
+		LINES,
+		&[
+			Event::from(StandardEvent::SetNamedMark),
+			Event::from(KeyCode::Esc),
+			Event::from(StandardEvent::MoveCursorEnd),
+			Event::from(StandardEvent::JumpToNamedMark),
+			Event::from(KeyCode::Esc),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert!(module.pending_named_mark.is_none());
+			assert_eq!(module.todo_file.lock().get_selected_line_index(), 4);
+		},
+	);
+}
+
+#[test]
+fn named_mark_name_is_not_a_key_binding() {
+	testers::module(
+		LINES,
+		&[Event::from(StandardEvent::SetNamedMark), Event::from('d')],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.todo_file.lock().get_line(0).unwrap().to_text(), "pick aaa c1");
+			assert_eq!(module.named_marks.find('d', &module.todo_file.lock()), Some(0));
+		},
+	);
+}
+
+#[test]
+fn render_set_named_mark() {
+	testers::module(
+		&["pick aaa c1"],
+		&[Event::from(StandardEvent::SetNamedMark)],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_rendered_output!(
+				Style test_context.build_view_data(&mut module),
+				"{TITLE}{HELP}",
+				"{LEADING}",
+				"{IndicatorColor}Press a key to name a mark for the selected line",
+				"{BODY}",
+				"{Selected}{Normal} > {ActionPick}pick   {Normal}aaa      c1{Pad( )}"
+			);
+		},
+	);
+}
+
+#[test]
+fn render_jump_to_named_mark() {
+	testers::module(
+		&["pick aaa c1"],
+		&[Event::from(StandardEvent::JumpToNamedMark)],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_rendered_output!(
+				Style test_context.build_view_data(&mut module),
+				"{TITLE}{HELP}",
+				"{LEADING}",
+				"{IndicatorColor}Press the key of the mark to jump to",
+				"{BODY}",
+				"{Selected}{Normal} > {ActionPick}pick   {Normal}aaa      c1{Pad( )}"
+			);
+		},
+	);
+}
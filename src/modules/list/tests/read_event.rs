@@ -46,18 +46,39 @@ fn search() {
 #[case::actionedit('e', StandardEvent::ActionEdit)]
 #[case::actionfixup('f', StandardEvent::ActionFixup)]
 #[case::actionpick('p', StandardEvent::ActionPick)]
+#[case::actionrevert('R', StandardEvent::ActionRevert)]
 #[case::actionreword('r', StandardEvent::ActionReword)]
 #[case::actionsquash('s', StandardEvent::ActionSquash)]
 #[case::edit('E', StandardEvent::Edit)]
 #[case::forceabort('Q', StandardEvent::ForceAbort)]
 #[case::forcerebase('W', StandardEvent::ForceRebase)]
 #[case::insertline('I', StandardEvent::InsertLine)]
+#[case::insertupdaterefs('B', StandardEvent::InsertUpdateRefs)]
+#[case::insertexeclines('x', StandardEvent::InsertExecLines)]
+#[case::rewordmessage('C', StandardEvent::RewordMessage)]
+#[case::rewritemetadata('T', StandardEvent::RewriteMetadata)]
+#[case::showundohistory('H', StandardEvent::ShowUndoHistory)]
+#[case::setnamedmark('m', StandardEvent::SetNamedMark)]
+#[case::jumptonamedmark('\'', StandardEvent::JumpToNamedMark)]
 #[case::swapselecteddown('j', StandardEvent::SwapSelectedDown)]
 #[case::swapselectedup('k', StandardEvent::SwapSelectedUp)]
 #[case::openineditor('!', StandardEvent::OpenInEditor)]
+#[case::openselectionineditor('@', StandardEvent::OpenSelectionInEditor)]
 #[case::rebase('w', StandardEvent::Rebase)]
 #[case::showcommit('c', StandardEvent::ShowCommit)]
 #[case::togglevisualmode('v', StandardEvent::ToggleVisualMode)]
+#[case::togglestackview('S', StandardEvent::ToggleStackView)]
+#[case::togglefoldview('Z', StandardEvent::ToggleFoldView)]
+#[case::togglefilter('F', StandardEvent::ToggleFilter)]
+#[case::applytomatches('A', StandardEvent::ApplyToMatches)]
+#[case::togglemark(' ', StandardEvent::ToggleMark)]
+#[case::gathermarked('G', StandardEvent::GatherMarked)]
+#[case::clearmarks('M', StandardEvent::ClearMarks)]
+#[case::sortbyauthordate('o', StandardEvent::SortByAuthorDate)]
+#[case::sortbycommitdate('O', StandardEvent::SortByCommitDate)]
+#[case::reverseselection('V', StandardEvent::ReverseSelection)]
+#[case::restoreorder('X', StandardEvent::RestoreOrder)]
+#[case::togglesection('z', StandardEvent::ToggleSection)]
 fn default_events_single_char(#[case] binding: char, #[case] expected: StandardEvent) {
 	testers::read_event(Event::from(binding), |mut context| {
 		let module = create_list(&create_config(), context.take_todo_file());
@@ -0,0 +1,209 @@
+use rstest::rstest;
+
+use super::*;
+use crate::{
+	git::Repository,
+	test_helpers::{create_commit, with_temp_repository, CreateCommitOptions, JAN_2021_EPOCH},
+};
+
+const LINES: &[&str] = &["pick aaa c1", "pick bbb c2", "pick ccc c3", "pick ddd c4"];
+
+fn lines(module: &List) -> Vec<String> {
+	module
+		.todo_file
+		.lock()
+		.get_lines_owned()
+		.iter()
+		.map(Line::to_text)
+		.collect()
+}
+
+fn select_all() -> Vec<Event> {
+	vec![
+		Event::from(StandardEvent::ToggleVisualMode),
+		Event::from(StandardEvent::MoveCursorEnd),
+	]
+}
+
+// create commits with the author and committer times offset from the same date, returning the todo lines for them
+fn create_commits(repository: &Repository, times: &[(i64, i64)]) -> Vec<String> {
+	times
+		.iter()
+		.enumerate()
+		.map(|(i, &(author_time, commit_time))| {
+			create_commit(
+				repository,
+				Some(
+					CreateCommitOptions::new()
+						.author_time(JAN_2021_EPOCH + author_time)
+						.commit_time(JAN_2021_EPOCH + commit_time),
+				),
+			);
+			let hash = repository.commit_id_from_ref("refs/heads/main").unwrap().to_string();
+			format!("pick {hash} c{}", i + 1)
+		})
+		.collect()
+}
+
+#[rstest]
+#[case::author_date(StandardEvent::SortByAuthorDate, [1, 2, 0])]
+#[case::commit_date(StandardEvent::SortByCommitDate, [0, 2, 1])]
+fn sort_by_date(#[case] event: StandardEvent, #[case] expected_order: [usize; 3]) {
+	with_temp_repository(|repository| {
+		let commit_lines = create_commits(&repository, &[(300, 100), (100, 300), (200, 200)]);
+		let todo_lines = [
+			commit_lines[0].as_str(),
+			commit_lines[1].as_str(),
+			"exec make",
+			commit_lines[2].as_str(),
+		];
+		let mut events = select_all();
+		events.push(Event::from(event));
+		testers::module(&todo_lines, &events, |mut test_context| {
+			let mut module = List::new(
+				&create_config(),
+				Arc::new(Mutex::new(test_context.take_todo_file())),
+				vec![],
+				Some(repository),
+				InputHistory::default(),
+			);
+			_ = test_context.handle_all_events(&mut module);
+			let expected = [
+				commit_lines[expected_order[0]].clone(),
+				commit_lines[expected_order[1]].clone(),
+				String::from("exec make"),
+				commit_lines[expected_order[2]].clone(),
+			];
+			assert_eq!(lines(&module), expected);
+			assert_eq!(module.state, ListState::Visual);
+		});
+	});
+}
+
+#[test]
+fn sort_without_repository() {
+	let mut events = select_all();
+	events.push(Event::from(StandardEvent::SortByAuthorDate));
+	testers::module(&["pick bbb c2", "pick aaa c1"], &events, |mut test_context| {
+		let mut module = create_list(&create_config(), test_context.take_todo_file());
+		_ = test_context.handle_all_events(&mut module);
+		assert_eq!(lines(&module), vec!["pick bbb c2", "pick aaa c1"]);
+	});
+}
+
+#[test]
+fn reverse_selection() {
+	testers::module(
+		LINES,
+		&[
+			Event::from(StandardEvent::MoveCursorDown),
+			Event::from(StandardEvent::ToggleVisualMode),
+			Event::from(StandardEvent::MoveCursorEnd),
+			Event::from(StandardEvent::ReverseSelection),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(lines(&module), vec![
+				"pick aaa c1",
+				"pick ddd c4",
+				"pick ccc c3",
+				"pick bbb c2"
+			]);
+		},
+	);
+}
+
+#[test]
+fn reverse_selection_undo() {
+	let mut events = select_all();
+	events.push(Event::from(StandardEvent::ReverseSelection));
+	events.push(Event::from(StandardEvent::Undo));
+	testers::module(LINES, &events, |mut test_context| {
+		let mut module = create_list(&create_config(), test_context.take_todo_file());
+		_ = test_context.handle_all_events(&mut module);
+		assert_eq!(lines(&module), LINES);
+	});
+}
+
+#[test]
+fn reverse_selection_normal_mode() {
+	testers::module(
+		LINES,
+		&[
+			Event::from(StandardEvent::MoveCursorEnd),
+			Event::from(StandardEvent::ReverseSelection),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(lines(&module), LINES);
+		},
+	);
+}
+
+#[test]
+fn reverse_selection_keeps_hidden_lines() {
+	let mut events = select_all();
+	events.push(Event::from(StandardEvent::ReverseSelection));
+	testers::module(LINES, &events, |mut test_context| {
+		let mut module = create_list(&create_config(), test_context.take_todo_file());
+		module.filter = Some(Filter::new("c1|c2|c4"));
+		_ = test_context.handle_all_events(&mut module);
+		assert_eq!(lines(&module), vec![
+			"pick ddd c4",
+			"pick bbb c2",
+			"pick ccc c3",
+			"pick aaa c1"
+		]);
+	});
+}
+
+#[test]
+fn reverse_selection_clears_marks() {
+	let mut events = select_all();
+	events.push(Event::from(StandardEvent::ReverseSelection));
+	testers::module(LINES, &events, |mut test_context| {
+		let mut module = create_list(&create_config(), test_context.take_todo_file());
+		module.marked_lines.extend([0, 2]);
+		_ = test_context.handle_all_events(&mut module);
+		assert!(module.marked_lines.is_empty());
+	});
+}
+
+#[test]
+fn restore_order() {
+	testers::module(
+		&["pick aaa c1", "pick bbb c2", "pick ccc c3", "pick ddd c4", "pick eee c5"],
+		&[
+			Event::from(StandardEvent::MoveCursorDown),
+			Event::from(StandardEvent::SwapSelectedUp),
+			Event::from(StandardEvent::MoveCursorDown),
+			Event::from(StandardEvent::ToggleVisualMode),
+			Event::from(StandardEvent::MoveCursorEnd),
+			Event::from(StandardEvent::ActionFixup),
+			Event::from(StandardEvent::ReverseSelection),
+			Event::from(StandardEvent::RestoreOrder),
+			Event::from(StandardEvent::Undo),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_n_events(&mut module, 8);
+			assert_eq!(lines(&module), vec![
+				"pick bbb c2",
+				"fixup aaa c1",
+				"fixup ccc c3",
+				"fixup ddd c4",
+				"fixup eee c5"
+			]);
+			_ = test_context.handle_event(&mut module);
+			assert_eq!(lines(&module), vec![
+				"pick bbb c2",
+				"fixup eee c5",
+				"fixup ddd c4",
+				"fixup ccc c3",
+				"fixup aaa c1"
+			]);
+		},
+	);
+}
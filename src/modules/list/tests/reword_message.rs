@@ -0,0 +1,262 @@
+use super::*;
+use crate::{
+	assert_rendered_output,
+	input::{KeyCode, KeyEvent, KeyModifiers},
+	test_helpers::with_sequencer_todo_file,
+};
+
+fn finish_event() -> Event {
+	Event::from(KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL))
+}
+
+fn message(module: &List, index: usize) -> Option<String> {
+	module
+		.todo_file
+		.lock()
+		.get_line(index)
+		.and_then(|line| line.message().map(String::from))
+}
+
+#[test]
+fn compose_message() {
+	testers::module(
+		&["pick aaa c1", "pick bbb c2"],
+		&[
+			Event::from(StandardEvent::RewordMessage),
+			Event::from(KeyCode::Char('x')),
+			Event::from(KeyCode::Enter),
+			Event::from(KeyCode::Enter),
+			Event::from(KeyCode::Char('y')),
+			finish_event(),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.state, ListState::Normal);
+			assert_eq!(message(&module, 0), Some(String::from("c1x\n\ny")));
+			assert_eq!(message(&module, 1), None);
+		},
+	);
+}
+
+#[test]
+fn compose_message_for_reword_sets_pick() {
+	testers::module(
+		&["reword aaa c1"],
+		&[Event::from(StandardEvent::RewordMessage), finish_event()],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			let todo_file = module.todo_file.lock();
+			assert_eq!(todo_file.get_line(0).unwrap().get_action(), &Action::Pick);
+			assert_eq!(todo_file.get_line(0).unwrap().message(), Some("c1"));
+		},
+	);
+}
+
+#[test]
+fn edit_composed_message() {
+	testers::module(
+		&["pick aaa c1"],
+		&[
+			Event::from(StandardEvent::RewordMessage),
+			Event::from(KeyCode::Char('x')),
+			finish_event(),
+			Event::from(StandardEvent::RewordMessage),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.state, ListState::Reword);
+			assert_eq!(module.reword.get_content(), "c1x");
+		},
+	);
+}
+
+#[test]
+fn cancel() {
+	testers::module(
+		&["pick aaa c1"],
+		&[
+			Event::from(StandardEvent::RewordMessage),
+			Event::from(KeyCode::Char('x')),
+			Event::from(KeyCode::Esc),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.state, ListState::Normal);
+			assert_eq!(message(&module, 0), None);
+		},
+	);
+}
+
+#[test]
+fn empty_message_keeps_original() {
+	testers::module(
+		&["pick aaa c1"],
+		&[
+			Event::from(StandardEvent::RewordMessage),
+			Event::from(KeyCode::Char('x')),
+			finish_event(),
+			Event::from(StandardEvent::RewordMessage),
+			Event::from(KeyCode::Backspace),
+			Event::from(KeyCode::Backspace),
+			Event::from(KeyCode::Backspace),
+			Event::from(KeyCode::Enter),
+			finish_event(),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(message(&module, 0), None);
+		},
+	);
+}
+
+#[test]
+fn not_allowed_for_line_without_commit() {
+	testers::module(
+		&["exec make"],
+		&[Event::from(StandardEvent::RewordMessage)],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.state, ListState::Normal);
+		},
+	);
+}
+
+#[test]
+fn not_allowed_for_fixup() {
+	testers::module(
+		&["fixup aaa c1"],
+		&[Event::from(StandardEvent::RewordMessage)],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.state, ListState::Normal);
+		},
+	);
+}
+
+#[test]
+fn not_allowed_in_sequencer() {
+	with_sequencer_todo_file(&["pick aaa c1"], |todo_file| {
+		testers::module(
+			&[],
+			&[Event::from(StandardEvent::RewordMessage)],
+			|mut test_context| {
+				let mut module = create_list(&create_config(), todo_file);
+				_ = test_context.handle_all_events(&mut module);
+				assert_eq!(module.state, ListState::Normal);
+			},
+		);
+	});
+}
+
+#[test]
+fn render() {
+	testers::module(
+		&["pick aaaaaaaaaa c1"],
+		&[Event::from(StandardEvent::RewordMessage)],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			let view_data = test_context.build_view_data(&mut module);
+			assert_rendered_output!(
+				Style view_data,
+				"{TITLE}",
+				"{LEADING}",
+				"{IndicatorColor}Composing the message of commit aaaaaaaa. An empty message keeps the original \
+				 message.",
+				"{IndicatorColor}Subject: 2 characters",
+				"",
+				"{BODY}",
+				"{Normal}c1{Normal,Underline}",
+				"{TRAILING}",
+				"{IndicatorColor}Ctrl+D to finish, Esc to cancel"
+			);
+		},
+	);
+}
+
+#[test]
+fn render_message_hints() {
+	let subject = "s".repeat(60);
+	let body = "b".repeat(80);
+	testers::module(
+		&["pick aaa c1"],
+		&[Event::from(StandardEvent::RewordMessage)],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			module
+				.reword
+				.start(0, format!("{subject}\nbody\n{body}\n{body}").as_str());
+			let view_data = test_context.build_view_data(&mut module);
+			assert_rendered_output!(
+				Style view_data,
+				"{TITLE}",
+				"{LEADING}",
+				"{IndicatorColor}Composing the message of commit aaa. An empty message keeps the original message.",
+				"{DiffChangeColor}Subject: 60 characters, longer than the recommended 50",
+				"{DiffChangeColor}The subject should be followed by an empty line",
+				"{DiffChangeColor}2 body line(s) longer than 72 characters",
+				"",
+				"{BODY}",
+				format!("{{Normal}}{subject}{{Normal,Underline}}"),
+				"{Normal}body",
+				format!("{{Normal}}{body}"),
+				format!("{{Normal}}{body}"),
+				"{TRAILING}",
+				"{IndicatorColor}Ctrl+D to finish, Esc to cancel"
+			);
+		},
+	);
+}
+
+#[test]
+fn render_subject_over_maximum() {
+	let subject = "s".repeat(73);
+	testers::module(
+		&["pick aaa c1"],
+		&[Event::from(StandardEvent::RewordMessage)],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			module.reword.start(0, subject.as_str());
+			let view_data = test_context.build_view_data(&mut module);
+			assert_rendered_output!(
+				Style view_data,
+				"{TITLE}",
+				"{LEADING}",
+				"{IndicatorColor}Composing the message of commit aaa. An empty message keeps the original message.",
+				"{DiffChangeColor}Subject: 73 characters, longer than the maximum of 72",
+				"",
+				"{BODY}",
+				format!("{{Normal}}{subject}{{Normal,Underline}}"),
+				"{TRAILING}",
+				"{IndicatorColor}Ctrl+D to finish, Esc to cancel"
+			);
+		},
+	);
+}
+
+#[test]
+fn undo_restores_previous_message() {
+	testers::module(
+		&["pick aaa c1"],
+		&[
+			Event::from(StandardEvent::RewordMessage),
+			Event::from(KeyCode::Char('x')),
+			finish_event(),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			_ = module.todo_file.lock().undo();
+			assert_eq!(message(&module, 0), None);
+		},
+	);
+}
@@ -0,0 +1,294 @@
+use claims::assert_none;
+
+use super::*;
+use crate::{assert_rendered_output, input::KeyCode, test_helpers::with_sequencer_todo_file};
+
+fn type_value(value: &str) -> Vec<Event> {
+	let mut events: Vec<Event> = value.chars().map(|c| Event::from(KeyCode::Char(c))).collect();
+	events.push(Event::from(KeyCode::Enter));
+	events
+}
+
+fn create_events(before: &[Event], value: &str) -> Vec<Event> {
+	let mut events = before.to_vec();
+	events.extend(type_value(value));
+	events
+}
+
+fn authors(module: &List) -> Vec<Option<String>> {
+	module
+		.todo_file
+		.lock()
+		.lines_iter()
+		.map(|line| line.metadata_changes().author().map(String::from))
+		.collect()
+}
+
+#[test]
+fn set_author_of_selected_commits() {
+	testers::module(
+		&["pick aaa c1", "pick bbb c2", "exec make", "pick ccc c3", "pick ddd c4"],
+		&create_events(
+			&[
+				Event::from(StandardEvent::MoveCursorDown),
+				Event::from(StandardEvent::ToggleVisualMode),
+				Event::from(StandardEvent::MoveCursorDown),
+				Event::from(StandardEvent::MoveCursorDown),
+				Event::from(StandardEvent::RewriteMetadata),
+				Event::from(KeyCode::Char('a')),
+			],
+			"Jane <jane@example.com>",
+		),
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.state, ListState::Normal);
+			let jane = Some(String::from("Jane <jane@example.com>"));
+			assert_eq!(authors(&module), vec![None, jane.clone(), None, jane, None]);
+		},
+	);
+}
+
+#[test]
+fn set_committer_date_of_selected_commit() {
+	testers::module(
+		&["pick aaa c1", "pick bbb c2"],
+		&create_events(
+			&[
+				Event::from(StandardEvent::RewriteMetadata),
+				Event::from(KeyCode::Char('d')),
+			],
+			"2024-01-02",
+		),
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			let todo_file = module.todo_file.lock();
+			assert_eq!(
+				todo_file.get_line(0).unwrap().metadata_changes().committer_date(),
+				Some("2024-01-02")
+			);
+			assert!(todo_file.get_line(1).unwrap().metadata_changes().is_empty());
+		},
+	);
+}
+
+#[test]
+fn add_trailer() {
+	testers::module(
+		&["pick aaa c1"],
+		&create_events(
+			&[
+				Event::from(StandardEvent::RewriteMetadata),
+				Event::from(KeyCode::Char('t')),
+			],
+			"Reviewed-by: John",
+		),
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(
+				module.todo_file.lock().get_line(0).unwrap().metadata_changes().trailers(),
+				&[String::from("Reviewed-by: John")]
+			);
+		},
+	);
+}
+
+#[test]
+fn empty_trailer_is_not_recorded() {
+	testers::module(
+		&["pick aaa c1"],
+		&create_events(
+			&[
+				Event::from(StandardEvent::RewriteMetadata),
+				Event::from(KeyCode::Char('t')),
+			],
+			"",
+		),
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.state, ListState::Normal);
+			assert_none!(module.todo_file.lock().undo());
+		},
+	);
+}
+
+#[test]
+fn edit_starts_from_shared_value() {
+	let mut events = create_events(
+		&[
+			Event::from(StandardEvent::RewriteMetadata),
+			Event::from(KeyCode::Char('a')),
+		],
+		"Jane",
+	);
+	events.extend([
+		Event::from(StandardEvent::RewriteMetadata),
+		Event::from(KeyCode::Char('a')),
+	]);
+	testers::module(&["pick aaa c1"], &events, |mut test_context| {
+		let mut module = create_list(&create_config(), test_context.take_todo_file());
+		_ = test_context.handle_all_events(&mut module);
+		assert_eq!(module.state, ListState::RewriteMetadata);
+		assert_eq!(module.rewrite_metadata.get_content(), "Jane");
+	});
+}
+
+#[test]
+fn remove_all_changes() {
+	let mut events = create_events(
+		&[
+			Event::from(StandardEvent::RewriteMetadata),
+			Event::from(KeyCode::Char('a')),
+		],
+		"Jane",
+	);
+	events.extend(create_events(
+		&[
+			Event::from(StandardEvent::RewriteMetadata),
+			Event::from(KeyCode::Char('t')),
+		],
+		"Acked-by: John",
+	));
+	events.extend([
+		Event::from(StandardEvent::RewriteMetadata),
+		Event::from(KeyCode::Char('r')),
+	]);
+	testers::module(&["pick aaa c1"], &events, |mut test_context| {
+		let mut module = create_list(&create_config(), test_context.take_todo_file());
+		_ = test_context.handle_all_events(&mut module);
+		assert_eq!(module.state, ListState::Normal);
+		assert!(module.todo_file.lock().get_line(0).unwrap().metadata_changes().is_empty());
+		_ = module.todo_file.lock().undo();
+		let todo_file = module.todo_file.lock();
+		let metadata_changes = todo_file.get_line(0).unwrap().metadata_changes();
+		assert_eq!(metadata_changes.author(), Some("Jane"));
+		assert_eq!(metadata_changes.trailers(), &[String::from("Acked-by: John")]);
+	});
+}
+
+#[test]
+fn cancel() {
+	testers::module(
+		&["pick aaa c1"],
+		&[
+			Event::from(StandardEvent::RewriteMetadata),
+			Event::from(KeyCode::Char('q')),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.state, ListState::Normal);
+			assert_eq!(authors(&module), vec![None]);
+		},
+	);
+}
+
+#[test]
+fn not_allowed_without_commits() {
+	testers::module(
+		&["exec make", "fixup aaa c1"],
+		&[
+			Event::from(StandardEvent::ToggleVisualMode),
+			Event::from(StandardEvent::MoveCursorDown),
+			Event::from(StandardEvent::RewriteMetadata),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.state, ListState::Visual);
+		},
+	);
+}
+
+#[test]
+fn not_allowed_in_sequencer() {
+	with_sequencer_todo_file(&["pick aaa c1"], |todo_file| {
+		testers::module(
+			&[],
+			&[Event::from(StandardEvent::RewriteMetadata)],
+			|mut test_context| {
+				let mut module = create_list(&create_config(), todo_file);
+				_ = test_context.handle_all_events(&mut module);
+				assert_eq!(module.state, ListState::Normal);
+			},
+		);
+	});
+}
+
+#[test]
+fn render_choices() {
+	testers::module(
+		&["pick aaa c1"],
+		&[Event::from(StandardEvent::RewriteMetadata)],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			let view_data = test_context.build_view_data(&mut module);
+			assert_rendered_output!(
+				view_data,
+				"{TITLE}",
+				"{LEADING}",
+				"Select the change to the selected commits:",
+				"",
+				"{BODY}",
+				"a) Set the author",
+				"d) Set the committer date",
+				"t) Add a trailer",
+				"r) Remove all changes",
+				"q) Cancel",
+				"",
+				"Please choose an option."
+			);
+		},
+	);
+}
+
+#[test]
+fn render_value_edit() {
+	testers::module(
+		&["pick aaa c1"],
+		&[
+			Event::from(StandardEvent::RewriteMetadata),
+			Event::from(KeyCode::Char('a')),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			let view_data = test_context.build_view_data(&mut module);
+			assert_rendered_output!(
+				Style view_data,
+				"{TITLE}",
+				"{LEADING}",
+				"{IndicatorColor}Enter the author of the selected commits, as `Name <email>`. Empty content removes \
+				 the change.",
+				"",
+				"{BODY}",
+				"{Normal,Dimmed}author: {Normal,Underline}",
+				"{TRAILING}",
+				"{IndicatorColor}Enter to finish"
+			);
+		},
+	);
+}
+
+#[test]
+fn render_badge() {
+	testers::module(&["pick aaa c1"], &[], |mut test_context| {
+		let mut module = create_list(&create_config(), test_context.take_todo_file());
+		_ = module.todo_file.lock().update_metadata_changes(0, 0, |changes| {
+			changes.set_author(Some("Jane"));
+			changes.set_committer_date(Some("yesterday"));
+			changes.add_trailer("Signed-off-by: Jane");
+			changes.add_trailer("Reviewed-by: John");
+		});
+		let view_data = test_context.build_view_data(&mut module);
+		assert_rendered_output!(
+			Body view_data,
+			"{Selected} > pick   aaa      c1 [author: Jane, date: yesterday, Signed-off-by, \
+			 Reviewed-by]{Pad( )}"
+		);
+	});
+}
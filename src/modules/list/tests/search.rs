@@ -8,7 +8,7 @@ use crate::{
 	assert_rendered_output,
 	assert_results,
 	input::KeyCode,
-	modules::list::search::LineMatch,
+	modules::list::search::{LineMatch, MatchedFields},
 	process::Artifact,
 	search::Interrupter,
 	test_helpers::{assertions::AnyArtifact, create_test_keybindings, testers::ModuleTestContext},
@@ -341,7 +341,7 @@ fn start_search_with_term() {
 				AnyArtifact,
 				Artifact::SearchTerm(String::from("aaa"))
 			);
-			assert_some_eq!(test_context.list.search.current_match(), LineMatch::new(0, true, false));
+			assert_some_eq!(test_context.list.search.current_match(), LineMatch::new(0, MatchedFields::HASH));
 		},
 	);
 }
@@ -361,7 +361,7 @@ fn next() {
 				AnyArtifact,
 				Artifact::SearchTerm(String::from("aaa"))
 			);
-			assert_some_eq!(test_context.list.search.current_match(), LineMatch::new(1, true, false));
+			assert_some_eq!(test_context.list.search.current_match(), LineMatch::new(1, MatchedFields::HASH));
 		},
 	);
 }
@@ -381,7 +381,7 @@ fn previous() {
 				AnyArtifact,
 				Artifact::SearchTerm(String::from("aaa"))
 			);
-			assert_some_eq!(test_context.list.search.current_match(), LineMatch::new(2, true, false));
+			assert_some_eq!(test_context.list.search.current_match(), LineMatch::new(2, MatchedFields::HASH));
 		},
 	);
 }
@@ -407,3 +407,172 @@ fn ignored_event() {
 		},
 	);
 }
+
+#[test]
+fn render_match_content_regex() {
+	search_test(
+		&[Action::Start("m+e"), Action::Search],
+		&["pick aaaaaaaa comment member"],
+		|mut test_context| {
+			assert_rendered_output!(
+				Style test_context.build_view_data(),
+				"{TITLE}{HELP}",
+				"{BODY}",
+				"{Selected}{Normal} > {ActionPick}pick   {Normal}aaaaaaaa co{IndicatorColor}mme{Normal}nt \
+				 {IndicatorColor}me{Normal}mber{Pad( )}",
+				"{TRAILING}",
+				"{Normal}/m+e{Normal,Underline}"
+			);
+		},
+	);
+}
+
+#[test]
+fn render_match_action() {
+	search_test(
+		&[Action::Start("action:fixup"), Action::Search],
+		&["pick aaaaaaaa comment", "fixup bbbbbbbb comment"],
+		|mut test_context| {
+			assert_rendered_output!(
+				Style test_context.build_view_data(),
+				"{TITLE}{HELP}",
+				"{BODY}",
+				"{Selected}{Normal} > {ActionPick}pick   {Normal}aaaaaaaa comment{Pad( )}",
+				"{Normal}   {IndicatorColor}fixup  {Normal}bbbbbbbb comment",
+				"{TRAILING}",
+				"{Normal}/action:fixup{Normal,Underline}"
+			);
+		},
+	);
+}
+
+#[test]
+fn search_action_field() {
+	search_test(
+		&[Action::Start("action:s"), Action::Search, Action::Finish],
+		&["pick aaaaaaaa comment", "squash bbbbbbbb comment"],
+		|test_context| {
+			assert_some_eq!(test_context.list.search.current_match(), LineMatch::new(1, MatchedFields::ACTION));
+		},
+	);
+}
+
+fn lines(list: &List) -> Vec<String> {
+	list.todo_file
+		.lock()
+		.get_lines_owned()
+		.iter()
+		.map(Line::to_text)
+		.collect()
+}
+
+#[test]
+fn apply_action_to_matches() {
+	search_test(
+		&[
+			Action::Start("msg:^fixup!"),
+			Action::Search,
+			Action::Finish,
+			Action::Event(Event::from('A')),
+			Action::Event(Event::from('f')),
+		],
+		&[
+			"pick aaaaaaaa feature",
+			"pick bbbbbbbb fixup! feature",
+			"pick cccccccc other",
+			"pick dddddddd fixup! feature",
+		],
+		|test_context| {
+			assert_eq!(lines(&test_context.list), vec![
+				"pick aaaaaaaa feature",
+				"fixup bbbbbbbb fixup! feature",
+				"pick cccccccc other",
+				"fixup dddddddd fixup! feature",
+			]);
+		},
+	);
+}
+
+#[test]
+fn apply_action_to_matches_undo() {
+	search_test(
+		&[
+			Action::Start("WIP"),
+			Action::Search,
+			Action::Finish,
+			Action::Event(Event::from('A')),
+			Action::Event(Event::from('d')),
+		],
+		&["pick aaaaaaaa WIP one", "pick bbbbbbbb done", "pick cccccccc WIP two"],
+		|test_context| {
+			assert_some_eq!(test_context.list.todo_file.lock().undo(), (0, 2));
+			assert_eq!(lines(&test_context.list), vec![
+				"pick aaaaaaaa WIP one",
+				"pick bbbbbbbb done",
+				"pick cccccccc WIP two",
+			]);
+		},
+	);
+}
+
+#[test]
+fn apply_action_to_matches_render() {
+	search_test(
+		&[
+			Action::Start("aaa"),
+			Action::Search,
+			Action::Finish,
+			Action::Search,
+			Action::Event(Event::from('A')),
+		],
+		&["pick aaaaaaaa comment"],
+		|mut test_context| {
+			assert_rendered_output!(
+				test_context.build_view_data(),
+				"{TITLE}{HELP}",
+				"{BODY}",
+				action_line!(Selected Pick "aaaaaaaa", "comment"),
+				"{TRAILING}",
+				"[aaa]: 1/1 Select an action for all matches"
+			);
+		},
+	);
+}
+
+#[test]
+fn apply_action_to_matches_cancelled_by_other_event() {
+	search_test(
+		&[
+			Action::Start("WIP"),
+			Action::Search,
+			Action::Finish,
+			Action::Event(Event::from('A')),
+			Action::Event(Event::from(KeyCode::Down)),
+			Action::Event(Event::from('d')),
+		],
+		&["pick aaaaaaaa WIP one", "pick bbbbbbbb WIP two", "pick cccccccc WIP three"],
+		|test_context| {
+			assert_eq!(lines(&test_context.list), vec![
+				"pick aaaaaaaa WIP one",
+				"drop bbbbbbbb WIP two",
+				"pick cccccccc WIP three",
+			]);
+		},
+	);
+}
+
+#[test]
+fn apply_action_to_matches_without_matches() {
+	search_test(
+		&[
+			Action::Start("xxx"),
+			Action::Search,
+			Action::Finish,
+			Action::Event(Event::from('A')),
+		],
+		&["pick aaaaaaaa comment"],
+		|test_context| {
+			assert!(!test_context.list.apply_to_matches);
+		},
+	);
+}
@@ -0,0 +1,135 @@
+use super::*;
+use crate::{action_line, assert_rendered_output, test_helpers::with_sequencer_todo_file};
+
+#[test]
+fn normal_mode_help() {
+	with_sequencer_todo_file(&["pick aaa c1"], |todo_file| {
+		testers::module(&[], &[Event::from(StandardEvent::Help)], |mut test_context| {
+			let mut module = create_list(&create_config(), todo_file);
+			_ = test_context.handle_all_events(&mut module);
+			let view_data = test_context.build_view_data(&mut module);
+			assert_rendered_output!(
+				Body view_data,
+				" Up       |Move selection up",
+				" Down     |Move selection down",
+				" PageUp   |Move selection up half a page",
+				" PageDown |Move selection down half a page",
+				" Home, g g|Move selection to top of the list",
+				" End      |Move selection to end of the list",
+				" Left     |Scroll content to the left",
+				" Right    |Scroll content to the right",
+				" q        |Abort interactive rebase",
+				" Q        |Immediately abort interactive rebase",
+				" w        |Write interactive rebase file",
+				" W        |Immediately write interactive rebase file",
+				" ?        |Show help",
+				" j        |Move selected lines down",
+				" k        |Move selected lines up",
+				" c        |Show commit information",
+				" p        |Set selected commits to be picked",
+				" R        |Set selected commits to be reverted",
+				" A        |Set the next action for all search matches",
+				" Space    |Mark or unmark the selected lines",
+				" G        |Move the marked lines after the selected line",
+				" M        |Clear the marked lines",
+				" m        |Name a mark for the selected line with the next key",
+				" '        |Jump to the line of the mark named by the next key",
+				" I        |Insert a new line",
+				" Delete   |Completely remove the selected lines",
+				" Controlz |Undo the last change",
+				" Controly |Redo the previous undone change",
+				" H        |Browse the undo history and restore a previous state",
+				" !        |Open the todo file in the default editor",
+				" @        |Open the selected lines in the default editor",
+				" v        |Enter visual selection mode",
+				" F        |Show only the lines matching the search"
+			);
+		});
+	});
+}
+
+#[test]
+fn normal_mode_action_change_to_revert() {
+	with_sequencer_todo_file(&["pick aaa c1"], |todo_file| {
+		testers::module(&[], &[Event::from(StandardEvent::ActionRevert)], |mut test_context| {
+			let mut module = create_list(&create_config(), todo_file);
+			_ = test_context.handle_all_events(&mut module);
+			let view_data = test_context.build_view_data(&mut module);
+			assert_rendered_output!(Body view_data, action_line!(Selected Revert "aaa", "c1"));
+		});
+	});
+}
+
+#[test]
+fn normal_mode_action_change_to_pick() {
+	with_sequencer_todo_file(&["revert aaa c1"], |todo_file| {
+		testers::module(&[], &[Event::from(StandardEvent::ActionPick)], |mut test_context| {
+			let mut module = create_list(&create_config(), todo_file);
+			_ = test_context.handle_all_events(&mut module);
+			let view_data = test_context.build_view_data(&mut module);
+			assert_rendered_output!(Body view_data, action_line!(Selected Pick "aaa", "c1"));
+		});
+	});
+}
+
+#[test]
+fn visual_mode_action_change_to_revert() {
+	with_sequencer_todo_file(&["pick aaa c1", "pick bbb c2", "pick ccc c3"], |todo_file| {
+		testers::module(
+			&[],
+			&[
+				Event::from(StandardEvent::ToggleVisualMode),
+				Event::from(StandardEvent::MoveCursorDown),
+				Event::from(StandardEvent::ActionRevert),
+			],
+			|mut test_context| {
+				let mut module = create_list(&create_config(), todo_file);
+				_ = test_context.handle_all_events(&mut module);
+				let view_data = test_context.build_view_data(&mut module);
+				assert_rendered_output!(
+					Body view_data,
+					action_line!(Selected Revert "aaa", "c1"),
+					action_line!(Selected Revert "bbb", "c2"),
+					action_line!(Pick "ccc", "c3")
+				);
+			},
+		);
+	});
+}
+
+#[test]
+fn rebase_actions_ignored() {
+	with_sequencer_todo_file(&["pick aaa c1"], |todo_file| {
+		testers::module(
+			&[],
+			&[
+				Event::from(StandardEvent::ActionBreak),
+				Event::from(StandardEvent::ActionDrop),
+				Event::from(StandardEvent::ActionEdit),
+				Event::from(StandardEvent::ActionFixup),
+				Event::from(StandardEvent::ActionReword),
+				Event::from(StandardEvent::ActionSquash),
+			],
+			|mut test_context| {
+				let mut module = create_list(&create_config(), todo_file);
+				_ = test_context.handle_all_events(&mut module);
+				let view_data = test_context.build_view_data(&mut module);
+				assert_rendered_output!(Body view_data, action_line!(Selected Pick "aaa", "c1"));
+			},
+		);
+	});
+}
+
+#[test]
+fn revert_action_ignored_outside_sequencer() {
+	testers::module(
+		&["pick aaa c1"],
+		&[Event::from(StandardEvent::ActionRevert)],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			let view_data = test_context.build_view_data(&mut module);
+			assert_rendered_output!(Body view_data, action_line!(Selected Pick "aaa", "c1"));
+		},
+	);
+}
@@ -0,0 +1,412 @@
+use claims::assert_none;
+
+use super::*;
+use crate::{
+	assert_rendered_output,
+	test_helpers::{assertions::assert_rendered_output::AssertRenderOptions, with_sequencer_todo_file},
+};
+
+const STACK: &[&str] = &[
+	"pick aaa c1",
+	"pick bbb c2",
+	"update-ref refs/heads/one",
+	"pick ccc c3",
+	"update-ref refs/heads/two",
+	"pick ddd c4",
+];
+
+fn lines(module: &List) -> Vec<String> {
+	module
+		.todo_file
+		.lock()
+		.get_lines_owned()
+		.iter()
+		.map(Line::to_text)
+		.collect()
+}
+
+#[test]
+fn render() {
+	testers::module(STACK, &[Event::from(StandardEvent::ToggleStackView)], |mut test_context| {
+		let mut module = create_list(&create_config(), test_context.take_todo_file());
+		_ = test_context.handle_all_events(&mut module);
+		let view_data = test_context.build_view_data(&mut module);
+		assert_rendered_output!(
+			Body view_data,
+			"   - one (2 commits)",
+			"{Selected} > pick   aaa      c1{Pad( )}",
+			"   pick   bbb      c2",
+			"   update-ref refs/heads/one",
+			"   - two (1 commit)",
+			"   pick   ccc      c3",
+			"   update-ref refs/heads/two",
+			"   - HEAD (1 commit)",
+			"   pick   ddd      c4"
+		);
+	});
+}
+
+#[test]
+fn render_collapsed_section() {
+	testers::module(
+		STACK,
+		&[
+			Event::from(StandardEvent::ToggleStackView),
+			Event::from(StandardEvent::ToggleSection),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			let view_data = test_context.build_view_data(&mut module);
+			assert_rendered_output!(
+				Options AssertRenderOptions::INCLUDE_STYLE | AssertRenderOptions::BODY_ONLY,
+				view_data,
+				"{Selected}{Normal} > {IndicatorColor}+ one (2 commits){Normal}{Pad( )}",
+				"{Normal}   {IndicatorColor}- two (1 commit)",
+				"{Normal}   {ActionPick}pick   {Normal}ccc      c3",
+				"{Normal}   {ActionUpdateRef}update-ref {Normal}refs/heads/two",
+				"{Normal}   {IndicatorColor}- HEAD (1 commit)",
+				"{Normal}   {ActionPick}pick   {Normal}ddd      c4"
+			);
+		},
+	);
+}
+
+#[test]
+fn toggle_section_expand() {
+	testers::module(
+		STACK,
+		&[
+			Event::from(StandardEvent::ToggleStackView),
+			Event::from(StandardEvent::ToggleSection),
+			Event::from(StandardEvent::ToggleSection),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			let todo_file = module.todo_file.lock();
+			assert!(get_sections(&todo_file).iter().all(|section| !module.stack.is_collapsed(section)));
+		},
+	);
+}
+
+#[test]
+fn toggle_section_outside_stack_view() {
+	testers::module(STACK, &[Event::from(StandardEvent::ToggleSection)], |mut test_context| {
+		let mut module = create_list(&create_config(), test_context.take_todo_file());
+		_ = test_context.handle_all_events(&mut module);
+		let todo_file = module.todo_file.lock();
+		assert!(get_sections(&todo_file).iter().all(|section| !module.stack.is_collapsed(section)));
+	});
+}
+
+#[test]
+fn toggle_stack_view_off() {
+	testers::module(
+		STACK,
+		&[
+			Event::from(StandardEvent::ToggleStackView),
+			Event::from(StandardEvent::ToggleStackView),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			let view_data = test_context.build_view_data(&mut module);
+			assert_rendered_output!(
+				Body view_data,
+				"{Selected} > pick   aaa      c1{Pad( )}",
+				"   pick   bbb      c2",
+				"   update-ref refs/heads/one",
+				"   pick   ccc      c3",
+				"   update-ref refs/heads/two",
+				"   pick   ddd      c4"
+			);
+		},
+	);
+}
+
+#[test]
+fn toggle_stack_view_sequencer() {
+	with_sequencer_todo_file(&["pick aaa c1"], |todo_file| {
+		testers::module(&[], &[Event::from(StandardEvent::ToggleStackView)], |mut test_context| {
+			let mut module = create_list(&create_config(), todo_file);
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.view, ListView::Lines);
+		});
+	});
+}
+
+#[test]
+fn toggle_stack_view_exits_visual_mode() {
+	testers::module(
+		STACK,
+		&[
+			Event::from(StandardEvent::ToggleVisualMode),
+			Event::from(StandardEvent::ToggleStackView),
+			Event::from(StandardEvent::ToggleVisualMode),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.state, ListState::Normal);
+			assert_none!(module.visual_index_start);
+		},
+	);
+}
+
+#[test]
+fn move_cursor_skips_collapsed_section() {
+	testers::module(
+		STACK,
+		&[
+			Event::from(StandardEvent::ToggleStackView),
+			Event::from(StandardEvent::ToggleSection),
+			Event::from(StandardEvent::MoveCursorDown),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.todo_file.lock().get_selected_line_index(), 3);
+		},
+	);
+}
+
+#[test]
+fn move_cursor_into_collapsed_section() {
+	testers::module(
+		STACK,
+		&[
+			Event::from(StandardEvent::ToggleStackView),
+			Event::from(StandardEvent::ToggleSection),
+			Event::from(StandardEvent::MoveCursorEnd),
+			Event::from(StandardEvent::MoveCursorUp),
+			Event::from(StandardEvent::MoveCursorUp),
+			Event::from(StandardEvent::MoveCursorUp),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.todo_file.lock().get_selected_line_index(), 0);
+		},
+	);
+}
+
+#[test]
+fn move_section_down() {
+	testers::module(
+		STACK,
+		&[
+			Event::from(StandardEvent::ToggleStackView),
+			Event::from(StandardEvent::ToggleSection),
+			Event::from(StandardEvent::SwapSelectedDown),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(lines(&module), vec![
+				"pick ccc c3",
+				"update-ref refs/heads/two",
+				"pick aaa c1",
+				"pick bbb c2",
+				"update-ref refs/heads/one",
+				"pick ddd c4",
+			]);
+			assert_eq!(module.todo_file.lock().get_selected_line_index(), 2);
+		},
+	);
+}
+
+#[test]
+fn move_section_up() {
+	testers::module(
+		STACK,
+		&[
+			Event::from(StandardEvent::ToggleStackView),
+			Event::from(StandardEvent::MoveCursorDown),
+			Event::from(StandardEvent::MoveCursorDown),
+			Event::from(StandardEvent::MoveCursorDown),
+			Event::from(StandardEvent::ToggleSection),
+			Event::from(StandardEvent::SwapSelectedUp),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(lines(&module), vec![
+				"pick ccc c3",
+				"update-ref refs/heads/two",
+				"pick aaa c1",
+				"pick bbb c2",
+				"update-ref refs/heads/one",
+				"pick ddd c4",
+			]);
+			assert_eq!(module.todo_file.lock().get_selected_line_index(), 0);
+		},
+	);
+}
+
+#[test]
+fn move_section_is_single_undo() {
+	testers::module(
+		STACK,
+		&[
+			Event::from(StandardEvent::ToggleStackView),
+			Event::from(StandardEvent::ToggleSection),
+			Event::from(StandardEvent::SwapSelectedDown),
+			Event::from(StandardEvent::Undo),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(lines(&module), STACK);
+			assert_eq!(module.state, ListState::Normal);
+		},
+	);
+}
+
+#[test]
+fn move_first_section_up() {
+	testers::module(
+		STACK,
+		&[
+			Event::from(StandardEvent::ToggleStackView),
+			Event::from(StandardEvent::ToggleSection),
+			Event::from(StandardEvent::SwapSelectedUp),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(lines(&module), STACK);
+		},
+	);
+}
+
+#[test]
+fn move_commit_to_next_section() {
+	testers::module(
+		STACK,
+		&[
+			Event::from(StandardEvent::ToggleStackView),
+			Event::from(StandardEvent::MoveCursorDown),
+			Event::from(StandardEvent::SwapSelectedDown),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(lines(&module), vec![
+				"pick aaa c1",
+				"update-ref refs/heads/one",
+				"pick bbb c2",
+				"pick ccc c3",
+				"update-ref refs/heads/two",
+				"pick ddd c4",
+			]);
+			assert_eq!(module.todo_file.lock().get_selected_line_index(), 2);
+		},
+	);
+}
+
+#[test]
+fn move_commit_to_previous_section() {
+	testers::module(
+		STACK,
+		&[
+			Event::from(StandardEvent::ToggleStackView),
+			Event::from(StandardEvent::MoveCursorEnd),
+			Event::from(StandardEvent::SwapSelectedUp),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(lines(&module), vec![
+				"pick aaa c1",
+				"pick bbb c2",
+				"update-ref refs/heads/one",
+				"pick ccc c3",
+				"pick ddd c4",
+				"update-ref refs/heads/two",
+			]);
+			assert_eq!(module.todo_file.lock().get_selected_line_index(), 4);
+		},
+	);
+}
+
+#[test]
+fn move_commit_within_section() {
+	testers::module(
+		STACK,
+		&[
+			Event::from(StandardEvent::ToggleStackView),
+			Event::from(StandardEvent::SwapSelectedDown),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(lines(&module), vec![
+				"pick bbb c2",
+				"pick aaa c1",
+				"update-ref refs/heads/one",
+				"pick ccc c3",
+				"update-ref refs/heads/two",
+				"pick ddd c4",
+			]);
+		},
+	);
+}
+
+#[test]
+fn drop_section() {
+	testers::module(
+		STACK,
+		&[
+			Event::from(StandardEvent::ToggleStackView),
+			Event::from(StandardEvent::ToggleSection),
+			Event::from(StandardEvent::ActionDrop),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(lines(&module), vec![
+				"drop aaa c1",
+				"drop bbb c2",
+				"update-ref refs/heads/one",
+				"pick ccc c3",
+				"update-ref refs/heads/two",
+				"pick ddd c4",
+			]);
+		},
+	);
+}
+
+#[test]
+fn remove_section() {
+	testers::module(
+		STACK,
+		&[
+			Event::from(StandardEvent::ToggleStackView),
+			Event::from(StandardEvent::ToggleSection),
+			Event::from(StandardEvent::Delete),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(lines(&module), vec!["pick ccc c3", "update-ref refs/heads/two", "pick ddd c4"]);
+		},
+	);
+}
+
+#[test]
+fn edit_ignored_on_collapsed_section() {
+	testers::module(
+		&["exec make", "update-ref refs/heads/one", "pick aaa c1"],
+		&[
+			Event::from(StandardEvent::ToggleStackView),
+			Event::from(StandardEvent::ToggleSection),
+			Event::from(StandardEvent::Edit),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.state, ListState::Normal);
+		},
+	);
+}
@@ -0,0 +1,152 @@
+use super::*;
+use crate::{action_line, assert_rendered_output, input::KeyCode};
+
+#[test]
+fn render() {
+	testers::module(
+		&["pick aaa c1", "pick bbb c2"],
+		&[
+			Event::from(StandardEvent::ActionDrop),
+			Event::from(StandardEvent::ShowUndoHistory),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.state, ListState::UndoHistory);
+			assert_rendered_output!(
+				Style test_context.build_view_data(&mut module),
+				"{TITLE}",
+				"{LEADING}",
+				"{IndicatorColor}Undo history, Enter to restore the selected state, Esc to cancel",
+				"",
+				"{BODY}",
+				"{Selected}{Normal}* Changed aaa to drop{Pad( )}",
+				"{Normal}  Oldest recorded state",
+				"{TRAILING}",
+				"",
+				"{IndicatorColor}> drop aaa c1",
+				"{Normal}  pick bbb c2"
+			);
+		},
+	);
+}
+
+#[test]
+fn render_redo_state() {
+	testers::module(
+		&["pick aaa c1"],
+		&[
+			Event::from(StandardEvent::ActionDrop),
+			Event::from(StandardEvent::Undo),
+			Event::from(StandardEvent::ShowUndoHistory),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_rendered_output!(
+				Style test_context.build_view_data(&mut module),
+				"{TITLE}",
+				"{LEADING}",
+				"{IndicatorColor}Undo history, Enter to restore the selected state, Esc to cancel",
+				"",
+				"{BODY}",
+				"{Normal}  {Normal,Dimmed}Changed aaa to drop",
+				"{Selected}{Normal}* Oldest recorded state{Pad( )}",
+				"{TRAILING}",
+				"",
+				"{IndicatorColor}> pick aaa c1"
+			);
+		},
+	);
+}
+
+#[test]
+fn restore_older_state() {
+	testers::module(
+		&["pick aaa c1", "pick bbb c2"],
+		&[
+			Event::from(StandardEvent::ActionDrop),
+			Event::from(StandardEvent::ShowUndoHistory),
+			Event::from(KeyCode::Down),
+			Event::from(KeyCode::Enter),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.state, ListState::Normal);
+			assert_rendered_output!(
+				Body test_context.build_view_data(&mut module),
+				action_line!(Selected Pick "aaa", "c1"),
+				action_line!(Pick "bbb", "c2")
+			);
+		},
+	);
+}
+
+#[test]
+fn restore_undone_state() {
+	testers::module(
+		&["pick aaa c1"],
+		&[
+			Event::from(StandardEvent::ActionDrop),
+			Event::from(StandardEvent::Undo),
+			Event::from(StandardEvent::ShowUndoHistory),
+			Event::from(KeyCode::Up),
+			Event::from(KeyCode::Enter),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_rendered_output!(
+				Body test_context.build_view_data(&mut module),
+				action_line!(Selected Drop "aaa", "c1")
+			);
+		},
+	);
+}
+
+#[test]
+fn cancel() {
+	testers::module(
+		&["pick aaa c1"],
+		&[
+			Event::from(StandardEvent::ActionDrop),
+			Event::from(StandardEvent::ShowUndoHistory),
+			Event::from(KeyCode::Down),
+			Event::from(KeyCode::Esc),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.state, ListState::Normal);
+			assert_rendered_output!(
+				Body test_context.build_view_data(&mut module),
+				action_line!(Selected Drop "aaa", "c1")
+			);
+		},
+	);
+}
+
+#[test]
+fn cursor_stays_within_history() {
+	testers::module(
+		&["pick aaa c1"],
+		&[
+			Event::from(StandardEvent::ActionDrop),
+			Event::from(StandardEvent::ShowUndoHistory),
+			Event::from(KeyCode::PageDown),
+			Event::from(KeyCode::Down),
+			Event::from(KeyCode::PageUp),
+			Event::from(KeyCode::Up),
+		],
+		|mut test_context| {
+			let mut module = create_list(&create_config(), test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_rendered_output!(
+				Body test_context.build_view_data(&mut module),
+				"{Selected}* Changed aaa to drop{Pad( )}",
+				"  Oldest recorded state"
+			);
+		},
+	);
+}
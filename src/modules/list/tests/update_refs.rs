@@ -0,0 +1,253 @@
+use super::*;
+use crate::{
+	assert_rendered_output,
+	git::ReferenceKind,
+	test_helpers::{assertions::assert_rendered_output::AssertRenderOptions, with_sequencer_todo_file},
+};
+
+fn create_list_with_branches(todo_file: TodoFile) -> List {
+	List::new(
+		&create_config(),
+		Arc::new(Mutex::new(todo_file)),
+		vec![
+			Reference::new(
+				String::from("aaaaaaaa"),
+				String::from("refs/heads/feature-a"),
+				String::from("feature-a"),
+				ReferenceKind::Branch,
+			),
+			Reference::new(
+				String::from("aaaaaaaa"),
+				String::from("refs/heads/feature-b"),
+				String::from("feature-b"),
+				ReferenceKind::Branch,
+			),
+			Reference::new(
+				String::from("bbbbbbbb"),
+				String::from("refs/heads/feature-c"),
+				String::from("feature-c"),
+				ReferenceKind::Branch,
+			),
+		],
+		None,
+		InputHistory::default(),
+	)
+}
+
+#[test]
+fn render_branch_names() {
+	testers::module(
+		&["pick aaaaaaaa comment 1", "pick bbbbbbbb comment 2", "pick cccccccc comment 3"],
+		&[],
+		|mut test_context| {
+			let mut module = create_list_with_branches(test_context.take_todo_file());
+			let view_data = test_context.build_view_data(&mut module);
+			assert_rendered_output!(
+				Options AssertRenderOptions::INCLUDE_STYLE | AssertRenderOptions::BODY_ONLY,
+				view_data,
+				"{Selected}{Normal} > {ActionPick}pick   {Normal}aaaaaaaa comment 1{IndicatorColor} (feature-a, \
+				 feature-b){Normal}{Pad( )}",
+				"{Normal}   {ActionPick}pick   {Normal}bbbbbbbb comment 2{IndicatorColor} (feature-c)",
+				"{Normal}   {ActionPick}pick   {Normal}cccccccc comment 3"
+			);
+		},
+	);
+}
+
+#[test]
+fn render_update_ref_warning() {
+	testers::module(
+		&[
+			"pick cccccccc comment 1",
+			"update-ref refs/heads/feature-a",
+			"pick aaaaaaaa comment 2",
+			"update-ref refs/heads/feature-b",
+		],
+		&[],
+		|mut test_context| {
+			let mut module = create_list_with_branches(test_context.take_todo_file());
+			let view_data = test_context.build_view_data(&mut module);
+			assert_rendered_output!(
+				Options AssertRenderOptions::INCLUDE_STYLE | AssertRenderOptions::BODY_ONLY,
+				view_data,
+				"{Selected}{Normal} > {ActionPick}pick   {Normal}cccccccc comment 1{Pad( )}",
+				"{Normal}   {ActionUpdateRef}update-ref {Normal}refs/heads/feature-a{IndicatorColor} (warning: branch \
+				 will no longer point to aaaaaaaa)",
+				"{Normal}   {ActionPick}pick   {Normal}aaaaaaaa comment 2{IndicatorColor} (feature-a, feature-b)",
+				"{Normal}   {ActionUpdateRef}update-ref {Normal}refs/heads/feature-b"
+			);
+		},
+	);
+}
+
+#[test]
+fn insert_update_refs_choose_branches() {
+	testers::module(
+		&["pick aaaaaaaa comment 1", "pick bbbbbbbb comment 2"],
+		&[Event::from(StandardEvent::InsertUpdateRefs)],
+		|mut test_context| {
+			let mut module = create_list_with_branches(test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.state, ListState::UpdateRefs);
+			let view_data = test_context.build_view_data(&mut module);
+			assert_rendered_output!(
+				view_data,
+				"{TITLE}",
+				"{LEADING}",
+				"Select the branches to insert update-ref lines for:",
+				"",
+				"{BODY}",
+				"a) All branches",
+				"1) feature-a",
+				"2) feature-b",
+				"3) feature-c",
+				"q) Cancel",
+				"",
+				"Please choose an option."
+			);
+		},
+	);
+}
+
+#[test]
+fn insert_update_refs() {
+	testers::module(
+		&[
+			"pick aaaaaaaa comment 1",
+			"update-ref refs/heads/feature-b",
+			"pick bbbbbbbb comment 2",
+			"pick cccccccc comment 3",
+		],
+		&[
+			Event::from(StandardEvent::InsertUpdateRefs),
+			Event::from(KeyCode::Char('a')),
+		],
+		|mut test_context| {
+			let mut module = create_list_with_branches(test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.todo_file.lock().get_lines_owned(), vec![
+				Line::parse("pick aaaaaaaa comment 1").unwrap(),
+				Line::new_update_ref("refs/heads/feature-a"),
+				Line::new_update_ref("refs/heads/feature-b"),
+				Line::parse("pick bbbbbbbb comment 2").unwrap(),
+				Line::new_update_ref("refs/heads/feature-c"),
+				Line::parse("pick cccccccc comment 3").unwrap(),
+			]);
+			assert_eq!(module.state, ListState::Normal);
+		},
+	);
+}
+
+#[test]
+fn insert_update_refs_chosen_branch() {
+	testers::module(
+		&["pick aaaaaaaa comment 1", "pick bbbbbbbb comment 2"],
+		&[
+			Event::from(StandardEvent::InsertUpdateRefs),
+			Event::from(KeyCode::Char('2')),
+		],
+		|mut test_context| {
+			let mut module = create_list_with_branches(test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.todo_file.lock().get_lines_owned(), vec![
+				Line::parse("pick aaaaaaaa comment 1").unwrap(),
+				Line::new_update_ref("refs/heads/feature-b"),
+				Line::parse("pick bbbbbbbb comment 2").unwrap(),
+			]);
+			assert_eq!(module.state, ListState::Normal);
+		},
+	);
+}
+
+#[test]
+fn insert_update_refs_cancel() {
+	testers::module(
+		&["pick aaaaaaaa comment 1", "pick bbbbbbbb comment 2"],
+		&[
+			Event::from(StandardEvent::InsertUpdateRefs),
+			Event::from(KeyCode::Char('q')),
+		],
+		|mut test_context| {
+			let mut module = create_list_with_branches(test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.todo_file.lock().get_lines_owned(), vec![
+				Line::parse("pick aaaaaaaa comment 1").unwrap(),
+				Line::parse("pick bbbbbbbb comment 2").unwrap(),
+			]);
+			assert_eq!(module.state, ListState::Normal);
+		},
+	);
+}
+
+#[test]
+fn insert_single_update_ref_without_choice() {
+	testers::module(
+		&["pick bbbbbbbb comment 2"],
+		&[Event::from(StandardEvent::InsertUpdateRefs)],
+		|mut test_context| {
+			let mut module = create_list_with_branches(test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.todo_file.lock().get_lines_owned(), vec![
+				Line::parse("pick bbbbbbbb comment 2").unwrap(),
+				Line::new_update_ref("refs/heads/feature-c"),
+			]);
+			assert_eq!(module.state, ListState::Normal);
+		},
+	);
+}
+
+#[test]
+fn insert_update_refs_undo_in_one_step() {
+	testers::module(
+		&["pick aaaaaaaa comment 1", "pick bbbbbbbb comment 2"],
+		&[
+			Event::from(StandardEvent::InsertUpdateRefs),
+			Event::from(KeyCode::Char('a')),
+			Event::from(StandardEvent::Undo),
+		],
+		|mut test_context| {
+			let mut module = create_list_with_branches(test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.todo_file.lock().get_lines_owned(), vec![
+				Line::parse("pick aaaaaaaa comment 1").unwrap(),
+				Line::parse("pick bbbbbbbb comment 2").unwrap(),
+			]);
+		},
+	);
+}
+
+#[test]
+fn insert_update_refs_keeps_selected_line() {
+	testers::module(
+		&["pick aaaaaaaa comment 1", "pick bbbbbbbb comment 2", "pick cccccccc comment 3"],
+		&[
+			Event::from(StandardEvent::MoveCursorDown),
+			Event::from(StandardEvent::InsertUpdateRefs),
+			Event::from(KeyCode::Char('a')),
+		],
+		|mut test_context| {
+			let mut module = create_list_with_branches(test_context.take_todo_file());
+			_ = test_context.handle_all_events(&mut module);
+			let todo_file = module.todo_file.lock();
+			assert_eq!(todo_file.get_selected_line_index(), 3);
+			assert_eq!(todo_file.get_selected_line().unwrap().get_hash(), "bbbbbbbb");
+		},
+	);
+}
+
+#[test]
+fn insert_update_refs_sequencer() {
+	with_sequencer_todo_file(&["pick aaaaaaaa comment 1"], |todo_file| {
+		testers::module(
+			&[],
+			&[Event::from(StandardEvent::InsertUpdateRefs)],
+			|mut test_context| {
+				let mut module = create_list_with_branches(todo_file);
+				_ = test_context.handle_all_events(&mut module);
+				assert_eq!(module.todo_file.lock().get_lines_owned(), vec![
+					Line::parse("pick aaaaaaaa comment 1").unwrap()
+				]);
+			},
+		);
+	});
+}
@@ -0,0 +1,150 @@
+use std::cmp::{max, min};
+
+use version_track::Version;
+
+use crate::{
+	display::DisplayColor,
+	input::{Event, KeyCode, KeyEvent, KeyModifiers},
+	todo_file::{HistoryEntry, Line, TodoFile},
+	view::{LineSegment, LineSegmentOptions, ViewData, ViewLine},
+};
+
+const PAGE_SIZE: usize = 10;
+const PREVIEW_CONTEXT_LINES: usize = 3;
+const PREVIEW_MAX_LINES: usize = 10;
+
+/// Browses the states of the undo history, newest first, to restore one of the states in a single step. The entries
+/// and the preview are kept for the version of the todo file, since building them replays the history.
+pub(crate) struct UndoHistory {
+	current: usize,
+	cursor: usize,
+	entries: Vec<HistoryEntry>,
+	entries_version: Option<Version>,
+	finished: bool,
+	preview: Vec<Line>,
+	preview_key: Option<(usize, Version)>,
+	restore: bool,
+	view_data: ViewData,
+}
+
+impl UndoHistory {
+	pub(crate) fn new() -> Self {
+		Self {
+			current: 0,
+			cursor: 0,
+			entries: vec![],
+			entries_version: None,
+			finished: false,
+			preview: vec![],
+			preview_key: None,
+			restore: false,
+			view_data: ViewData::new(|updater| {
+				updater.set_show_title(true);
+			}),
+		}
+	}
+
+	/// Start browsing the undo history of the todo file, from the current state.
+	pub(crate) fn reset(&mut self, todo_file: &TodoFile) {
+		let version = *todo_file.version();
+		if self.entries_version != Some(version) {
+			let (entries, current) = todo_file.history_entries();
+			self.entries = entries;
+			self.current = current;
+			self.entries_version = Some(version);
+		}
+		self.cursor = self.current;
+		self.finished = false;
+		self.restore = false;
+	}
+
+	pub(crate) const fn is_finished(&self) -> bool {
+		self.finished
+	}
+
+	/// The position of the state to restore, or `None` when browsing was cancelled or the current state was chosen.
+	pub(crate) fn restore_position(&self) -> Option<usize> {
+		(self.restore && self.cursor != self.current).then_some(self.cursor)
+	}
+
+	pub(crate) fn handle_event(&mut self, event: Event) {
+		let Event::Key(KeyEvent { code, modifiers }) = event
+		else {
+			return;
+		};
+		let last = self.entries.len().saturating_sub(1);
+		match (code, modifiers) {
+			(KeyCode::Enter, KeyModifiers::NONE) => {
+				self.restore = true;
+				self.finished = true;
+			},
+			(KeyCode::Esc | KeyCode::Char('q'), KeyModifiers::NONE) => self.finished = true,
+			// the newest state is shown first, so moving up moves to a newer state
+			(KeyCode::Up, KeyModifiers::NONE) => self.cursor = min(self.cursor + 1, last),
+			(KeyCode::Down, KeyModifiers::NONE) => self.cursor = self.cursor.saturating_sub(1),
+			(KeyCode::PageUp, KeyModifiers::NONE) => self.cursor = min(self.cursor + PAGE_SIZE, last),
+			(KeyCode::PageDown, KeyModifiers::NONE) => self.cursor = self.cursor.saturating_sub(PAGE_SIZE),
+			(KeyCode::Home, KeyModifiers::NONE) => self.cursor = last,
+			(KeyCode::End, KeyModifiers::NONE) => self.cursor = 0,
+			_ => {},
+		}
+	}
+
+	pub(crate) fn build_view_data(&mut self, todo_file: &TodoFile) -> &ViewData {
+		let preview_key = (self.cursor, *todo_file.version());
+		if self.preview_key != Some(preview_key) {
+			self.preview = todo_file.history_preview(self.cursor);
+			self.preview_key = Some(preview_key);
+		}
+		let preview = &self.preview;
+		let cursor_entry = self.entries.get(self.cursor);
+		self.view_data.update_view_data(|updater| {
+			updater.clear();
+			updater.push_leading_line(ViewLine::from(LineSegment::new_with_color(
+				"Undo history, Enter to restore the selected state, Esc to cancel",
+				DisplayColor::IndicatorColor,
+			)));
+			updater.push_leading_line(ViewLine::new_empty_line());
+
+			for (position, entry) in self.entries.iter().enumerate().rev() {
+				let marker = if position == self.current { "* " } else { "  " };
+				// states after the current state are only reachable by redoing, so are shown dimmed
+				let options = LineSegmentOptions::conditional(position > self.current, LineSegmentOptions::DIMMED);
+				let line = ViewLine::from(vec![
+					LineSegment::new(marker),
+					LineSegment::new_with_color_and_style(entry.description(), DisplayColor::Normal, options),
+				]);
+				updater.push_line(if position == self.cursor {
+					line.set_selected(true).set_padding(' ')
+				}
+				else {
+					line
+				});
+			}
+
+			if let Some(entry) = cursor_entry {
+				let changed_start = min(entry.start_index(), entry.end_index());
+				let changed_end = max(entry.start_index(), entry.end_index());
+				let first = changed_start.saturating_sub(PREVIEW_CONTEXT_LINES);
+				updater.push_trailing_line(ViewLine::new_empty_line());
+				for (index, line) in preview.iter().enumerate().skip(first).take(PREVIEW_MAX_LINES) {
+					updater.push_trailing_line(ViewLine::from(
+						if (changed_start..=changed_end).contains(&index) {
+							LineSegment::new_with_color(
+								format!("> {}", line.to_text()).as_str(),
+								DisplayColor::IndicatorColor,
+							)
+						}
+						else {
+							LineSegment::new(format!("  {}", line.to_text()).as_str())
+						},
+					));
+				}
+			}
+			if !self.entries.is_empty() {
+				updater.ensure_line_visible(self.entries.len() - 1 - self.cursor);
+			}
+		});
+		&self.view_data
+	}
+}
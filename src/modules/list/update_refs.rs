@@ -0,0 +1,136 @@
+use std::mem;
+
+use crate::{
+	components::choice::Choice,
+	display::DisplayColor,
+	input::Event,
+	view::{LineSegment, ViewData, ViewLine},
+};
+
+/// The keys to choose a single branch, which skip the keys to choose all branches and to cancel.
+const BRANCH_CHOICE_KEYS: &str = "123456789bcdefghijklmnoprstuvwxyz";
+
+/// The branches to insert `update-ref` lines for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum UpdateRefTarget {
+	All,
+	Branch(String),
+}
+
+impl UpdateRefTarget {
+	/// Does the target include the branch with the full name.
+	pub(crate) fn includes(&self, name: &str) -> bool {
+		match *self {
+			Self::All => true,
+			Self::Branch(ref branch) => branch == name,
+		}
+	}
+}
+
+/// Build the choices of the branches to insert `update-ref` lines for, from the missing `update-ref` lines. Branches
+/// past the number of keys can only be inserted with all the other branches.
+pub(crate) fn update_ref_choices(missing_refs: &[(usize, String)]) -> Vec<(Option<UpdateRefTarget>, char, String)> {
+	let mut choices = vec![(Some(UpdateRefTarget::All), 'a', String::from("All branches"))];
+	for (key, update_ref) in BRANCH_CHOICE_KEYS.chars().zip(missing_refs) {
+		let name = &update_ref.1;
+		choices.push((
+			Some(UpdateRefTarget::Branch(name.clone())),
+			key,
+			String::from(name.strip_prefix("refs/heads/").unwrap_or(name)),
+		));
+	}
+	choices.push((None, 'q', String::from("Cancel")));
+	choices
+}
+
+/// Chooses the branches to insert `update-ref` lines for, when more than one branch is missing an `update-ref` line.
+pub(crate) struct UpdateRefs {
+	choices: Choice<Option<UpdateRefTarget>>,
+	finished: bool,
+	missing_refs: Vec<(usize, String)>,
+	target: Option<UpdateRefTarget>,
+}
+
+impl UpdateRefs {
+	pub(crate) fn new() -> Self {
+		Self {
+			choices: Choice::new(vec![]),
+			finished: false,
+			missing_refs: vec![],
+			target: None,
+		}
+	}
+
+	/// Start choosing the branches, from the missing `update-ref` lines.
+	pub(crate) fn start(&mut self, missing_refs: Vec<(usize, String)>) {
+		self.choices = Choice::new(update_ref_choices(&missing_refs));
+		self.choices.set_prompt(vec![ViewLine::from(LineSegment::new_with_color(
+			"Select the branches to insert update-ref lines for:",
+			DisplayColor::IndicatorColor,
+		))]);
+		self.finished = false;
+		self.missing_refs = missing_refs;
+		self.target = None;
+	}
+
+	pub(crate) const fn is_finished(&self) -> bool {
+		self.finished
+	}
+
+	/// Take the missing `update-ref` lines of the chosen branches, which are none when the choice was cancelled.
+	pub(crate) fn take_chosen_update_refs(&mut self) -> Vec<(usize, String)> {
+		let Some(target) = self.target.take()
+		else {
+			return vec![];
+		};
+		mem::take(&mut self.missing_refs)
+			.into_iter()
+			.filter(|update_ref| target.includes(update_ref.1.as_str()))
+			.collect()
+	}
+
+	pub(crate) fn handle_event(&mut self, event: Event, view_state: &crate::view::State) {
+		if let Some(choice) = self.choices.handle_event(event, view_state) {
+			self.target = choice.clone();
+			self.finished = true;
+		}
+	}
+
+	pub(crate) fn build_view_data(&mut self) -> &ViewData {
+		self.choices.get_view_data()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn update_ref_choices_for_branches() {
+		let choices = update_ref_choices(&[
+			(0, String::from("refs/heads/feature-a")),
+			(1, String::from("refs/heads/feature-b")),
+		]);
+		assert_eq!(choices, vec![
+			(Some(UpdateRefTarget::All), 'a', String::from("All branches")),
+			(
+				Some(UpdateRefTarget::Branch(String::from("refs/heads/feature-a"))),
+				'1',
+				String::from("feature-a")
+			),
+			(
+				Some(UpdateRefTarget::Branch(String::from("refs/heads/feature-b"))),
+				'2',
+				String::from("feature-b")
+			),
+			(None, 'q', String::from("Cancel")),
+		]);
+	}
+
+	#[test]
+	fn update_ref_target_includes() {
+		assert!(UpdateRefTarget::All.includes("refs/heads/feature-a"));
+		assert!(UpdateRefTarget::Branch(String::from("refs/heads/feature-a")).includes("refs/heads/feature-a"));
+		assert!(!UpdateRefTarget::Branch(String::from("refs/heads/feature-a")).includes("refs/heads/feature-b"));
+	}
+}
@@ -2,13 +2,18 @@ use std::cmp;
 
 use bitflags::bitflags;
 use if_chain::if_chain;
+use unicode_segmentation::UnicodeSegmentation;
 
 use crate::{
 	config::KeyBindings,
 	display::DisplayColor,
-	modules::list::search::LineMatch,
+	modules::list::{
+		search::{LineMatch, Query},
+		stack::Section,
+	},
+	git::Reference,
 	todo_file::{Action, Line, TodoFile},
-	view::{LineSegment, LineSegmentOptions},
+	view::{LineSegment, LineSegmentOptions, ViewLine},
 };
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -18,8 +23,12 @@ enum HelpLinesSelector {
 	Common,
 }
 
-fn build_help_lines(key_bindings: &KeyBindings, selector: HelpLinesSelector) -> Vec<(Vec<String>, String)> {
-	let lines = vec![
+fn build_help_lines(
+	key_bindings: &KeyBindings,
+	selector: HelpLinesSelector,
+	is_sequencer: bool,
+) -> Vec<(Vec<String>, String)> {
+	let mut lines = vec![
 		(&key_bindings.move_up, "Move selection up", HelpLinesSelector::Common),
 		(
 			&key_bindings.move_down,
@@ -92,51 +101,166 @@ fn build_help_lines(key_bindings: &KeyBindings, selector: HelpLinesSelector) ->
 			"Show commit information",
 			HelpLinesSelector::Normal,
 		),
-		(
+	];
+
+	// the sequencer, used for a multiple commit cherry-pick or revert, only accepts the pick and revert actions
+	if !is_sequencer {
+		lines.push((
 			&key_bindings.action_break,
 			"Toggle break action",
 			HelpLinesSelector::Normal,
-		),
-		(
-			&key_bindings.action_pick,
-			"Set selected commits to be picked",
-			HelpLinesSelector::Common,
-		),
-		(
-			&key_bindings.action_reword,
-			"Set selected commits to be reworded",
-			HelpLinesSelector::Common,
-		),
-		(
-			&key_bindings.action_edit,
-			"Set selected commits to be edited",
-			HelpLinesSelector::Common,
-		),
-		(
-			&key_bindings.action_squash,
-			"Set selected commits to be squashed",
+		));
+	}
+	lines.push((
+		&key_bindings.action_pick,
+		"Set selected commits to be picked",
+		HelpLinesSelector::Common,
+	));
+	if is_sequencer {
+		lines.push((
+			&key_bindings.action_revert,
+			"Set selected commits to be reverted",
 			HelpLinesSelector::Common,
-		),
+		));
+	}
+	else {
+		lines.extend([
+			(
+				&key_bindings.action_reword,
+				"Set selected commits to be reworded",
+				HelpLinesSelector::Common,
+			),
+			(
+				&key_bindings.action_edit,
+				"Set selected commits to be edited",
+				HelpLinesSelector::Common,
+			),
+			(
+				&key_bindings.action_squash,
+				"Set selected commits to be squashed",
+				HelpLinesSelector::Common,
+			),
+			(
+				&key_bindings.action_fixup,
+				"Set selected commits to be fixed-up",
+				HelpLinesSelector::Common,
+			),
+			(
+				&key_bindings.action_drop,
+				"Set selected commits to be dropped",
+				HelpLinesSelector::Common,
+			),
+			(
+				&key_bindings.edit,
+				"Edit an exec, label, reset or merge action's content",
+				HelpLinesSelector::Normal,
+			),
+		]);
+	}
+	lines.push((
+		&key_bindings.apply_to_matches,
+		"Set the next action for all search matches",
+		HelpLinesSelector::Common,
+	));
+	lines.push((
+		&key_bindings.toggle_mark,
+		"Mark or unmark the selected lines",
+		HelpLinesSelector::Common,
+	));
+	lines.push((
+		&key_bindings.gather_marked,
+		"Move the marked lines after the selected line",
+		HelpLinesSelector::Common,
+	));
+	lines.push((&key_bindings.clear_marks, "Clear the marked lines", HelpLinesSelector::Common));
+	lines.push((
+		&key_bindings.set_named_mark,
+		"Name a mark for the selected line with the next key",
+		HelpLinesSelector::Common,
+	));
+	lines.push((
+		&key_bindings.jump_to_named_mark,
+		"Jump to the line of the mark named by the next key",
+		HelpLinesSelector::Common,
+	));
+	lines.extend([
 		(
-			&key_bindings.action_fixup,
-			"Set selected commits to be fixed-up",
-			HelpLinesSelector::Common,
+			&key_bindings.sort_by_author_date,
+			"Sort selected commits by author date",
+			HelpLinesSelector::Visual,
 		),
 		(
-			&key_bindings.action_drop,
-			"Set selected commits to be dropped",
-			HelpLinesSelector::Common,
+			&key_bindings.sort_by_commit_date,
+			"Sort selected commits by committer date",
+			HelpLinesSelector::Visual,
 		),
 		(
-			&key_bindings.edit,
-			"Edit an exec, label, reset or merge action's content",
-			HelpLinesSelector::Normal,
+			&key_bindings.reverse_selection,
+			"Reverse the order of the selected lines",
+			HelpLinesSelector::Visual,
 		),
 		(
-			&key_bindings.insert_line,
-			"Insert a new line",
-			HelpLinesSelector::Normal,
+			&key_bindings.restore_order,
+			"Restore the original order of the selected lines",
+			HelpLinesSelector::Visual,
 		),
+	]);
+
+	lines.push((
+		&key_bindings.insert_line,
+		"Insert a new line",
+		HelpLinesSelector::Normal,
+	));
+	if !is_sequencer {
+		lines.extend([
+			(
+				&key_bindings.insert_update_refs,
+				"Insert update-ref lines for branches of the commits",
+				HelpLinesSelector::Normal,
+			),
+			(
+				&key_bindings.insert_exec_lines,
+				"Insert exec lines after every commit",
+				HelpLinesSelector::Normal,
+			),
+			(
+				&key_bindings.insert_exec_lines,
+				"Insert exec lines after the selected commits",
+				HelpLinesSelector::Visual,
+			),
+			(
+				&key_bindings.reword_message,
+				"Compose a new message for the selected commit",
+				HelpLinesSelector::Normal,
+			),
+			(
+				&key_bindings.rewrite_metadata,
+				"Change the author, committer date or trailers of the selected commit",
+				HelpLinesSelector::Normal,
+			),
+			(
+				&key_bindings.rewrite_metadata,
+				"Change the author, committer date or trailers of the selected commits",
+				HelpLinesSelector::Visual,
+			),
+			(
+				&key_bindings.toggle_stack_view,
+				"Toggle the stack view of branches",
+				HelpLinesSelector::Normal,
+			),
+			(
+				&key_bindings.toggle_section,
+				"Collapse or expand the selected section or fixup group",
+				HelpLinesSelector::Normal,
+			),
+			(
+				&key_bindings.toggle_fold_view,
+				"Toggle folding fixup and squash lines into their commit",
+				HelpLinesSelector::Normal,
+			),
+		]);
+	}
+	lines.extend([
 		(
 			&key_bindings.remove_line,
 			"Completely remove the selected lines",
@@ -148,11 +272,21 @@ fn build_help_lines(key_bindings: &KeyBindings, selector: HelpLinesSelector) ->
 			"Redo the previous undone change",
 			HelpLinesSelector::Common,
 		),
+		(
+			&key_bindings.show_undo_history,
+			"Browse the undo history and restore a previous state",
+			HelpLinesSelector::Common,
+		),
 		(
 			&key_bindings.open_in_external_editor,
 			"Open the todo file in the default editor",
 			HelpLinesSelector::Common,
 		),
+		(
+			&key_bindings.open_selection_in_external_editor,
+			"Open the selected lines in the default editor",
+			HelpLinesSelector::Common,
+		),
 		(
 			&key_bindings.toggle_visual_mode,
 			"Enter visual selection mode",
@@ -163,7 +297,12 @@ fn build_help_lines(key_bindings: &KeyBindings, selector: HelpLinesSelector) ->
 			"Exit visual selection mode",
 			HelpLinesSelector::Visual,
 		),
-	];
+		(
+			&key_bindings.toggle_filter,
+			"Show only the lines matching the search",
+			HelpLinesSelector::Normal,
+		),
+	]);
 
 	lines
 		.iter()
@@ -174,12 +313,18 @@ fn build_help_lines(key_bindings: &KeyBindings, selector: HelpLinesSelector) ->
 		.collect()
 }
 
-pub(super) fn get_list_normal_mode_help_lines(key_bindings: &KeyBindings) -> Vec<(Vec<String>, String)> {
-	build_help_lines(key_bindings, HelpLinesSelector::Normal)
+pub(super) fn get_list_normal_mode_help_lines(
+	key_bindings: &KeyBindings,
+	is_sequencer: bool,
+) -> Vec<(Vec<String>, String)> {
+	build_help_lines(key_bindings, HelpLinesSelector::Normal, is_sequencer)
 }
 
-pub(super) fn get_list_visual_mode_help_lines(key_bindings: &KeyBindings) -> Vec<(Vec<String>, String)> {
-	build_help_lines(key_bindings, HelpLinesSelector::Visual)
+pub(super) fn get_list_visual_mode_help_lines(
+	key_bindings: &KeyBindings,
+	is_sequencer: bool,
+) -> Vec<(Vec<String>, String)> {
+	build_help_lines(key_bindings, HelpLinesSelector::Visual, is_sequencer)
 }
 
 const fn get_action_color(action: Action) -> DisplayColor {
@@ -190,6 +335,7 @@ const fn get_action_color(action: Action) -> DisplayColor {
 		Action::Exec => DisplayColor::ActionExec,
 		Action::Fixup => DisplayColor::ActionFixup,
 		Action::Pick => DisplayColor::ActionPick,
+		Action::Revert => DisplayColor::ActionRevert,
 		Action::Reword => DisplayColor::ActionReword,
 		Action::Squash => DisplayColor::ActionSquash,
 		Action::Label => DisplayColor::ActionLabel,
@@ -204,7 +350,7 @@ const fn get_action_color(action: Action) -> DisplayColor {
 pub(super) fn get_line_action_maximum_width(todo_file: &TodoFile) -> usize {
 	let mut max_width = 0;
 
-	for line in todo_file.lines_iter() {
+	for line in todo_file.done_lines_iter().chain(todo_file.lines_iter()) {
 		let action_length = match line.get_action() {
 			// allow these to overflow their bounds
 			&Action::Exec | &Action::UpdateRef => 0,
@@ -218,7 +364,7 @@ pub(super) fn get_line_action_maximum_width(todo_file: &TodoFile) -> usize {
 					5
 				}
 			},
-			&Action::Reword | &Action::Squash => 6,
+			&Action::Revert | &Action::Reword | &Action::Squash => 6,
 		};
 		if max_width < action_length {
 			max_width = action_length;
@@ -235,6 +381,8 @@ bitflags! {
 		const SELECTED = 0b0000_0010;
 		const FULL_WIDTH = 0b0000_0100;
 		const SEARCH_LINE = 0b0000_1000;
+		const DONE = 0b0001_0000;
+		const MARKED = 0b0010_0000;
 	}
 }
 
@@ -242,7 +390,7 @@ bitflags! {
 #[allow(clippy::string_slice)]
 pub(super) fn get_todo_line_segments(
 	line: &Line,
-	search_term: Option<&str>,
+	search_query: Option<&Query>,
 	search_match: Option<LineMatch>,
 	options: TodoLineSegmentsOptions,
 	maximum_action_width: usize,
@@ -253,11 +401,25 @@ pub(super) fn get_todo_line_segments(
 	let selected = options.contains(TodoLineSegmentsOptions::SELECTED);
 	let is_full_width = options.contains(TodoLineSegmentsOptions::FULL_WIDTH);
 	let is_search_index = options.contains(TodoLineSegmentsOptions::SEARCH_LINE);
+	let is_done = options.contains(TodoLineSegmentsOptions::DONE);
+	let is_marked = options.contains(TodoLineSegmentsOptions::MARKED);
+	let done_style = LineSegmentOptions::conditional(is_done, LineSegmentOptions::DIMMED);
 
 	let action = line.get_action();
 
 	let indicator = if is_cursor_line || selected {
-		if is_full_width { " > " } else { ">" }
+		if !is_full_width {
+			">"
+		}
+		else if is_marked {
+			" >*"
+		}
+		else {
+			" > "
+		}
+	}
+	else if is_marked {
+		if is_full_width { "  *" } else { "*" }
 	}
 	else if is_full_width {
 		"   "
@@ -269,7 +431,7 @@ pub(super) fn get_todo_line_segments(
 	segments.push(LineSegment::new_with_color_and_style(
 		indicator,
 		DisplayColor::Normal,
-		LineSegmentOptions::conditional(!is_cursor_line && selected, LineSegmentOptions::DIMMED),
+		LineSegmentOptions::conditional(!is_cursor_line && (selected || is_done), LineSegmentOptions::DIMMED),
 	));
 
 	let action_padding = cmp::max(maximum_action_width, 6);
@@ -290,17 +452,32 @@ pub(super) fn get_todo_line_segments(
 		)
 	};
 
-	segments.push(LineSegment::new_with_color(
+	let search_action_match = search_match.map_or(false, |m| m.action());
+	segments.push(LineSegment::new_with_color_and_style(
 		action_name.as_str(),
-		get_action_color(*action),
+		if search_action_match {
+			DisplayColor::IndicatorColor
+		}
+		else {
+			get_action_color(*action)
+		},
+		LineSegmentOptions::conditional(search_action_match && is_search_index, LineSegmentOptions::UNDERLINED)
+			| done_style,
 	));
 
 	// render hash
 	match *action {
-		Action::Drop | Action::Edit | Action::Fixup | Action::Pick | Action::Reword | Action::Squash => {
+		Action::Drop
+		| Action::Edit
+		| Action::Fixup
+		| Action::Pick
+		| Action::Revert
+		| Action::Reword
+		| Action::Squash => {
 			let action_width = if is_full_width { 8 } else { 3 };
 			let max_index = cmp::min(line.get_hash().len(), action_width);
-			let search_hash_match = search_match.map_or(false, |m| m.hash());
+			// a match on the details of the commit is shown on the hash, as it identifies the commit
+			let search_hash_match = search_match.map_or(false, |m| m.hash() || m.metadata());
 
 			segments.push(LineSegment::new_with_color_and_style(
 				format!(
@@ -315,7 +492,8 @@ pub(super) fn get_todo_line_segments(
 				else {
 					DisplayColor::Normal
 				},
-				LineSegmentOptions::conditional(search_hash_match && is_search_index, LineSegmentOptions::UNDERLINED),
+				LineSegmentOptions::conditional(search_hash_match && is_search_index, LineSegmentOptions::UNDERLINED)
+					| done_style,
 			));
 			segments.push(LineSegment::new(" "));
 		},
@@ -333,25 +511,163 @@ pub(super) fn get_todo_line_segments(
 		let search_content_match = search_match.map_or(false, |m| m.content());
 		if_chain! {
 			if search_content_match;
-			if let Some(term) = search_term;
+			if let Some(query) = search_query;
 			then {
-				let mut split_iter = content.split(term);
-				segments.push(LineSegment::new(split_iter.next().unwrap()));
-				for split in split_iter {
+				let mut start = 0;
+				for range in query.content_matches(content) {
+					// safe slices, as the ranges of regex matches are always on character boundaries
+					segments.push(LineSegment::new(&content[start..range.start]));
 					segments.push(LineSegment::new_with_color_and_style(
-						term,
+						&content[range.clone()],
 						DisplayColor::IndicatorColor,
 						LineSegmentOptions::conditional(is_search_index, LineSegmentOptions::UNDERLINED),
 					));
-					if !split.is_empty() {
-						segments.push(LineSegment::new(split));
-					}
+					start = range.end;
+				}
+				if start < content.len() {
+					segments.push(LineSegment::new(&content[start..]));
 				}
 			}
 			else {
-				segments.push(LineSegment::new(content));
+				segments.push(LineSegment::new_with_color_and_style(content, DisplayColor::Normal, done_style));
 			}
 		}
 	}
+
+	// a new message is only applied to a commit that is picked
+	if let Some(message) = line.message() {
+		if matches!(*action, Action::Pick | Action::Edit) {
+			segments.push(LineSegment::new_with_color_and_style(
+				format!(" (new message: {})", message.lines().next().unwrap_or_default()).as_str(),
+				DisplayColor::IndicatorColor,
+				done_style,
+			));
+		}
+	}
+
+	// the metadata changes are only applied to a commit that is picked
+	if matches!(*action, Action::Pick | Action::Reword | Action::Edit) {
+		if let Some(badge) = get_metadata_changes_badge(line) {
+			segments.push(LineSegment::new_with_color_and_style(
+				badge.as_str(),
+				DisplayColor::IndicatorColor,
+				done_style,
+			));
+		}
+	}
+	segments
+}
+
+/// Get the badge listing the changes to the author, committer date and trailers of the commit of a line, or `None`
+/// when the commit is not changed.
+fn get_metadata_changes_badge(line: &Line) -> Option<String> {
+	let metadata_changes = line.metadata_changes();
+	if metadata_changes.is_empty() {
+		return None;
+	}
+	let mut changes = vec![];
+	if let Some(author) = metadata_changes.author() {
+		changes.push(format!("author: {author}"));
+	}
+	if let Some(committer_date) = metadata_changes.committer_date() {
+		changes.push(format!("date: {committer_date}"));
+	}
+	for trailer in metadata_changes.trailers() {
+		changes.push(String::from(trailer.split(':').next().unwrap_or_default()));
+	}
+	Some(format!(" [{}]", changes.join(", ")))
+}
+
+/// Get the segments listing the branches that point to the commit of a line, and a warning when an `update-ref` line
+/// will leave its branch pointing to a different commit.
+pub(super) fn get_branch_segments(
+	branches: &[&Reference],
+	unexpected_target: Option<&str>,
+	is_done: bool,
+) -> Vec<LineSegment> {
+	let mut segments = vec![];
+	if !branches.is_empty() {
+		let names = branches.iter().map(|b| b.shortname()).collect::<Vec<&str>>().join(", ");
+		segments.push(LineSegment::new_with_color_and_style(
+			format!(" ({names})").as_str(),
+			DisplayColor::IndicatorColor,
+			LineSegmentOptions::conditional(is_done, LineSegmentOptions::DIMMED),
+		));
+	}
+	if let Some(hash) = unexpected_target {
+		segments.push(LineSegment::new_with_color(
+			format!(" (warning: branch will no longer point to {hash:.8})").as_str(),
+			DisplayColor::IndicatorColor,
+		));
+	}
 	segments
 }
+
+/// Get the segments for the header of a section in the stack view.
+pub(super) fn get_section_header_segments(
+	section: &Section,
+	is_collapsed: bool,
+	is_cursor_line: bool,
+	is_full_width: bool,
+) -> Vec<LineSegment> {
+	let indicator = if is_cursor_line {
+		if is_full_width { " > " } else { ">" }
+	}
+	else if is_full_width {
+		"   "
+	}
+	else {
+		" "
+	};
+	vec![
+		LineSegment::new(indicator),
+		LineSegment::new_with_color(
+			format!("{} {}", if is_collapsed { "+" } else { "-" }, section.title()).as_str(),
+			DisplayColor::IndicatorColor,
+		),
+	]
+}
+
+/// Get the lines of hints for a commit message, with the length of the subject, and warnings when the subject is
+/// longer than the recommended 50, or the maximum 72, characters, when the subject is not followed by an empty line,
+/// and when lines of the body are longer than 72 characters.
+pub(super) fn get_message_hint_lines(lines: &[&str]) -> Vec<ViewLine> {
+	let subject_length = lines.first().map_or(0, |subject| subject.graphemes(true).count());
+	let subject_hint = if subject_length > 72 {
+		format!("Subject: {subject_length} characters, longer than the maximum of 72")
+	}
+	else if subject_length > 50 {
+		format!("Subject: {subject_length} characters, longer than the recommended 50")
+	}
+	else {
+		format!("Subject: {subject_length} characters")
+	};
+	let mut hint_lines = vec![ViewLine::from(LineSegment::new_with_color(
+		subject_hint.as_str(),
+		if subject_length > 50 {
+			DisplayColor::DiffChangeColor
+		}
+		else {
+			DisplayColor::IndicatorColor
+		},
+	))];
+
+	if lines.get(1).map_or(false, |line| !line.is_empty()) {
+		hint_lines.push(ViewLine::from(LineSegment::new_with_color(
+			"The subject should be followed by an empty line",
+			DisplayColor::DiffChangeColor,
+		)));
+	}
+	let long_lines = lines
+		.iter()
+		.skip(2)
+		.filter(|line| line.graphemes(true).count() > 72)
+		.count();
+	if long_lines > 0 {
+		hint_lines.push(ViewLine::from(LineSegment::new_with_color(
+			format!("{long_lines} body line(s) longer than 72 characters").as_str(),
+			DisplayColor::DiffChangeColor,
+		)));
+	}
+	hint_lines
+}
@@ -1,3 +1,4 @@
+mod search;
 mod show_commit_state;
 mod util;
 mod view_builder;
@@ -12,35 +13,47 @@ use captur::capture;
 use parking_lot::Mutex;
 
 use self::{
+	search::Search,
 	show_commit_state::ShowCommitState,
 	util::get_show_commit_help_lines,
 	view_builder::{ViewBuilder, ViewBuilderOptions},
 };
 use crate::{
-	components::help::Help,
+	components::{
+		help::Help,
+		search_bar::{SearchBar, SearchBarAction},
+		InputHistory,
+	},
 	config::{Config, DiffIgnoreWhitespaceSetting, DiffShowWhitespaceSetting},
 	git::{CommitDiff, CommitDiffLoaderOptions, Repository},
 	input::{Event, InputOptions, KeyBindings, StandardEvent},
 	module::{Module, State},
 	process::Results,
+	search::Searchable,
 	select,
 	todo_file::TodoFile,
 	util::handle_view_data_scroll,
-	view::{RenderContext, ViewData},
+	view::{LineSegment, RenderContext, ViewData, ViewDataUpdater, ViewLine},
 };
 
 // TODO Remove `union` call when bitflags/bitflags#180 is resolved
 const INPUT_OPTIONS: InputOptions = InputOptions::UNDO_REDO
 	.union(InputOptions::MOVEMENT)
-	.union(InputOptions::HELP);
+	.union(InputOptions::HELP)
+	.union(InputOptions::SEARCH_START);
+// scrolling remains available while moving between the search matches
+const INPUT_OPTIONS_SEARCHING: InputOptions = INPUT_OPTIONS.union(InputOptions::SEARCH);
 
 pub(crate) struct ShowCommit {
 	commit_diff_loader_options: CommitDiffLoaderOptions,
 	diff: Option<CommitDiff>,
 	diff_view_data: ViewData,
 	help: Help,
+	history: InputHistory,
 	overview_view_data: ViewData,
 	repository: Repository,
+	search: Search,
+	search_bar: SearchBar,
 	state: ShowCommitState,
 	todo_file: Arc<Mutex<TodoFile>>,
 	view_builder: ViewBuilder,
@@ -57,6 +70,8 @@ impl Module for ShowCommit {
 					return results;
 				}
 			}
+			self.search_bar.reset();
+			self.search.reset();
 			self.overview_view_data.update_view_data(|updater| {
 				updater.clear();
 				updater.reset_scroll_position();
@@ -96,30 +111,40 @@ impl Module for ShowCommit {
 		let view_builder = &self.view_builder;
 		let is_full_width = context.is_full_width();
 
-		match *state {
-			ShowCommitState::Overview => {
-				if self.overview_view_data.is_empty() {
-					self.overview_view_data.update_view_data(|updater| {
-						capture!(view_builder, diff);
+		let view_data = match *state {
+			ShowCommitState::Overview => &mut self.overview_view_data,
+			ShowCommitState::Diff => &mut self.diff_view_data,
+		};
+		if view_data.is_empty() {
+			view_data.update_view_data(|updater| {
+				capture!(view_builder, diff);
+				match *state {
+					ShowCommitState::Overview => {
 						view_builder.build_view_data_for_overview(updater, diff, is_full_width);
-					});
-				}
-				&self.overview_view_data
-			},
-			ShowCommitState::Diff => {
-				if self.diff_view_data.is_empty() {
-					self.diff_view_data.update_view_data(|updater| {
-						capture!(view_builder, diff);
+					},
+					ShowCommitState::Diff => {
 						view_builder.build_view_data_diff(updater, diff, is_full_width);
-					});
+					},
 				}
-				&self.diff_view_data
-			},
+			});
+			self.search.set_lines();
+			let search = &self.search;
+			let search_bar = &self.search_bar;
+			view_data.update_view_data(|updater| {
+				capture!(search, search_bar);
+				Self::build_search_view_data(updater, search, search_bar);
+			});
 		}
+		view_data
 	}
 
 	fn input_options(&self) -> &InputOptions {
-		select!(default & INPUT_OPTIONS, self.help.input_options())
+		select!(
+			default & INPUT_OPTIONS,
+			self.help.input_options(),
+			self.search_bar.is_editing().then(|| self.search_bar.input_options()).flatten(),
+			self.search_bar.is_active().then_some(&INPUT_OPTIONS_SEARCHING)
+		)
 	}
 
 	fn read_event(&self, event: Event, key_bindings: &KeyBindings) -> Event {
@@ -131,7 +156,8 @@ impl Module for ShowCommit {
 					.then(|| Event::from(StandardEvent::ShowDiff))
 					.unwrap_or(event)
 			},
-			self.help.read_event(event)
+			self.help.read_event(event),
+			self.search_bar.read_event(event)
 		)
 	}
 
@@ -154,6 +180,14 @@ impl Module for ShowCommit {
 						}
 					},
 					Event::Standard(StandardEvent::Help) => self.help.set_active(),
+					Event::Standard(StandardEvent::SearchStart) => {
+						// the search thread may hold the search of the list, so replace it with the search of this view
+						let searchable: Box<dyn Searchable> = Box::new(self.search.clone());
+						results = Results::from(searchable);
+						results.search_cancel();
+						self.search_bar.start_search(Some(""));
+						active_view_data.update_view_data(|updater| updater.clear());
+					},
 					Event::Key(_) => {
 						active_view_data.update_view_data(|updater| updater.clear());
 						if self.state == ShowCommitState::Diff {
@@ -169,13 +203,19 @@ impl Module for ShowCommit {
 				results
 			},
 			self.help.handle_event(event, view_state),
+			self.handle_search_input(event),
 			handle_view_data_scroll(event, view_state)
 		)
 	}
 }
 
 impl ShowCommit {
-	pub(crate) fn new(config: &Config, repository: Repository, todo_file: Arc<Mutex<TodoFile>>) -> Self {
+	pub(crate) fn new(
+		config: &Config,
+		repository: Repository,
+		todo_file: Arc<Mutex<TodoFile>>,
+		history: InputHistory,
+	) -> Self {
 		let overview_view_data = ViewData::new(|updater| {
 			updater.set_show_title(true);
 			updater.set_show_help(true);
@@ -203,16 +243,118 @@ impl ShowCommit {
 			.interhunk_context(config.git.diff_interhunk_lines)
 			.renames(config.git.diff_renames, config.git.diff_rename_limit);
 
+		let search = Search::default();
+		let mut search_bar = SearchBar::new();
+		search_bar.set_history(history.clone());
+
 		Self {
 			commit_diff_loader_options,
 			diff: None,
 			diff_view_data,
 			help: Help::new_from_keybindings(&get_show_commit_help_lines(&config.key_bindings)),
+			history,
 			overview_view_data,
 			repository,
+			search: search.clone(),
+			search_bar,
 			state: ShowCommitState::Overview,
 			todo_file,
-			view_builder: ViewBuilder::new(view_builder_options),
+			view_builder: ViewBuilder::new(view_builder_options, search),
 		}
 	}
+
+	/// Add the search bar while editing the search term, or the search results, as the trailing line of the view.
+	fn build_search_view_data(updater: &mut ViewDataUpdater<'_>, search: &Search, search_bar: &SearchBar) {
+		if search_bar.is_editing() {
+			updater.push_trailing_line(search_bar.build_view_line());
+		}
+		else if let Some(term) = search_bar.search_value() {
+			let total = search.total_results();
+			let results = if total == 0 {
+				String::from("No Results")
+			}
+			else {
+				format!("{}/{total}", search.current_result_selected().unwrap_or(0) + 1)
+			};
+			updater.push_trailing_line(ViewLine::from(LineSegment::new(
+				format!("[{term}]: {results}").as_str(),
+			)));
+		}
+
+		if let Some(index) = search.current_match() {
+			updater.ensure_line_visible(index);
+		}
+	}
+
+	/// Clear the view data of both views, so they are rebuilt with the updated search.
+	fn clear_view_data(&mut self) {
+		self.overview_view_data.update_view_data(|updater| updater.clear());
+		self.diff_view_data.update_view_data(|updater| updater.clear());
+	}
+
+	fn search_update(&mut self) {
+		// select the first match, once the search term is no longer being edited
+		if self.search.current_match().is_none() && !self.search_bar.is_editing() {
+			_ = self.search.next();
+		}
+		self.clear_view_data();
+	}
+
+	fn handle_search_input(&mut self, event: Event) -> Option<Results> {
+		if !self.search_bar.is_active() {
+			return None;
+		}
+
+		let mut results = Results::new();
+		match event {
+			Event::Standard(StandardEvent::SearchUpdate) => {},
+			Event::Resize(..) => return None,
+			_ => {
+				match self.search_bar.handle_event(event) {
+					SearchBarAction::Update(term) => {
+						if term.is_empty() {
+							results.search_cancel();
+						}
+						else {
+							results.search_term(term.as_str());
+						}
+					},
+					SearchBarAction::Start(term) => {
+						if term.is_empty() {
+							results.search_cancel();
+							self.search_bar.reset();
+						}
+						else {
+							results.search_term(term.as_str());
+						}
+						if let Err(err) = self.history.save() {
+							results.error_with_return(
+								Error::from(err).context("Unable to write input history"),
+								State::ShowCommit,
+							);
+						}
+					},
+					SearchBarAction::Next(term) => {
+						results.search_term(term.as_str());
+						_ = self.search.next();
+					},
+					SearchBarAction::Previous(term) => {
+						results.search_term(term.as_str());
+						_ = self.search.previous();
+					},
+					SearchBarAction::Cancel => {
+						results.search_cancel();
+						self.search.reset();
+						self.clear_view_data();
+						return Some(results);
+					},
+					// other keys are part of the search term while it is edited
+					SearchBarAction::None => return self.search_bar.is_editing().then(Results::new),
+				}
+			},
+		}
+
+		self.search_update();
+		Some(results)
+	}
 }
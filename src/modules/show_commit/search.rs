@@ -0,0 +1,224 @@
+use std::{mem, ops::Range, sync::Arc};
+
+use parking_lot::RwLock;
+use regex::Regex;
+
+use crate::search::{build_regex, Interrupter, SearchResult, Searchable};
+
+#[derive(Debug, Default)]
+struct State {
+	lines: Vec<String>,
+	matches: Vec<usize>,
+	pending_lines: Vec<String>,
+	regex: Option<Regex>,
+	selected: Option<usize>,
+}
+
+impl State {
+	fn update_matches(&mut self) {
+		self.matches = self.regex.as_ref().map_or_else(Vec::new, |regex| {
+			self.lines
+				.iter()
+				.enumerate()
+				.filter(|&(_, line)| regex.is_match(line))
+				.map(|(index, _)| index)
+				.collect()
+		});
+		if self.selected.map_or(false, |selected| selected >= self.matches.len()) {
+			self.selected = None;
+		}
+	}
+}
+
+/// Search of the text of the body lines of the commit overview or diff view.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Search {
+	state: Arc<RwLock<State>>,
+}
+
+impl Searchable for Search {
+	fn reset(&mut self) {
+		let mut state = self.state.write();
+		state.matches.clear();
+		state.regex = None;
+		state.selected = None;
+	}
+
+	fn search(&mut self, _: Interrupter, term: &str) -> SearchResult {
+		// the lines of a single commit are few enough to always search in a single pass
+		let mut state = self.state.write();
+		state.regex = build_regex(term, false, false);
+		state.selected = None;
+		state.update_matches();
+		SearchResult::Complete
+	}
+}
+
+impl Search {
+	/// Add the text of a body line, as the body of the view data is built, to be searched once the body is built.
+	pub(crate) fn push_line(&self, line: String) {
+		self.state.write().pending_lines.push(line);
+	}
+
+	/// Set the lines that are searched to the lines pushed since the lines were last set, searching the new lines with
+	/// the current term.
+	pub(crate) fn set_lines(&self) {
+		let mut state = self.state.write();
+		let lines = mem::take(&mut state.pending_lines);
+		if state.lines != lines {
+			state.lines = lines;
+			state.update_matches();
+		}
+	}
+
+	/// Select the next match, returning the index of the line of the match.
+	pub(crate) fn next(&self) -> Option<usize> {
+		let mut state = self.state.write();
+		let total = state.matches.len();
+		if total == 0 {
+			return None;
+		}
+		let selected = state.selected.map_or(0, |selected| (selected + 1) % total);
+		state.selected = Some(selected);
+		state.matches.get(selected).copied()
+	}
+
+	/// Select the previous match, returning the index of the line of the match.
+	pub(crate) fn previous(&self) -> Option<usize> {
+		let mut state = self.state.write();
+		let total = state.matches.len();
+		if total == 0 {
+			return None;
+		}
+		let selected = state.selected.map_or(total - 1, |selected| (selected + total - 1) % total);
+		state.selected = Some(selected);
+		state.matches.get(selected).copied()
+	}
+
+	/// Get the index of the line of the selected match.
+	pub(crate) fn current_match(&self) -> Option<usize> {
+		let state = self.state.read();
+		state.matches.get(state.selected?).copied()
+	}
+
+	/// Get the selected result number.
+	pub(crate) fn current_result_selected(&self) -> Option<usize> {
+		self.state.read().selected
+	}
+
+	/// Get the total number of results.
+	pub(crate) fn total_results(&self) -> usize {
+		self.state.read().matches.len()
+	}
+
+	/// Get the ranges of the content that match the search term.
+	pub(crate) fn content_matches(&self, content: &str) -> Vec<Range<usize>> {
+		self.state.read().regex.as_ref().map_or_else(Vec::new, |regex| {
+			regex
+				.find_iter(content)
+				.filter(|m| !m.is_empty())
+				.map(|m| m.range())
+				.collect()
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::time::Duration;
+
+	use claims::{assert_none, assert_some_eq};
+
+	use super::*;
+
+	fn create_search(lines: &[&str], term: &str) -> Search {
+		let mut search = Search::default();
+		for line in lines {
+			search.push_line(String::from(*line));
+		}
+		search.set_lines();
+		assert_eq!(
+			search.search(Interrupter::new(Duration::from_secs(1)), term),
+			SearchResult::Complete
+		);
+		search
+	}
+
+	#[test]
+	fn search_matches() {
+		let search = create_search(&["foo", "bar", "Foo bar"], "foo");
+		assert_eq!(search.total_results(), 2);
+		assert_none!(search.current_match());
+	}
+
+	#[test]
+	fn search_smart_case() {
+		let search = create_search(&["foo", "bar", "Foo bar"], "Foo");
+		assert_eq!(search.total_results(), 1);
+	}
+
+	#[test]
+	fn search_invalid_regex() {
+		let search = create_search(&["fn foo(", "foo"], "foo(");
+		assert_eq!(search.total_results(), 1);
+	}
+
+	#[test]
+	fn search_empty_term() {
+		let search = create_search(&["foo"], "");
+		assert_eq!(search.total_results(), 0);
+		assert_none!(search.next());
+		assert_none!(search.previous());
+	}
+
+	#[test]
+	fn next_and_previous() {
+		let search = create_search(&["foo", "bar", "foo", "foo"], "foo");
+		assert_some_eq!(search.next(), 0);
+		assert_some_eq!(search.next(), 2);
+		assert_some_eq!(search.next(), 3);
+		assert_some_eq!(search.next(), 0);
+		assert_some_eq!(search.previous(), 3);
+		assert_some_eq!(search.current_match(), 3);
+		assert_some_eq!(search.current_result_selected(), 2);
+	}
+
+	#[test]
+	fn previous_without_selection() {
+		let search = create_search(&["foo", "bar", "foo"], "foo");
+		assert_some_eq!(search.previous(), 2);
+	}
+
+	#[test]
+	fn set_lines_updates_matches() {
+		let search = create_search(&["foo", "foo", "foo"], "foo");
+		_ = search.previous();
+		search.push_line(String::from("foo"));
+		search.set_lines();
+		assert_eq!(search.total_results(), 1);
+		assert_none!(search.current_match());
+	}
+
+	#[test]
+	fn set_lines_takes_pushed_lines() {
+		let search = create_search(&["foo", "bar"], "foo");
+		search.set_lines();
+		assert_eq!(search.total_results(), 0);
+	}
+
+	#[test]
+	fn content_matches() {
+		let search = create_search(&[], "o+");
+		assert_eq!(search.content_matches("foo bor"), vec![1..3, 5..6]);
+	}
+
+	#[test]
+	fn reset() {
+		let mut search = create_search(&["foo"], "foo");
+		_ = search.next();
+		search.reset();
+		assert_eq!(search.total_results(), 0);
+		assert_none!(search.current_match());
+		assert!(search.content_matches("foo").is_empty());
+	}
+}
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use anyhow::anyhow;
 use rstest::rstest;
 
@@ -9,18 +11,24 @@ use crate::{
 	input::StandardEvent,
 	process::Artifact,
 	render_line,
+	search::Interrupter,
 	test_helpers::{
-		assertions::assert_rendered_output::AssertRenderOptions,
+		assertions::{assert_rendered_output::AssertRenderOptions, AnyArtifact},
 		builders::{CommitBuilder, CommitDiffBuilder, FileStatusBuilder},
 		create_config,
-		testers,
+		testers::{self, ModuleTestContext},
 		with_temp_repository,
 	},
 	view::ViewLine,
 };
 
 fn create_show_commit(config: &Config, repository: Repository, todo_file: TodoFile) -> ShowCommit {
-	ShowCommit::new(config, repository, Arc::new(Mutex::new(todo_file)))
+	ShowCommit::new(
+		config,
+		repository,
+		Arc::new(Mutex::new(todo_file)),
+		InputHistory::default(),
+	)
 }
 
 fn render_options() -> AssertRenderOptions {
@@ -1207,6 +1215,9 @@ fn render_help() {
 					" End     |Scroll to the bottom",
 					" Right   |Scroll right",
 					" Left    |Scroll left",
+					" /       |Search the view",
+					" n       |Next search match",
+					" N       |Previous search match",
 					" d       |Show full diff",
 					" ?       |Show help"
 				);
@@ -1300,3 +1311,277 @@ fn scroll_events(#[case] event: StandardEvent) {
 		});
 	});
 }
+
+fn create_search_module(repo: Repository, test_context: &mut ModuleTestContext) -> ShowCommit {
+	let diff = CommitDiffBuilder::new(
+		CommitBuilder::new("0123456789abcdef0123456789abcdef")
+			.summary("Commit title")
+			.message("First line\nSecond title line")
+			.build(),
+	)
+	.build();
+	let mut module = create_show_commit(&create_config(), repo, test_context.take_todo_file());
+	module.diff = Some(diff);
+	_ = test_context.build_view_data(&mut module);
+	module
+}
+
+fn search_module(module: &mut ShowCommit, term: &str) {
+	_ = module.search.search(Interrupter::new(Duration::from_secs(1)), term);
+}
+
+#[test]
+fn search_start() {
+	with_temp_repository(|repo| {
+		testers::module(
+			&["pick 0123456789abcdef0123456789abcdef c1"],
+			&[Event::from(StandardEvent::SearchStart)],
+			|mut test_context| {
+				let mut module = create_search_module(repo, &mut test_context);
+				assert_results!(
+					test_context.handle_event(&mut module),
+					Artifact::Event(Event::from(StandardEvent::SearchStart)),
+					AnyArtifact,
+					Artifact::SearchCancel
+				);
+				assert!(module.search_bar.is_editing());
+				assert_rendered_output!(
+					Options AssertRenderOptions::INCLUDE_STYLE,
+					test_context.build_view_data(&mut module),
+					"{TITLE}{HELP}",
+					"{LEADING}",
+					"{IndicatorColor}Commit: {Normal}0123456789abcdef0123456789abcdef",
+					"{BODY}",
+					"{IndicatorColor}Date: {Normal}Fri Jan  1 00:00:00 2021 +0000",
+					"{Normal}Commit title",
+					"{Normal}",
+					"{Normal}First line",
+					"{Normal}Second title line",
+					"{Normal}",
+					"{IndicatorColor}0{Normal} files with {DiffAddColor}0{Normal} insertions and \
+					 {DiffRemoveColor}0{Normal} deletions",
+					"{TRAILING}",
+					"{Normal}/{Normal,Underline}"
+				);
+			},
+		);
+	});
+}
+
+#[test]
+fn search_highlights_matches() {
+	with_temp_repository(|repo| {
+		testers::module(
+			&["pick 0123456789abcdef0123456789abcdef c1"],
+			&[
+				Event::from(StandardEvent::SearchStart),
+				Event::from('t'),
+				Event::from('i'),
+				Event::from('t'),
+				Event::from(StandardEvent::SearchFinish),
+			],
+			|mut test_context| {
+				let mut module = create_search_module(repo, &mut test_context);
+				_ = test_context.handle_all_events(&mut module);
+				search_module(&mut module, "tit");
+				_ = module.handle_event(
+					Event::from(StandardEvent::SearchUpdate),
+					&test_context.view_context.state,
+				);
+				assert_eq!(module.search.total_results(), 2);
+				assert_rendered_output!(
+					Options AssertRenderOptions::INCLUDE_STYLE,
+					test_context.build_view_data(&mut module),
+					"{TITLE}{HELP}",
+					"{LEADING}",
+					"{IndicatorColor}Commit: {Normal}0123456789abcdef0123456789abcdef",
+					"{BODY}",
+					"{IndicatorColor}Date: {Normal}Fri Jan  1 00:00:00 2021 +0000",
+					"{Normal}Commit {IndicatorColor}tit{Normal}le",
+					"{Normal}",
+					"{Normal}First line",
+					"{Normal}Second {IndicatorColor}tit{Normal}le line",
+					"{Normal}",
+					"{IndicatorColor}0{Normal} files with {DiffAddColor}0{Normal} insertions and \
+					 {DiffRemoveColor}0{Normal} deletions",
+					"{TRAILING}",
+					"{Normal}[tit]: 1/2"
+				);
+			},
+		);
+	});
+}
+
+#[test]
+fn search_next_and_previous() {
+	with_temp_repository(|repo| {
+		testers::module(
+			&["pick 0123456789abcdef0123456789abcdef c1"],
+			&[
+				Event::from(StandardEvent::SearchStart),
+				Event::from('t'),
+				Event::from('i'),
+				Event::from('t'),
+				Event::from(StandardEvent::SearchFinish),
+			],
+			|mut test_context| {
+				let mut module = create_search_module(repo, &mut test_context);
+				_ = test_context.handle_all_events(&mut module);
+				search_module(&mut module, "tit");
+				let state = test_context.view_context.state.clone();
+				_ = module.handle_event(Event::from(StandardEvent::SearchUpdate), &state);
+				assert_results!(
+					module.handle_event(Event::from(StandardEvent::SearchNext), &state),
+					Artifact::SearchTerm(String::from("tit"))
+				);
+				assert_rendered_output!(
+					Skip 12,
+					test_context.build_view_data(&mut module),
+					"[tit]: 2/2"
+				);
+				_ = module.handle_event(Event::from(StandardEvent::SearchPrevious), &state);
+				assert_eq!(module.search.current_result_selected(), Some(0));
+			},
+		);
+	});
+}
+
+#[test]
+fn search_no_results() {
+	with_temp_repository(|repo| {
+		testers::module(
+			&["pick 0123456789abcdef0123456789abcdef c1"],
+			&[
+				Event::from(StandardEvent::SearchStart),
+				Event::from('x'),
+				Event::from(StandardEvent::SearchFinish),
+			],
+			|mut test_context| {
+				let mut module = create_search_module(repo, &mut test_context);
+				_ = test_context.handle_all_events(&mut module);
+				search_module(&mut module, "x");
+				_ = module.handle_event(
+					Event::from(StandardEvent::SearchUpdate),
+					&test_context.view_context.state,
+				);
+				assert_rendered_output!(
+					Skip 12,
+					test_context.build_view_data(&mut module),
+					"[x]: No Results"
+				);
+			},
+		);
+	});
+}
+
+#[test]
+fn search_cancel() {
+	with_temp_repository(|repo| {
+		testers::module(
+			&["pick 0123456789abcdef0123456789abcdef c1"],
+			&[
+				Event::from(StandardEvent::SearchStart),
+				Event::from('t'),
+				Event::from(StandardEvent::SearchFinish),
+			],
+			|mut test_context| {
+				let mut module = create_search_module(repo, &mut test_context);
+				_ = test_context.handle_all_events(&mut module);
+				search_module(&mut module, "t");
+				assert_results!(
+					module.handle_event(
+						Event::from(StandardEvent::SearchCancel),
+						&test_context.view_context.state
+					),
+					Artifact::SearchCancel
+				);
+				assert!(!module.search_bar.is_active());
+				assert_eq!(module.search.total_results(), 0);
+				assert_rendered_output!(
+					Options render_options(),
+					Skip 4;2,
+					test_context.build_view_data(&mut module),
+					"{Normal}Second title line"
+				);
+			},
+		);
+	});
+}
+
+#[test]
+fn search_highlights_match_across_segments() {
+	with_temp_repository(|repo| {
+		testers::module(
+			&["pick 0123456789abcdef0123456789abcdef c1"],
+			&[],
+			|mut test_context| {
+				let mut module = create_search_module(repo, &mut test_context);
+				search_module(&mut module, "e: Fri");
+				module.search_update();
+				assert_eq!(module.search.total_results(), 1);
+				assert_rendered_output!(
+					Options AssertRenderOptions::INCLUDE_STYLE,
+					Skip 3,
+					test_context.build_view_data(&mut module),
+					"{BODY}",
+					"{IndicatorColor}Date: Fri{Normal} Jan  1 00:00:00 2021 +0000",
+					"{Normal}Commit title",
+					"{Normal}",
+					"{Normal}First line",
+					"{Normal}Second title line",
+					"{Normal}",
+					"{IndicatorColor}0{Normal} files with {DiffAddColor}0{Normal} insertions and \
+					 {DiffRemoveColor}0{Normal} deletions"
+				);
+			},
+		);
+	});
+}
+
+#[test]
+fn search_highlights_match_in_visible_whitespace() {
+	with_temp_repository(|repo| {
+		testers::module(
+			&["pick 0123456789abcdef0123456789abcdef comment1"],
+			&[],
+			|mut test_context| {
+				let mut config = create_config();
+				config.diff_show_whitespace = DiffShowWhitespaceSetting::Both;
+				config.diff_tab_symbol = String::from("#>");
+				config.diff_space_symbol = String::from("%");
+				config.diff_tab_width = 2;
+				let diff = CommitDiffBuilder::new(CommitBuilder::new("0123456789abcdef0123456789abcdef").build())
+					.file_statuses(vec![
+						FileStatusBuilder::new()
+							.source_path("file.txt")
+							.destination_path("file.txt")
+							.status(Status::Modified)
+							.push_delta(generate_white_space_delta())
+							.build(),
+					])
+					.build();
+				let mut module = create_show_commit(&config, repo, test_context.take_todo_file());
+				module.diff = Some(diff);
+				module.state = ShowCommitState::Diff;
+				_ = test_context.build_view_data(&mut module);
+				search_module(&mut module, "  sp");
+				module.search_update();
+				assert_eq!(module.search.total_results(), 2);
+				assert_rendered_output!(
+					Options AssertRenderOptions::INCLUDE_STYLE,
+					Skip 10,
+					test_context.build_view_data(&mut module),
+					render_line!(EndsWith "#>#>{DiffContextColor}sp tabs    content"),
+					render_line!(EndsWith "sp tabs    content{DiffWhitespaceColor}#>#>"),
+					render_line!(EndsWith "#>#>{DiffContextColor}sp tabs    content{DiffWhitespaceColor}#>#>"),
+					render_line!(EndsWith "%%{IndicatorColor}%%sp{DiffContextColor} tabs    content"),
+					render_line!(EndsWith "sp tabs    content{DiffWhitespaceColor}%%%%"),
+					render_line!(
+						EndsWith "%%{IndicatorColor}%%sp{DiffContextColor} tabs    content{DiffWhitespaceColor}%%%%"
+					),
+					render_line!(EndsWith "%#>#>%{DiffContextColor}sp tabs    content{DiffWhitespaceColor}#>%%#>")
+				);
+			},
+		);
+	});
+}
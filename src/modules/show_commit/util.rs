@@ -29,6 +29,12 @@ pub(super) fn get_show_commit_help_lines(key_bindings: &KeyBindings) -> Vec<(Vec
 		(key_bindings.scroll_end.clone(), String::from("Scroll to the bottom")),
 		(key_bindings.scroll_right.clone(), String::from("Scroll right")),
 		(key_bindings.scroll_left.clone(), String::from("Scroll left")),
+		(key_bindings.search_start.clone(), String::from("Search the view")),
+		(key_bindings.search_next.clone(), String::from("Next search match")),
+		(
+			key_bindings.search_previous.clone(),
+			String::from("Previous search match"),
+		),
 		(key_bindings.show_diff.clone(), String::from("Show full diff")),
 		(key_bindings.help.clone(), String::from("Show help")),
 	]
@@ -1,10 +1,14 @@
+use std::{
+	cmp::{max, min},
+	ops::Range,
+};
+
 use crate::{
 	display::DisplayColor,
 	git::{Commit, CommitDiff, DiffLine, Origin},
-	modules::show_commit::util::{
-		get_files_changed_summary,
-		get_partition_index_on_whitespace_for_line,
-		get_stat_item_segments,
+	modules::show_commit::{
+		search::Search,
+		util::{get_files_changed_summary, get_partition_index_on_whitespace_for_line, get_stat_item_segments},
 	},
 	view::{LineSegment, LineSegmentOptions, ViewDataUpdater, ViewLine},
 };
@@ -37,23 +41,124 @@ impl ViewBuilderOptions {
 	}
 }
 
+/// A segment of a body line, with the text of the segment that is searched, and if the whitespace of the text is
+/// replaced, and shown, when the segment is rendered.
+struct BodySegment {
+	segment: LineSegment,
+	text: String,
+	whitespace: Option<bool>,
+}
+
+impl BodySegment {
+	fn new(segment: LineSegment, text: &str, whitespace: Option<bool>) -> Self {
+		Self {
+			segment,
+			text: String::from(text),
+			whitespace,
+		}
+	}
+
+	fn from_segment(segment: &LineSegment) -> Self {
+		Self::new(segment.clone(), segment.get_content(), None)
+	}
+}
+
 pub(super) struct ViewBuilder {
 	invisible_tab_string: String,
 	visible_tab_string: String,
 	visible_space_string: String,
 	show_leading_whitespace: bool,
 	show_trailing_whitespace: bool,
+	search: Search,
 }
 
 impl ViewBuilder {
-	pub(crate) fn new(options: ViewBuilderOptions) -> Self {
+	pub(crate) fn new(options: ViewBuilderOptions, search: Search) -> Self {
 		Self {
 			invisible_tab_string: " ".repeat(options.tab_width),
 			visible_tab_string: format!("{0:width$}", options.tab_character, width = options.tab_width),
 			visible_space_string: options.space_character,
 			show_leading_whitespace: options.show_leading_whitespace,
 			show_trailing_whitespace: options.show_trailing_whitespace,
+			search,
+		}
+	}
+
+	/// Push a body line, with the content that matches the search highlighted.
+	fn push_line(&self, updater: &mut ViewDataUpdater<'_>, view_line: ViewLine) {
+		let body_segments: Vec<BodySegment> = view_line.get_segments().iter().map(BodySegment::from_segment).collect();
+		self.push_body_line(updater, view_line, &body_segments);
+	}
+
+	/// Push a body line that is rendered from the body segments. The search matches the text of the whole line, so a
+	/// match can span segments, and include whitespace that is replaced when rendered.
+	fn push_body_line(&self, updater: &mut ViewDataUpdater<'_>, view_line: ViewLine, body_segments: &[BodySegment]) {
+		let text: String = body_segments.iter().map(|body_segment| body_segment.text.as_str()).collect();
+		let matches = self.search.content_matches(text.as_str());
+		self.search.push_line(text);
+		updater.push_line(
+			if matches.is_empty() {
+				view_line
+			}
+			else {
+				self.highlight_search_matches(view_line, body_segments, &matches)
+			},
+		);
+	}
+
+	fn highlight_search_matches(
+		&self,
+		view_line: ViewLine,
+		body_segments: &[BodySegment],
+		matches: &[Range<usize>],
+	) -> ViewLine {
+		let mut segments = vec![];
+		let mut pinned_segments = 0;
+		let mut segment_start = 0;
+		for (index, body_segment) in body_segments.iter().enumerate() {
+			let segment_end = segment_start + body_segment.text.len();
+			// the ranges within the text of the segment
+			let mut start = 0;
+			for range in matches
+				.iter()
+				.filter(|range| range.start < segment_end && range.end > segment_start)
+			{
+				let match_start = max(range.start, segment_start) - segment_start;
+				let match_end = min(range.end, segment_end) - segment_start;
+				self.push_segment(&mut segments, body_segment, start..match_start, false);
+				self.push_segment(&mut segments, body_segment, match_start..match_end, true);
+				start = match_end;
+			}
+			self.push_segment(&mut segments, body_segment, start..body_segment.text.len(), false);
+			if index < view_line.get_number_of_pinned_segment() {
+				pinned_segments = segments.len();
+			}
+			segment_start = segment_end;
 		}
+		view_line.set_segments(segments, pinned_segments)
+	}
+
+	/// Push the segment for a range of the text of a body segment, rendered as the body segment is rendered.
+	fn push_segment(
+		&self,
+		segments: &mut Vec<LineSegment>,
+		body_segment: &BodySegment,
+		range: Range<usize>,
+		highlight: bool,
+	) {
+		let text = body_segment.text.get(range).unwrap_or_default();
+		let content = body_segment
+			.whitespace
+			.map_or_else(|| String::from(text), |visible| self.replace_whitespace(text, visible));
+		if content.is_empty() {
+			return;
+		}
+		segments.push(if highlight {
+			LineSegment::new_with_color(content.as_str(), DisplayColor::IndicatorColor)
+		}
+		else {
+			LineSegment::new_copy_style(content.as_str(), &body_segment.segment)
+		});
 	}
 
 	fn replace_whitespace(&self, value: &str, visible: bool) -> String {
@@ -89,7 +194,6 @@ impl ViewBuilder {
 		ViewLine::from(segments)
 	}
 
-	#[allow(clippy::unused_self)]
 	pub(super) fn build_view_data_for_overview(
 		&self,
 		updater: &mut ViewDataUpdater<'_>,
@@ -99,7 +203,7 @@ impl ViewBuilder {
 		let commit = diff.commit();
 		updater.push_leading_line(Self::build_leading_summary(commit, is_full_width));
 		// TODO handle authored date
-		updater.push_line(ViewLine::from(vec![
+		self.push_line(updater, ViewLine::from(vec![
 			LineSegment::new_with_color(
 				if is_full_width { "Date: " } else { "D: " },
 				DisplayColor::IndicatorColor,
@@ -108,7 +212,7 @@ impl ViewBuilder {
 		]));
 
 		if commit.author().is_some() {
-			updater.push_line(ViewLine::from(vec![
+			self.push_line(updater, ViewLine::from(vec![
 				LineSegment::new_with_color(
 					if is_full_width { "Author: " } else { "A: " },
 					DisplayColor::IndicatorColor,
@@ -118,7 +222,7 @@ impl ViewBuilder {
 		}
 
 		if let Some(committer) = commit.committer().as_ref() {
-			updater.push_line(ViewLine::from(vec![
+			self.push_line(updater, ViewLine::from(vec![
 				LineSegment::new_with_color(
 					if is_full_width { "Committer: " } else { "C: " },
 					DisplayColor::IndicatorColor,
@@ -128,22 +232,26 @@ impl ViewBuilder {
 		}
 
 		if let Some(summary) = commit.summary() {
-			updater.push_lines(summary);
-			updater.push_line(ViewLine::from(""));
+			for line in summary.lines() {
+				self.push_line(updater, ViewLine::from(line));
+			}
+			self.push_line(updater, ViewLine::from(""));
 		}
 
 		if let Some(message) = commit.message() {
-			updater.push_lines(message);
-			updater.push_line(ViewLine::from(""));
+			for line in message.lines() {
+				self.push_line(updater, ViewLine::from(line));
+			}
+			self.push_line(updater, ViewLine::from(""));
 		}
 
 		if commit.summary().is_none() && commit.message().is_none() {
-			updater.push_line(ViewLine::from(""));
+			self.push_line(updater, ViewLine::from(""));
 		}
 
-		updater.push_line(get_files_changed_summary(diff, is_full_width));
+		self.push_line(updater, get_files_changed_summary(diff, is_full_width));
 		for status in diff.file_statuses() {
-			updater.push_line(ViewLine::from(get_stat_item_segments(
+			self.push_line(updater, ViewLine::from(get_stat_item_segments(
 				status.status(),
 				status.destination_path(),
 				status.source_path(),
@@ -152,23 +260,31 @@ impl ViewBuilder {
 		}
 	}
 
-	fn build_diff_line_line_segment(content: &str, origin: Origin) -> LineSegment {
-		LineSegment::new_with_color(content, match origin {
+	const fn get_diff_line_color(origin: Origin) -> DisplayColor {
+		match origin {
 			Origin::Addition => DisplayColor::DiffAddColor,
 			Origin::Deletion => DisplayColor::DiffRemoveColor,
 			Origin::Context | Origin::Binary | Origin::Header => DisplayColor::DiffContextColor,
-		})
+		}
+	}
+
+	/// Create the body segment of a part of the text of a diff line, where the whitespace of the part is replaced, and
+	/// shown if visible.
+	fn build_diff_text_body_segment(&self, text: &str, visible: bool, color: DisplayColor) -> BodySegment {
+		BodySegment::new(
+			LineSegment::new_with_color(self.replace_whitespace(text, visible).as_str(), color),
+			text,
+			Some(visible),
+		)
 	}
 
-	// safe slice, only slices across graphemes whitespace
-	#[allow(clippy::string_slice)]
 	fn get_diff_line_segments(
 		&self,
 		diff_line: &DiffLine,
 		old_largest_line_number_length: usize,
 		new_largest_line_number_length: usize,
-	) -> Vec<LineSegment> {
-		let mut line_segments = vec![
+	) -> Vec<BodySegment> {
+		let mut body_segments: Vec<BodySegment> = [
 			match diff_line.old_line_number() {
 				Some(line_number) => {
 					LineSegment::new(format!("{line_number:<old_largest_line_number_length$}").as_str())
@@ -183,50 +299,40 @@ impl ViewBuilder {
 				None => LineSegment::new(" ".repeat(new_largest_line_number_length).as_str()),
 			},
 			LineSegment::new("| "),
-		];
+		]
+		.iter()
+		.map(BodySegment::from_segment)
+		.collect();
 
+		let line = diff_line.line().trim_end_matches('\n');
+		let color = Self::get_diff_line_color(diff_line.origin());
 		if self.show_leading_whitespace || self.show_trailing_whitespace {
-			let line = diff_line.line();
-			let (leading, content, trailing) = if line.trim().is_empty() {
-				(
-					self.replace_whitespace(line, self.show_leading_whitespace || self.show_trailing_whitespace),
-					String::new(),
-					String::new(),
-				)
+			let is_whitespace = line.trim().is_empty();
+			let (start, end) = if is_whitespace {
+				(line.len(), line.len())
 			}
 			else {
-				let (start, end) = get_partition_index_on_whitespace_for_line(line);
-				(
-					self.replace_whitespace(&line[0..start], self.show_leading_whitespace),
-					self.replace_whitespace(&line[start..end], false),
-					self.replace_whitespace(&line[end..], self.show_trailing_whitespace),
-				)
+				get_partition_index_on_whitespace_for_line(line)
 			};
-
-			if !leading.is_empty() {
-				line_segments.push(LineSegment::new_with_color(
-					leading.as_str(),
-					DisplayColor::DiffWhitespaceColor,
-				));
-			}
-			if !content.is_empty() {
-				line_segments.push(Self::build_diff_line_line_segment(content.as_str(), diff_line.origin()));
-			}
-			if !trailing.is_empty() {
-				line_segments.push(LineSegment::new_with_color(
-					trailing.as_str(),
-					DisplayColor::DiffWhitespaceColor,
-				));
+			let (leading, rest) = line.split_at(start);
+			let (content, trailing) = rest.split_at(end - start);
+			let show_leading_whitespace =
+				self.show_leading_whitespace || (is_whitespace && self.show_trailing_whitespace);
+			for (text, visible, text_color) in [
+				(leading, show_leading_whitespace, DisplayColor::DiffWhitespaceColor),
+				(content, false, color),
+				(trailing, self.show_trailing_whitespace, DisplayColor::DiffWhitespaceColor),
+			] {
+				if !text.is_empty() {
+					body_segments.push(self.build_diff_text_body_segment(text, visible, text_color));
+				}
 			}
 		}
 		else {
-			line_segments.push(Self::build_diff_line_line_segment(
-				self.replace_whitespace(diff_line.line(), false).as_str(),
-				diff_line.origin(),
-			));
+			body_segments.push(self.build_diff_text_body_segment(line, false, color));
 		}
 
-		line_segments
+		body_segments
 	}
 
 	pub(super) fn build_view_data_diff(
@@ -237,11 +343,11 @@ impl ViewBuilder {
 	) {
 		updater.push_leading_line(Self::build_leading_summary(diff.commit(), is_full_width));
 		updater.push_leading_line(get_files_changed_summary(diff, is_full_width));
-		updater.push_line(ViewLine::new_empty_line().set_padding(PADDING_CHARACTER));
+		self.push_line(updater, ViewLine::new_empty_line().set_padding(PADDING_CHARACTER));
 
 		let file_statuses = diff.file_statuses();
 		for (s_i, status) in file_statuses.iter().enumerate() {
-			updater.push_line(ViewLine::from(get_stat_item_segments(
+			self.push_line(updater, ViewLine::from(get_stat_item_segments(
 				status.status(),
 				status.destination_path(),
 				status.source_path(),
@@ -251,8 +357,8 @@ impl ViewBuilder {
 			let old_largest_line_number_length = status.last_old_line_number().to_string().len();
 			let new_largest_line_number_length = status.last_new_line_number().to_string().len();
 			for delta in status.deltas() {
-				updater.push_line(ViewLine::new_empty_line());
-				updater.push_line(ViewLine::from(vec![
+				self.push_line(updater, ViewLine::new_empty_line());
+				self.push_line(updater, ViewLine::from(vec![
 					LineSegment::new_with_color_and_style("@@", DisplayColor::Normal, LineSegmentOptions::DIMMED),
 					LineSegment::new_with_color(
 						format!(
@@ -271,7 +377,7 @@ impl ViewBuilder {
 						DisplayColor::DiffContextColor,
 					),
 				]));
-				updater.push_line(ViewLine::new_pinned(vec![]).set_padding_with_color_and_style(
+				self.push_line(updater, ViewLine::new_pinned(vec![]).set_padding_with_color_and_style(
 					PADDING_CHARACTER,
 					DisplayColor::Normal,
 					LineSegmentOptions::DIMMED,
@@ -279,7 +385,7 @@ impl ViewBuilder {
 
 				for line in delta.lines() {
 					if line.end_of_file() && line.line() != "\n" {
-						updater.push_line(ViewLine::from(vec![
+						self.push_line(updater, ViewLine::from(vec![
 							LineSegment::new(
 								" ".repeat(old_largest_line_number_length + new_largest_line_number_length + 3)
 									.as_str(),
@@ -289,15 +395,22 @@ impl ViewBuilder {
 						continue;
 					}
 
-					updater.push_line(ViewLine::from(self.get_diff_line_segments(
+					let body_segments = self.get_diff_line_segments(
 						line,
 						old_largest_line_number_length,
 						new_largest_line_number_length,
-					)));
+					);
+					let view_line = ViewLine::from(
+						body_segments
+							.iter()
+							.map(|body_segment| body_segment.segment.clone())
+							.collect::<Vec<LineSegment>>(),
+					);
+					self.push_body_line(updater, view_line, &body_segments);
 				}
 			}
 			if s_i + 1 != file_statuses.len() {
-				updater.push_line(ViewLine::new_empty_line().set_padding(PADDING_CHARACTER));
+				self.push_line(updater, ViewLine::new_empty_line().set_padding(PADDING_CHARACTER));
 			}
 		}
 	}
@@ -0,0 +1,153 @@
+use std::{env::current_exe, path::Path, process::Command};
+
+use crate::{arguments::Args, exit::Exit, git::Repository, module::ExitStatus};
+
+/// Rebase the current branch on to the upstream, with the tool as the sequence editor of the rebase, so Git generates
+/// the todo file and the tool is run again by Git to edit it.
+#[cfg(not(tarpaulin_include))]
+pub(crate) fn run(args: &Args) -> Exit {
+	let Some(upstream) = args.upstream().as_deref()
+	else {
+		return Exit::new(ExitStatus::StateError, "An upstream must be provided to rebase.");
+	};
+
+	let repository = match Repository::open_from_env() {
+		Ok(repository) => repository,
+		Err(err) => {
+			return Exit::new(
+				ExitStatus::StateError,
+				format!("Unable to load Git repository: {err}").as_str(),
+			);
+		},
+	};
+
+	if let Err(err) = repository.resolve_commit(upstream) {
+		return Exit::new(
+			ExitStatus::StateError,
+			format!("Unable to find upstream {upstream}: {err:#}").as_str(),
+		);
+	}
+
+	let executable = match current_exe() {
+		Ok(executable) => executable,
+		Err(err) => {
+			return Exit::new(
+				ExitStatus::StateError,
+				format!("Unable to find the interactive rebase tool executable: {err}").as_str(),
+			);
+		},
+	};
+
+	let mut command = Command::new("git");
+	_ = command
+		.args(rebase_arguments(args, upstream))
+		.env("GIT_SEQUENCE_EDITOR", sequence_editor_command(executable.as_path()));
+
+	match command.status() {
+		Ok(status) if status.success() => Exit::from(ExitStatus::Good),
+		Ok(status) => Exit::new(ExitStatus::StateError, format!("git rebase failed: {status}").as_str()),
+		Err(err) => {
+			Exit::new(
+				ExitStatus::StateError,
+				format!("Unable to run git rebase: {err}").as_str(),
+			)
+		},
+	}
+}
+
+fn rebase_arguments<'args>(args: &Args, upstream: &'args str) -> Vec<&'args str> {
+	let mut arguments = vec!["rebase", "--interactive"];
+	if args.rebase_merges() {
+		arguments.push("--rebase-merges");
+	}
+	if args.update_refs() {
+		arguments.push("--update-refs");
+	}
+	arguments.push(upstream);
+	arguments
+}
+
+/// Git runs the sequence editor through a shell, with the path to its todo file as the last argument, so the tool
+/// edits the todo file that Git generated.
+fn sequence_editor_command(executable: &Path) -> String {
+	shell_quote(executable.to_string_lossy().as_ref())
+}
+
+/// Quote a value for the POSIX shell that Git uses to run the sequence editor, which is also used by Git for Windows.
+fn shell_quote(value: &str) -> String {
+	format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+#[cfg(all(unix, test))]
+mod tests {
+	use std::{ffi::OsString, path::PathBuf};
+
+	use super::*;
+	use crate::test_helpers::with_git_directory;
+
+	fn args(args: &[&str]) -> Args {
+		Args::try_from(args.iter().map(OsString::from).collect::<Vec<OsString>>()).unwrap()
+	}
+
+	#[test]
+	fn run_without_upstream() {
+		let exit = run(&args(&["todofile"]));
+		assert_eq!(exit.get_status(), &ExitStatus::StateError);
+	}
+
+	#[test]
+	fn run_repository_error() {
+		with_git_directory("fixtures/not-a-repository", |_| {
+			let exit = run(&args(&["--rebase", "main"]));
+			assert_eq!(exit.get_status(), &ExitStatus::StateError);
+			assert!(
+				exit.get_message()
+					.as_ref()
+					.unwrap()
+					.contains("Unable to load Git repository: ")
+			);
+		});
+	}
+
+	#[test]
+	fn run_invalid_upstream() {
+		with_git_directory("fixtures/simple", |_| {
+			let exit = run(&args(&["--rebase", "does-not-exist"]));
+			assert_eq!(exit.get_status(), &ExitStatus::StateError);
+			assert!(
+				exit.get_message()
+					.as_ref()
+					.unwrap()
+					.contains("Unable to find upstream does-not-exist")
+			);
+		});
+	}
+
+	#[test]
+	fn rebase_arguments_without_flags() {
+		assert_eq!(rebase_arguments(&args(&["--rebase", "main"]), "main"), vec![
+			"rebase",
+			"--interactive",
+			"main"
+		]);
+	}
+
+	#[test]
+	fn rebase_arguments_with_flags() {
+		assert_eq!(
+			rebase_arguments(
+				&args(&["--rebase", "main", "--rebase-merges", "--update-refs"]),
+				"main"
+			),
+			vec!["rebase", "--interactive", "--rebase-merges", "--update-refs", "main"]
+		);
+	}
+
+	#[test]
+	fn sequence_editor_command_quotes_path() {
+		assert_eq!(
+			sequence_editor_command(PathBuf::from("/opt/it's a tool").as_path()),
+			"'/opt/it'\\''s a tool'"
+		);
+	}
+}
@@ -1,5 +1,6 @@
 mod action;
 mod interrupter;
+mod pattern;
 mod search_result;
 mod searchable;
 mod state;
@@ -10,6 +11,7 @@ mod update_handler;
 pub(crate) use self::{
 	action::Action,
 	interrupter::Interrupter,
+	pattern::build_regex,
 	search_result::SearchResult,
 	searchable::Searchable,
 	state::State,
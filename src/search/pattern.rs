@@ -0,0 +1,66 @@
+use regex::{Regex, RegexBuilder};
+
+/// Build the regular expression for a search pattern, or `None` for an empty pattern. Matching is case-insensitive,
+/// unless the pattern contains an uppercase character. A pattern that is not a valid regular expression, or any
+/// pattern when `literal` is set, is matched literally. With `full_match` set, the pattern must match the whole value.
+pub(crate) fn build_regex(pattern: &str, literal: bool, full_match: bool) -> Option<Regex> {
+	if pattern.is_empty() {
+		return None;
+	}
+
+	let case_insensitive = !pattern.chars().any(char::is_uppercase);
+	let build = |regex_pattern: &str| {
+		let full_pattern = if full_match {
+			format!("^(?:{regex_pattern})$")
+		}
+		else {
+			String::from(regex_pattern)
+		};
+		RegexBuilder::new(full_pattern.as_str())
+			.case_insensitive(case_insensitive)
+			.build()
+	};
+	let build_literal = || build(regex::escape(pattern).as_str());
+
+	if literal {
+		build_literal().ok()
+	}
+	else {
+		build(pattern).or_else(|_| build_literal()).ok()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn empty_pattern() {
+		assert!(build_regex("", false, false).is_none());
+	}
+
+	#[test]
+	fn smart_case() {
+		assert!(build_regex("foo", false, false).unwrap().is_match("FOO"));
+		assert!(!build_regex("Foo", false, false).unwrap().is_match("FOO"));
+	}
+
+	#[test]
+	fn invalid_regex_matched_literally() {
+		assert!(build_regex("foo(", false, false).unwrap().is_match("fn foo()"));
+	}
+
+	#[test]
+	fn literal() {
+		let regex = build_regex("a.c", true, false).unwrap();
+		assert!(regex.is_match("a.c"));
+		assert!(!regex.is_match("abc"));
+	}
+
+	#[test]
+	fn full_match() {
+		let regex = build_regex("e", false, true).unwrap();
+		assert!(regex.is_match("e"));
+		assert!(!regex.is_match("edit"));
+	}
+}
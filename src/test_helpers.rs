@@ -31,7 +31,7 @@ pub(crate) use self::{
 	create_invalid_utf::invalid_utf,
 	create_test_keybindings::create_test_keybindings,
 	create_test_module_handler::create_test_module_handler,
-	shared::TestModuleProvider,
+	shared::{with_temporary_path, TestModuleProvider},
 	with_env_var::{with_env_var, EnvVarAction},
 	with_event_handler::{with_event_handler, EventHandlerTestContext},
 	with_git_config::with_git_config,
@@ -39,6 +39,6 @@ pub(crate) use self::{
 	with_search::{with_search, SearchTestContext},
 	with_temp_bare_repository::with_temp_bare_repository,
 	with_temp_repository::with_temp_repository,
-	with_todo_file::with_todo_file,
+	with_todo_file::{with_sequencer_todo_file, with_todo_file},
 	with_view_state::{with_view_state, ViewStateTestContext},
 };
@@ -212,6 +212,14 @@ macro_rules! action_line {
 		use $crate::test_helpers::assertions::assert_rendered_output::ActionPattern;
 		ActionPattern::new_pick($hash, $comment, true)
 	}};
+	(Revert $hash:expr, $comment:expr) => {{
+		use $crate::test_helpers::assertions::assert_rendered_output::ActionPattern;
+		ActionPattern::new_revert($hash, $comment, false)
+	}};
+	(Selected Revert $hash:expr, $comment:expr) => {{
+		use $crate::test_helpers::assertions::assert_rendered_output::ActionPattern;
+		ActionPattern::new_revert($hash, $comment, true)
+	}};
 	(Reword $hash:expr, $comment:expr) => {{
 		use $crate::test_helpers::assertions::assert_rendered_output::ActionPattern;
 		ActionPattern::new_reword($hash, $comment, false)
@@ -314,6 +314,10 @@ impl ActionPattern {
 		Self::new(format!("pick {hash} {comment}").as_str(), selected)
 	}
 
+	pub(crate) fn new_revert(hash: &str, comment: &str, selected: bool) -> Self {
+		Self::new(format!("revert {hash} {comment}").as_str(), selected)
+	}
+
 	pub(crate) fn new_reword(hash: &str, comment: &str, selected: bool) -> Self {
 		Self::new(format!("reword {hash} {comment}").as_str(), selected)
 	}
@@ -8,6 +8,7 @@ pub(crate) fn render_style(line_segment: &LineSegment) -> String {
 		DisplayColor::ActionExec => String::from("ActionExec"),
 		DisplayColor::ActionFixup => String::from("ActionFixup"),
 		DisplayColor::ActionPick => String::from("ActionPick"),
+		DisplayColor::ActionRevert => String::from("ActionRevert"),
 		DisplayColor::ActionReword => String::from("ActionReword"),
 		DisplayColor::ActionSquash => String::from("ActionSquash"),
 		DisplayColor::DiffAddColor => String::from("DiffAddColor"),
@@ -31,8 +31,8 @@ where EventGeneratorFunction: Fn() -> Result<Option<Event>> + Sync + Send + 'sta
 					Event::Mouse(mouse_event) => Ok(Some(c_event::Event::Mouse(mouse_event))),
 					Event::None => Ok(None),
 					Event::Resize(width, height) => Ok(Some(c_event::Event::Resize(width, height))),
-					Event::Standard(_) => {
-						panic!("MetaEvent and Standard are not supported, please use other event types")
+					Event::Standard(_) | Event::KeySequence(_) | Event::PendingKeySequence(_) => {
+						panic!("MetaEvent, Standard and KeySequence are not supported, please use other event types")
 					},
 				}
 			},
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use crate::input::{map_keybindings, KeyBindings};
 
 /// Create a mocked version of `KeyBindings`.
@@ -24,6 +26,7 @@ pub(crate) fn create_test_keybindings() -> KeyBindings {
 		action_edit: map_keybindings(&[String::from("e")]),
 		action_fixup: map_keybindings(&[String::from("f")]),
 		action_pick: map_keybindings(&[String::from("p")]),
+		action_revert: map_keybindings(&[String::from("R")]),
 		action_reword: map_keybindings(&[String::from("r")]),
 		action_squash: map_keybindings(&[String::from("s")]),
 		confirm_yes: map_keybindings(&[String::from("y")]),
@@ -31,6 +34,11 @@ pub(crate) fn create_test_keybindings() -> KeyBindings {
 		force_abort: map_keybindings(&[String::from("Q")]),
 		force_rebase: map_keybindings(&[String::from("W")]),
 		insert_line: map_keybindings(&[String::from("I")]),
+		insert_update_refs: map_keybindings(&[String::from("B")]),
+		insert_exec_lines: map_keybindings(&[String::from("x")]),
+		reword_message: map_keybindings(&[String::from("C")]),
+		rewrite_metadata: map_keybindings(&[String::from("T")]),
+		show_undo_history: map_keybindings(&[String::from("H")]),
 		move_down: map_keybindings(&[String::from("Down")]),
 		move_down_step: map_keybindings(&[String::from("PageDown")]),
 		move_end: map_keybindings(&[String::from("End")]),
@@ -42,12 +50,29 @@ pub(crate) fn create_test_keybindings() -> KeyBindings {
 		move_up: map_keybindings(&[String::from("Up")]),
 		move_up_step: map_keybindings(&[String::from("PageUp")]),
 		open_in_external_editor: map_keybindings(&[String::from('!')]),
+		open_selection_in_external_editor: map_keybindings(&[String::from('@')]),
 		rebase: map_keybindings(&[String::from('w')]),
 		remove_line: map_keybindings(&[String::from("Delete")]),
 		show_commit: map_keybindings(&[String::from("c")]),
 		show_diff: map_keybindings(&[String::from("d")]),
 		toggle_visual_mode: map_keybindings(&[String::from("v")]),
+		toggle_stack_view: map_keybindings(&[String::from("S")]),
+		toggle_fold_view: map_keybindings(&[String::from("Z")]),
+		toggle_filter: map_keybindings(&[String::from("F")]),
+		toggle_section: map_keybindings(&[String::from("z")]),
 		fixup_keep_message: map_keybindings(&[String::from("u")]),
+		apply_to_matches: map_keybindings(&[String::from("A")]),
+		toggle_mark: map_keybindings(&[String::from("Space")]),
+		gather_marked: map_keybindings(&[String::from("G")]),
+		clear_marks: map_keybindings(&[String::from("M")]),
+		set_named_mark: map_keybindings(&[String::from("m")]),
+		jump_to_named_mark: map_keybindings(&[String::from("'")]),
+		sort_by_author_date: map_keybindings(&[String::from("o")]),
+		sort_by_commit_date: map_keybindings(&[String::from("O")]),
+		reverse_selection: map_keybindings(&[String::from("V")]),
+		restore_order: map_keybindings(&[String::from("X")]),
 		fixup_keep_message_with_editor: map_keybindings(&[String::from("U")]),
+		key_sequences: vec![],
+		key_sequence_timeout: Duration::from_secs(1),
 	}
 }
@@ -2,6 +2,7 @@
 use std::{
 	cell::RefCell,
 	fmt::{Debug, Formatter},
+	fs::create_dir_all,
 	path::Path,
 };
 
@@ -93,3 +94,18 @@ where C: FnOnce(TodoFileTestContext) {
 		todo_file,
 	});
 }
+
+/// Provide a `TodoFile` instance, for the todo file of the Git sequencer, for use in tests.
+///
+/// # Panics
+/// Will panic if a temporary directory cannot be created
+pub(crate) fn with_sequencer_todo_file<C>(lines: &[&str], callback: C)
+where C: FnOnce(TodoFile) {
+	let git_directory = Builder::new().prefix("git-sequencer-scratch").tempdir().unwrap();
+	let sequencer_directory = git_directory.path().join("sequencer");
+	create_dir_all(sequencer_directory.as_path()).unwrap();
+
+	let mut todo_file = TodoFile::new(sequencer_directory.join("todo"), TodoFileOptions::new(1, "#"));
+	todo_file.set_lines(lines.iter().map(|l| Line::parse(l).unwrap()).collect());
+	callback(todo_file);
+}
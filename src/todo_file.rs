@@ -4,15 +4,20 @@
 //! This module is used to handle working with the rebase todo file.
 
 mod action;
+mod changed_command;
 mod edit_content;
 mod errors;
 mod history;
 mod line;
 mod line_parser;
+mod metadata_changes;
 mod todo_file_options;
 mod utils;
 
 use std::{
+	cmp::{max, min},
+	collections::HashMap,
+	ffi::{OsStr, OsString},
 	fs::{read_to_string, File},
 	io::Write,
 	path::{Path, PathBuf},
@@ -25,27 +30,54 @@ pub(crate) use self::{
 	action::Action,
 	edit_content::EditContext,
 	errors::ParseError,
+	history::HistoryEntry,
 	line::Line,
 	line_parser::LineParser,
+	metadata_changes::MetadataChanges,
 	todo_file_options::TodoFileOptions,
 };
 use self::{
+	changed_command::{changed_command_line, escape},
 	history::{History, HistoryItem},
-	utils::{remove_range, swap_range_down, swap_range_up},
+	utils::{message_file_name, remove_range, swap_range_down, swap_range_up, write_error},
 };
 use crate::todo_file::{
 	errors::{FileReadErrorCause, IoError},
 	history::Operation,
 };
 
+/// The name of the todo file used by Git during an interactive rebase.
+const REBASE_TODO_FILE_NAME: &str = "git-rebase-todo";
+
+/// The name of the file, next to the rebase todo file, that contains the lines that Git has already applied.
+const REBASE_DONE_FILE_NAME: &str = "done";
+
+/// The name of the directory used by the Git sequencer for a multiple commit cherry-pick or revert.
+const SEQUENCER_DIRECTORY_NAME: &str = "sequencer";
+
+/// The name of the todo file used by the Git sequencer for a multiple commit cherry-pick or revert.
+const SEQUENCER_TODO_FILE_NAME: &str = "todo";
+
+/// The name of the directory, next to the rebase todo file, that contains the commit messages composed in the
+/// application.
+const MESSAGE_DIRECTORY_NAME: &str = "interactive-rebase-tool-messages";
+
+/// The suffix added to the name of the rebase todo file, for the file that a range of lines is written to when only
+/// the range is edited outside of the application.
+const EDIT_RANGE_FILE_SUFFIX: &str = "-range";
+
 /// Represents a rebase file.
 #[derive(Debug)]
 pub(crate) struct TodoFile {
+	done_lines: Vec<Line>,
+	edit_range: Option<(usize, usize)>,
 	filepath: PathBuf,
 	history: History,
 	is_noop: bool,
 	lines: Vec<Line>,
+	loaded_lines: Vec<Line>,
 	options: TodoFileOptions,
+	selected_done_line_index: Option<usize>,
 	selected_line_index: usize,
 	version: Version,
 }
@@ -57,11 +89,15 @@ impl TodoFile {
 		let history = History::new(options.undo_limit);
 
 		Self {
+			done_lines: vec![],
+			edit_range: None,
 			filepath: PathBuf::from(path.as_ref()),
 			history,
 			is_noop: false,
 			lines: vec![],
+			loaded_lines: vec![],
 			options,
+			selected_done_line_index: None,
 			selected_line_index: 0,
 			version: Version::new(),
 		}
@@ -76,23 +112,153 @@ impl TodoFile {
 		else {
 			lines.into_iter().filter(|l| l.get_action() != &Action::Noop).collect()
 		};
+		self.loaded_lines = self.lines.clone();
 		if self.selected_line_index >= self.lines.len() {
 			self.selected_line_index = if self.lines.is_empty() { 0 } else { self.lines.len() - 1 };
 		}
+		self.selected_done_line_index = None;
 		self.version.reset();
 		self.history.reset();
 	}
 
+	/// Replace all the rebase lines, keeping the history. Only the lines between the unchanged leading and trailing
+	/// lines are replaced, and the replacement is recorded as a single change, so it can be undone in one step.
+	pub(crate) fn replace_lines(&mut self, lines: Vec<Line>) {
+		self.is_noop = !lines.is_empty() && lines[0].get_action() == &Action::Noop;
+		let new_lines = if self.is_noop { vec![] } else { lines };
+		self.splice_lines(0, self.lines.len(), new_lines);
+	}
+
+	/// Replace the lines from the start index, up to but not including the end index, with the changed lines, as a
+	/// single change.
+	fn splice_lines(&mut self, start: usize, end: usize, lines: Vec<Line>) {
+		let mut new_lines: Vec<Line> = lines.into_iter().filter(|l| l.get_action() != &Action::Noop).collect();
+		let existing = &self.lines[start..end];
+		let prefix = existing.iter().zip(&new_lines).take_while(|&(a, b)| a == b).count();
+		let suffix = existing
+			.iter()
+			.rev()
+			.zip(new_lines.iter().rev())
+			.take(min(existing.len(), new_lines.len()) - prefix)
+			.take_while(|&(a, b)| a == b)
+			.count();
+		let first = start + prefix;
+		let old_end = end - suffix;
+		let inserted: Vec<Line> = new_lines.drain(prefix..(new_lines.len() - suffix)).collect();
+
+		match (old_end > first, inserted.is_empty()) {
+			(true, false) => self.replace_range(first, old_end - 1, inserted),
+			(true, true) => self.remove_lines(first, old_end - 1),
+			(false, false) => self.add_lines(first, inserted),
+			(false, true) => {},
+		}
+
+		if self.selected_line_index >= self.lines.len() {
+			self.selected_line_index = self.lines.len().saturating_sub(1);
+		}
+	}
+
+	/// Set the lines that have already been applied by Git. These lines are read-only, and are never written back to
+	/// the rebase file.
+	pub(crate) fn set_done_lines(&mut self, lines: Vec<Line>) {
+		self.done_lines = lines.into_iter().filter(|l| l.get_action() != &Action::Noop).collect();
+		self.selected_done_line_index = None;
+		self.version.increment();
+	}
+
 	/// Load the rebase file from disk.
 	///
 	/// # Errors
 	///
 	/// Returns error if the file cannot be read.
 	pub(crate) fn load_file(&mut self) -> Result<(), IoError> {
-		let lines: Result<Vec<Line>, IoError> = read_to_string(self.filepath.as_path())
+		let lines = self.read_lines(&self.filepath)?;
+		self.set_lines(lines);
+		self.load_done_file()?;
+		Ok(())
+	}
+
+	/// Reload the rebase file from disk, after it was edited outside of the application. Unlike loading the file, the
+	/// history is kept and the differences are recorded as a single change, and each line is matched, by hash or by
+	/// content, to its earlier version so that modifications made before the edit are still tracked.
+	///
+	/// # Errors
+	///
+	/// Returns error if the file cannot be read.
+	pub(crate) fn reload_file(&mut self) -> Result<(), IoError> {
+		let mut lines = self.read_lines(&self.filepath)?;
+		Self::restore_originals(&self.lines, &mut lines);
+		self.replace_lines(lines);
+		Ok(())
+	}
+
+	/// Reload a range of lines from the edit range file, after the range was written with `write_range_for_edit` and
+	/// edited outside of the application, replacing only that range of lines. The edit range file is removed once it
+	/// is read. See `reload_file`.
+	///
+	/// # Errors
+	///
+	/// Returns error if the file cannot be read.
+	pub(crate) fn reload_range(&mut self, start_index: usize, end_index: usize) -> Result<(), IoError> {
+		let range_filepath = self.get_edit_range_filepath();
+		let mut lines = self.read_lines(&range_filepath)?;
+		// the file is rewritten for every edit, so a file that cannot be removed is not an error
+		let _removed = std::fs::remove_file(range_filepath);
+		let (start, end) = self.edit_bounds(start_index, end_index);
+		Self::restore_originals(&self.lines[start..end], &mut lines);
+		self.splice_lines(start, end, lines);
+		Ok(())
+	}
+
+	/// Get the path of the file that a range of lines is written to for editing outside of the application. The file is
+	/// next to the rebase file, so the rebase file is never left with only part of the lines.
+	#[must_use]
+	pub(crate) fn get_edit_range_filepath(&self) -> PathBuf {
+		let mut file_name = self.filepath.file_name().map(OsString::from).unwrap_or_default();
+		file_name.push(EDIT_RANGE_FILE_SUFFIX);
+		self.filepath.with_file_name(file_name)
+	}
+
+	/// Limit the next edit in the external editor to a range of lines.
+	pub(crate) fn set_edit_range(&mut self, start_index: usize, end_index: usize) {
+		self.edit_range = Some((start_index, end_index));
+	}
+
+	/// Take the range of lines to edit in the external editor, if the edit is limited to a range.
+	pub(crate) fn take_edit_range(&mut self) -> Option<(usize, usize)> {
+		self.edit_range.take()
+	}
+
+	/// Get the bounds of an inclusive range of lines, as a start and an exclusive end, within the lines.
+	fn edit_bounds(&self, start_index: usize, end_index: usize) -> (usize, usize) {
+		let end = min(max(start_index, end_index) + 1, self.lines.len());
+		(min(min(start_index, end_index), end), end)
+	}
+
+	fn restore_originals(existing: &[Line], lines: &mut [Line]) {
+		let mut used = vec![false; existing.len()];
+		for line in lines {
+			let position = existing.iter().enumerate().position(|(index, existing_line)| {
+				!used[index]
+					&& if line.has_reference() {
+						existing_line.get_hash() == line.get_hash()
+					}
+					else {
+						!existing_line.has_reference() && existing_line.to_text() == line.to_text()
+					}
+			});
+			if let Some(index) = position {
+				used[index] = true;
+				line.restore_original(&existing[index]);
+			}
+		}
+	}
+
+	fn read_lines(&self, filepath: &Path) -> Result<Vec<Line>, IoError> {
+		read_to_string(filepath)
 			.map_err(|err| {
 				IoError::FileRead {
-					file: self.filepath.clone(),
+					file: filepath.to_path_buf(),
 					cause: FileReadErrorCause::from(err),
 				}
 			})?
@@ -104,25 +270,161 @@ impl TodoFile {
 				else {
 					Some(Line::parse(l).map_err(|err| {
 						IoError::FileRead {
-							file: self.filepath.clone(),
+							file: filepath.to_path_buf(),
 							cause: FileReadErrorCause::from(err),
 						}
 					}))
 				}
 			})
+			.collect()
+	}
+
+	/// Load the lines already applied by Git, when editing the rebase file of a rebase that is in progress, such as
+	/// with `git rebase --edit-todo`. Lines that cannot be parsed are skipped, since they are only informational.
+	fn load_done_file(&mut self) -> Result<(), IoError> {
+		let Some(done_filepath) = self.done_filepath()
+		else {
+			return Ok(());
+		};
+
+		if !done_filepath.is_file() {
+			self.set_done_lines(vec![]);
+			return Ok(());
+		}
+
+		let contents = read_to_string(done_filepath.as_path()).map_err(|err| {
+			IoError::FileRead {
+				file: done_filepath.clone(),
+				cause: FileReadErrorCause::from(err),
+			}
+		})?;
+		let done_lines = contents
+			.lines()
+			.filter(|l| !l.starts_with(self.options.comment_prefix.as_str()) && !l.is_empty())
+			.filter_map(|l| Line::parse(l).ok())
 			.collect();
-		self.set_lines(lines?);
+		self.set_done_lines(done_lines);
 		Ok(())
 	}
 
+	/// The path to the done file, only available when the todo file is the one Git uses for interactive rebase.
+	fn done_filepath(&self) -> Option<PathBuf> {
+		if self.filepath.file_name()? != REBASE_TODO_FILE_NAME {
+			return None;
+		}
+		Some(self.filepath.parent()?.join(REBASE_DONE_FILE_NAME))
+	}
+
+	/// Is the todo file the one used by the Git sequencer, for a multiple commit cherry-pick or revert, instead of
+	/// the one used for an interactive rebase.
+	#[must_use]
+	pub(crate) fn is_sequencer(&self) -> bool {
+		self.filepath.file_name().map_or(false, |name| name == SEQUENCER_TODO_FILE_NAME)
+			&& self
+				.filepath
+				.parent()
+				.and_then(Path::file_name)
+				.map_or(false, |name| name == SEQUENCER_DIRECTORY_NAME)
+	}
+
 	/// Write the rebase file to disk.
 	/// # Errors
 	///
 	/// Returns error if the file cannot be written.
 	pub(crate) fn write_file(&self) -> Result<(), IoError> {
-		let mut file = File::create(&self.filepath).map_err(|err| {
+		let message_paths = self.write_messages()?;
+		self.write_lines(&self.filepath, &self.lines, &message_paths, true)
+	}
+
+	/// Write the rebase file to disk for editing outside of the application. The `exec` lines for modified lines are
+	/// left out, since the modifications are still tracked when the file is reloaded.
+	/// # Errors
+	///
+	/// Returns error if the file cannot be written.
+	pub(crate) fn write_file_for_edit(&self) -> Result<(), IoError> {
+		self.write_lines(&self.filepath, &self.lines, &HashMap::new(), false)
+	}
+
+	/// Write only a range of lines to the edit range file, for editing the range outside of the application, leaving
+	/// the rebase file unchanged. The edited range is read back with `reload_range`.
+	/// # Errors
+	///
+	/// Returns error if the file cannot be written.
+	pub(crate) fn write_range_for_edit(&self, start_index: usize, end_index: usize) -> Result<(), IoError> {
+		let (start, end) = self.edit_bounds(start_index, end_index);
+		self.write_lines(&self.get_edit_range_filepath(), &self.lines[start..end], &HashMap::new(), false)
+	}
+
+	/// Write the commit messages composed in the application to files, in a directory next to the rebase file, so the
+	/// commits can be amended with them during the rebase. Only the messages of `pick` and `edit` lines are written,
+	/// since the other actions either open an editor for the message or do not keep a commit of their own. The files
+	/// are named from the commit and the message, so a file is never reused for a different message. Returns the paths
+	/// of the written files, by the index of the line of the message.
+	fn write_messages(&self) -> Result<HashMap<usize, PathBuf>, IoError> {
+		// the sequencer does not support exec lines, so the messages could not be applied
+		if self.is_noop || self.is_sequencer() {
+			return Ok(HashMap::new());
+		}
+		let directory = self
+			.filepath
+			.parent()
+			.map_or_else(|| PathBuf::from(MESSAGE_DIRECTORY_NAME), |parent| parent.join(MESSAGE_DIRECTORY_NAME));
+		let mut message_paths = HashMap::new();
+		for (index, line) in self.lines.iter().enumerate() {
+			let Some(message) = line.message()
+			else {
+				continue;
+			};
+			if !matches!(*line.get_action(), Action::Pick | Action::Edit) {
+				continue;
+			}
+			let message_path = directory.join(message_file_name(line.get_hash(), message));
+			std::fs::create_dir_all(&directory)
+				.and_then(|()| std::fs::write(&message_path, message))
+				.map_err(|err| write_error(&message_path, err))?;
+			let _previous_path = message_paths.insert(index, message_path);
+		}
+		self.remove_stale_messages(&directory, &message_paths)?;
+		Ok(message_paths)
+	}
+
+	/// Remove the message files that are neither written for a line nor used by an `exec` line that is still to be run,
+	/// such as the files of an earlier edit of the rebase file, and remove the directory once it has no files.
+	fn remove_stale_messages(&self, directory: &Path, message_paths: &HashMap<usize, PathBuf>) -> Result<(), IoError> {
+		if !directory.is_dir() {
+			return Ok(());
+		}
+		let mut has_messages = false;
+		for entry in std::fs::read_dir(directory).map_err(|err| write_error(directory, err))? {
+			let path = entry.map_err(|err| write_error(directory, err))?.path();
+			let is_referenced = path.file_name().and_then(OsStr::to_str).is_some_and(|file_name| {
+				self.lines
+					.iter()
+					.any(|line| *line.get_action() == Action::Exec && line.get_content().contains(file_name))
+			});
+			if is_referenced || message_paths.values().any(|message_path| *message_path == path) {
+				has_messages = true;
+			}
+			else {
+				std::fs::remove_file(&path).map_err(|err| write_error(&path, err))?;
+			}
+		}
+		if !has_messages {
+			std::fs::remove_dir(directory).map_err(|err| write_error(directory, err))?;
+		}
+		Ok(())
+	}
+
+	fn write_lines(
+		&self,
+		filepath: &Path,
+		lines_to_write: &[Line],
+		message_paths: &HashMap<usize, PathBuf>,
+		with_changed_commands: bool,
+	) -> Result<(), IoError> {
+		let mut file = File::create(filepath).map_err(|err| {
 			IoError::FileRead {
-				file: self.filepath.clone(),
+				file: filepath.to_path_buf(),
 				cause: FileReadErrorCause::from(err),
 			}
 		})?;
@@ -130,45 +432,45 @@ impl TodoFile {
 			String::from("noop")
 		}
 		else {
-			self.lines
-				.iter()
-				.flat_map(|l| {
-					let mut lines = vec![Line::to_text(l)];
-					if let Some(command) = self.options.line_changed_command.as_deref() {
-						if l.is_modified() {
-							let action = l.get_action();
-
-							match *action {
-								Action::Break | Action::Noop => {},
-								Action::Drop
-								| Action::Fixup
-								| Action::Edit
-								| Action::Pick
-								| Action::Reword
-								| Action::Squash => {
-									lines.push(format!("exec {command} \"{}\" \"{}\"", action, l.get_hash()));
-								},
-								Action::Exec | Action::Label | Action::Reset | Action::Merge | Action::UpdateRef => {
-									let original_label =
-										l.original().map_or_else(|| l.get_content(), Line::get_content);
-									lines.push(format!(
-										"exec {command} \"{}\" \"{}\" \"{}\"",
-										action,
-										original_label,
-										l.get_content()
-									));
-								},
-							}
-						}
+			// the sequencer does not support exec lines
+			let with_exec_lines = with_changed_commands && !self.is_sequencer();
+			let mut lines = vec![];
+			// the metadata changes are applied after any fixup and squash lines of the commit, so they are kept in
+			// the commit that results from the chain
+			let mut metadata_exec_line = None;
+			for (index, l) in lines_to_write.iter().enumerate() {
+				if !matches!(*l.get_action(), Action::Fixup | Action::Squash) {
+					lines.extend(metadata_exec_line.take());
+				}
+				lines.push(Line::to_text(l));
+				if !with_exec_lines {
+					continue;
+				}
+				if let Some(message_path) = message_paths.get(&index) {
+					lines.push(format!(
+						"exec git commit --amend --allow-empty --only -F \"{}\"",
+						escape(message_path.to_string_lossy().as_ref())
+					));
+				}
+				if matches!(*l.get_action(), Action::Pick | Action::Reword | Action::Edit) {
+					metadata_exec_line = l.metadata_changes().exec_line();
+				}
+				if l.is_modified() {
+					if let Some(exec_line) = self
+						.options
+						.get_line_changed_command(*l.get_action())
+						.and_then(|command| changed_command_line(command, l, index + 1))
+					{
+						lines.push(exec_line);
 					}
-					lines
-				})
-				.collect::<Vec<String>>()
-				.join("\n")
+				}
+			}
+			lines.extend(metadata_exec_line);
+			lines.join("\n")
 		};
 		writeln!(file, "{file_contents}").map_err(|err| {
 			IoError::FileRead {
-				file: self.filepath.clone(),
+				file: filepath.to_path_buf(),
 				cause: FileReadErrorCause::from(err),
 			}
 		})?;
@@ -177,6 +479,7 @@ impl TodoFile {
 
 	/// Set the selected line index returning the new index based after ensuring within range.
 	pub(crate) fn set_selected_line_index(&mut self, selected_line_index: usize) -> usize {
+		self.selected_done_line_index = None;
 		self.selected_line_index = if self.lines.is_empty() {
 			0
 		}
@@ -189,6 +492,17 @@ impl TodoFile {
 		self.selected_line_index
 	}
 
+	/// Select a done line, returning the new index after ensuring within range.
+	pub(crate) fn set_selected_done_line_index(&mut self, selected_done_line_index: usize) -> Option<usize> {
+		self.selected_done_line_index = if self.done_lines.is_empty() {
+			None
+		}
+		else {
+			Some(min(selected_done_line_index, self.done_lines.len() - 1))
+		};
+		self.selected_done_line_index
+	}
+
 	/// Swap a range of lines up.
 	pub(crate) fn swap_range_up(&mut self, start_index: usize, end_index: usize) -> bool {
 		if end_index == 0 || start_index == 0 || self.lines.is_empty() {
@@ -225,6 +539,41 @@ impl TodoFile {
 		true
 	}
 
+	/// Move a range of lines, so that the first line of the range is at the destination index. The move is recorded as
+	/// a single modification of the lines between the original and new positions, so it can be undone in one step.
+	pub(crate) fn move_range(&mut self, start_index: usize, end_index: usize, destination_index: usize) -> bool {
+		if self.lines.is_empty() {
+			return false;
+		}
+
+		let max_index = self.lines.len() - 1;
+		let (start, end) = if end_index <= start_index {
+			(end_index, start_index)
+		}
+		else {
+			(start_index, end_index)
+		};
+		if end > max_index {
+			return false;
+		}
+		let range_length = end - start + 1;
+		let destination = min(destination_index, self.lines.len() - range_length);
+		if destination == start {
+			return false;
+		}
+
+		let modified_start = min(start, destination);
+		let modified_end = max(end, destination + range_length - 1);
+		let original_lines = self.lines[modified_start..=modified_end].to_vec();
+
+		let moved_lines = self.lines.drain(start..=end).collect::<Vec<Line>>();
+		_ = self.lines.splice(destination..destination, moved_lines);
+		self.version.increment();
+		self.history
+			.record(HistoryItem::new_modify(modified_start, modified_end, original_lines));
+		true
+	}
+
 	/// Add a new line.
 	pub(crate) fn add_line(&mut self, index: usize, line: Line) {
 		let i = if index > self.lines.len() {
@@ -238,6 +587,18 @@ impl TodoFile {
 		self.history.record(HistoryItem::new_add(i, i));
 	}
 
+	/// Add new lines, as a single change.
+	pub(crate) fn add_lines(&mut self, index: usize, lines: Vec<Line>) {
+		if lines.is_empty() {
+			return;
+		}
+		let start = min(index, self.lines.len());
+		let end = start + lines.len() - 1;
+		drop(self.lines.splice(start..start, lines));
+		self.version.increment();
+		self.history.record(HistoryItem::new_add(start, end));
+	}
+
 	/// Remove a range of lines.
 	pub(crate) fn remove_lines(&mut self, start_index: usize, end_index: usize) {
 		if self.lines.is_empty() {
@@ -279,20 +640,302 @@ impl TodoFile {
 		for index in range {
 			let line = &mut self.lines[index];
 			lines.push(line.clone());
-			if let Some(action) = edit_context.get_action() {
-				line.set_action(action);
+			Self::edit_line(line, edit_context);
+		}
+		self.version.increment();
+		self.history.record(HistoryItem::new_modify(start, end, lines));
+	}
+
+	/// Set, or clear, the new message of the commit of a line, that the commit is amended with after it is picked. A
+	/// `reword` line is changed to a `pick`, since the message no longer has to be edited during the rebase. Returns
+	/// false when the line does not have a commit.
+	pub(crate) fn set_message(&mut self, index: usize, message: Option<&str>) -> bool {
+		let Some(line) = self.lines.get_mut(index)
+		else {
+			return false;
+		};
+		if !line.has_reference() {
+			return false;
+		}
+		let previous = line.clone();
+		line.set_message(message);
+		if message.is_some() && *line.get_action() == Action::Reword {
+			line.set_action(Action::Pick);
+		}
+		self.version.increment();
+		self.history.record(HistoryItem::new_modify(index, index, vec![previous]));
+		true
+	}
+
+	/// Update the changes to the author, committer date and trailers of the commits of the `pick`, `reword` and `edit`
+	/// lines in a range. The update is recorded as a single modification of the range, so it can be undone in one step.
+	/// Returns the number of lines that were updated.
+	pub(crate) fn update_metadata_changes<F>(&mut self, start_index: usize, end_index: usize, update: F) -> usize
+	where F: Fn(&mut MetadataChanges) {
+		if self.lines.is_empty() {
+			return 0;
+		}
+		let max_index = self.lines.len() - 1;
+		let (start, end) = (min(start_index, end_index), min(max(start_index, end_index), max_index));
+
+		let previous_lines = self.lines[start..=end].to_vec();
+		let mut updated = 0;
+		for line in &mut self.lines[start..=end] {
+			if !line.has_reference() || !matches!(*line.get_action(), Action::Pick | Action::Reword | Action::Edit) {
+				continue;
+			}
+			let mut metadata_changes = line.metadata_changes().clone();
+			update(&mut metadata_changes);
+			line.set_metadata_changes(metadata_changes);
+			updated += 1;
+		}
+		if updated > 0 {
+			self.version.increment();
+			self.history.record(HistoryItem::new_modify(start, end, previous_lines));
+		}
+		updated
+	}
+
+	/// Update a set of lines, that do not need to be contiguous. The update is recorded as a single modification of the
+	/// lines between the first and last index, so it can be undone in one step.
+	pub(crate) fn update_lines(&mut self, indexes: &[usize], edit_context: &EditContext) {
+		let updated_indexes = self.valid_indexes(indexes);
+		let (Some(&start), Some(&end)) = (updated_indexes.first(), updated_indexes.last())
+		else {
+			return;
+		};
+
+		let lines = self.lines[start..=end].to_vec();
+		for index in updated_indexes {
+			Self::edit_line(&mut self.lines[index], edit_context);
+		}
+		self.version.increment();
+		self.history.record(HistoryItem::new_modify(start, end, lines));
+	}
+
+	/// Replace a range of lines with new lines, which can be a different number of lines than the range.
+	pub(crate) fn replace_range(&mut self, start_index: usize, end_index: usize, lines: Vec<Line>) {
+		if self.lines.is_empty() || lines.is_empty() {
+			return;
+		}
+
+		let max_index = self.lines.len() - 1;
+		let start = min(min(start_index, end_index), max_index);
+		let end = min(max(start_index, end_index), max_index);
+		let new_end = start + lines.len() - 1;
+
+		let replaced_lines = self.lines.splice(start..=end, lines).collect();
+		self.version.increment();
+		self.history
+			.record(HistoryItem::new_replace(start, new_end, replaced_lines));
+	}
+
+	/// Remove a set of lines, that do not need to be contiguous. The removal is recorded as a single change of the
+	/// lines between the first and last index, so it can be undone in one step.
+	pub(crate) fn remove_lines_at(&mut self, indexes: &[usize]) {
+		let removed_indexes = self.valid_indexes(indexes);
+		let (Some(&start), Some(&end)) = (removed_indexes.first(), removed_indexes.last())
+		else {
+			return;
+		};
+
+		let kept_lines: Vec<Line> = (start..=end)
+			.filter(|index| removed_indexes.binary_search(index).is_err())
+			.map(|index| self.lines[index].clone())
+			.collect();
+		if kept_lines.is_empty() {
+			self.remove_lines(start, end);
+		}
+		else {
+			self.replace_range(start, end, kept_lines);
+		}
+	}
+
+	/// Insert a copy of the line after each of a set of lines, that do not need to be contiguous. The insertion is
+	/// recorded as a single replacement of the lines between the first and last index, so it can be undone in one step.
+	/// Returns the number of inserted lines.
+	pub(crate) fn add_line_after_lines(&mut self, indexes: &[usize], line: &Line) -> usize {
+		let after_indexes = self.valid_indexes(indexes);
+		let (Some(&start), Some(&end)) = (after_indexes.first(), after_indexes.last())
+		else {
+			return 0;
+		};
+
+		let mut lines = vec![];
+		for index in start..=end {
+			lines.push(self.lines[index].clone());
+			if after_indexes.binary_search(&index).is_ok() {
+				lines.push(line.clone());
 			}
+		}
+		self.replace_range(start, end, lines);
+		after_indexes.len()
+	}
+
+	/// Insert lines after a set of lines, that do not need to be contiguous, as pairs of the index of the line to
+	/// insert after and the new line. Lines inserted after the same line keep their relative order. The insertion is
+	/// recorded as a single replacement of the lines between the first and last index, so it can be undone in one
+	/// step. Returns the number of inserted lines.
+	pub(crate) fn add_lines_after(&mut self, mut added_lines: Vec<(usize, Line)>) -> usize {
+		let len = self.lines.len();
+		added_lines.retain(|added_line| added_line.0 < len);
+		added_lines.sort_by_key(|added_line| added_line.0);
+		let (Some(start), Some(end)) = (
+			added_lines.first().map(|added_line| added_line.0),
+			added_lines.last().map(|added_line| added_line.0),
+		)
+		else {
+			return 0;
+		};
 
-			if let Some(content) = edit_context.get_content() {
-				line.edit_content(content);
+		let count = added_lines.len();
+		let mut added = added_lines.into_iter().peekable();
+		let mut lines = vec![];
+		for index in start..=end {
+			lines.push(self.lines[index].clone());
+			while let Some((_, line)) = added.next_if(|added_line| added_line.0 == index) {
+				lines.push(line);
 			}
+		}
+		self.replace_range(start, end, lines);
+		count
+	}
 
-			if let Some(option) = edit_context.get_option() {
-				line.toggle_option(option);
+	/// Move a set of lines, that do not need to be contiguous, so they are contiguous and keep their relative order.
+	/// The lines are placed after the line at the index, or, when the line at the index is one of the moved lines, at
+	/// the position of that line. Returns the new range of the moved lines. The move is recorded as a single
+	/// modification, so it can be undone in one step.
+	pub(crate) fn gather_lines(&mut self, indexes: &[usize], index: usize) -> Option<(usize, usize)> {
+		let gathered_indexes = self.valid_indexes(indexes);
+		let (Some(&first), Some(&last)) = (gathered_indexes.first(), gathered_indexes.last())
+		else {
+			return None;
+		};
+		let target_index = min(index, self.lines.len() - 1);
+		let target_gathered = gathered_indexes.binary_search(&target_index).is_ok();
+
+		let mut gathered = vec![];
+		let mut others = vec![];
+		let mut destination = 0;
+		for (i, line) in self.lines.iter().enumerate() {
+			if gathered_indexes.binary_search(&i).is_ok() {
+				gathered.push(line.clone());
+			}
+			else {
+				if i < target_index || (i == target_index && !target_gathered) {
+					destination += 1;
+				}
+				others.push(line.clone());
 			}
 		}
+		let gathered_end = destination + gathered.len() - 1;
+		let lines = [&others[..destination], gathered.as_slice(), &others[destination..]].concat();
+
+		let start = min(first, destination);
+		let end = max(max(last, target_index), gathered_end);
+		if lines[start..=end] != self.lines[start..=end] {
+			let original_lines = self.lines[start..=end].to_vec();
+			self.lines[start..=end].clone_from_slice(&lines[start..=end]);
+			self.version.increment();
+			self.history.record(HistoryItem::new_modify(start, end, original_lines));
+		}
+		Some((destination, gathered_end))
+	}
+
+	/// Sort a set of lines, that do not need to be contiguous, by a key. The sorted lines are placed in the positions
+	/// of the set of lines, and lines without a key are left in their position. The sort is stable, and is recorded as
+	/// a single modification, so it can be undone in one step. Returns true if the order of the lines changed.
+	pub(crate) fn sort_lines_by_key<K: Ord, F: FnMut(&Line) -> Option<K>>(
+		&mut self,
+		indexes: &[usize],
+		mut key: F,
+	) -> bool {
+		let mut keyed: Vec<(K, usize)> = self
+			.valid_indexes(indexes)
+			.into_iter()
+			.filter_map(|index| key(&self.lines[index]).map(|k| (k, index)))
+			.collect();
+		let positions: Vec<usize> = keyed.iter().map(|&(_, index)| index).collect();
+		keyed.sort_by(|(a, _), (b, _)| a.cmp(b));
+		self.reorder_lines(&positions, keyed.into_iter().map(|(_, index)| index).collect())
+	}
+
+	/// Reverse the order of a set of lines, that do not need to be contiguous, within the positions of the set of
+	/// lines. The reversal is recorded as a single modification, so it can be undone in one step. Returns true if the
+	/// order of the lines changed.
+	pub(crate) fn reverse_lines(&mut self, indexes: &[usize]) -> bool {
+		let positions = self.valid_indexes(indexes);
+		let sources = positions.iter().rev().copied().collect();
+		self.reorder_lines(&positions, sources)
+	}
+
+	/// Restore the order of a set of lines, that do not need to be contiguous, to the order the lines had when the file
+	/// was loaded, using the original of each line. Lines that were not loaded from the file, such as inserted lines,
+	/// are left in their position. Returns true if the order of the lines changed.
+	pub(crate) fn restore_lines_order(&mut self, indexes: &[usize]) -> bool {
+		let mut used = vec![false; self.loaded_lines.len()];
+		let loaded_lines = &self.loaded_lines;
+		let load_positions: Vec<Option<usize>> = self
+			.lines
+			.iter()
+			.map(|line| {
+				let position = loaded_lines
+					.iter()
+					.enumerate()
+					.position(|(i, loaded)| !used[i] && loaded.original() == line.original())?;
+				used[position] = true;
+				Some(position)
+			})
+			.collect();
+		let mut keyed: Vec<(usize, usize)> = self
+			.valid_indexes(indexes)
+			.into_iter()
+			.filter_map(|index| load_positions[index].map(|position| (position, index)))
+			.collect();
+		let positions: Vec<usize> = keyed.iter().map(|&(_, index)| index).collect();
+		keyed.sort_unstable();
+		self.reorder_lines(&positions, keyed.into_iter().map(|(_, index)| index).collect())
+	}
+
+	/// Place the lines from the source indexes at the position indexes, recording the change as a single modification.
+	fn reorder_lines(&mut self, positions: &[usize], sources: Vec<usize>) -> bool {
+		let (Some(&start), Some(&end)) = (positions.first(), positions.last())
+		else {
+			return false;
+		};
+		if positions.iter().zip(sources.iter()).all(|(position, source)| position == source) {
+			return false;
+		}
+
+		let original_lines = self.lines[start..=end].to_vec();
+		for (&position, source) in positions.iter().zip(sources) {
+			self.lines[position] = original_lines[source - start].clone();
+		}
 		self.version.increment();
-		self.history.record(HistoryItem::new_modify(start, end, lines));
+		self.history.record(HistoryItem::new_modify(start, end, original_lines));
+		true
+	}
+
+	/// The indexes that are within the lines, sorted and without duplicates.
+	fn valid_indexes(&self, indexes: &[usize]) -> Vec<usize> {
+		let mut valid: Vec<usize> = indexes.iter().copied().filter(|&i| i < self.lines.len()).collect();
+		valid.sort_unstable();
+		valid.dedup();
+		valid
+	}
+
+	fn edit_line(line: &mut Line, edit_context: &EditContext) {
+		if let Some(action) = edit_context.get_action() {
+			line.set_action(action);
+		}
+
+		if let Some(content) = edit_context.get_content() {
+			line.edit_content(content);
+		}
+
+		if let Some(option) = edit_context.get_option() {
+			line.toggle_option(option);
+		}
 	}
 
 	/// Undo the last modification.
@@ -315,15 +958,41 @@ impl TodoFile {
 		self.history.redo(&mut self.lines).map(|(_, start, end)| (start, end))
 	}
 
+	/// Get the states of the undo history, from the oldest to the newest state, and the position of the current state.
+	#[must_use]
+	pub(crate) fn history_entries(&self) -> (Vec<HistoryEntry>, usize) {
+		self.history.entries(&self.lines)
+	}
+
+	/// Get the lines of a state of the undo history, without restoring the state.
+	#[must_use]
+	pub(crate) fn history_preview(&self, position: usize) -> Vec<Line> {
+		self.history.preview(&self.lines, position)
+	}
+
+	/// Restore a state of the undo history, in a single step. Returns if the lines changed.
+	pub(crate) fn restore_history(&mut self, position: usize) -> bool {
+		if !self.history.jump(&mut self.lines, position) {
+			return false;
+		}
+		self.version.increment();
+		let max_index = self.get_max_selected_line_index();
+		self.selected_line_index = min(self.selected_line_index, max_index);
+		true
+	}
+
 	/// Get the current version
 	#[must_use]
 	pub(crate) const fn version(&self) -> &Version {
 		&self.version
 	}
 
-	/// Get the selected line.
+	/// Get the selected line, which may be a done line.
 	#[must_use]
 	pub(crate) fn get_selected_line(&self) -> Option<&Line> {
+		if let Some(index) = self.selected_done_line_index {
+			return self.done_lines.get(index);
+		}
 		self.lines.get(self.selected_line_index)
 	}
 
@@ -340,6 +1009,18 @@ impl TodoFile {
 		self.selected_line_index
 	}
 
+	/// Get the selected done line index, if a done line is selected.
+	#[must_use]
+	pub(crate) const fn get_selected_done_line_index(&self) -> Option<usize> {
+		self.selected_done_line_index
+	}
+
+	/// Is the selected line a done line.
+	#[must_use]
+	pub(crate) const fn is_done_line_selected(&self) -> bool {
+		self.selected_done_line_index.is_some()
+	}
+
 	/// Get the file path to the rebase file.
 	#[must_use]
 	pub(crate) fn get_filepath(&self) -> &Path {
@@ -352,6 +1033,18 @@ impl TodoFile {
 		self.lines.get(index)
 	}
 
+	/// Get the labels of the `label` lines before the index, in the order they are defined, without duplicates.
+	#[must_use]
+	pub(crate) fn labels_before(&self, index: usize) -> Vec<String> {
+		let mut labels: Vec<String> = vec![];
+		for line in self.lines.iter().take(index) {
+			if *line.get_action() == Action::Label && !labels.iter().any(|label| label == line.get_content()) {
+				labels.push(String::from(line.get_content()));
+			}
+		}
+		labels
+	}
+
 	/// Get an owned copy of the lines.
 	#[must_use]
 	pub(crate) fn get_lines_owned(&self) -> Vec<Line> {
@@ -369,6 +1062,17 @@ impl TodoFile {
 		self.lines.iter()
 	}
 
+	/// Get an iterator over the done lines.
+	pub(crate) fn done_lines_iter(&self) -> Iter<'_, Line> {
+		self.done_lines.iter()
+	}
+
+	/// Get the number of done lines.
+	#[must_use]
+	pub(crate) fn done_lines_count(&self) -> usize {
+		self.done_lines.len()
+	}
+
 	/// Does the rebase file contain no lines.
 	#[must_use]
 	pub(crate) fn is_empty(&self) -> bool {
@@ -378,8 +1082,9 @@ impl TodoFile {
 
 #[cfg(test)]
 mod tests {
-	use claims::{assert_none, assert_some_eq};
-	use tempfile::{Builder, NamedTempFile};
+	use claims::{assert_err_eq, assert_none, assert_some_eq};
+	use rstest::rstest;
+	use tempfile::{Builder, NamedTempFile, TempDir};
 
 	use super::*;
 	use crate::{assert_empty, assert_not_empty};
@@ -453,10 +1158,300 @@ mod tests {
 		assert_todo_lines!(todo_file, "pick aaa foobar");
 	}
 
+	fn edit_todo_file(todo_file: &TodoFile, file_contents: &[&str]) {
+		std::fs::write(todo_file.get_filepath(), file_contents.join("\n")).unwrap();
+	}
+
 	#[test]
-	fn set_lines() {
-		let (mut todo_file, _) = create_and_load_todo_file(&[]);
-		let old_version = todo_file.version;
+	fn reload_file_records_single_change() {
+		let (mut todo_file, _todo_path) = create_and_load_todo_file(&["pick aaa c1", "pick bbb c2", "pick ccc c3"]);
+		edit_todo_file(&todo_file, &["pick aaa c1", "drop ccc c3", "pick bbb c2", "exec make"]);
+		let version = *todo_file.version();
+		todo_file.reload_file().unwrap();
+		assert_todo_lines!(todo_file, "pick aaa c1", "drop ccc c3", "pick bbb c2", "exec make");
+		assert_ne!(todo_file.version(), &version);
+		assert_some_eq!(todo_file.undo(), (1, 2));
+		assert_todo_lines!(todo_file, "pick aaa c1", "pick bbb c2", "pick ccc c3");
+		assert_some_eq!(todo_file.redo(), (1, 3));
+		assert_todo_lines!(todo_file, "pick aaa c1", "drop ccc c3", "pick bbb c2", "exec make");
+	}
+
+	#[test]
+	fn reload_file_keeps_modifications() {
+		let (mut todo_file, _todo_path) = create_and_load_todo_file(&["pick aaa c1", "pick bbb c2", "pick ccc c3"]);
+		todo_file.update_range(0, 0, &EditContext::new().action(Action::Reword));
+		edit_todo_file(&todo_file, &["pick ccc c3", "reword aaa c1", "fixup bbb c2", "break"]);
+		todo_file.reload_file().unwrap();
+		let modified: Vec<bool> = todo_file.lines_iter().map(Line::is_modified).collect();
+		assert_eq!(modified, vec![false, true, true, false]);
+		assert_eq!(
+			todo_file.get_line(1).unwrap().original().unwrap().get_action(),
+			&Action::Pick
+		);
+		assert_eq!(
+			todo_file.get_line(2).unwrap().original().unwrap().get_action(),
+			&Action::Pick
+		);
+	}
+
+	#[test]
+	fn reload_file_changed_option() {
+		let (mut todo_file, _todo_path) = create_and_load_todo_file(&["pick aaa c1", "fixup bbb c2"]);
+		edit_todo_file(&todo_file, &["pick aaa c1", "fixup -C bbb c2"]);
+		todo_file.reload_file().unwrap();
+		let modified: Vec<bool> = todo_file.lines_iter().map(Line::is_modified).collect();
+		assert_eq!(modified, vec![false, true]);
+	}
+
+	#[test]
+	fn reload_file_added_lines() {
+		let (mut todo_file, _todo_path) = create_and_load_todo_file(&["pick aaa c1", "pick bbb c2"]);
+		edit_todo_file(&todo_file, &["pick aaa c1", "exec make", "break", "pick bbb c2"]);
+		todo_file.reload_file().unwrap();
+		assert_todo_lines!(todo_file, "pick aaa c1", "exec make", "break", "pick bbb c2");
+		assert_some_eq!(todo_file.undo(), (1, 1));
+		assert_todo_lines!(todo_file, "pick aaa c1", "pick bbb c2");
+	}
+
+	#[test]
+	fn reload_file_removed_lines() {
+		let (mut todo_file, _todo_path) = create_and_load_todo_file(&["pick aaa c1", "pick bbb c2", "pick ccc c3"]);
+		_ = todo_file.set_selected_line_index(2);
+		edit_todo_file(&todo_file, &["pick aaa c1"]);
+		todo_file.reload_file().unwrap();
+		assert_todo_lines!(todo_file, "pick aaa c1");
+		assert_eq!(todo_file.get_selected_line_index(), 0);
+		assert_some_eq!(todo_file.undo(), (1, 2));
+		assert_todo_lines!(todo_file, "pick aaa c1", "pick bbb c2", "pick ccc c3");
+	}
+
+	#[test]
+	fn reload_file_unchanged() {
+		let (mut todo_file, _todo_path) = create_and_load_todo_file(&["pick aaa c1"]);
+		todo_file.reload_file().unwrap();
+		assert_none!(todo_file.undo());
+	}
+
+	#[test]
+	fn reload_file_noop() {
+		let (mut todo_file, _todo_path) = create_and_load_todo_file(&["pick aaa c1"]);
+		edit_todo_file(&todo_file, &["noop"]);
+		todo_file.reload_file().unwrap();
+		assert_empty!(todo_file);
+		assert!(todo_file.is_noop());
+	}
+
+	#[test]
+	fn reload_file_error() {
+		let (mut todo_file, _todo_path) = create_and_load_todo_file(&["pick aaa c1"]);
+		edit_todo_file(&todo_file, &["invalid line"]);
+		assert!(todo_file.reload_file().is_err());
+		assert_todo_lines!(todo_file, "pick aaa c1");
+	}
+
+	#[test]
+	fn write_file_for_edit_without_exec_command() {
+		let mut options = TodoFileOptions::new(10, "#");
+		options.line_changed_command("command");
+		let (mut todo_file, _todo_path) = create_and_load_todo_file_with_options(&["pick aaa c1"], options);
+		todo_file.update_range(0, 0, &EditContext::new().action(Action::Drop));
+		todo_file.write_file_for_edit().unwrap();
+		assert_read_todo_file!(todo_file.get_filepath(), "drop aaa c1");
+	}
+
+	#[test]
+	fn edit_range() {
+		let (mut todo_file, _todo_path) = create_and_load_todo_file(&["pick aaa c1"]);
+		assert_none!(todo_file.take_edit_range());
+		todo_file.set_edit_range(0, 0);
+		assert_some_eq!(todo_file.take_edit_range(), (0, 0));
+		assert_none!(todo_file.take_edit_range());
+	}
+
+	#[test]
+	fn write_range_for_edit() {
+		let (todo_file, _todo_path) = create_and_load_todo_file(&["pick aaa c1", "pick bbb c2", "pick ccc c3"]);
+		todo_file.write_range_for_edit(2, 1).unwrap();
+		assert_read_todo_file!(&todo_file.get_edit_range_filepath(), "pick bbb c2", "pick ccc c3");
+		std::fs::remove_file(todo_file.get_edit_range_filepath()).unwrap();
+	}
+
+	#[test]
+	fn write_range_for_edit_leaves_todo_file_unchanged() {
+		let (todo_file, _todo_path) = create_and_load_todo_file(&["pick aaa c1", "pick bbb c2", "pick ccc c3"]);
+		let contents = read_to_string(todo_file.get_filepath()).unwrap();
+		todo_file.write_range_for_edit(1, 1).unwrap();
+		assert_eq!(read_to_string(todo_file.get_filepath()).unwrap(), contents);
+		std::fs::remove_file(todo_file.get_edit_range_filepath()).unwrap();
+	}
+
+	#[test]
+	fn write_range_for_edit_out_of_bounds() {
+		let (todo_file, _todo_path) = create_and_load_todo_file(&["pick aaa c1", "pick bbb c2"]);
+		todo_file.write_range_for_edit(1, 5).unwrap();
+		assert_read_todo_file!(&todo_file.get_edit_range_filepath(), "pick bbb c2");
+		std::fs::remove_file(todo_file.get_edit_range_filepath()).unwrap();
+	}
+
+	fn edit_range_file(todo_file: &TodoFile, file_contents: &[&str]) {
+		std::fs::write(todo_file.get_edit_range_filepath(), file_contents.join("\n")).unwrap();
+	}
+
+	#[test]
+	fn reload_range() {
+		let (mut todo_file, _todo_path) =
+			create_and_load_todo_file(&["pick aaa c1", "pick bbb c2", "pick ccc c3", "pick ddd c4"]);
+		todo_file.update_range(1, 1, &EditContext::new().action(Action::Squash));
+		edit_range_file(&todo_file, &["pick ccc c3", "squash bbb c2", "break"]);
+		todo_file.reload_range(1, 2).unwrap();
+		assert_todo_lines!(
+			todo_file,
+			"pick aaa c1",
+			"pick ccc c3",
+			"squash bbb c2",
+			"break",
+			"pick ddd c4"
+		);
+		assert!(todo_file.get_line(2).unwrap().is_modified());
+		assert!(!todo_file.get_line(1).unwrap().is_modified());
+		assert!(!todo_file.get_edit_range_filepath().exists());
+		assert_some_eq!(todo_file.undo(), (1, 2));
+		assert_todo_lines!(todo_file, "pick aaa c1", "squash bbb c2", "pick ccc c3", "pick ddd c4");
+	}
+
+	#[test]
+	fn reload_range_removed() {
+		let (mut todo_file, _todo_path) = create_and_load_todo_file(&["pick aaa c1", "pick bbb c2", "pick ccc c3"]);
+		edit_range_file(&todo_file, &[]);
+		todo_file.reload_range(0, 1).unwrap();
+		assert_todo_lines!(todo_file, "pick ccc c3");
+	}
+
+	#[test]
+	fn get_edit_range_filepath() {
+		let todo_file = TodoFile::new("rebase-merge/git-rebase-todo", TodoFileOptions::new(1, "#"));
+		assert_eq!(
+			todo_file.get_edit_range_filepath(),
+			Path::new("rebase-merge/git-rebase-todo-range")
+		);
+	}
+
+	fn create_rebase_directory(todo_contents: &[&str], done_contents: Option<&[&str]>) -> TempDir {
+		let rebase_directory = Builder::new().prefix("rebase-merge").tempdir().unwrap();
+		let todo_file_path = rebase_directory.path().join(REBASE_TODO_FILE_NAME);
+		std::fs::write(todo_file_path, todo_contents.join("\n")).unwrap();
+		if let Some(done) = done_contents {
+			std::fs::write(rebase_directory.path().join(REBASE_DONE_FILE_NAME), done.join("\n")).unwrap();
+		}
+		rebase_directory
+	}
+
+	#[rstest]
+	#[case::sequencer("sequencer/todo", true)]
+	#[case::rebase("rebase-merge/git-rebase-todo", false)]
+	#[case::todo_outside_sequencer("rebase-merge/todo", false)]
+	#[case::other_file_in_sequencer("sequencer/head", false)]
+	#[case::todo_without_parent("todo", false)]
+	fn is_sequencer(#[case] path: &str, #[case] expected: bool) {
+		let todo_file = TodoFile::new(path, TodoFileOptions::new(1, "#"));
+		assert_eq!(todo_file.is_sequencer(), expected);
+	}
+
+	#[test]
+	fn load_file_sequencer() {
+		let temp_directory = Builder::new().tempdir().unwrap();
+		let sequencer_directory = temp_directory.path().join(SEQUENCER_DIRECTORY_NAME);
+		std::fs::create_dir_all(&sequencer_directory).unwrap();
+		let todo_file_path = sequencer_directory.join(SEQUENCER_TODO_FILE_NAME);
+		std::fs::write(&todo_file_path, "pick aaa c1\nrevert bbb c2").unwrap();
+		let mut todo_file = TodoFile::new(todo_file_path, TodoFileOptions::new(1, "#"));
+		todo_file.load_file().unwrap();
+		assert!(todo_file.is_sequencer());
+		assert_todo_lines!(todo_file, "pick aaa c1", "revert bbb c2");
+	}
+
+	#[test]
+	fn load_file_with_done_file() {
+		let rebase_directory = create_rebase_directory(
+			&["pick ccc c3"],
+			Some(&["# comment", "pick aaa c1", "", "exec echo foo", "not a valid line"]),
+		);
+		let mut todo_file = TodoFile::new(
+			rebase_directory.path().join(REBASE_TODO_FILE_NAME),
+			TodoFileOptions::new(1, "#"),
+		);
+		todo_file.load_file().unwrap();
+		assert_eq!(todo_file.done_lines_count(), 2);
+		assert_eq!(
+			todo_file.done_lines_iter().map(Line::to_text).collect::<Vec<String>>(),
+			vec!["pick aaa c1", "exec echo foo"]
+		);
+		assert_todo_lines!(todo_file, "pick ccc c3");
+	}
+
+	#[test]
+	fn load_file_without_done_file() {
+		let rebase_directory = create_rebase_directory(&["pick ccc c3"], None);
+		let mut todo_file = TodoFile::new(
+			rebase_directory.path().join(REBASE_TODO_FILE_NAME),
+			TodoFileOptions::new(1, "#"),
+		);
+		todo_file.load_file().unwrap();
+		assert_eq!(todo_file.done_lines_count(), 0);
+	}
+
+	#[test]
+	fn load_file_ignores_done_file_for_other_todo_files() {
+		let rebase_directory = create_rebase_directory(&["pick ccc c3"], Some(&["pick aaa c1"]));
+		let todo_file_path = rebase_directory.path().join("other-todo");
+		std::fs::write(todo_file_path.as_path(), "pick ccc c3").unwrap();
+		let mut todo_file = TodoFile::new(todo_file_path, TodoFileOptions::new(1, "#"));
+		todo_file.load_file().unwrap();
+		assert_eq!(todo_file.done_lines_count(), 0);
+	}
+
+	#[test]
+	fn write_file_excludes_done_lines() {
+		let rebase_directory = create_rebase_directory(&["pick ccc c3"], Some(&["pick aaa c1"]));
+		let mut todo_file = TodoFile::new(
+			rebase_directory.path().join(REBASE_TODO_FILE_NAME),
+			TodoFileOptions::new(1, "#"),
+		);
+		todo_file.load_file().unwrap();
+		todo_file.write_file().unwrap();
+		assert_read_todo_file!(todo_file.get_filepath(), "pick ccc c3");
+	}
+
+	#[test]
+	fn selected_done_line() {
+		let (mut todo_file, _) = create_and_load_todo_file(&["pick ccc c3"]);
+		todo_file.set_done_lines(vec![create_line("pick aaa c1"), create_line("pick bbb c2")]);
+		assert_some_eq!(todo_file.set_selected_done_line_index(1), 1);
+		assert!(todo_file.is_done_line_selected());
+		assert_some_eq!(todo_file.get_selected_line(), &create_line("pick bbb c2"));
+		_ = todo_file.set_selected_line_index(0);
+		assert_none!(todo_file.get_selected_done_line_index());
+		assert_some_eq!(todo_file.get_selected_line(), &create_line("pick ccc c3"));
+	}
+
+	#[test]
+	fn selected_done_line_overflow() {
+		let (mut todo_file, _) = create_and_load_todo_file(&["pick ccc c3"]);
+		todo_file.set_done_lines(vec![create_line("pick aaa c1"), create_line("pick bbb c2")]);
+		assert_some_eq!(todo_file.set_selected_done_line_index(99), 1);
+	}
+
+	#[test]
+	fn selected_done_line_without_done_lines() {
+		let (mut todo_file, _) = create_and_load_todo_file(&["pick ccc c3"]);
+		assert_none!(todo_file.set_selected_done_line_index(0));
+		assert!(!todo_file.is_done_line_selected());
+	}
+
+	#[test]
+	fn set_lines() {
+		let (mut todo_file, _) = create_and_load_todo_file(&[]);
+		let old_version = todo_file.version;
 		todo_file.set_lines(vec![create_line("pick bbb comment")]);
 		assert_todo_lines!(todo_file, "pick bbb comment");
 		assert_ne!(todo_file.version(), &old_version);
@@ -569,6 +1564,21 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn write_file_with_exec_command_modified_line_in_sequencer() {
+		let temp_directory = Builder::new().tempdir().unwrap();
+		let sequencer_directory = temp_directory.path().join(SEQUENCER_DIRECTORY_NAME);
+		std::fs::create_dir_all(&sequencer_directory).unwrap();
+		let mut options = TodoFileOptions::new(10, "#");
+		options.line_changed_command("command");
+		let mut todo_file = TodoFile::new(sequencer_directory.join(SEQUENCER_TODO_FILE_NAME), options);
+		let mut line = create_line("pick bbb comment");
+		line.set_action(Action::Revert);
+		todo_file.set_lines(vec![line]);
+		todo_file.write_file().unwrap();
+		assert_read_todo_file!(todo_file.get_filepath(), "revert bbb comment");
+	}
+
 	#[test]
 	fn write_file_with_exec_command_modified_line_with_exec() {
 		let mut options = TodoFileOptions::new(10, "#");
@@ -585,6 +1595,254 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn write_file_with_exec_command_template_and_action_command() {
+		let mut options = TodoFileOptions::new(10, "#");
+		options.line_changed_command("audit {line_number} {original_action} {action} {hash}");
+		options.line_changed_action_command(Action::Drop, "dropped {subject}");
+		let (mut todo_file, _) = create_and_load_todo_file_with_options(&[], options);
+		let mut reworded = create_line("pick aaa comment");
+		reworded.set_action(Action::Reword);
+		let mut dropped = create_line("pick bbb other comment");
+		dropped.set_action(Action::Drop);
+		todo_file.set_lines(vec![create_line("pick ccc comment"), reworded, dropped]);
+		todo_file.write_file().unwrap();
+		assert_read_todo_file!(
+			todo_file.get_filepath(),
+			"pick ccc comment",
+			"reword aaa comment",
+			"exec audit '2' 'pick' 'reword' 'aaa'",
+			"drop bbb other comment",
+			"exec dropped 'other comment'"
+		);
+	}
+
+	fn create_todo_file_in_directory(lines: &[&str], options: TodoFileOptions) -> (TodoFile, TempDir) {
+		let temp_directory = Builder::new().tempdir().unwrap();
+		let mut todo_file = TodoFile::new(temp_directory.path().join(REBASE_TODO_FILE_NAME), options);
+		todo_file.set_lines(lines.iter().map(|line| create_line(line)).collect());
+		(todo_file, temp_directory)
+	}
+
+	#[test]
+	fn set_message() {
+		let (mut todo_file, _) = create_and_load_todo_file(&["reword aaa comment", "exec make"]);
+		assert!(todo_file.set_message(0, Some("subject\n\nbody")));
+		assert!(!todo_file.set_message(1, Some("subject")));
+		assert!(!todo_file.set_message(2, Some("subject")));
+		let line = todo_file.get_line(0).unwrap();
+		assert_eq!(line.get_action(), &Action::Pick);
+		assert_some_eq!(line.message(), "subject\n\nbody");
+		assert_some_eq!(todo_file.undo(), (0, 0));
+		let line = todo_file.get_line(0).unwrap();
+		assert_eq!(line.get_action(), &Action::Reword);
+		assert_none!(line.message());
+	}
+
+	#[test]
+	fn write_file_with_message() {
+		let mut options = TodoFileOptions::new(10, "#");
+		options.line_changed_command("command");
+		let (mut todo_file, temp_directory) =
+			create_todo_file_in_directory(&["pick aaa c1", "edit bbb c2", "fixup ccc c3"], options);
+		assert!(todo_file.set_message(0, Some("new \"subject\"\n\nbody")));
+		assert!(todo_file.set_message(1, Some("edited")));
+		assert!(todo_file.set_message(2, Some("ignored")));
+		todo_file.update_range(1, 1, &EditContext::new().action(Action::Pick));
+		todo_file.write_file().unwrap();
+		let message_directory = temp_directory.path().join(MESSAGE_DIRECTORY_NAME);
+		let amend_command = |name: &str| {
+			format!(
+				"exec git commit --amend --allow-empty --only -F \"{}\"",
+				message_directory.join(name).display()
+			)
+		};
+		let first_file = message_file_name("aaa", "new \"subject\"\n\nbody");
+		let second_file = message_file_name("bbb", "edited");
+		let (first_amend, second_amend) = (amend_command(&first_file), amend_command(&second_file));
+		assert_read_todo_file!(
+			todo_file.get_filepath(),
+			"pick aaa c1",
+			first_amend.as_str(),
+			"pick bbb c2",
+			second_amend.as_str(),
+			"exec command \"pick\" \"bbb\"",
+			"fixup ccc c3"
+		);
+		assert_eq!(
+			read_to_string(message_directory.join(first_file)).unwrap(),
+			"new \"subject\"\n\nbody"
+		);
+		assert_eq!(read_to_string(message_directory.join(second_file)).unwrap(), "edited");
+		assert_eq!(std::fs::read_dir(&message_directory).unwrap().count(), 2);
+	}
+
+	#[test]
+	fn write_file_removes_stale_messages() {
+		let (mut todo_file, temp_directory) =
+			create_todo_file_in_directory(&["pick aaa c1", "pick bbb c2"], TodoFileOptions::new(10, "#"));
+		let message_directory = temp_directory.path().join(MESSAGE_DIRECTORY_NAME);
+		std::fs::create_dir_all(&message_directory).unwrap();
+		let kept_path = message_directory.join(message_file_name("ccc", "kept"));
+		std::fs::write(&kept_path, "kept").unwrap();
+		std::fs::write(message_directory.join(message_file_name("ddd", "stale")), "stale").unwrap();
+		todo_file.add_line(
+			2,
+			Line::new_exec(format!("git commit --amend --allow-empty --only -F \"{}\"", kept_path.display()).as_str()),
+		);
+		assert!(todo_file.set_message(1, Some("new")));
+		todo_file.write_file().unwrap();
+		let mut file_names = std::fs::read_dir(&message_directory)
+			.unwrap()
+			.map(|entry| entry.unwrap().file_name().into_string().unwrap())
+			.collect::<Vec<String>>();
+		file_names.sort();
+		let mut expected = vec![message_file_name("bbb", "new"), message_file_name("ccc", "kept")];
+		expected.sort();
+		assert_eq!(file_names, expected);
+	}
+
+	#[test]
+	fn write_file_removes_message_directory_without_messages() {
+		let (mut todo_file, temp_directory) =
+			create_todo_file_in_directory(&["pick aaa c1"], TodoFileOptions::new(10, "#"));
+		let message_directory = temp_directory.path().join(MESSAGE_DIRECTORY_NAME);
+		assert!(todo_file.set_message(0, Some("subject")));
+		todo_file.write_file().unwrap();
+		assert!(message_directory.is_dir());
+		assert!(todo_file.set_message(0, None));
+		todo_file.write_file().unwrap();
+		assert!(!message_directory.exists());
+	}
+
+	#[test]
+	fn message_file_name_differs_by_message() {
+		assert_eq!(message_file_name("aaa", "subject"), message_file_name("aaa", "subject"));
+		assert_ne!(message_file_name("aaa", "subject"), message_file_name("aaa", "other"));
+		assert!(message_file_name("aaa", "subject").starts_with("message-aaa-"));
+	}
+
+	#[test]
+	fn write_file_with_message_write_error() {
+		let (mut todo_file, temp_directory) =
+			create_todo_file_in_directory(&["pick aaa c1"], TodoFileOptions::new(10, "#"));
+		assert!(todo_file.set_message(0, Some("subject")));
+		let message_directory = temp_directory.path().join(MESSAGE_DIRECTORY_NAME);
+		std::fs::write(&message_directory, "").unwrap();
+		assert_err_eq!(todo_file.write_file(), IoError::FileWrite {
+			file: message_directory.join(message_file_name("aaa", "subject")),
+			cause: errors::FileWriteErrorCause::from(std::io::Error::from(std::io::ErrorKind::AlreadyExists)),
+		});
+	}
+
+	#[test]
+	fn write_file_for_edit_without_message() {
+		let (mut todo_file, temp_directory) =
+			create_todo_file_in_directory(&["pick aaa c1"], TodoFileOptions::new(10, "#"));
+		assert!(todo_file.set_message(0, Some("subject")));
+		todo_file.write_file_for_edit().unwrap();
+		assert_read_todo_file!(todo_file.get_filepath(), "pick aaa c1");
+		assert!(!temp_directory.path().join(MESSAGE_DIRECTORY_NAME).exists());
+	}
+
+	#[test]
+	fn reload_file_keeps_message() {
+		let (mut todo_file, _temp_directory) =
+			create_todo_file_in_directory(&["pick aaa c1", "pick bbb c2"], TodoFileOptions::new(10, "#"));
+		assert!(todo_file.set_message(0, Some("subject")));
+		todo_file.write_file_for_edit().unwrap();
+		edit_todo_file(&todo_file, &["pick bbb c2", "pick aaa c1"]);
+		todo_file.reload_file().unwrap();
+		assert_some_eq!(todo_file.get_line(1).unwrap().message(), "subject");
+		assert_none!(todo_file.get_line(0).unwrap().message());
+	}
+
+	#[test]
+	fn update_metadata_changes() {
+		let (mut todo_file, _) =
+			create_and_load_todo_file(&["pick aaa c1", "exec make", "fixup bbb c2", "reword ccc c3", "edit ddd c4"]);
+		assert_eq!(
+			todo_file.update_metadata_changes(4, 0, |changes| changes.add_trailer("Signed-off-by: Jane")),
+			3
+		);
+		assert_eq!(
+			todo_file
+				.lines_iter()
+				.map(|line| line.metadata_changes().trailers().len())
+				.collect::<Vec<usize>>(),
+			vec![1, 0, 0, 1, 1]
+		);
+		assert_some_eq!(todo_file.undo(), (0, 4));
+		assert!(todo_file.lines_iter().all(|line| line.metadata_changes().is_empty()));
+	}
+
+	#[test]
+	fn update_metadata_changes_without_commits() {
+		let (mut todo_file, _) = create_and_load_todo_file(&["exec make", "drop aaa c1"]);
+		assert_eq!(
+			todo_file.update_metadata_changes(0, 1, |changes| changes.set_author(Some("Jane <jane@example.com>"))),
+			0
+		);
+		assert_none!(todo_file.undo());
+	}
+
+	#[test]
+	fn write_file_with_metadata_changes() {
+		let mut options = TodoFileOptions::new(10, "#");
+		options.line_changed_command("command");
+		let (mut todo_file, temp_directory) =
+			create_todo_file_in_directory(&["pick aaa c1", "pick bbb c2", "pick ccc c3"], options);
+		_ = todo_file.update_metadata_changes(0, 1, |changes| changes.set_author(Some("Jane <jane@example.com>")));
+		_ = todo_file.update_metadata_changes(1, 1, |changes| changes.set_committer_date(Some("yesterday")));
+		assert!(todo_file.set_message(1, Some("subject")));
+		todo_file.update_range(2, 2, &EditContext::new().action(Action::Drop));
+		_ = todo_file.update_metadata_changes(2, 2, |changes| changes.add_trailer("Reviewed-by: John"));
+		todo_file.write_file().unwrap();
+		let message_amend = format!(
+			"exec git commit --amend --allow-empty --only -F \"{}\"",
+			temp_directory
+				.path()
+				.join(MESSAGE_DIRECTORY_NAME)
+				.join(message_file_name("bbb", "subject"))
+				.display()
+		);
+		assert_read_todo_file!(
+			todo_file.get_filepath(),
+			"pick aaa c1",
+			"exec git commit --amend --no-edit --allow-empty --only --author=\"Jane <jane@example.com>\"",
+			"pick bbb c2",
+			message_amend.as_str(),
+			"exec GIT_COMMITTER_DATE=\"yesterday\" git commit --amend --no-edit --allow-empty --only \
+			 --author=\"Jane <jane@example.com>\"",
+			"drop ccc c3",
+			"exec command \"drop\" \"ccc\""
+		);
+	}
+
+	#[test]
+	fn write_file_with_metadata_changes_after_squash() {
+		let (mut todo_file, _) =
+			create_and_load_todo_file(&["pick aaa c1", "squash bbb c2", "fixup ccc c3", "pick ddd c4"]);
+		_ = todo_file.update_metadata_changes(0, 0, |changes| changes.add_trailer("Reviewed-by: John"));
+		todo_file.write_file().unwrap();
+		assert_read_todo_file!(
+			todo_file.get_filepath(),
+			"pick aaa c1",
+			"squash bbb c2",
+			"fixup ccc c3",
+			"exec git commit --amend --no-edit --allow-empty --only --trailer \"Reviewed-by: John\"",
+			"pick ddd c4"
+		);
+	}
+
+	#[test]
+	fn write_file_for_edit_without_metadata_changes() {
+		let (mut todo_file, _) = create_and_load_todo_file(&["pick aaa c1"]);
+		_ = todo_file.update_metadata_changes(0, 0, |changes| changes.add_trailer("Reviewed-by: John"));
+		todo_file.write_file_for_edit().unwrap();
+		assert_read_todo_file!(todo_file.get_filepath(), "pick aaa c1");
+	}
+
 	#[test]
 	fn write_file_with_exec_command_modified_line_with_break() {
 		let mut options = TodoFileOptions::new(10, "#");
@@ -633,6 +1891,53 @@ mod tests {
 		assert_ne!(todo_file.version(), &old_version);
 	}
 
+	#[test]
+	fn labels_before() {
+		let (todo_file, _) = create_and_load_todo_file(&[
+			"label onto",
+			"pick aaa comment",
+			"label feature",
+			"label onto",
+			"reset onto",
+			"label later",
+		]);
+		assert_eq!(todo_file.labels_before(5), vec!["onto", "feature"]);
+		assert_eq!(todo_file.labels_before(100), vec!["onto", "feature", "later"]);
+		assert!(todo_file.labels_before(0).is_empty());
+	}
+
+	#[test]
+	fn add_lines() {
+		let (mut todo_file, _) = create_and_load_todo_file(&["pick aaa comment", "drop bbb comment"]);
+		let old_version = *todo_file.version();
+		todo_file.add_lines(1, vec![create_line("pick ccc comment"), create_line("pick ddd comment")]);
+		assert_todo_lines!(
+			todo_file,
+			"pick aaa comment",
+			"pick ccc comment",
+			"pick ddd comment",
+			"drop bbb comment"
+		);
+		assert_ne!(todo_file.version(), &old_version);
+	}
+
+	#[test]
+	fn add_lines_empty() {
+		let (mut todo_file, _) = create_and_load_todo_file(&["pick aaa comment"]);
+		let old_version = *todo_file.version();
+		todo_file.add_lines(1, vec![]);
+		assert_todo_lines!(todo_file, "pick aaa comment");
+		assert_eq!(todo_file.version(), &old_version);
+	}
+
+	#[test]
+	fn add_lines_record_history() {
+		let (mut todo_file, _) = create_and_load_todo_file(&["pick aaa comment"]);
+		todo_file.add_lines(1, vec![create_line("pick ccc comment"), create_line("pick ddd comment")]);
+		let _undo_result = todo_file.undo();
+		assert_todo_lines!(todo_file, "pick aaa comment");
+	}
+
 	#[test]
 	fn add_line_record_history() {
 		let (mut todo_file, _) = create_and_load_todo_file(&["pick aaa comment"]);
@@ -689,6 +1994,348 @@ mod tests {
 		assert_todo_lines!(todo_file, "pick aaa comment", "edit ccc comment");
 	}
 
+	#[test]
+	fn update_lines() {
+		let (mut todo_file, _) =
+			create_and_load_todo_file(&["pick aaa comment", "pick bbb comment", "pick ccc comment"]);
+		let old_version = *todo_file.version();
+		todo_file.update_lines(&[0, 2, 10], &EditContext::new().action(Action::Drop));
+		assert_todo_lines!(todo_file, "drop aaa comment", "pick bbb comment", "drop ccc comment");
+		assert_ne!(todo_file.version(), &old_version);
+	}
+
+	#[test]
+	fn update_lines_is_single_undo() {
+		let (mut todo_file, _) =
+			create_and_load_todo_file(&["pick aaa comment", "pick bbb comment", "pick ccc comment"]);
+		todo_file.update_lines(&[2, 0], &EditContext::new().action(Action::Drop));
+		assert_some_eq!(todo_file.undo(), (0, 2));
+		assert_todo_lines!(todo_file, "pick aaa comment", "pick bbb comment", "pick ccc comment");
+	}
+
+	#[test]
+	fn update_lines_without_indexes() {
+		let (mut todo_file, _) = create_and_load_todo_file(&["pick aaa comment"]);
+		let old_version = *todo_file.version();
+		todo_file.update_lines(&[5], &EditContext::new().action(Action::Drop));
+		assert_eq!(todo_file.version(), &old_version);
+	}
+
+	#[test]
+	fn replace_range() {
+		let (mut todo_file, _) =
+			create_and_load_todo_file(&["pick aaa comment", "pick bbb comment", "pick ccc comment"]);
+		todo_file.replace_range(2, 0, vec![Line::parse("pick bbb comment").unwrap()]);
+		assert_todo_lines!(todo_file, "pick bbb comment");
+		assert_some_eq!(todo_file.undo(), (0, 2));
+		assert_todo_lines!(todo_file, "pick aaa comment", "pick bbb comment", "pick ccc comment");
+		assert_some_eq!(todo_file.redo(), (0, 0));
+		assert_todo_lines!(todo_file, "pick bbb comment");
+	}
+
+	#[test]
+	fn replace_range_without_lines() {
+		let (mut todo_file, _) = create_and_load_todo_file(&["pick aaa comment"]);
+		todo_file.replace_range(0, 0, vec![]);
+		assert_todo_lines!(todo_file, "pick aaa comment");
+	}
+
+	#[test]
+	fn remove_lines_at() {
+		let (mut todo_file, _) = create_and_load_todo_file(&[
+			"pick aaa comment",
+			"pick bbb comment",
+			"pick ccc comment",
+			"pick ddd comment",
+		]);
+		todo_file.remove_lines_at(&[3, 0, 10]);
+		assert_todo_lines!(todo_file, "pick bbb comment", "pick ccc comment");
+		assert_some_eq!(todo_file.undo(), (0, 3));
+		assert_todo_lines!(
+			todo_file,
+			"pick aaa comment",
+			"pick bbb comment",
+			"pick ccc comment",
+			"pick ddd comment"
+		);
+	}
+
+	#[test]
+	fn remove_lines_at_contiguous() {
+		let (mut todo_file, _) =
+			create_and_load_todo_file(&["pick aaa comment", "pick bbb comment", "pick ccc comment"]);
+		todo_file.remove_lines_at(&[1, 2]);
+		assert_todo_lines!(todo_file, "pick aaa comment");
+		_ = todo_file.undo();
+		assert_todo_lines!(todo_file, "pick aaa comment", "pick bbb comment", "pick ccc comment");
+	}
+
+	#[test]
+	fn remove_lines_at_without_indexes() {
+		let (mut todo_file, _) = create_and_load_todo_file(&["pick aaa comment"]);
+		let old_version = *todo_file.version();
+		todo_file.remove_lines_at(&[]);
+		assert_eq!(todo_file.version(), &old_version);
+	}
+
+	#[test]
+	fn gather_lines_after_index() {
+		let (mut todo_file, _) = create_and_load_todo_file(&[
+			"pick aaa comment",
+			"fixup bbb comment",
+			"pick ccc comment",
+			"fixup ddd comment",
+			"pick eee comment",
+		]);
+		assert_some_eq!(todo_file.gather_lines(&[3, 1], 4), (3, 4));
+		assert_todo_lines!(
+			todo_file,
+			"pick aaa comment",
+			"pick ccc comment",
+			"pick eee comment",
+			"fixup bbb comment",
+			"fixup ddd comment"
+		);
+		assert_some_eq!(todo_file.undo(), (1, 4));
+		assert_todo_lines!(
+			todo_file,
+			"pick aaa comment",
+			"fixup bbb comment",
+			"pick ccc comment",
+			"fixup ddd comment",
+			"pick eee comment"
+		);
+	}
+
+	#[test]
+	fn gather_lines_before_moved_lines() {
+		let (mut todo_file, _) = create_and_load_todo_file(&[
+			"pick aaa comment",
+			"pick bbb comment",
+			"fixup ccc comment",
+			"pick ddd comment",
+			"fixup eee comment",
+		]);
+		assert_some_eq!(todo_file.gather_lines(&[2, 4], 0), (1, 2));
+		assert_todo_lines!(
+			todo_file,
+			"pick aaa comment",
+			"fixup ccc comment",
+			"fixup eee comment",
+			"pick bbb comment",
+			"pick ddd comment"
+		);
+	}
+
+	#[test]
+	fn gather_lines_at_moved_line() {
+		let (mut todo_file, _) = create_and_load_todo_file(&[
+			"pick aaa comment",
+			"pick bbb comment",
+			"pick ccc comment",
+			"pick ddd comment",
+		]);
+		assert_some_eq!(todo_file.gather_lines(&[0, 2], 2), (1, 2));
+		assert_todo_lines!(
+			todo_file,
+			"pick bbb comment",
+			"pick aaa comment",
+			"pick ccc comment",
+			"pick ddd comment"
+		);
+	}
+
+	#[test]
+	fn gather_lines_unchanged() {
+		let (mut todo_file, _) =
+			create_and_load_todo_file(&["pick aaa comment", "pick bbb comment", "pick ccc comment"]);
+		let old_version = *todo_file.version();
+		assert_some_eq!(todo_file.gather_lines(&[1, 2], 0), (1, 2));
+		assert_eq!(todo_file.version(), &old_version);
+	}
+
+	#[test]
+	fn gather_lines_without_indexes() {
+		let (mut todo_file, _) = create_and_load_todo_file(&["pick aaa comment"]);
+		assert_none!(todo_file.gather_lines(&[4], 0));
+	}
+
+	#[test]
+	fn add_line_after_lines() {
+		let (mut todo_file, _) = create_and_load_todo_file(&[
+			"pick aaa comment",
+			"pick bbb comment",
+			"pick ccc comment",
+			"pick ddd comment",
+		]);
+		assert_eq!(todo_file.add_line_after_lines(&[0, 2, 9], &create_line("exec make")), 2);
+		assert_todo_lines!(
+			todo_file,
+			"pick aaa comment",
+			"exec make",
+			"pick bbb comment",
+			"pick ccc comment",
+			"exec make",
+			"pick ddd comment"
+		);
+		assert_some_eq!(todo_file.undo(), (0, 2));
+		assert_todo_lines!(
+			todo_file,
+			"pick aaa comment",
+			"pick bbb comment",
+			"pick ccc comment",
+			"pick ddd comment"
+		);
+	}
+
+	#[test]
+	fn add_line_after_lines_without_indexes() {
+		let (mut todo_file, _) = create_and_load_todo_file(&["pick aaa comment"]);
+		let old_version = *todo_file.version();
+		assert_eq!(todo_file.add_line_after_lines(&[4], &create_line("exec make")), 0);
+		assert_todo_lines!(todo_file, "pick aaa comment");
+		assert_eq!(todo_file.version(), &old_version);
+	}
+
+	#[test]
+	fn add_lines_after() {
+		let (mut todo_file, _) =
+			create_and_load_todo_file(&["pick aaa comment", "pick bbb comment", "pick ccc comment"]);
+		assert_eq!(
+			todo_file.add_lines_after(vec![
+				(2, create_line("update-ref refs/heads/c")),
+				(0, create_line("update-ref refs/heads/a")),
+				(0, create_line("update-ref refs/heads/b")),
+				(9, create_line("update-ref refs/heads/d")),
+			]),
+			3
+		);
+		assert_todo_lines!(
+			todo_file,
+			"pick aaa comment",
+			"update-ref refs/heads/a",
+			"update-ref refs/heads/b",
+			"pick bbb comment",
+			"pick ccc comment",
+			"update-ref refs/heads/c"
+		);
+		assert_some_eq!(todo_file.undo(), (0, 2));
+		assert_todo_lines!(todo_file, "pick aaa comment", "pick bbb comment", "pick ccc comment");
+	}
+
+	#[test]
+	fn add_lines_after_without_lines() {
+		let (mut todo_file, _) = create_and_load_todo_file(&["pick aaa comment"]);
+		let old_version = *todo_file.version();
+		assert_eq!(todo_file.add_lines_after(vec![(4, create_line("exec make"))]), 0);
+		assert_todo_lines!(todo_file, "pick aaa comment");
+		assert_eq!(todo_file.version(), &old_version);
+	}
+
+	#[test]
+	fn sort_lines_by_key() {
+		let (mut todo_file, _) = create_and_load_todo_file(&[
+			"pick ccc comment",
+			"break",
+			"pick aaa comment",
+			"pick bbb comment",
+		]);
+		assert!(todo_file.sort_lines_by_key(&[0, 1, 2, 3], |line| {
+			(!line.get_hash().is_empty()).then(|| String::from(line.get_hash()))
+		}));
+		assert_todo_lines!(todo_file, "pick aaa comment", "break", "pick bbb comment", "pick ccc comment");
+		assert_some_eq!(todo_file.undo(), (0, 3));
+		assert_todo_lines!(todo_file, "pick ccc comment", "break", "pick aaa comment", "pick bbb comment");
+	}
+
+	#[test]
+	fn sort_lines_by_key_stable() {
+		let (mut todo_file, _) = create_and_load_todo_file(&["pick bbb b", "pick aaa b", "pick ccc a"]);
+		assert!(todo_file.sort_lines_by_key(&[0, 1, 2], |line| Some(String::from(line.get_content()))));
+		assert_todo_lines!(todo_file, "pick ccc a", "pick bbb b", "pick aaa b");
+	}
+
+	#[test]
+	fn sort_lines_by_key_already_sorted() {
+		let (mut todo_file, _) = create_and_load_todo_file(&["pick aaa comment", "pick bbb comment"]);
+		let old_version = *todo_file.version();
+		assert!(!todo_file.sort_lines_by_key(&[0, 1], |line| Some(String::from(line.get_hash()))));
+		assert_eq!(todo_file.version(), &old_version);
+		assert_none!(todo_file.undo());
+	}
+
+	#[test]
+	fn reverse_lines() {
+		let (mut todo_file, _) = create_and_load_todo_file(&[
+			"pick aaa comment",
+			"pick bbb comment",
+			"pick ccc comment",
+			"pick ddd comment",
+		]);
+		assert!(todo_file.reverse_lines(&[1, 2, 3]));
+		assert_todo_lines!(
+			todo_file,
+			"pick aaa comment",
+			"pick ddd comment",
+			"pick ccc comment",
+			"pick bbb comment"
+		);
+		assert_some_eq!(todo_file.undo(), (1, 3));
+		assert_todo_lines!(
+			todo_file,
+			"pick aaa comment",
+			"pick bbb comment",
+			"pick ccc comment",
+			"pick ddd comment"
+		);
+	}
+
+	#[test]
+	fn reverse_lines_single_line() {
+		let (mut todo_file, _) = create_and_load_todo_file(&["pick aaa comment", "pick bbb comment"]);
+		assert!(!todo_file.reverse_lines(&[1]));
+	}
+
+	#[test]
+	fn restore_lines_order() {
+		let (mut todo_file, _) = create_and_load_todo_file(&[
+			"pick aaa comment",
+			"pick bbb comment",
+			"pick ccc comment",
+			"pick ddd comment",
+		]);
+		assert!(todo_file.reverse_lines(&[0, 1, 2, 3]));
+		todo_file.update_range(1, 1, &EditContext::new().action(Action::Fixup));
+		todo_file.add_line(2, Line::new_break());
+		assert!(todo_file.restore_lines_order(&[0, 1, 2, 3, 4]));
+		assert_todo_lines!(
+			todo_file,
+			"pick aaa comment",
+			"pick bbb comment",
+			"break",
+			"fixup ccc comment",
+			"pick ddd comment"
+		);
+		_ = todo_file.undo();
+		assert_todo_lines!(
+			todo_file,
+			"pick ddd comment",
+			"fixup ccc comment",
+			"break",
+			"pick bbb comment",
+			"pick aaa comment"
+		);
+	}
+
+	#[test]
+	fn restore_lines_order_duplicate_lines() {
+		let (mut todo_file, _) = create_and_load_todo_file(&["break", "pick aaa comment", "break"]);
+		assert!(!todo_file.restore_lines_order(&[0, 1, 2]));
+		assert!(todo_file.reverse_lines(&[0, 1]));
+		assert!(todo_file.restore_lines_order(&[0, 1, 2]));
+		assert_todo_lines!(todo_file, "break", "pick aaa comment", "break");
+	}
+
 	#[test]
 	fn update_range_full_set_action() {
 		let (mut todo_file, _) =
@@ -818,6 +2465,39 @@ mod tests {
 		assert_some_eq!(todo_file.redo(), (0, 1));
 	}
 
+	#[test]
+	fn restore_history() {
+		let (mut todo_file, _) = create_and_load_todo_file_with_options(
+			&["pick aaa comment", "drop bbb comment", "edit ccc comment"],
+			TodoFileOptions::new(10, "#"),
+		);
+		todo_file.update_range(0, 0, &EditContext::new().action(Action::Drop));
+		todo_file.remove_lines(1, 2);
+		let (entries, position) = todo_file.history_entries();
+		assert_eq!(
+			entries.iter().map(HistoryEntry::description).collect::<Vec<&str>>(),
+			vec!["Loaded the todo file", "Changed aaa to drop", "Removed 2 lines"]
+		);
+		assert_eq!(position, 2);
+		assert_eq!(
+			todo_file
+				.history_preview(0)
+				.iter()
+				.map(Line::to_text)
+				.collect::<Vec<String>>(),
+			vec!["pick aaa comment", "drop bbb comment", "edit ccc comment"]
+		);
+		let old_version = *todo_file.version();
+		assert!(todo_file.restore_history(0));
+		assert_todo_lines!(todo_file, "pick aaa comment", "drop bbb comment", "edit ccc comment");
+		assert_ne!(todo_file.version(), &old_version);
+		assert!(!todo_file.restore_history(0));
+		assert!(todo_file.restore_history(1));
+		assert_todo_lines!(todo_file, "drop aaa comment", "drop bbb comment", "edit ccc comment");
+		assert_some_eq!(todo_file.undo(), (0, 0));
+		assert_todo_lines!(todo_file, "pick aaa comment", "drop bbb comment", "edit ccc comment");
+	}
+
 	#[test]
 	fn swap_up() {
 		let (mut todo_file, _) =
@@ -942,6 +2622,86 @@ mod tests {
 		assert_todo_lines!(todo_file, "pick aaa comment", "pick bbb comment", "pick ccc comment");
 	}
 
+	#[rstest]
+	#[case::down(0, 1, 1, &["pick ccc comment", "pick aaa comment", "pick bbb comment", "pick ddd comment"])]
+	#[case::up(2, 3, 0, &["pick ccc comment", "pick ddd comment", "pick aaa comment", "pick bbb comment"])]
+	#[case::reverse_index(3, 2, 1, &["pick aaa comment", "pick ccc comment", "pick ddd comment", "pick bbb comment"])]
+	#[case::single_line(0, 0, 3, &["pick bbb comment", "pick ccc comment", "pick ddd comment", "pick aaa comment"])]
+	#[case::overflow(0, 1, 10, &["pick ccc comment", "pick ddd comment", "pick aaa comment", "pick bbb comment"])]
+	fn move_range(
+		#[case] start_index: usize,
+		#[case] end_index: usize,
+		#[case] destination_index: usize,
+		#[case] expected: &[&str],
+	) {
+		let (mut todo_file, _) = create_and_load_todo_file(&[
+			"pick aaa comment",
+			"pick bbb comment",
+			"pick ccc comment",
+			"pick ddd comment",
+		]);
+		let old_version = *todo_file.version();
+		assert!(todo_file.move_range(start_index, end_index, destination_index));
+		assert_eq!(
+			todo_file.get_lines_owned(),
+			expected.iter().map(|l| create_line(l)).collect::<Vec<Line>>()
+		);
+		assert_ne!(todo_file.version(), &old_version);
+	}
+
+	#[test]
+	fn move_range_records_single_history_item() {
+		let (mut todo_file, _) = create_and_load_todo_file(&[
+			"pick aaa comment",
+			"pick bbb comment",
+			"pick ccc comment",
+			"pick ddd comment",
+		]);
+		assert!(todo_file.move_range(0, 1, 2));
+		assert_some_eq!(todo_file.undo(), (0, 3));
+		assert_todo_lines!(
+			todo_file,
+			"pick aaa comment",
+			"pick bbb comment",
+			"pick ccc comment",
+			"pick ddd comment"
+		);
+		assert_some_eq!(todo_file.redo(), (0, 3));
+		assert_todo_lines!(
+			todo_file,
+			"pick ccc comment",
+			"pick ddd comment",
+			"pick aaa comment",
+			"pick bbb comment"
+		);
+	}
+
+	#[rstest]
+	#[case::same_position(1, 2, 1)]
+	#[case::out_of_range(3, 4, 0)]
+	fn move_range_no_change(#[case] start_index: usize, #[case] end_index: usize, #[case] destination_index: usize) {
+		let (mut todo_file, _) = create_and_load_todo_file(&[
+			"pick aaa comment",
+			"pick bbb comment",
+			"pick ccc comment",
+			"pick ddd comment",
+		]);
+		assert!(!todo_file.move_range(start_index, end_index, destination_index));
+		assert_todo_lines!(
+			todo_file,
+			"pick aaa comment",
+			"pick bbb comment",
+			"pick ccc comment",
+			"pick ddd comment"
+		);
+	}
+
+	#[test]
+	fn move_range_empty() {
+		let (mut todo_file, _) = create_and_load_todo_file(&[]);
+		assert!(!todo_file.move_range(0, 0, 1));
+	}
+
 	#[test]
 	fn selected_line_index() {
 		let (mut todo_file, _) = create_and_load_todo_file(&["exec foo", "exec bar", "exec foobar"]);
@@ -20,6 +20,8 @@ pub(crate) enum Action {
 	Noop,
 	/// A pick action.
 	Pick,
+	/// A revert action.
+	Revert,
 	/// A reword action.
 	Reword,
 	/// A squash action.
@@ -49,6 +51,7 @@ impl Action {
 			Self::Noop => "n",
 			Self::Pick => "p",
 			Self::Reset => "t",
+			Self::Revert => "v",
 			Self::Reword => "r",
 			Self::Squash => "s",
 			Self::UpdateRef => "u",
@@ -60,7 +63,13 @@ impl Action {
 	pub(crate) const fn is_static(self) -> bool {
 		match self {
 			Self::Break | Self::Exec | Self::Noop | Self::Reset | Self::Label | Self::Merge | Self::UpdateRef => true,
-			Self::Drop | Self::Edit | Self::Fixup | Self::Pick | Self::Reword | Self::Squash => false,
+			Self::Drop
+			| Self::Edit
+			| Self::Fixup
+			| Self::Pick
+			| Self::Revert
+			| Self::Reword
+			| Self::Squash => false,
 		}
 	}
 }
@@ -78,6 +87,7 @@ impl Display for Action {
 			Self::Noop => "noop",
 			Self::Pick => "pick",
 			Self::Reset => "reset",
+			Self::Revert => "revert",
 			Self::Reword => "reword",
 			Self::Squash => "squash",
 			Self::UpdateRef => "update-ref",
@@ -97,6 +107,8 @@ impl TryFrom<&str> for Action {
 			"fixup" | "f" => Ok(Self::Fixup),
 			"noop" | "n" => Ok(Self::Noop),
 			"pick" | "p" => Ok(Self::Pick),
+			// git does not have an abbreviation for revert
+			"revert" => Ok(Self::Revert),
 			"reword" | "r" => Ok(Self::Reword),
 			"squash" | "s" => Ok(Self::Squash),
 			"label" | "l" => Ok(Self::Label),
@@ -123,6 +135,7 @@ mod tests {
 	#[case::fixup(Action::Fixup, "fixup")]
 	#[case::noop(Action::Noop, "noop")]
 	#[case::pick(Action::Pick, "pick")]
+	#[case::revert(Action::Revert, "revert")]
 	#[case::reword(Action::Reword, "reword")]
 	#[case::squash(Action::Squash, "squash")]
 	#[case::label(Action::Label, "label")]
@@ -148,6 +161,7 @@ mod tests {
 	#[case::noop("noop", Action::Noop)]
 	#[case::p("p", Action::Pick)]
 	#[case::pick("pick", Action::Pick)]
+	#[case::revert("revert", Action::Revert)]
 	#[case::r("r", Action::Reword)]
 	#[case::reword("reword", Action::Reword)]
 	#[case::s("s", Action::Squash)]
@@ -164,6 +178,11 @@ mod tests {
 		assert_ok_eq!(Action::try_from(action_str), expected);
 	}
 
+	#[test]
+	fn action_try_from_revert_abbreviation() {
+		assert_err_eq!(Action::try_from("v"), ParseError::InvalidAction(String::from("v")));
+	}
+
 	#[test]
 	fn action_try_from_invalid() {
 		let invalid = String::from("invalid");
@@ -178,6 +197,7 @@ mod tests {
 	#[case::f(Action::Fixup, "f")]
 	#[case::n(Action::Noop, "n")]
 	#[case::p(Action::Pick, "p")]
+	#[case::v(Action::Revert, "v")]
 	#[case::r(Action::Reword, "r")]
 	#[case::s(Action::Squash, "s")]
 	#[case::l(Action::Label, "l")]
@@ -196,6 +216,7 @@ mod tests {
 	#[case::fixup(Action::Fixup, false)]
 	#[case::noop(Action::Noop, true)]
 	#[case::pick(Action::Pick, false)]
+	#[case::revert(Action::Revert, false)]
 	#[case::reword(Action::Reword, false)]
 	#[case::squash(Action::Squash, false)]
 	#[case::label(Action::Label, true)]
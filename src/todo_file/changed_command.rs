@@ -0,0 +1,192 @@
+use crate::todo_file::{Action, Line};
+
+/// The placeholders that are replaced in a templated changed line command.
+const PLACEHOLDERS: [&str; 6] = [
+	"{action}",
+	"{original_action}",
+	"{hash}",
+	"{subject}",
+	"{original_content}",
+	"{line_number}",
+];
+
+/// Escape a value, so it can be placed within double quotes in a shell command.
+pub(super) fn escape(value: &str) -> String {
+	let mut escaped = String::with_capacity(value.len());
+	for c in value.chars() {
+		if matches!(c, '\\' | '"' | '$' | '`') {
+			escaped.push('\\');
+		}
+		escaped.push(c);
+	}
+	escaped
+}
+
+/// Quote a value as a single shell word, so it is passed to the command as is, whatever characters it contains.
+fn quote(value: &str) -> String {
+	format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// Replace the placeholders of a template with the quoted values, in a single pass over the template, so placeholders
+/// within the values are never replaced.
+fn fill_template(template: &str, values: &[String; PLACEHOLDERS.len()]) -> String {
+	let mut filled = String::with_capacity(template.len());
+	let mut rest = template;
+	while let Some(start) = rest.find('{') {
+		let (before, from_brace) = rest.split_at(start);
+		filled.push_str(before);
+		if let Some((after, value)) = PLACEHOLDERS
+			.iter()
+			.zip(values)
+			.find_map(|(placeholder, value)| from_brace.strip_prefix(placeholder).map(|after| (after, value)))
+		{
+			filled.push_str(quote(value).as_str());
+			rest = after;
+		}
+		else {
+			filled.push('{');
+			rest = from_brace.strip_prefix('{').unwrap_or_default();
+		}
+	}
+	filled.push_str(rest);
+	filled
+}
+
+/// Create the `exec` line to follow a modified line, or `None` when no line should follow the modified line.
+///
+/// A command that contains any of the placeholders is used as a template, with each placeholder replaced by the
+/// value for the line, quoted as a single shell word. Otherwise the action and commit hash, or the action and the
+/// original and new content, are appended to the command.
+pub(super) fn changed_command_line(command: &str, line: &Line, line_number: usize) -> Option<String> {
+	let action = *line.get_action();
+	let has_hash = match action {
+		Action::Break | Action::Noop => return None,
+		Action::Drop
+		| Action::Fixup
+		| Action::Edit
+		| Action::Pick
+		| Action::Revert
+		| Action::Reword
+		| Action::Squash => true,
+		Action::Exec | Action::Label | Action::Reset | Action::Merge | Action::UpdateRef => false,
+	};
+	let original = line.original().unwrap_or(line);
+
+	if PLACEHOLDERS.iter().any(|placeholder| command.contains(placeholder)) {
+		let subject = if has_hash { line.get_content() } else { "" };
+		let exec_command = fill_template(command, &[
+			action.to_string(),
+			original.get_action().to_string(),
+			String::from(line.get_hash()),
+			String::from(subject),
+			String::from(original.get_content()),
+			line_number.to_string(),
+		]);
+		return Some(format!("exec {exec_command}"));
+	}
+
+	Some(
+		if has_hash {
+			format!("exec {command} \"{action}\" \"{}\"", line.get_hash())
+		}
+		else {
+			format!(
+				"exec {command} \"{action}\" \"{}\" \"{}\"",
+				original.get_content(),
+				line.get_content()
+			)
+		},
+	)
+}
+
+#[cfg(test)]
+mod tests {
+	use claims::{assert_none, assert_some_eq};
+	use rstest::rstest;
+
+	use super::*;
+
+	fn create_modified_line(line: &str, action: Action, content: Option<&str>) -> Line {
+		let mut parsed = Line::parse(line).unwrap();
+		parsed.set_action(action);
+		if let Some(new_content) = content {
+			parsed.edit_content(new_content);
+		}
+		parsed
+	}
+
+	#[rstest]
+	#[case::break_line("break")]
+	#[case::noop("noop")]
+	fn no_command(#[case] line: &str) {
+		assert_none!(changed_command_line("command", &Line::parse(line).unwrap(), 1));
+	}
+
+	#[test]
+	fn legacy_with_hash() {
+		let line = create_modified_line("pick aaa comment", Action::Fixup, None);
+		assert_some_eq!(
+			changed_command_line("command", &line, 1),
+			"exec command \"fixup\" \"aaa\""
+		);
+	}
+
+	#[test]
+	fn legacy_with_reference() {
+		let line = create_modified_line("label old", Action::Label, Some("new"));
+		assert_some_eq!(
+			changed_command_line("command", &line, 1),
+			"exec command \"label\" \"old\" \"new\""
+		);
+	}
+
+	#[test]
+	fn template_with_hash() {
+		let line = create_modified_line("pick aaa comment", Action::Reword, None);
+		assert_some_eq!(
+			changed_command_line(
+				"audit {line_number} {original_action} {action} {hash} {subject} {original_content}",
+				&line,
+				3
+			),
+			"exec audit '3' 'pick' 'reword' 'aaa' 'comment' 'comment'"
+		);
+	}
+
+	#[test]
+	fn template_with_reference() {
+		let line = create_modified_line("update-ref refs/heads/old", Action::UpdateRef, Some("refs/heads/new"));
+		assert_some_eq!(
+			changed_command_line("audit {action} {original_content} {subject} {hash}", &line, 1),
+			"exec audit 'update-ref' 'refs/heads/old' '' ''"
+		);
+	}
+
+	#[test]
+	fn template_unmodified_line() {
+		let line = Line::parse("exec make").unwrap();
+		assert_some_eq!(
+			changed_command_line("audit {original_action} {original_content}", &line, 1),
+			"exec audit 'exec' 'make'"
+		);
+	}
+
+	#[test]
+	fn template_keeps_other_braces() {
+		let line = create_modified_line("pick aaa comment", Action::Drop, None);
+		assert_some_eq!(
+			changed_command_line("audit {hash} {unknown} {", &line, 1),
+			"exec audit 'aaa' {unknown} {"
+		);
+	}
+
+	#[rstest]
+	#[case::placeholder("pick aaa fix {hash} {line_number}", "exec audit 'fix {hash} {line_number}' 'aaa'")]
+	#[case::single_quote("pick aaa '; rm -rf x'", r"exec audit ''\''; rm -rf x'\''' 'aaa'")]
+	#[case::command_substitution("pick aaa $(id) `id`", "exec audit '$(id) `id`' 'aaa'")]
+	#[case::double_quote(r#"pick aaa say "$HOME" \"#, r#"exec audit 'say "$HOME" \' 'aaa'"#)]
+	fn template_quotes_subject(#[case] line: &str, #[case] expected: &str) {
+		let modified_line = create_modified_line(line, Action::Drop, None);
+		assert_some_eq!(changed_command_line("audit {subject} {hash}", &modified_line, 1), expected);
+	}
+}
@@ -7,6 +7,6 @@ mod io;
 mod parse;
 
 pub(crate) use self::{
-	io::{FileReadErrorCause, IoError},
+	io::{FileReadErrorCause, FileWriteErrorCause, IoError},
 	parse::ParseError,
 };
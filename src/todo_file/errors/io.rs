@@ -28,6 +28,23 @@ impl PartialEq for FileReadErrorCause {
 	}
 }
 
+/// The cause of a `FileWrite` error
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub(crate) enum FileWriteErrorCause {
+	/// Caused by an io error
+	#[error(transparent)]
+	IoError(#[from] io::Error),
+}
+
+impl PartialEq for FileWriteErrorCause {
+	fn eq(&self, other: &Self) -> bool {
+		match (self, other) {
+			(Self::IoError(self_err), Self::IoError(other_err)) => self_err.kind() == other_err.kind(),
+		}
+	}
+}
+
 /// IO baser errors
 #[derive(Error, Debug, PartialEq)]
 #[non_exhaustive]
@@ -40,6 +57,14 @@ pub(crate) enum IoError {
 		/// The reason for the read error
 		cause: FileReadErrorCause,
 	},
+	/// The file could not be written
+	#[error("Unable to write file `{file}`")]
+	FileWrite {
+		/// The file path that failed to write
+		file: PathBuf,
+		/// The reason for the write error
+		cause: FileWriteErrorCause,
+	},
 }
 
 #[cfg(test)]
@@ -70,6 +95,22 @@ mod test {
 		);
 	}
 
+	#[test]
+	fn partial_eq_file_write_error_cause_io_error_same_kind() {
+		assert_eq!(
+			FileWriteErrorCause::IoError(io::Error::from(io::ErrorKind::Other)),
+			FileWriteErrorCause::IoError(io::Error::from(io::ErrorKind::Other))
+		);
+	}
+
+	#[test]
+	fn partial_eq_file_write_error_cause_io_error_different_kind() {
+		assert_ne!(
+			FileWriteErrorCause::IoError(io::Error::from(io::ErrorKind::Other)),
+			FileWriteErrorCause::IoError(io::Error::from(io::ErrorKind::NotFound))
+		);
+	}
+
 	#[test]
 	fn partial_eq_file_read_error_cause_different_parse_error() {
 		assert_ne!(
@@ -1,3 +1,5 @@
+mod description;
+mod history_entry;
 mod history_item;
 mod operation;
 
@@ -6,7 +8,8 @@ mod tests;
 
 use std::{cmp::min, collections::VecDeque};
 
-pub(crate) use self::{history_item::HistoryItem, operation::Operation};
+use self::description::describe;
+pub(crate) use self::{history_entry::HistoryEntry, history_item::HistoryItem, operation::Operation};
 use crate::todo_file::{
 	line::Line,
 	utils::{add_range, remove_range, swap_range_down, swap_range_up},
@@ -17,6 +20,7 @@ use crate::todo_file::{
 pub(crate) struct History {
 	redo_history: VecDeque<HistoryItem>,
 	undo_history: VecDeque<HistoryItem>,
+	undone_history: VecDeque<HistoryItem>,
 	limit: usize,
 }
 
@@ -25,6 +29,7 @@ impl History {
 		Self {
 			redo_history: VecDeque::new(),
 			undo_history: VecDeque::from([HistoryItem::new_load()]),
+			undone_history: VecDeque::new(),
 			limit: limit.try_into().expect("History limit is too large"),
 		}
 	}
@@ -55,6 +60,19 @@ impl History {
 				add_range(lines, &operation.lines, operation.start_index, operation.end_index);
 				HistoryItem::new_add(operation.start_index, operation.end_index)
 			},
+			Operation::Replace => {
+				let replaced_lines = lines
+					.splice(
+						operation.start_index..=operation.end_index,
+						operation.lines.iter().cloned(),
+					)
+					.collect();
+				HistoryItem::new_replace(
+					operation.start_index,
+					operation.start_index + operation.lines.len() - 1,
+					replaced_lines,
+				)
+			},
 			Operation::SwapUp => {
 				swap_range_down(lines, operation.start_index - 1, operation.end_index - 1);
 				HistoryItem::new_swap_down(operation.start_index - 1, operation.end_index - 1)
@@ -66,11 +84,16 @@ impl History {
 		}
 	}
 
+	/// Record a change. The history is linear: any changes that were undone are kept, by recording the undo operations
+	/// as ordinary changes before the new change, so that undoing the new change and then the undo operations returns
+	/// to the state before the undo. Once the limit is reached, the oldest changes are removed, including the load of
+	/// the todo file, after which the oldest state is the oldest recorded state.
 	pub(crate) fn record(&mut self, operations: HistoryItem) {
-		self.redo_history.clear();
-		// delete old entries on limit reached
+		self.undo_history.extend(self.undone_history.drain(..).rev());
+		self.undo_history.extend(self.redo_history.drain(..));
 		self.undo_history.push_back(operations);
-		if self.undo_history.len() > self.limit {
+		// delete old entries on limit reached
+		while self.undo_history.len() > self.limit {
 			let _pop_result = self.undo_history.pop_front();
 		}
 	}
@@ -84,8 +107,10 @@ impl History {
 				return (history.operation, history.start_index, history.end_index);
 			}
 			let update_range = Self::get_last_index_range(&history, current.len());
+			let operation = history_item.operation;
 			self.redo_history.push_back(history);
-			(history_item.operation, update_range.0, update_range.1)
+			self.undone_history.push_back(history_item);
+			(operation, update_range.0, update_range.1)
 		})
 	}
 
@@ -96,6 +121,7 @@ impl History {
 			let history = Self::apply_operation(current, &history_item);
 			let update_range = Self::get_last_index_range(&history, current.len());
 			self.undo_history.push_back(history);
+			let _undone = self.undone_history.pop_back();
 			(history_item.operation, update_range.0, update_range.1)
 		})
 	}
@@ -104,11 +130,101 @@ impl History {
 		self.undo_history.clear();
 		self.undo_history.push_back(HistoryItem::new_load());
 		self.redo_history.clear();
+		self.undone_history.clear();
+	}
+
+	/// The position of the current state within the entries of the history.
+	fn current_position(&self) -> usize {
+		let has_load = self
+			.undo_history
+			.front()
+			.is_some_and(|item| item.operation == Operation::Load);
+		self.undo_history.len() - usize::from(has_load)
+	}
+
+	/// Describe every state of the history, from the oldest to the newest state, where the lines are the current lines.
+	/// Returns the entries, and the position of the current state within the entries.
+	pub(crate) fn entries(&self, current: &[Line]) -> (Vec<HistoryEntry>, usize) {
+		let mut lines = current.to_vec();
+		let mut entries = vec![];
+		for item in self.undo_history.iter().rev() {
+			entries.push(Self::create_entry(item, &lines));
+			let _inverse = Self::apply_operation(&mut lines, item);
+		}
+		if self.current_position() == self.undo_history.len() {
+			entries.push(HistoryEntry::new("Oldest recorded state", 0, 0));
+		}
+		entries.reverse();
+
+		let mut redo_lines = current.to_vec();
+		for item in self.redo_history.iter().rev() {
+			let inverse = Self::apply_operation(&mut redo_lines, item);
+			entries.push(Self::create_entry(&inverse, &redo_lines));
+		}
+		(entries, self.current_position())
+	}
+
+	/// Get the lines of the state at a position within the entries of the history, where the lines are the current
+	/// lines.
+	pub(crate) fn preview(&self, current: &[Line], position: usize) -> Vec<Line> {
+		let mut lines = current.to_vec();
+		let current_position = self.current_position();
+		if position < current_position {
+			for item in self.undo_history.iter().rev().take(current_position - position) {
+				let _inverse = Self::apply_operation(&mut lines, item);
+			}
+		}
+		else {
+			for item in self.redo_history.iter().rev().take(position - current_position) {
+				let _inverse = Self::apply_operation(&mut lines, item);
+			}
+		}
+		lines
+	}
+
+	/// Restore the state at a position within the entries of the history. The restore is recorded as a single change of
+	/// the lines that differ from the state, so it can be undone in one step. Returns if the lines changed.
+	pub(crate) fn jump(&mut self, current: &mut Vec<Line>, position: usize) -> bool {
+		let target = self.preview(current, position);
+		let prefix = current.iter().zip(&target).take_while(|&(a, b)| a == b).count();
+		let suffix = current[prefix..]
+			.iter()
+			.rev()
+			.zip(target[prefix..].iter().rev())
+			.take_while(|&(a, b)| a == b)
+			.count();
+		let replaced_end = current.len() - suffix;
+		let target_end = target.len() - suffix;
+		if prefix == replaced_end && prefix == target_end {
+			return false;
+		}
+
+		let replaced_lines: Vec<Line> = current
+			.splice(prefix..replaced_end, target[prefix..target_end].iter().cloned())
+			.collect();
+		let history_item = if replaced_lines.is_empty() {
+			HistoryItem::new_add(prefix, target_end - 1)
+		}
+		else if prefix == target_end {
+			HistoryItem::new_remove(prefix, replaced_end - 1, replaced_lines)
+		}
+		else {
+			HistoryItem::new_replace(prefix, target_end - 1, replaced_lines)
+		};
+		self.record(history_item);
+		true
+	}
+
+	fn create_entry(item: &HistoryItem, lines: &[Line]) -> HistoryEntry {
+		let (start_index, end_index) = Self::get_last_index_range(item, lines.len());
+		HistoryEntry::new(describe(item, lines).as_str(), start_index, end_index)
 	}
 
 	fn get_last_index_range(history_item: &HistoryItem, list_length: usize) -> (usize, usize) {
 		match history_item.operation {
-			Operation::Add | Operation::Modify | Operation::Load => (history_item.start_index, history_item.end_index),
+			Operation::Add | Operation::Modify | Operation::Load | Operation::Replace => {
+				(history_item.start_index, history_item.end_index)
+			},
 			Operation::Remove => {
 				let index = min(history_item.start_index, history_item.end_index);
 				if index == 0 || list_length == 0 {
@@ -0,0 +1,177 @@
+use std::cmp::{max, min};
+
+use crate::todo_file::{history::HistoryItem, Action, Line, Operation};
+
+/// How a line was changed by a modification.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum LineChange {
+	Action(Action),
+	Content,
+	Message,
+	Metadata,
+	Option,
+	Order,
+}
+
+/// The name of a line, the commit hash for a line with a commit, or otherwise the text of the line.
+fn line_name(line: Option<&Line>) -> String {
+	line.map_or_else(String::new, |l| {
+		if l.has_reference() {
+			String::from(l.get_hash())
+		}
+		else {
+			l.to_text()
+		}
+	})
+}
+
+fn line_change(previous: &Line, current: &Line) -> LineChange {
+	if previous.get_hash() != current.get_hash() || previous.has_reference() != current.has_reference() {
+		LineChange::Order
+	}
+	else if previous.get_content() != current.get_content() {
+		if previous.has_reference() || previous.get_action() != current.get_action() {
+			LineChange::Order
+		}
+		else {
+			LineChange::Content
+		}
+	}
+	else if previous.get_action() != current.get_action() {
+		LineChange::Action(*current.get_action())
+	}
+	else if previous.message() != current.message() {
+		LineChange::Message
+	}
+	else if previous.metadata_changes() != current.metadata_changes() {
+		LineChange::Metadata
+	}
+	else {
+		LineChange::Option
+	}
+}
+
+fn describe_modify(previous_lines: &[Line], current_lines: &[Line]) -> String {
+	let changes: Vec<(LineChange, &Line)> = previous_lines
+		.iter()
+		.zip(current_lines)
+		.filter(|&(previous, current)| previous != current)
+		.map(|(previous, current)| (line_change(previous, current), current))
+		.collect();
+	let Some(&(first_change, first_line)) = changes.first()
+	else {
+		return format!("Changed {} lines", current_lines.len());
+	};
+	let count = changes.len();
+	if changes.iter().any(|&(change, _)| change == LineChange::Order) {
+		return format!("Reordered {count} lines");
+	}
+	if changes.iter().any(|&(change, _)| change != first_change) {
+		return format!("Changed {count} lines");
+	}
+
+	let name = line_name(Some(first_line));
+	match (first_change, count) {
+		(LineChange::Action(action), 1) => format!("Changed {name} to {action}"),
+		(LineChange::Action(action), _) => format!("Changed {count} lines to {action}"),
+		(LineChange::Content, 1) => format!("Edited {name}"),
+		(LineChange::Content, _) => format!("Edited {count} lines"),
+		(LineChange::Message, 1) => format!("Changed the message of {name}"),
+		(LineChange::Message, _) => format!("Changed the message of {count} commits"),
+		(LineChange::Metadata, 1) => format!("Changed the author, date or trailers of {name}"),
+		(LineChange::Metadata, _) => format!("Changed the author, date or trailers of {count} commits"),
+		(LineChange::Option, 1) => format!("Toggled the option of {name}"),
+		(LineChange::Option | LineChange::Order, _) => format!("Changed {count} lines"),
+	}
+}
+
+/// Describe the change that a history item reverts, where the lines are the lines after the change was made.
+pub(crate) fn describe(item: &HistoryItem, lines: &[Line]) -> String {
+	let start = min(item.start_index, item.end_index);
+	let end = max(item.start_index, item.end_index);
+	let count = end - start + 1;
+	match item.operation {
+		Operation::Load => String::from("Loaded the todo file"),
+		Operation::Add if count == 1 => format!("Added {}", line_name(lines.get(start))),
+		Operation::Add => format!("Added {count} lines"),
+		Operation::Remove if count == 1 => format!("Removed {}", line_name(item.lines.first())),
+		Operation::Remove => format!("Removed {count} lines"),
+		Operation::SwapUp if count == 1 => format!("Moved {} up", line_name(lines.get(start.saturating_sub(1)))),
+		Operation::SwapUp => format!("Moved {count} lines up"),
+		Operation::SwapDown if count == 1 => format!("Moved {} down", line_name(lines.get(start + 1))),
+		Operation::SwapDown => format!("Moved {count} lines down"),
+		Operation::Replace => format!("Replaced {} lines with {count} lines", item.lines.len()),
+		Operation::Modify => describe_modify(&item.lines, lines.get(start..=end).unwrap_or_default()),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use rstest::rstest;
+
+	use super::*;
+	use crate::todo_file::MetadataChanges;
+
+	fn parse_lines(lines: &[&str]) -> Vec<Line> {
+		lines.iter().map(|line| Line::parse(line).unwrap()).collect()
+	}
+
+	#[rstest]
+	#[case::load(HistoryItem::new_load(), "Loaded the todo file")]
+	#[case::add_line(HistoryItem::new_add(1, 1), "Added exec make")]
+	#[case::add_commit(HistoryItem::new_add(0, 0), "Added aaa")]
+	#[case::add_lines(HistoryItem::new_add(2, 0), "Added 3 lines")]
+	#[case::remove_line(HistoryItem::new_remove(1, 1, parse_lines(&["pick fff c6"])), "Removed fff")]
+	#[case::remove_lines(HistoryItem::new_remove(1, 2, parse_lines(&["break", "break"])), "Removed 2 lines")]
+	#[case::swap_up_line(HistoryItem::new_swap_up(1, 1), "Moved aaa up")]
+	#[case::swap_up_lines(HistoryItem::new_swap_up(1, 3), "Moved 3 lines up")]
+	#[case::swap_down_line(HistoryItem::new_swap_down(0, 0), "Moved exec make down")]
+	#[case::swap_down_lines(HistoryItem::new_swap_down(1, 0), "Moved 2 lines down")]
+	#[case::replace(HistoryItem::new_replace(0, 2, parse_lines(&["break"])), "Replaced 1 lines with 3 lines")]
+	fn describe_operation(#[case] item: HistoryItem, #[case] expected: &str) {
+		let lines = parse_lines(&["pick aaa c1", "exec make", "pick bbb c2", "pick ccc c3"]);
+		assert_eq!(describe(&item, &lines), expected);
+	}
+
+	#[rstest]
+	#[case::action(&["pick aaa c1"], &["fixup aaa c1"], "Changed aaa to fixup")]
+	#[case::actions(
+		&["pick aaa c1", "pick bbb c2", "pick ccc c3"],
+		&["drop aaa c1", "pick bbb c2", "drop ccc c3"],
+		"Changed 2 lines to drop"
+	)]
+	#[case::mixed_actions(&["pick aaa c1", "pick bbb c2"], &["drop aaa c1", "edit bbb c2"], "Changed 2 lines")]
+	#[case::content(&["exec make"], &["exec make test"], "Edited exec make test")]
+	#[case::reorder(&["pick aaa c1", "pick bbb c2"], &["pick bbb c2", "pick aaa c1"], "Reordered 2 lines")]
+	#[case::unchanged(&["pick aaa c1"], &["pick aaa c1"], "Changed 1 lines")]
+	fn describe_modify_lines(#[case] previous: &[&str], #[case] current: &[&str], #[case] expected: &str) {
+		let item = HistoryItem::new_modify(0, previous.len() - 1, parse_lines(previous));
+		assert_eq!(describe(&item, &parse_lines(current)), expected);
+	}
+
+	#[test]
+	fn describe_message() {
+		let mut line = Line::parse("pick aaa c1").unwrap();
+		line.set_message(Some("message"));
+		let item = HistoryItem::new_modify(0, 0, parse_lines(&["pick aaa c1"]));
+		assert_eq!(describe(&item, &[line]), "Changed the message of aaa");
+	}
+
+	#[test]
+	fn describe_metadata_changes() {
+		let mut line = Line::parse("pick aaa c1").unwrap();
+		let mut metadata_changes = MetadataChanges::default();
+		metadata_changes.add_trailer("Acked-by: Jane");
+		line.set_metadata_changes(metadata_changes);
+		let item = HistoryItem::new_modify(0, 0, parse_lines(&["pick aaa c1"]));
+		assert_eq!(describe(&item, &[line]), "Changed the author, date or trailers of aaa");
+	}
+
+	#[test]
+	fn describe_option() {
+		let mut line = Line::parse("fixup aaa c1").unwrap();
+		line.toggle_option("-C");
+		let item = HistoryItem::new_modify(0, 0, parse_lines(&["fixup aaa c1"]));
+		assert_eq!(describe(&item, &[line]), "Toggled the option of aaa");
+	}
+}
@@ -0,0 +1,32 @@
+/// A state of the history, described by the change that led to the state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct HistoryEntry {
+	description: String,
+	start_index: usize,
+	end_index: usize,
+}
+
+impl HistoryEntry {
+	pub(crate) fn new(description: &str, start_index: usize, end_index: usize) -> Self {
+		Self {
+			description: String::from(description),
+			start_index,
+			end_index,
+		}
+	}
+
+	/// Get the description of the change that led to the state.
+	pub(crate) fn description(&self) -> &str {
+		self.description.as_str()
+	}
+
+	/// Get the first index of the lines changed by the change that led to the state.
+	pub(crate) const fn start_index(&self) -> usize {
+		self.start_index
+	}
+
+	/// Get the last index of the lines changed by the change that led to the state.
+	pub(crate) const fn end_index(&self) -> usize {
+		self.end_index
+	}
+}
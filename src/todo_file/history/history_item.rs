@@ -45,6 +45,15 @@ impl HistoryItem {
 		}
 	}
 
+	pub(crate) fn new_replace(start_index: usize, end_index: usize, lines: Vec<Line>) -> Self {
+		Self {
+			operation: Operation::Replace,
+			start_index,
+			end_index,
+			lines,
+		}
+	}
+
 	pub(crate) const fn new_swap_up(start_index: usize, end_index: usize) -> Self {
 		Self {
 			operation: Operation::SwapUp,
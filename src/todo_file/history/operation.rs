@@ -1,4 +1,4 @@
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub(crate) enum Operation {
 	Load,
 	Modify,
@@ -6,4 +6,5 @@ pub(crate) enum Operation {
 	SwapDown,
 	Add,
 	Remove,
+	Replace,
 }
@@ -67,12 +67,70 @@ fn new() {
 #[test]
 fn record_history() {
 	let mut history = History::new(5);
-	history.redo_history.push_front(HistoryItem::new_add(1, 1));
 	history.record(HistoryItem::new_add(1, 1));
 	assert_history_items!(history.undo_history, HistoryItem::new_add(1, 1));
 	assert_empty!(history.redo_history);
 }
 
+#[test]
+fn record_history_keeps_undone_changes() {
+	let mut history = History::new(10);
+	let mut lines = create_lines();
+	lines[0] = Line::parse("drop aaa c1").unwrap();
+	history.record(HistoryItem::new_modify(0, 0, vec![Line::parse("pick aaa c1").unwrap()]));
+	_ = history.undo(&mut lines);
+	let removed = lines.remove(4);
+	history.record(HistoryItem::new_remove(4, 4, vec![removed]));
+	assert_empty!(history.redo_history);
+	assert_empty!(history.undone_history);
+
+	assert_some_eq!(history.undo(&mut lines), (Operation::Remove, 4, 4));
+	assert_todo_lines!(
+		lines,
+		"pick aaa c1",
+		"pick bbb c2",
+		"pick ccc c3",
+		"pick ddd c4",
+		"pick eee c5"
+	);
+	assert_some_eq!(history.undo(&mut lines), (Operation::Modify, 0, 0));
+	assert_todo_lines!(
+		lines,
+		"drop aaa c1",
+		"pick bbb c2",
+		"pick ccc c3",
+		"pick ddd c4",
+		"pick eee c5"
+	);
+	assert_some_eq!(history.undo(&mut lines), (Operation::Modify, 0, 0));
+	assert_todo_lines!(
+		lines,
+		"pick aaa c1",
+		"pick bbb c2",
+		"pick ccc c3",
+		"pick ddd c4",
+		"pick eee c5"
+	);
+	assert_some_eq!(history.undo(&mut lines), (Operation::Load, 0, 0));
+}
+
+#[test]
+fn record_history_keeps_undone_changes_overflow_limit() {
+	let mut history = History::new(3);
+	let mut lines = create_lines();
+	history.record(HistoryItem::new_swap_up(1, 1));
+	history.record(HistoryItem::new_swap_up(2, 2));
+	_ = history.undo(&mut lines);
+	_ = history.undo(&mut lines);
+	history.record(HistoryItem::new_add(1, 1));
+	assert_history_items!(
+		history.undo_history,
+		HistoryItem::new_swap_down(1, 1),
+		HistoryItem::new_swap_down(0, 0),
+		HistoryItem::new_add(1, 1)
+	);
+}
+
 #[test]
 fn record_history_overflow_limit() {
 	let mut history = History::new(3);
@@ -901,3 +959,238 @@ fn reset() {
 	assert_some_eq!(history.undo_history.pop_back(), HistoryItem::new_load());
 	assert_empty!(history.redo_history);
 }
+
+#[test]
+fn undo_redo_replace_fewer_lines() {
+	let mut history = History::new(10);
+	// the lines at 1-1 replaced the original lines, that are restored on undo
+	history.record(HistoryItem::new_replace(1, 1, vec![
+		Line::parse("pick bbb c2").unwrap(),
+		Line::parse("drop xxx cx").unwrap(),
+		Line::parse("pick ccc c3").unwrap(),
+	]));
+	let mut lines = vec![
+		Line::parse("pick aaa c1").unwrap(),
+		Line::parse("pick yyy cy").unwrap(),
+		Line::parse("pick ddd c4").unwrap(),
+	];
+	assert_some_eq!(history.undo(&mut lines), (Operation::Replace, 1, 3));
+	assert_todo_lines!(
+		lines,
+		"pick aaa c1",
+		"pick bbb c2",
+		"drop xxx cx",
+		"pick ccc c3",
+		"pick ddd c4"
+	);
+	assert_some_eq!(history.redo(&mut lines), (Operation::Replace, 1, 1));
+	assert_todo_lines!(lines, "pick aaa c1", "pick yyy cy", "pick ddd c4");
+}
+
+#[test]
+fn undo_redo_replace_more_lines() {
+	let mut history = History::new(10);
+	history.record(HistoryItem::new_replace(0, 2, vec![Line::parse("drop xxx cx").unwrap()]));
+	let mut lines = create_lines();
+	assert_some_eq!(history.undo(&mut lines), (Operation::Replace, 0, 0));
+	assert_todo_lines!(lines, "drop xxx cx", "pick ddd c4", "pick eee c5");
+	assert_some_eq!(history.redo(&mut lines), (Operation::Replace, 0, 2));
+	assert_todo_lines!(
+		lines,
+		"pick aaa c1",
+		"pick bbb c2",
+		"pick ccc c3",
+		"pick ddd c4",
+		"pick eee c5"
+	);
+}
+
+fn create_history_with_states() -> (History, Vec<Line>) {
+	let mut history = History::new(10);
+	let mut lines = create_lines();
+	lines[0] = Line::parse("drop aaa c1").unwrap();
+	history.record(HistoryItem::new_modify(0, 0, vec![Line::parse("pick aaa c1").unwrap()]));
+	lines.swap(2, 3);
+	history.record(HistoryItem::new_swap_up(3, 3));
+	let removed = lines.remove(4);
+	history.record(HistoryItem::new_remove(4, 4, vec![removed]));
+	_ = history.undo(&mut lines);
+	(history, lines)
+}
+
+#[test]
+fn entries() {
+	let (history, lines) = create_history_with_states();
+	let (entries, position) = history.entries(&lines);
+	assert_eq!(position, 2);
+	assert_eq!(
+		entries
+			.iter()
+			.map(|entry| format!("{} {}-{}", entry.description(), entry.start_index(), entry.end_index()))
+			.collect::<Vec<String>>(),
+		vec![
+			"Loaded the todo file 0-0",
+			"Changed aaa to drop 0-0",
+			"Moved ddd up 2-2",
+			"Removed eee 3-3",
+		]
+	);
+}
+
+#[test]
+fn entries_without_load() {
+	let mut history = History::new(2);
+	history.record(HistoryItem::new_swap_up(1, 1));
+	history.record(HistoryItem::new_swap_up(2, 2));
+	let lines = create_lines();
+	let (entries, position) = history.entries(&lines);
+	assert_eq!(position, 2);
+	assert_eq!(
+		entries.iter().map(HistoryEntry::description).collect::<Vec<&str>>(),
+		vec!["Oldest recorded state", "Moved aaa up", "Moved bbb up"]
+	);
+}
+
+#[test]
+fn preview() {
+	let (history, lines) = create_history_with_states();
+	assert_todo_lines!(
+		history.preview(&lines, 0),
+		"pick aaa c1",
+		"pick bbb c2",
+		"pick ccc c3",
+		"pick ddd c4",
+		"pick eee c5"
+	);
+	assert_todo_lines!(
+		history.preview(&lines, 3),
+		"drop aaa c1",
+		"pick bbb c2",
+		"pick ddd c4",
+		"pick ccc c3"
+	);
+	assert_todo_lines!(
+		history.preview(&lines, 2),
+		"drop aaa c1",
+		"pick bbb c2",
+		"pick ddd c4",
+		"pick ccc c3",
+		"pick eee c5"
+	);
+}
+
+#[test]
+fn jump() {
+	let (mut history, mut lines) = create_history_with_states();
+	assert!(history.jump(&mut lines, 0));
+	assert_todo_lines!(
+		lines,
+		"pick aaa c1",
+		"pick bbb c2",
+		"pick ccc c3",
+		"pick ddd c4",
+		"pick eee c5"
+	);
+	let (entries, position) = history.entries(&lines);
+	assert_eq!(position, entries.len() - 1);
+	assert_eq!(
+		entries.last().map(HistoryEntry::description),
+		Some("Replaced 4 lines with 4 lines")
+	);
+	assert!(!history.jump(&mut lines, position));
+	assert_some_eq!(history.undo(&mut lines), (Operation::Replace, 0, 3));
+	assert_todo_lines!(
+		lines,
+		"drop aaa c1",
+		"pick bbb c2",
+		"pick ddd c4",
+		"pick ccc c3",
+		"pick eee c5"
+	);
+}
+
+#[test]
+fn jump_to_state_with_fewer_lines() {
+	let (mut history, mut lines) = create_history_with_states();
+	assert!(history.jump(&mut lines, 3));
+	assert_todo_lines!(lines, "drop aaa c1", "pick bbb c2", "pick ddd c4", "pick ccc c3");
+	assert_some_eq!(history.undo(&mut lines), (Operation::Remove, 4, 4));
+	assert_todo_lines!(
+		lines,
+		"drop aaa c1",
+		"pick bbb c2",
+		"pick ddd c4",
+		"pick ccc c3",
+		"pick eee c5"
+	);
+	assert_some_eq!(history.redo(&mut lines), (Operation::Add, 3, 3));
+	assert_todo_lines!(lines, "drop aaa c1", "pick bbb c2", "pick ddd c4", "pick ccc c3");
+}
+
+#[test]
+fn entries_after_undo_edit_undo() {
+	let mut history = History::new(10);
+	let mut lines = create_lines();
+	lines[0] = Line::parse("drop aaa c1").unwrap();
+	history.record(HistoryItem::new_modify(0, 0, vec![Line::parse("pick aaa c1").unwrap()]));
+	_ = history.undo(&mut lines);
+	lines.swap(2, 3);
+	history.record(HistoryItem::new_swap_up(3, 3));
+	_ = history.undo(&mut lines);
+	let (entries, position) = history.entries(&lines);
+	// the history is linear, so the undone change is kept, followed by the change that undid it
+	assert_eq!(
+		entries
+			.iter()
+			.map(|entry| format!("{} {}-{}", entry.description(), entry.start_index(), entry.end_index()))
+			.collect::<Vec<String>>(),
+		vec![
+			"Loaded the todo file 0-0",
+			"Changed aaa to drop 0-0",
+			"Changed aaa to pick 0-0",
+			"Moved ddd up 2-2",
+		]
+	);
+	assert_eq!(position, 2);
+	assert_todo_lines!(
+		history.preview(&lines, 1),
+		"drop aaa c1",
+		"pick bbb c2",
+		"pick ccc c3",
+		"pick ddd c4",
+		"pick eee c5"
+	);
+}
+
+#[test]
+fn record_past_limit_after_undo_keeps_newest_entries() {
+	let mut history = History::new(3);
+	let mut lines = create_lines();
+	lines[0] = Line::parse("drop aaa c1").unwrap();
+	history.record(HistoryItem::new_modify(0, 0, vec![Line::parse("pick aaa c1").unwrap()]));
+	lines[1] = Line::parse("drop bbb c2").unwrap();
+	history.record(HistoryItem::new_modify(1, 1, vec![Line::parse("pick bbb c2").unwrap()]));
+	_ = history.undo(&mut lines);
+	_ = history.undo(&mut lines);
+	lines.swap(2, 3);
+	history.record(HistoryItem::new_swap_up(3, 3));
+	let (entries, position) = history.entries(&lines);
+	assert_eq!(
+		entries.iter().map(HistoryEntry::description).collect::<Vec<&str>>(),
+		vec![
+			"Oldest recorded state",
+			"Changed bbb to pick",
+			"Changed aaa to pick",
+			"Moved ddd up"
+		]
+	);
+	assert_eq!(position, 3);
+	assert_todo_lines!(
+		history.preview(&lines, 0),
+		"drop aaa c1",
+		"drop bbb c2",
+		"pick ccc c3",
+		"pick ddd c4",
+		"pick eee c5"
+	);
+}
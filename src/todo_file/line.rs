@@ -1,4 +1,4 @@
-use crate::todo_file::{Action, LineParser, ParseError};
+use crate::todo_file::{Action, LineParser, MetadataChanges, ParseError};
 
 /// Represents a line in the rebase file.
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -7,6 +7,8 @@ pub(crate) struct Line {
 	action: Action,
 	content: String,
 	hash: String,
+	message: Option<String>,
+	metadata_changes: MetadataChanges,
 	mutated: bool,
 	option: Option<String>,
 	original_line: Option<Box<Line>>,
@@ -22,12 +24,16 @@ impl Line {
 			action,
 			content: String::from(content),
 			hash: String::from(hash),
+			message: None,
+			metadata_changes: MetadataChanges::default(),
 			mutated: false,
 			option: original_option.clone(),
 			original_line: Some(Box::new(Line {
 				action: original_action,
 				content: original_content,
 				hash: String::from(hash),
+				message: None,
+				metadata_changes: MetadataChanges::default(),
 				mutated: false,
 				option: original_option,
 				original_line: None,
@@ -47,6 +53,12 @@ impl Line {
 		Self::new(Action::Pick, hash, "", None)
 	}
 
+	/// Create a new revert line.
+	#[must_use]
+	pub(crate) fn new_revert(hash: &str) -> Self {
+		Self::new(Action::Revert, hash, "", None)
+	}
+
 	/// Create a new break line.
 	#[must_use]
 	pub(crate) fn new_break() -> Self {
@@ -95,7 +107,7 @@ impl Line {
 		Ok(match action {
 			Action::Noop => Self::new_noop(),
 			Action::Break => Self::new_break(),
-			Action::Pick | Action::Reword | Action::Edit | Action::Squash | Action::Drop => {
+			Action::Pick | Action::Revert | Action::Reword | Action::Edit | Action::Squash | Action::Drop => {
 				Self::new(action, line_parser.next()?, line_parser.take_remaining(), None)
 			},
 			Action::Fixup => {
@@ -138,6 +150,22 @@ impl Line {
 		}
 	}
 
+	/// Set, or clear, the new message of the commit, that is composed in the application and that the commit is amended
+	/// with after it is picked. Only lines with a commit can have a message.
+	pub(crate) fn set_message(&mut self, message: Option<&str>) {
+		if self.has_reference() {
+			self.message = message.map(String::from);
+		}
+	}
+
+	/// Set the changes to the author, committer date and trailers of the commit, that the commit is amended with after
+	/// it is picked. Only lines with a commit can have metadata changes.
+	pub(crate) fn set_metadata_changes(&mut self, metadata_changes: MetadataChanges) {
+		if self.has_reference() {
+			self.metadata_changes = metadata_changes;
+		}
+	}
+
 	/// Set the option on the line, toggling if the existing option matches.
 	pub(crate) fn toggle_option(&mut self, option: &str) {
 		// try toggle off first
@@ -150,6 +178,18 @@ impl Line {
 		self.option = Some(String::from(option));
 	}
 
+	/// Take over the original of an earlier version of this line, such as when the rebase file is reloaded after an
+	/// external edit. The line is modified when the earlier version was, or when the action, option or content differ.
+	pub(crate) fn restore_original(&mut self, line: &Self) {
+		self.mutated = line.mutated
+			|| self.action != line.action
+			|| self.option != line.option
+			|| self.content != line.content;
+		self.original_line.clone_from(&line.original_line);
+		self.message.clone_from(&line.message);
+		self.metadata_changes.clone_from(&line.metadata_changes);
+	}
+
 	/// Get the original line, before any modifications
 	#[must_use]
 	pub(crate) fn original(&self) -> Option<&Line> {
@@ -174,6 +214,18 @@ impl Line {
 		self.hash.as_str()
 	}
 
+	/// Get the new message of the commit, if one was composed in the application.
+	#[must_use]
+	pub(crate) fn message(&self) -> Option<&str> {
+		self.message.as_deref()
+	}
+
+	/// Get the changes to the metadata of the commit.
+	#[must_use]
+	pub(crate) const fn metadata_changes(&self) -> &MetadataChanges {
+		&self.metadata_changes
+	}
+
 	/// Get the commit hash for the line.
 	#[must_use]
 	pub(crate) fn option(&self) -> Option<&str> {
@@ -197,6 +249,7 @@ impl Line {
 			| Action::Fixup
 			| Action::Noop
 			| Action::Pick
+			| Action::Revert
 			| Action::Reword
 			| Action::Squash => false,
 		}
@@ -212,7 +265,13 @@ impl Line {
 	#[must_use]
 	pub(crate) fn to_text(&self) -> String {
 		match self.action {
-			Action::Drop | Action::Edit | Action::Fixup | Action::Pick | Action::Reword | Action::Squash => {
+			Action::Drop
+			| Action::Edit
+			| Action::Fixup
+			| Action::Pick
+			| Action::Revert
+			| Action::Reword
+			| Action::Squash => {
 				if let Some(opt) = self.option.as_ref() {
 					format!("{} {opt} {} {}", self.action, self.hash, self.content)
 				}
@@ -237,6 +296,7 @@ mod tests {
 
 	#[rstest]
 	#[case::pick_action("pick aaa comment", &Line::new(Action::Pick, "aaa", "comment", None))]
+	#[case::revert_action("revert aaa comment", &Line::new(Action::Revert, "aaa", "comment", None))]
 	#[case::reword_action("reword aaa comment", &Line::new(Action::Reword, "aaa", "comment", None))]
 	#[case::edit_action("edit aaa comment", &Line::new(Action::Edit, "aaa", "comment", None))]
 	#[case::squash_action("squash aaa comment", &Line::new(Action::Squash, "aaa", "comment", None))]
@@ -261,12 +321,39 @@ mod tests {
 			action: Action::Pick,
 			hash: String::from("abc123"),
 			content: String::new(),
+			message: None,
+			metadata_changes: MetadataChanges::default(),
 			mutated: false,
 			option: None,
 			original_line: Some(Box::new(Line {
 				action: Action::Pick,
 				hash: String::from("abc123"),
 				content: String::new(),
+				message: None,
+				metadata_changes: MetadataChanges::default(),
+				mutated: false,
+				option: None,
+				original_line: None,
+			}))
+		});
+	}
+
+	#[test]
+	fn line_new_revert() {
+		assert_eq!(Line::new_revert("abc123"), Line {
+			action: Action::Revert,
+			hash: String::from("abc123"),
+			content: String::new(),
+			message: None,
+			metadata_changes: MetadataChanges::default(),
+			mutated: false,
+			option: None,
+			original_line: Some(Box::new(Line {
+				action: Action::Revert,
+				hash: String::from("abc123"),
+				content: String::new(),
+				message: None,
+				metadata_changes: MetadataChanges::default(),
 				mutated: false,
 				option: None,
 				original_line: None,
@@ -280,12 +367,16 @@ mod tests {
 			action: Action::Break,
 			hash: String::new(),
 			content: String::new(),
+			message: None,
+			metadata_changes: MetadataChanges::default(),
 			mutated: false,
 			option: None,
 			original_line: Some(Box::new(Line {
 				action: Action::Break,
 				hash: String::new(),
 				content: String::new(),
+				message: None,
+				metadata_changes: MetadataChanges::default(),
 				mutated: false,
 				option: None,
 				original_line: None,
@@ -299,12 +390,16 @@ mod tests {
 			action: Action::Exec,
 			hash: String::new(),
 			content: String::from("command"),
+			message: None,
+			metadata_changes: MetadataChanges::default(),
 			mutated: false,
 			option: None,
 			original_line: Some(Box::new(Line {
 				action: Action::Exec,
 				hash: String::new(),
 				content: String::from("command"),
+				message: None,
+				metadata_changes: MetadataChanges::default(),
 				mutated: false,
 				option: None,
 				original_line: None,
@@ -318,12 +413,16 @@ mod tests {
 			action: Action::Merge,
 			hash: String::new(),
 			content: String::from("command"),
+			message: None,
+			metadata_changes: MetadataChanges::default(),
 			mutated: false,
 			option: None,
 			original_line: Some(Box::new(Line {
 				action: Action::Merge,
 				hash: String::new(),
 				content: String::from("command"),
+				message: None,
+				metadata_changes: MetadataChanges::default(),
 				mutated: false,
 				option: None,
 				original_line: None,
@@ -337,12 +436,16 @@ mod tests {
 			action: Action::Label,
 			hash: String::new(),
 			content: String::from("label"),
+			message: None,
+			metadata_changes: MetadataChanges::default(),
 			mutated: false,
 			option: None,
 			original_line: Some(Box::new(Line {
 				action: Action::Label,
 				hash: String::new(),
 				content: String::from("label"),
+				message: None,
+				metadata_changes: MetadataChanges::default(),
 				mutated: false,
 				option: None,
 				original_line: None,
@@ -356,12 +459,16 @@ mod tests {
 			action: Action::Reset,
 			hash: String::new(),
 			content: String::from("label"),
+			message: None,
+			metadata_changes: MetadataChanges::default(),
 			mutated: false,
 			option: None,
 			original_line: Some(Box::new(Line {
 				action: Action::Reset,
 				hash: String::new(),
 				content: String::from("label"),
+				message: None,
+				metadata_changes: MetadataChanges::default(),
 				mutated: false,
 				option: None,
 				original_line: None,
@@ -375,12 +482,16 @@ mod tests {
 			action: Action::UpdateRef,
 			hash: String::new(),
 			content: String::from("reference"),
+			message: None,
+			metadata_changes: MetadataChanges::default(),
 			mutated: false,
 			option: None,
 			original_line: Some(Box::new(Line {
 				action: Action::UpdateRef,
 				hash: String::new(),
 				content: String::from("reference"),
+				message: None,
+				metadata_changes: MetadataChanges::default(),
 				mutated: false,
 				option: None,
 				original_line: None,
@@ -476,6 +587,34 @@ mod tests {
 		assert_eq!(line.get_content(), expected);
 	}
 
+	#[rstest]
+	#[case::pick("pick aaa comment", Some("message"))]
+	#[case::drop("drop aaa comment", Some("message"))]
+	#[case::exec("exec command", None)]
+	#[case::label("label reference", None)]
+	#[case::break_action("break", None)]
+	fn set_message(#[case] line: &str, #[case] expected: Option<&str>) {
+		let mut line = Line::parse(line).unwrap();
+		line.set_message(Some("message"));
+		assert_eq!(line.message(), expected);
+		line.set_message(None);
+		assert_eq!(line.message(), None);
+	}
+
+	#[rstest]
+	#[case::pick("pick aaa comment", false)]
+	#[case::drop("drop aaa comment", false)]
+	#[case::exec("exec command", true)]
+	#[case::label("label reference", true)]
+	#[case::break_action("break", true)]
+	fn set_metadata_changes(#[case] line: &str, #[case] expected_empty: bool) {
+		let mut line = Line::parse(line).unwrap();
+		let mut metadata_changes = MetadataChanges::default();
+		metadata_changes.set_author(Some("Jane <jane@example.com>"));
+		line.set_metadata_changes(metadata_changes);
+		assert_eq!(line.metadata_changes().is_empty(), expected_empty);
+	}
+
 	#[rstest]
 	#[case::break_action("break", "")]
 	#[case::drop("drop aaa comment", "comment")]
@@ -0,0 +1,140 @@
+use crate::todo_file::changed_command::escape;
+
+/// Changes to the author, committer date and trailers of a commit, that the commit is amended with after it is
+/// picked.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct MetadataChanges {
+	author: Option<String>,
+	committer_date: Option<String>,
+	trailers: Vec<String>,
+}
+
+impl MetadataChanges {
+	/// Set, or clear, the new author, in the `Name <email>` form.
+	pub(crate) fn set_author(&mut self, author: Option<&str>) {
+		self.author = author.map(String::from);
+	}
+
+	/// Set, or clear, the new committer date, in any of the date formats supported by Git.
+	pub(crate) fn set_committer_date(&mut self, committer_date: Option<&str>) {
+		self.committer_date = committer_date.map(String::from);
+	}
+
+	/// Add a trailer, such as `Signed-off-by: Name <email>`, unless the same trailer was already added.
+	pub(crate) fn add_trailer(&mut self, trailer: &str) {
+		if !self.trailers.iter().any(|t| t == trailer) {
+			self.trailers.push(String::from(trailer));
+		}
+	}
+
+	/// Get the new author.
+	#[must_use]
+	pub(crate) fn author(&self) -> Option<&str> {
+		self.author.as_deref()
+	}
+
+	/// Get the new committer date.
+	#[must_use]
+	pub(crate) fn committer_date(&self) -> Option<&str> {
+		self.committer_date.as_deref()
+	}
+
+	/// Get the added trailers.
+	#[must_use]
+	pub(crate) fn trailers(&self) -> &[String] {
+		&self.trailers
+	}
+
+	/// Are there no changes to the commit.
+	#[must_use]
+	pub(crate) fn is_empty(&self) -> bool {
+		self.author.is_none() && self.committer_date.is_none() && self.trailers.is_empty()
+	}
+
+	/// Create the `exec` line that amends the commit with the changes, or `None` when there are no changes.
+	#[must_use]
+	pub(crate) fn exec_line(&self) -> Option<String> {
+		if self.is_empty() {
+			return None;
+		}
+		let mut exec_line = String::from("exec ");
+		if let Some(committer_date) = self.committer_date.as_deref() {
+			exec_line.push_str(format!("GIT_COMMITTER_DATE=\"{}\" ", escape(committer_date)).as_str());
+		}
+		exec_line.push_str("git commit --amend --no-edit --allow-empty --only");
+		if let Some(author) = self.author.as_deref() {
+			exec_line.push_str(format!(" --author=\"{}\"", escape(author)).as_str());
+		}
+		for trailer in &self.trailers {
+			exec_line.push_str(format!(" --trailer \"{}\"", escape(trailer)).as_str());
+		}
+		Some(exec_line)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use claims::{assert_none, assert_some_eq};
+
+	use super::*;
+
+	#[test]
+	fn empty() {
+		let changes = MetadataChanges::default();
+		assert!(changes.is_empty());
+		assert_none!(changes.exec_line());
+	}
+
+	#[test]
+	fn author() {
+		let mut changes = MetadataChanges::default();
+		changes.set_author(Some("Jane <jane@example.com>"));
+		assert_some_eq!(changes.author(), "Jane <jane@example.com>");
+		assert_some_eq!(
+			changes.exec_line(),
+			"exec git commit --amend --no-edit --allow-empty --only --author=\"Jane <jane@example.com>\""
+		);
+		changes.set_author(None);
+		assert!(changes.is_empty());
+	}
+
+	#[test]
+	fn committer_date() {
+		let mut changes = MetadataChanges::default();
+		changes.set_committer_date(Some("2024-01-02 03:04:05"));
+		assert_some_eq!(changes.committer_date(), "2024-01-02 03:04:05");
+		assert_some_eq!(
+			changes.exec_line(),
+			"exec GIT_COMMITTER_DATE=\"2024-01-02 03:04:05\" git commit --amend --no-edit --allow-empty --only"
+		);
+	}
+
+	#[test]
+	fn trailers() {
+		let mut changes = MetadataChanges::default();
+		changes.add_trailer("Signed-off-by: Jane <jane@example.com>");
+		changes.add_trailer("Reviewed-by: John <john@example.com>");
+		changes.add_trailer("Signed-off-by: Jane <jane@example.com>");
+		assert_eq!(changes.trailers(), &[
+			String::from("Signed-off-by: Jane <jane@example.com>"),
+			String::from("Reviewed-by: John <john@example.com>"),
+		]);
+		assert_some_eq!(
+			changes.exec_line(),
+			"exec git commit --amend --no-edit --allow-empty --only --trailer \"Signed-off-by: Jane \
+			 <jane@example.com>\" --trailer \"Reviewed-by: John <john@example.com>\""
+		);
+	}
+
+	#[test]
+	fn exec_line_escapes_values() {
+		let mut changes = MetadataChanges::default();
+		changes.set_author(Some("\"$USER\" <`id`>"));
+		changes.set_committer_date(Some("$(date)"));
+		assert_some_eq!(
+			changes.exec_line(),
+			"exec GIT_COMMITTER_DATE=\"\\$(date)\" git commit --amend --no-edit --allow-empty --only \
+			 --author=\"\\\"\\$USER\\\" <\\`id\\`>\""
+		);
+	}
+}
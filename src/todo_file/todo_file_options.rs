@@ -1,8 +1,11 @@
+use crate::todo_file::Action;
+
 /// Options for `TodoFile`
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct TodoFileOptions {
 	pub(crate) comment_prefix: String,
 	pub(crate) line_changed_command: Option<String>,
+	pub(crate) line_changed_action_commands: Vec<(Action, String)>,
 	pub(crate) undo_limit: u32,
 }
 
@@ -13,6 +16,7 @@ impl TodoFileOptions {
 		Self {
 			comment_prefix: String::from(comment_prefix),
 			line_changed_command: None,
+			line_changed_action_commands: vec![],
 			undo_limit,
 		}
 	}
@@ -21,6 +25,22 @@ impl TodoFileOptions {
 	pub(crate) fn line_changed_command(&mut self, command: &str) {
 		self.line_changed_command = Some(String::from(command));
 	}
+
+	/// Set a command to be added after each changed line with the action, instead of the command for all changed lines
+	pub(crate) fn line_changed_action_command(&mut self, action: Action, command: &str) {
+		self.line_changed_action_commands.retain(|(a, _)| *a != action);
+		self.line_changed_action_commands.push((action, String::from(command)));
+	}
+
+	/// Get the command to be added after a changed line with the action
+	#[must_use]
+	pub(crate) fn get_line_changed_command(&self, action: Action) -> Option<&str> {
+		self.line_changed_action_commands
+			.iter()
+			.find(|(a, _)| *a == action)
+			.map(|(_, command)| command.as_str())
+			.or(self.line_changed_command.as_deref())
+	}
 }
 
 #[cfg(test)]
@@ -36,6 +56,7 @@ mod tests {
 		assert_eq!(options.undo_limit, 10);
 		assert_eq!(options.comment_prefix, "#");
 		assert_none!(options.line_changed_command);
+		assert!(options.line_changed_action_commands.is_empty());
 	}
 
 	#[test]
@@ -46,4 +67,32 @@ mod tests {
 
 		assert_some_eq!(options.line_changed_command, "command");
 	}
+
+	#[test]
+	fn line_changed_action_command() {
+		let mut options = TodoFileOptions::new(10, "#");
+
+		options.line_changed_action_command(Action::Pick, "first");
+		options.line_changed_action_command(Action::Drop, "drop");
+		options.line_changed_action_command(Action::Pick, "pick");
+
+		assert_eq!(options.line_changed_action_commands, vec![
+			(Action::Drop, String::from("drop")),
+			(Action::Pick, String::from("pick"))
+		]);
+	}
+
+	#[test]
+	fn get_line_changed_command() {
+		let mut options = TodoFileOptions::new(10, "#");
+		assert_none!(options.get_line_changed_command(Action::Pick));
+
+		options.line_changed_action_command(Action::Drop, "drop");
+		assert_none!(options.get_line_changed_command(Action::Pick));
+		assert_some_eq!(options.get_line_changed_command(Action::Drop), "drop");
+
+		options.line_changed_command("command");
+		assert_some_eq!(options.get_line_changed_command(Action::Pick), "command");
+		assert_some_eq!(options.get_line_changed_command(Action::Drop), "drop");
+	}
 }
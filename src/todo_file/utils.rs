@@ -1,4 +1,13 @@
-use crate::todo_file::Line;
+use std::{
+	collections::hash_map::DefaultHasher,
+	hash::{Hash, Hasher},
+	path::Path,
+};
+
+use crate::todo_file::{
+	errors::{FileWriteErrorCause, IoError},
+	Line,
+};
 
 pub(crate) fn swap_range_up(lines: &mut [Line], start_index: usize, end_index: usize) {
 	let range = if end_index <= start_index {
@@ -53,3 +62,19 @@ pub(crate) fn add_range(lines: &mut Vec<Line>, new_lines: &[Line], start_index:
 		lines.insert(index, new_lines[add_index].clone());
 	}
 }
+
+/// The name of the file for a commit message composed in the application, from the commit hash and a digest of the
+/// message.
+pub(crate) fn message_file_name(hash: &str, message: &str) -> String {
+	let mut hasher = DefaultHasher::new();
+	message.hash(&mut hasher);
+	format!("message-{hash}-{:016x}", hasher.finish())
+}
+
+/// Create the error for a file that could not be written.
+pub(crate) fn write_error(file: &Path, err: std::io::Error) -> IoError {
+	IoError::FileWrite {
+		file: file.to_path_buf(),
+		cause: FileWriteErrorCause::from(err),
+	}
+}
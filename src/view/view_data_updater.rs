@@ -65,6 +65,7 @@ impl<'view_data> ViewDataUpdater<'view_data> {
 	}
 
 	/// Push a set of new body lines to the view data automatically split on newlines.
+	#[cfg(test)]
 	pub(crate) fn push_lines(&mut self, lines: &str) {
 		self.modified = true;
 
@@ -176,6 +177,15 @@ mod tests {
 		assert_eq!(view_data.get_lines().len(), 1);
 	}
 
+	#[test]
+	fn push_lines() {
+		let mut view_data = ViewData::new(|_| {});
+		let mut updater = ViewDataUpdater::new(&mut view_data);
+		updater.push_lines("foo\nbar");
+		assert!(updater.is_modified());
+		assert_eq!(view_data.get_lines().len(), 2);
+	}
+
 	#[test]
 	fn push_trailing_line() {
 		let mut view_data = ViewData::new(|_| {});
@@ -67,6 +67,14 @@ impl ViewLine {
 		self
 	}
 
+	/// Replace the segments of the line, with a number of pinned leading segments.
+	#[must_use]
+	pub(crate) fn set_segments(mut self, segments: Vec<LineSegment>, pinned_segments: usize) -> Self {
+		self.segments = segments;
+		self.pinned_segments = pinned_segments;
+		self
+	}
+
 	/// Get the number of pinned line segments.
 	#[must_use]
 	pub(crate) const fn get_number_of_pinned_segment(&self) -> usize {